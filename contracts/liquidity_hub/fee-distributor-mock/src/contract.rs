@@ -1,13 +1,15 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint64,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint64,
 };
 
-use white_whale_std::fee_distributor::EpochResponse;
+use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
+use white_whale_std::fee_distributor::{Config, EpochResponse};
+use white_whale_std::pool_network::asset::AssetInfo;
 
 use crate::error::ContractError;
 use crate::msg::InstantiateMsg;
-use crate::state::CURRENT_EPOCH;
+use crate::state::{CONFIG, CURRENT_EPOCH};
 
 // use cw2::set_contract_version;
 
@@ -21,9 +23,26 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: info.sender,
+            bonding_contract_addr: Addr::unchecked(""),
+            fee_collector_addr: Addr::unchecked(""),
+            grace_period: Uint64::one(),
+            epoch_config: EpochConfig {
+                duration: Uint64::new(86_400_000_000_000u64),
+                genesis_epoch: Uint64::new(env.block.time.nanos()),
+            },
+            distribution_asset: AssetInfo::NativeToken {
+                denom: "uwhale".to_string(),
+            },
+        },
+    )?;
+
     CURRENT_EPOCH.save(
         deps.storage,
         &white_whale_std::fee_distributor::Epoch {
@@ -46,17 +65,29 @@ pub fn execute(
     _info: MessageInfo,
     msg: white_whale_std::fee_distributor::ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    if let white_whale_std::fee_distributor::ExecuteMsg::NewEpoch {} = msg {
-        CURRENT_EPOCH.update(deps.storage, |epoch| -> StdResult<_> {
-            Ok(white_whale_std::fee_distributor::Epoch {
-                id: epoch.id + Uint64::one(),
-                start_time: epoch.start_time.plus_seconds(86400u64),
-                total: vec![],
-                available: vec![],
-                claimed: vec![],
-                global_index: Default::default(),
-            })
-        })?;
+    match msg {
+        white_whale_std::fee_distributor::ExecuteMsg::NewEpoch {} => {
+            CURRENT_EPOCH.update(deps.storage, |epoch| -> StdResult<_> {
+                Ok(white_whale_std::fee_distributor::Epoch {
+                    id: epoch.id + Uint64::one(),
+                    start_time: epoch.start_time.plus_seconds(86400u64),
+                    total: vec![],
+                    available: vec![],
+                    claimed: vec![],
+                    global_index: Default::default(),
+                })
+            })?;
+        }
+        white_whale_std::fee_distributor::ExecuteMsg::UpdateConfig {
+            bonding_contract_addr: Some(bonding_contract_addr),
+            ..
+        } => {
+            CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                config.bonding_contract_addr = deps.api.addr_validate(&bonding_contract_addr)?;
+                Ok(config)
+            })?;
+        }
+        _ => {}
     }
 
     Ok(Response::default())
@@ -69,7 +100,9 @@ pub fn query(
     msg: white_whale_std::fee_distributor::QueryMsg,
 ) -> StdResult<Binary> {
     match msg {
-        white_whale_std::fee_distributor::QueryMsg::Config {} => {}
+        white_whale_std::fee_distributor::QueryMsg::Config {} => {
+            return to_json_binary(&CONFIG.load(deps.storage)?);
+        }
         white_whale_std::fee_distributor::QueryMsg::CurrentEpoch {} => {
             return to_json_binary(&EpochResponse {
                 epoch: CURRENT_EPOCH.load(deps.storage)?,
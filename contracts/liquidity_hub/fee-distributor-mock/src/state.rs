@@ -1,4 +1,5 @@
 use cw_storage_plus::Item;
-use white_whale_std::fee_distributor::Epoch;
+use white_whale_std::fee_distributor::{Config, Epoch};
 
 pub const CURRENT_EPOCH: Item<Epoch> = Item::new("epoch");
+pub const CONFIG: Item<Config> = Item::new("config");
@@ -79,6 +79,7 @@ fn collect_fees_for_factory(
                     msg: to_json_binary(
                         &white_whale_std::vault_network::vault_factory::QueryMsg::Vaults {
                             start_after,
+                            start_after_identifier: None,
                             limit,
                         },
                     )?,
@@ -187,6 +188,7 @@ pub fn aggregate_fees(
                             msg: to_json_binary(
                                 &white_whale_std::vault_network::vault_factory::QueryMsg::Vaults {
                                     start_after,
+                                    start_after_identifier: None,
                                     limit,
                                 },
                             )?,
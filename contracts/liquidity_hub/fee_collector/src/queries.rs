@@ -117,6 +117,7 @@ fn query_fees_for_factory(
                     msg: to_json_binary(
                         &white_whale_std::vault_network::vault_factory::QueryMsg::Vaults {
                             start_after,
+                            start_after_identifier: None,
                             limit,
                         },
                     )?,
@@ -1,7 +1,14 @@
 use cosmwasm_std::testing::mock_info;
-use cosmwasm_std::{Addr, Coin, MessageInfo, Uint128};
+use cosmwasm_std::{coin, Addr, BlockInfo, Coin, Decimal, MessageInfo, Timestamp, Uint128, Uint64};
 use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
 
+use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
+use white_whale_std::fee::Fee;
+use white_whale_std::fee_collector::{ExecuteMsg, InstantiateMsg};
+use white_whale_std::pool_network;
+use white_whale_std::pool_network::asset::{Asset, AssetInfo, PairType};
+use white_whale_std::pool_network::pair::PoolFee;
+
 use crate::contract::{execute, instantiate, migrate, query, reply};
 
 use super::dummy_contract::create_dummy_flash_loan_contract;
@@ -78,7 +85,8 @@ pub fn store_whale_lair_code(app: &mut App) -> u64 {
             whale_lair::contract::instantiate,
             whale_lair::contract::query,
         )
-        .with_migrate(whale_lair::contract::migrate),
+        .with_migrate(whale_lair::contract::migrate)
+        .with_reply(whale_lair::contract::reply),
     );
 
     app.store_code(contract)
@@ -188,3 +196,327 @@ pub fn increase_allowance(app: &mut App, sender: Addr, contract_addr: Addr, spen
     )
     .unwrap();
 }
+
+/// The contracts taking part in the fee distribution, set up by [setup_fee_distribution]
+pub struct FeeDistributionSetup {
+    pub app: App,
+    pub creator: MessageInfo,
+    pub fee_distributor_address: Addr,
+    pub whale_lair_address: Addr,
+    pub pool_factory_address: Addr,
+    pub pool_router_address: Addr,
+    /// The uwhale/usdc pair swapped on to generate fees
+    pub pair_address: Addr,
+}
+
+/// Sets up the fee collector, fee distributor and whale lair together with a pool factory, a pool
+/// router and a vault factory, and provides liquidity into a uwhale/usdc pool. Both the creator
+/// and "other" are funded with usdc, uwhale, ampWHALE and bWHALE.
+///
+/// If `liquid_bonding` is set, the whale lair accepts uwhale bonds into its liquid bonding pool.
+pub fn setup_fee_distribution(liquid_bonding: bool) -> FeeDistributionSetup {
+    let creator = mock_creator();
+    let funds = vec![
+        coin(1_000_000_000, "usdc"),
+        coin(1_000_000_000, "uwhale"),
+        coin(1_000_000_000, "ampWHALE"),
+        coin(1_000_000_000, "bWHALE"),
+    ];
+    let mut app = mock_app_with_balance(vec![
+        (creator.sender.clone(), funds.clone()),
+        (Addr::unchecked("other"), funds),
+    ]);
+
+    let fee_collector_id = store_fee_collector_code(&mut app);
+    let fee_distributor_id = store_fee_distributor_code(&mut app);
+    let whale_lair_id = store_whale_lair_code(&mut app);
+    let pool_factory_id = store_pool_factory_code(&mut app);
+    let pool_router_id = store_pool_router_code(&mut app);
+    let pair_id = store_pair_code(&mut app);
+    let trio_id = store_trio_code(&mut app);
+    let token_id = store_token_code(&mut app);
+    let vault_factory_id = store_vault_factory_code(&mut app);
+    let vault_id = store_vault_code(&mut app);
+
+    let fee_collector_address = app
+        .instantiate_contract(
+            fee_collector_id,
+            creator.sender.clone(),
+            &InstantiateMsg {},
+            &[],
+            "fee_collector",
+            None,
+        )
+        .unwrap();
+
+    #[cfg(feature = "osmosis")]
+    let osmosis_fee_collector_address = app
+        .instantiate_contract(
+            fee_collector_id,
+            creator.sender.clone(),
+            &InstantiateMsg {},
+            &[],
+            "osmosis_fee_collector",
+            None,
+        )
+        .unwrap();
+
+    let pool_factory_address = app
+        .instantiate_contract(
+            pool_factory_id,
+            creator.sender.clone(),
+            &pool_network::factory::InstantiateMsg {
+                pair_code_id: pair_id,
+                trio_code_id: trio_id,
+                token_code_id: token_id,
+                fee_collector_addr: fee_collector_address.to_string(),
+                #[cfg(feature = "osmosis")]
+                osmosis_fee_collector_addr: osmosis_fee_collector_address.to_string(),
+            },
+            &[],
+            "pool_factory",
+            None,
+        )
+        .unwrap();
+
+    let pool_router_address = app
+        .instantiate_contract(
+            pool_router_id,
+            creator.sender.clone(),
+            &pool_network::router::InstantiateMsg {
+                terraswap_factory: pool_factory_address.to_string(),
+            },
+            &[],
+            "pool_router",
+            None,
+        )
+        .unwrap();
+
+    let vault_factory_address = app
+        .instantiate_contract(
+            vault_factory_id,
+            creator.sender.clone(),
+            &white_whale_std::vault_network::vault_factory::InstantiateMsg {
+                owner: creator.sender.to_string(),
+                vault_id,
+                token_id,
+                fee_collector_addr: fee_collector_address.to_string(),
+            },
+            &[],
+            "vault_factory",
+            None,
+        )
+        .unwrap();
+
+    let mut bonding_assets = vec![
+        AssetInfo::NativeToken {
+            denom: "ampWHALE".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "bWHALE".to_string(),
+        },
+    ];
+    let liquid_bonding = liquid_bonding.then(|| {
+        let asset = AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        };
+        bonding_assets.push(asset.clone());
+
+        white_whale_std::whale_lair::LiquidBondingInstantiateMsg {
+            asset,
+            token_code_id: token_id,
+        }
+    });
+
+    let whale_lair_address = app
+        .instantiate_contract(
+            whale_lair_id,
+            creator.sender.clone(),
+            &white_whale_std::whale_lair::InstantiateMsg {
+                unbonding_period: Uint64::new(1_000_000_000_000u64),
+                growth_rate: Decimal::one(),
+                bonding_assets,
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding,
+            },
+            &[],
+            "whale_lair",
+            None,
+        )
+        .unwrap();
+
+    let fee_distributor_address = app
+        .instantiate_contract(
+            fee_distributor_id,
+            creator.sender.clone(),
+            &white_whale_std::fee_distributor::InstantiateMsg {
+                bonding_contract_addr: whale_lair_address.to_string(),
+                fee_collector_addr: fee_collector_address.to_string(),
+                grace_period: Uint64::new(3u64),
+                epoch_config: EpochConfig {
+                    duration: Uint64::new(86_400_000_000_000u64), // a day
+                    genesis_epoch: Uint64::new(1_678_802_400_000_000_000u64), // March 14, 2023 2:00:00 PM
+                },
+                distribution_asset: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+            },
+            &[],
+            "fee_distributor",
+            None,
+        )
+        .unwrap();
+
+    // add the fee distributor address to the whale lair contract so we can use it as a clock
+    app.execute_contract(
+        creator.sender.clone(),
+        whale_lair_address.clone(),
+        &white_whale_std::whale_lair::ExecuteMsg::UpdateConfig {
+            fee_distributor_addr: Some(fee_distributor_address.to_string()),
+            owner: None,
+            unbonding_period: None,
+            growth_rate: None,
+        },
+        &[],
+    )
+    .unwrap();
+    // add pool router address to the fee collector to be able to aggregate fees
+    app.execute_contract(
+        creator.sender.clone(),
+        fee_collector_address,
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            pool_router: Some(pool_router_address.to_string()),
+            fee_distributor: Some(fee_distributor_address.to_string()),
+            pool_factory: Some(pool_factory_address.to_string()),
+            vault_factory: Some(vault_factory_address.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // add native tokens to the factory
+    for denom in ["uwhale", "usdc", "ampWHALE", "bWHALE"] {
+        app.execute_contract(
+            creator.sender.clone(),
+            pool_factory_address.clone(),
+            &pool_network::factory::ExecuteMsg::AddNativeTokenDecimals {
+                denom: denom.to_string(),
+                decimals: 6,
+            },
+            &[coin(1u128, denom)],
+        )
+        .unwrap();
+    }
+
+    // create the uwhale/usdc pool and provide liquidity into it
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "usdc".to_string(),
+        },
+    ];
+    let res = app
+        .execute_contract(
+            creator.sender.clone(),
+            pool_factory_address.clone(),
+            &pool_network::factory::ExecuteMsg::CreatePair {
+                asset_infos: asset_infos.clone(),
+                pool_fees: PoolFee {
+                    protocol_fee: Fee {
+                        share: Decimal::percent(5u64),
+                    },
+                    swap_fee: Fee {
+                        share: Decimal::percent(7u64),
+                    },
+                    burn_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                },
+                pair_type: PairType::ConstantProduct,
+                token_factory_lp: false,
+            },
+            &[],
+        )
+        .unwrap();
+    let pair_address = Addr::unchecked(
+        res.events
+            .last()
+            .unwrap()
+            .attributes
+            .get(1)
+            .unwrap()
+            .value
+            .clone(),
+    );
+
+    app.execute_contract(
+        creator.sender.clone(),
+        pair_address.clone(),
+        &pool_network::pair::ExecuteMsg::ProvideLiquidity {
+            assets: asset_infos.map(|info| Asset {
+                info,
+                amount: Uint128::new(500_000u128),
+            }),
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &[coin(500_000u128, "uwhale"), coin(500_000u128, "usdc")],
+    )
+    .unwrap();
+
+    FeeDistributionSetup {
+        app,
+        creator,
+        fee_distributor_address,
+        whale_lair_address,
+        pool_factory_address,
+        pool_router_address,
+        pair_address,
+    }
+}
+
+/// Generates some fees by swapping on the pair of the [FeeDistributionSetup], then creates a new
+/// epoch on the fee distributor, `epochs` times.
+pub fn generate_fees_and_new_epochs(setup: &mut FeeDistributionSetup, epochs: u64) {
+    for _ in 0..epochs {
+        setup
+            .app
+            .execute_contract(
+                setup.creator.sender.clone(),
+                setup.pair_address.clone(),
+                &pool_network::pair::ExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "usdc".to_string(),
+                        },
+                        amount: Uint128::new(200_000u128),
+                    },
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(40u64)),
+                    to: None,
+                },
+                &[coin(200_000u128, "usdc")],
+            )
+            .unwrap();
+
+        setup.app.set_block(BlockInfo {
+            height: 123_456_789u64,
+            time: Timestamp::from_nanos(1_678_888_800_000_000_000u64),
+            chain_id: "".to_string(),
+        });
+
+        setup
+            .app
+            .execute_contract(
+                setup.creator.sender.clone(),
+                setup.fee_distributor_address.clone(),
+                &white_whale_std::fee_distributor::ExecuteMsg::NewEpoch {},
+                &[],
+            )
+            .unwrap();
+    }
+}
@@ -28,10 +28,11 @@ use white_whale_std::whale_lair::BondingWeightResponse;
 use white_whale_std::{pool_network, vault_network};
 
 use crate::tests::common_integration::{
-    increase_allowance, mock_app, mock_app_with_balance, mock_creator,
-    store_dummy_flash_loan_contract, store_fee_collector_code, store_fee_distributor_code,
-    store_pair_code, store_pool_factory_code, store_pool_router_code, store_token_code,
-    store_trio_code, store_vault_code, store_vault_factory_code, store_whale_lair_code,
+    generate_fees_and_new_epochs, increase_allowance, mock_app, mock_app_with_balance,
+    mock_creator, setup_fee_distribution, store_dummy_flash_loan_contract,
+    store_fee_collector_code, store_fee_distributor_code, store_pair_code, store_pool_factory_code,
+    store_pool_router_code, store_token_code, store_trio_code, store_vault_code,
+    store_vault_factory_code, store_whale_lair_code,
 };
 use crate::ContractError;
 
@@ -2096,6 +2097,7 @@ fn collect_fees_for_vault() {
                         },
                     },
                     token_factory_lp: false,
+                    vault_identifier: None,
                 },
                 &[],
             )
@@ -2190,6 +2192,7 @@ fn collect_fees_for_vault() {
                     amount: coins(return_flash_loan_value, coin.denom.clone()),
                 })
                 .unwrap(),
+                initiator: None,
             },
             &[],
         )
@@ -2530,6 +2533,7 @@ fn aggregate_fees_for_vault() {
                         },
                     },
                     token_factory_lp: false,
+                    vault_identifier: None,
                 },
                 &[],
             )
@@ -2624,6 +2628,7 @@ fn aggregate_fees_for_vault() {
                     amount: coins(return_flash_loan_value, coin.denom.clone()),
                 })
                 .unwrap(),
+                initiator: None,
             },
             &[],
         )
@@ -3096,6 +3101,9 @@ fn collect_and_distribute_fees_successfully() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -3513,6 +3521,9 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -3725,6 +3736,8 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
                 },
                 amount: Uint128::new(1_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -3743,6 +3756,8 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
                 },
                 amount: Uint128::new(1_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -3920,7 +3935,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -3948,7 +3966,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -3971,6 +3992,8 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
                 },
                 amount: Uint128::new(500u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -4112,7 +4135,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -4139,7 +4165,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -4163,6 +4192,8 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
                 },
                 amount: Uint128::new(500u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -4243,7 +4274,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -4269,7 +4303,10 @@ fn collect_and_dist_fees_where_one_bonder_is_increasing_weight_no_claims_until_e
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -4486,6 +4523,9 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -4697,6 +4737,8 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
                 },
                 amount: Uint128::new(300_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "bWHALE".to_string(),
@@ -4715,6 +4757,8 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -4867,7 +4911,10 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -4885,7 +4932,10 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
         .execute_contract(
             creator.sender.clone(),
             fee_distributor_address.clone(),
-            &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+            &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+                max_epochs: None,
+                until_epoch: None,
+            },
             &[],
         )
         .unwrap_err();
@@ -4916,7 +4966,10 @@ fn collect_and_distribute_fees_with_expiring_epoch_successfully() {
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -5060,6 +5113,9 @@ fn create_epoch_unsuccessfully() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -5565,6 +5621,9 @@ fn decrease_grace_period_fee_distributor() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -5987,6 +6046,9 @@ fn users_cannot_claim_rewards_from_past_epochs() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -6198,6 +6260,8 @@ fn users_cannot_claim_rewards_from_past_epochs() {
                 },
                 amount: Uint128::new(300_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "bWHALE".to_string(),
@@ -6323,6 +6387,8 @@ fn users_cannot_claim_rewards_from_past_epochs() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -6554,6 +6620,9 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -6765,6 +6834,8 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
                 },
                 amount: Uint128::new(300_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "bWHALE".to_string(),
@@ -6783,6 +6854,8 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -6926,7 +6999,10 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7057,7 +7133,10 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7074,7 +7153,10 @@ fn user_can_claim_even_when_his_weight_increases_for_past_epochs() {
     app.execute_contract(
         Addr::unchecked("creator"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7213,6 +7295,9 @@ fn user_weight_accounts_for_unbondings() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -7426,6 +7511,8 @@ fn user_weight_accounts_for_unbondings() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "bWHALE".to_string(),
@@ -7444,6 +7531,8 @@ fn user_weight_accounts_for_unbondings() {
                 },
                 amount: Uint128::new(200_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -7462,6 +7551,8 @@ fn user_weight_accounts_for_unbondings() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -7605,7 +7696,10 @@ fn user_weight_accounts_for_unbondings() {
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7748,7 +7842,10 @@ fn user_weight_accounts_for_unbondings() {
     app.execute_contract(
         Addr::unchecked("creator"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7787,7 +7884,10 @@ fn user_weight_accounts_for_unbondings() {
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -7901,7 +8001,10 @@ fn user_weight_accounts_for_unbondings() {
     app.execute_contract(
         Addr::unchecked("creator"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -8069,6 +8172,9 @@ fn users_can_claim_even_when_global_index_was_taken_after_epoch_was_created() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -8287,6 +8393,8 @@ fn users_can_claim_even_when_global_index_was_taken_after_epoch_was_created() {
                 },
                 amount: Uint128::new(300_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "bWHALE".to_string(),
@@ -8398,6 +8506,8 @@ fn users_can_claim_even_when_global_index_was_taken_after_epoch_was_created() {
                     },
                     amount: Uint128::new(100_000_000u128),
                 },
+                lock_duration: None,
+                owner: None,
             },
             &[Coin {
                 denom: "ampWHALE".to_string(),
@@ -8449,6 +8559,8 @@ fn users_can_claim_even_when_global_index_was_taken_after_epoch_was_created() {
                 },
                 amount: Uint128::new(100_000_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -8591,6 +8703,9 @@ fn collect_distribute_with_unbonders() {
                         denom: "bWHALE".to_string(),
                     },
                 ],
+                weight_multipliers: None,
+                max_lock_duration: None,
+                liquid_bonding: None,
             },
             &[],
             "whale_lair",
@@ -8803,6 +8918,8 @@ fn collect_distribute_with_unbonders() {
                 },
                 amount: Uint128::new(1_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -8821,6 +8938,8 @@ fn collect_distribute_with_unbonders() {
                 },
                 amount: Uint128::new(1_000u128),
             },
+            lock_duration: None,
+            owner: None,
         },
         &[Coin {
             denom: "ampWHALE".to_string(),
@@ -9048,7 +9167,10 @@ fn collect_distribute_with_unbonders() {
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -9185,7 +9307,10 @@ fn collect_distribute_with_unbonders() {
     app.execute_contract(
         Addr::unchecked("other"),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -9210,7 +9335,10 @@ fn collect_distribute_with_unbonders() {
     app.execute_contract(
         creator.sender.clone(),
         fee_distributor_address.clone(),
-        &white_whale_std::fee_distributor::ExecuteMsg::Claim {},
+        &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+            max_epochs: None,
+            until_epoch: None,
+        },
         &[],
     )
     .unwrap();
@@ -9233,3 +9361,712 @@ fn collect_distribute_with_unbonders() {
 
     assert_ne!(user_2_whale_received, user_1_whale_received);
 }
+
+#[test]
+fn bonding_and_unbonding_claims_pending_rewards() {
+    let mut setup = setup_fee_distribution(false);
+    let creator = setup.creator.clone();
+    let whale_lair_address = setup.whale_lair_address.clone();
+    let fee_distributor_address = setup.fee_distributor_address.clone();
+
+    // bond some tokens with both users
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "bWHALE".to_string(),
+                    },
+                    amount: Uint128::new(300_000_000u128),
+                },
+                lock_duration: None,
+                owner: None,
+            },
+            &[Coin {
+                denom: "bWHALE".to_string(),
+                amount: Uint128::new(300_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other").clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "ampWHALE".to_string(),
+                    },
+                    amount: Uint128::new(100_000_000u128),
+                },
+                lock_duration: None,
+                owner: None,
+            },
+            &[Coin {
+                denom: "ampWHALE".to_string(),
+                amount: Uint128::new(100_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    // generate some fees and create two epochs
+    generate_fees_and_new_epochs(&mut setup, 2);
+
+    for address in ["creator", "other"] {
+        let claimable_epochs_res: ClaimableEpochsResponse = setup
+            .app
+            .wrap()
+            .query_wasm_smart(
+                fee_distributor_address.clone(),
+                &white_whale_std::fee_distributor::QueryMsg::Claimable {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(claimable_epochs_res.epochs.len(), 2);
+    }
+
+    // only the bonding contract can claim on behalf of others
+    let err = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            fee_distributor_address.clone(),
+            &white_whale_std::fee_distributor::ExecuteMsg::ClaimFor {
+                address: creator.sender.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<fee_distributor::ContractError>().unwrap(),
+        fee_distributor::ContractError::Unauthorized {}
+    );
+
+    // bonding more without claiming first settles the pending rewards
+    let uwhale_balance_before_bonding = setup
+        .app
+        .wrap()
+        .query_balance(creator.sender.clone(), "uwhale")
+        .unwrap()
+        .amount;
+
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "bWHALE".to_string(),
+                    },
+                    amount: Uint128::new(100_000_000u128),
+                },
+                lock_duration: None,
+                owner: None,
+            },
+            &[Coin {
+                denom: "bWHALE".to_string(),
+                amount: Uint128::new(100_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    let uwhale_balance_after_bonding = setup
+        .app
+        .wrap()
+        .query_balance(creator.sender.clone(), "uwhale")
+        .unwrap()
+        .amount;
+    assert!(uwhale_balance_after_bonding > uwhale_balance_before_bonding);
+
+    let bonded_res: white_whale_std::whale_lair::BondedResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::Bonded {
+                address: creator.sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bonded_res.total_bonded, Uint128::new(400_000_000u128));
+
+    // unbonding without claiming first settles the pending rewards as well
+    let uwhale_balance_before_unbonding = setup
+        .app
+        .wrap()
+        .query_balance("other", "uwhale")
+        .unwrap()
+        .amount;
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Unbond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "ampWHALE".to_string(),
+                    },
+                    amount: Uint128::new(50_000_000u128),
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+    let uwhale_balance_after_unbonding = setup
+        .app
+        .wrap()
+        .query_balance("other", "uwhale")
+        .unwrap()
+        .amount;
+    assert!(uwhale_balance_after_unbonding > uwhale_balance_before_unbonding);
+
+    let bonded_res: white_whale_std::whale_lair::BondedResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::Bonded {
+                address: "other".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bonded_res.total_bonded, Uint128::new(50_000_000u128));
+
+    // nothing is left to claim for either user
+    for address in ["creator", "other"] {
+        let claimable_epochs_res: ClaimableEpochsResponse = setup
+            .app
+            .wrap()
+            .query_wasm_smart(
+                fee_distributor_address.clone(),
+                &white_whale_std::fee_distributor::QueryMsg::Claimable {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        assert!(claimable_epochs_res.epochs.is_empty());
+    }
+}
+
+#[test]
+fn liquid_bonding_compounds_rewards() {
+    let mut setup = setup_fee_distribution(true);
+    let creator = setup.creator.clone();
+    let whale_lair_address = setup.whale_lair_address.clone();
+    let fee_distributor_address = setup.fee_distributor_address.clone();
+
+    // bond some tokens, part of them in the liquid bonding pool
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "bWHALE".to_string(),
+                    },
+                    amount: Uint128::new(300_000_000u128),
+                },
+                lock_duration: None,
+                owner: None,
+            },
+            &[Coin {
+                denom: "bWHALE".to_string(),
+                amount: Uint128::new(300_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::LiquidBond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uwhale".to_string(),
+                    },
+                    amount: Uint128::new(100_000_000u128),
+                },
+            },
+            &[Coin {
+                denom: "uwhale".to_string(),
+                amount: Uint128::new(100_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    let liquid_bonding_res: white_whale_std::whale_lair::LiquidBondingResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::LiquidBonding {},
+        )
+        .unwrap();
+    assert_eq!(
+        liquid_bonding_res.total_shares,
+        Uint128::new(100_000_000u128)
+    );
+    assert_eq!(
+        liquid_bonding_res.bonded_amount,
+        Uint128::new(100_000_000u128)
+    );
+    assert_eq!(liquid_bonding_res.exchange_rate, Decimal::one());
+    let receipt_token = liquid_bonding_res.receipt_token;
+
+    // generate some fees and create two epochs
+    generate_fees_and_new_epochs(&mut setup, 2);
+
+    // the rewards of the pool are bonded back into it, raising the exchange rate
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Compound {},
+            &[],
+        )
+        .unwrap();
+
+    let liquid_bonding_res: white_whale_std::whale_lair::LiquidBondingResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::LiquidBonding {},
+        )
+        .unwrap();
+    assert_eq!(
+        liquid_bonding_res.total_shares,
+        Uint128::new(100_000_000u128)
+    );
+    assert!(liquid_bonding_res.bonded_amount > Uint128::new(100_000_000u128));
+    assert!(liquid_bonding_res.exchange_rate > Decimal::one());
+    let compounded_amount = liquid_bonding_res.bonded_amount;
+
+    let claimable_epochs_res: ClaimableEpochsResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            fee_distributor_address.clone(),
+            &white_whale_std::fee_distributor::QueryMsg::Claimable {
+                address: whale_lair_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(claimable_epochs_res.epochs.is_empty());
+
+    // nothing is left to compound
+    let err = setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Compound {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<whale_lair::ContractError>().unwrap(),
+        whale_lair::ContractError::NothingToCompound {}
+    );
+
+    // new receipt tokens are minted at the current exchange rate
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::LiquidBond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uwhale".to_string(),
+                    },
+                    amount: Uint128::new(compounded_amount.u128()),
+                },
+            },
+            &[Coin {
+                denom: "uwhale".to_string(),
+                amount: Uint128::new(compounded_amount.u128()),
+            }],
+        )
+        .unwrap();
+
+    let liquid_bonding_res: white_whale_std::whale_lair::LiquidBondingResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::LiquidBonding {},
+        )
+        .unwrap();
+    assert_eq!(
+        liquid_bonding_res.total_shares,
+        Uint128::new(200_000_000u128)
+    );
+    assert_eq!(
+        liquid_bonding_res.bonded_amount,
+        compounded_amount.checked_mul(Uint128::new(2u128)).unwrap()
+    );
+
+    let balance_res: BalanceResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            receipt_token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "other".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance_res.balance, Uint128::new(200_000_000u128));
+}
+
+#[test]
+fn claim_sends_rewards_to_reward_recipient() {
+    let mut setup = setup_fee_distribution(false);
+    let creator = setup.creator.clone();
+    let whale_lair_address = setup.whale_lair_address.clone();
+    let fee_distributor_address = setup.fee_distributor_address.clone();
+
+    // bond on behalf of another address, which sends its rewards to a treasury
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "bWHALE".to_string(),
+                    },
+                    amount: Uint128::new(300_000_000u128),
+                },
+                lock_duration: None,
+                owner: Some("other".to_string()),
+            },
+            &[Coin {
+                denom: "bWHALE".to_string(),
+                amount: Uint128::new(300_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::SetRewardRecipient {
+                recipient: Some("treasury".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let bonded_res: white_whale_std::whale_lair::BondedResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::Bonded {
+                address: "other".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bonded_res.total_bonded, Uint128::new(300_000_000u128));
+
+    // generate some fees and create two epochs
+    generate_fees_and_new_epochs(&mut setup, 2);
+
+    let other_balance_before_claiming = setup
+        .app
+        .wrap()
+        .query_balance("other", "uwhale")
+        .unwrap()
+        .amount;
+
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("other"),
+            fee_distributor_address.clone(),
+            &white_whale_std::fee_distributor::ExecuteMsg::Claim {
+                max_epochs: None,
+                until_epoch: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // the rewards went to the treasury instead of the bonder
+    let other_balance_after_claiming = setup
+        .app
+        .wrap()
+        .query_balance("other", "uwhale")
+        .unwrap()
+        .amount;
+    assert_eq!(other_balance_after_claiming, other_balance_before_claiming);
+
+    let treasury_balance = setup
+        .app
+        .wrap()
+        .query_balance("treasury", "uwhale")
+        .unwrap()
+        .amount;
+    assert!(treasury_balance > Uint128::zero());
+}
+
+#[test]
+fn bonds_are_converted_through_the_pool_router() {
+    let mut setup = setup_fee_distribution(false);
+    let creator = setup.creator.clone();
+    let whale_lair_address = setup.whale_lair_address.clone();
+    let pool_factory_address = setup.pool_factory_address.clone();
+    let pool_router_address = setup.pool_router_address.clone();
+
+    // create the ampWHALE/bWHALE pool and provide liquidity into it
+    let res = setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            pool_factory_address.clone(),
+            &CreatePair {
+                asset_infos: [
+                    AssetInfo::NativeToken {
+                        denom: "ampWHALE".to_string(),
+                    },
+                    AssetInfo::NativeToken {
+                        denom: "bWHALE".to_string(),
+                    },
+                ],
+                pool_fees: PoolFee {
+                    protocol_fee: Fee {
+                        share: Decimal::permille(1u64),
+                    },
+                    swap_fee: Fee {
+                        share: Decimal::permille(2u64),
+                    },
+                    burn_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                },
+                pair_type: PairType::ConstantProduct,
+                token_factory_lp: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let pair_address = Addr::unchecked(
+        res.events
+            .last()
+            .unwrap()
+            .attributes
+            .clone()
+            .get(1)
+            .unwrap()
+            .clone()
+            .value,
+    );
+
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            pair_address,
+            &pool_network::pair::ExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "ampWHALE".to_string(),
+                        },
+                        amount: Uint128::new(500_000_000u128),
+                    },
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "bWHALE".to_string(),
+                        },
+                        amount: Uint128::new(500_000_000u128),
+                    },
+                ],
+                slippage_tolerance: None,
+                receiver: None,
+            },
+            &[
+                Coin {
+                    denom: "ampWHALE".to_string(),
+                    amount: Uint128::new(500_000_000u128),
+                },
+                Coin {
+                    denom: "bWHALE".to_string(),
+                    amount: Uint128::new(500_000_000u128),
+                },
+            ],
+        )
+        .unwrap();
+
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::Bond {
+                asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "ampWHALE".to_string(),
+                    },
+                    amount: Uint128::new(1_000_000u128),
+                },
+                lock_duration: None,
+                owner: None,
+            },
+            &[Coin {
+                denom: "ampWHALE".to_string(),
+                amount: Uint128::new(1_000_000u128),
+            }],
+        )
+        .unwrap();
+
+    // let the weight of the bond grow
+    setup
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(100u64));
+
+    let route = vec![SwapOperation::TerraSwap {
+        offer_asset_info: AssetInfo::NativeToken {
+            denom: "ampWHALE".to_string(),
+        },
+        ask_asset_info: AssetInfo::NativeToken {
+            denom: "bWHALE".to_string(),
+        },
+    }];
+    let convert_bond_msg =
+        |minimum_receive: Uint128| white_whale_std::whale_lair::ExecuteMsg::ConvertBond {
+            from_denom: "ampWHALE".to_string(),
+            to_denom: "bWHALE".to_string(),
+            route: route.clone(),
+            amount: None,
+            minimum_receive,
+        };
+
+    // the pool router must be set by the owner first
+    let err = setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &convert_bond_msg(Uint128::new(900_000u128)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<whale_lair::ContractError>().unwrap(),
+        whale_lair::ContractError::BondConversionDisabled {}
+    );
+
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::ExecuteMsg::SetPoolRouter {
+                pool_router_addr: Some(pool_router_address.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let simulation: pool_network::router::SimulateSwapOperationsResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            pool_router_address,
+            &pool_network::router::QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::new(1_000_000u128),
+                operations: route.clone(),
+            },
+        )
+        .unwrap();
+
+    let weight_query = white_whale_std::whale_lair::QueryMsg::Weight {
+        address: creator.sender.to_string(),
+        timestamp: None,
+        global_index: None,
+    };
+    let weight_before: BondingWeightResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(whale_lair_address.clone(), &weight_query)
+        .unwrap();
+
+    // the conversion fails if less than the minimum is received
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &convert_bond_msg(simulation.amount + Uint128::one()),
+            &[],
+        )
+        .unwrap_err();
+
+    setup
+        .app
+        .execute_contract(
+            creator.sender.clone(),
+            whale_lair_address.clone(),
+            &convert_bond_msg(simulation.amount),
+            &[],
+        )
+        .unwrap();
+
+    let bonded_res: white_whale_std::whale_lair::BondedResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            whale_lair_address.clone(),
+            &white_whale_std::whale_lair::QueryMsg::Bonded {
+                address: creator.sender.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        bonded_res.bonded_assets,
+        vec![Asset {
+            info: AssetInfo::NativeToken {
+                denom: "bWHALE".to_string(),
+            },
+            amount: simulation.amount,
+        }]
+    );
+
+    // the weight accumulated by the bond is kept in proportion to the amount received
+    let weight_after: BondingWeightResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(whale_lair_address, &weight_query)
+        .unwrap();
+    assert_eq!(
+        weight_after.weight,
+        weight_before
+            .weight
+            .multiply_ratio(simulation.amount, Uint128::new(1_000_000u128))
+    );
+    assert_eq!(weight_after.global_weight, weight_after.weight);
+    assert!(weight_after.weight > simulation.amount);
+}
@@ -1,13 +1,13 @@
 use cosmwasm_std::{
-    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, ReplyOn, Response,
-    StdError, SubMsg, Timestamp, Uint64, WasmMsg, WasmQuery,
+    to_json_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, ReplyOn, Response,
+    SubMsg, Timestamp, Uint64, WasmMsg, WasmQuery,
 };
 use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
 
-use white_whale_std::fee_distributor::Epoch;
+use white_whale_std::fee_distributor::{ClaimResponse, Epoch, EpochClaim};
 use white_whale_std::pool_network::asset;
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
-use white_whale_std::whale_lair::{BondingWeightResponse, QueryMsg};
+use white_whale_std::whale_lair::{BondingWeightResponse, QueryMsg, RewardRecipientResponse};
 
 use crate::contract::EPOCH_CREATION_REPLY_ID;
 use crate::helpers::{validate_epoch_config, validate_grace_period};
@@ -74,100 +74,170 @@ pub fn create_new_epoch(deps: DepsMut, env: Env) -> Result<Response, ContractErr
         ]))
 }
 
-/// Claims pending rewards for the sender.
-pub fn claim(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    // Query the fee share of the sender based on the ratio of his weight and the global weight at the current moment
+/// Claims pending rewards for the sender, from up to the given number of epochs and up to the
+/// given epoch if set.
+pub fn claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_epochs: Option<u64>,
+    until_epoch: Option<Uint64>,
+) -> Result<Response, ContractError> {
+    if max_epochs == Some(0) {
+        return Err(ContractError::InvalidMaxEpochs {});
+    }
+
+    claim_rewards(deps, info.sender, max_epochs, until_epoch)
+}
+
+/// Claims pending rewards on behalf of the given address, sending them to its reward recipient.
+/// Only the bonding contract can claim on behalf of others, so rewards are settled before the bonds
+/// change.
+pub fn claim_for(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.bonding_contract_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    claim_rewards(deps, address, None, None)
+}
+
+/// Claims pending rewards for the given address, oldest epochs first, from up to the given number
+/// of epochs and up to the given epoch if set. Epochs whose rewards exceed their available fees are
+/// skipped instead of failing the whole claim, which happens when the weight of the address grew in
+/// relation to the global weight after the epoch was created, i.e. when others unbonded. A skipped
+/// epoch is left untouched, so its fees are forwarded to a new epoch once it expires.
+fn claim_rewards(
+    deps: DepsMut,
+    address: Addr,
+    max_epochs: Option<u64>,
+    until_epoch: Option<Uint64>,
+) -> Result<Response, ContractError> {
+    // Query the fee share of the address based on the ratio of its weight and the global weight at the current moment
     let config = CONFIG.load(deps.storage)?;
 
-    let claimable_epochs = query_claimable(deps.as_ref(), &info.sender)?.epochs;
+    let mut claimable_epochs = query_claimable(deps.as_ref(), &address)?.epochs;
+    claimable_epochs
+        .retain(|epoch| until_epoch.map_or(true, |until_epoch| epoch.id <= until_epoch));
     if claimable_epochs.is_empty() {
         return Err(ContractError::NothingToClaim {});
     }
 
+    // claim the oldest epochs first, so the newer ones can be claimed later on
+    claimable_epochs.reverse();
+    if let Some(max_epochs) = max_epochs {
+        claimable_epochs.truncate(usize::try_from(max_epochs).unwrap_or(usize::MAX));
+    }
+
     let mut claimable_fees = vec![];
+    let mut epoch_claims = vec![];
     for mut epoch in claimable_epochs.clone() {
         let bonding_weight_response: BondingWeightResponse =
             deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: config.bonding_contract_addr.to_string(),
                 msg: to_json_binary(&QueryMsg::Weight {
-                    address: info.sender.to_string(),
+                    address: address.to_string(),
                     timestamp: Some(epoch.start_time),
                     global_index: Some(epoch.global_index.clone()),
                 })?,
             }))?;
 
+        let mut rewards = vec![];
         for fee in epoch.total.iter() {
             let reward = fee
                 .amount
                 .checked_mul_floor(bonding_weight_response.share)?;
 
-            if reward.is_zero() {
-                // nothing to claim
-                continue;
-            }
-            // make sure the reward is sound
-            let _ = epoch
-                .available
-                .iter()
-                .find(|available_fee| available_fee.info == fee.info)
-                .map(|available_fee| {
-                    if reward > available_fee.amount {
-                        //todo maybe we can just skip this epoch and log something on the attributes instead
-                        // of returning an error and blocking the whole operation
-                        // this would "solve" the case when users unbond and then those who have not claimed
-                        // past epochs won't be able to do it as their rewards exceed the available claimable fees
-                        // cuz their weight increased in relation to the global weight
-                        return Err(ContractError::InvalidReward {});
-                    }
-                    Ok(())
-                })
-                .ok_or_else(|| StdError::generic_err("Invalid fee"))?;
-
-            // add the reward to the claimable fees
-            claimable_fees = asset::aggregate_assets(
-                claimable_fees,
-                vec![Asset {
+            if !reward.is_zero() {
+                rewards.push(Asset {
                     info: fee.info.clone(),
                     amount: reward,
-                }],
-            )?;
-
-            // modify the epoch to reflect the new available and claimed amount
-            for available_fee in epoch.available.iter_mut() {
-                if available_fee.info == fee.info {
-                    available_fee.amount = available_fee.amount.checked_sub(reward)?;
-                }
+                });
             }
+        }
 
-            if epoch.claimed.is_empty() {
-                epoch.claimed = vec![Asset {
-                    info: fee.info.clone(),
-                    amount: reward,
-                }];
-            } else {
-                for claimed_fee in epoch.claimed.iter_mut() {
-                    if claimed_fee.info == fee.info {
-                        claimed_fee.amount = claimed_fee.amount.checked_add(reward)?;
-                    }
-                }
-            }
+        // make sure the rewards are sound, skipping the epoch otherwise
+        let is_sound = rewards.iter().all(|reward| {
+            epoch.available.iter().any(|available_fee| {
+                available_fee.info == reward.info && available_fee.amount >= reward.amount
+            })
+        });
+
+        if !is_sound {
+            epoch_claims.push(EpochClaim {
+                epoch_id: epoch.id,
+                rewards: vec![],
+                skipped: true,
+            });
+            continue;
+        }
 
+        if !rewards.is_empty() {
+            // modify the epoch to reflect the new available and claimed amount
+            epoch.available = asset::deduct_assets(epoch.available, rewards.clone())?;
+            epoch.claimed = asset::aggregate_assets(epoch.claimed, rewards.clone())?;
             EPOCHS.save(deps.storage, &epoch.id.to_be_bytes(), &epoch)?;
+
+            // add the rewards to the claimable fees
+            claimable_fees = asset::aggregate_assets(claimable_fees, rewards.clone())?;
         }
+
+        epoch_claims.push(EpochClaim {
+            epoch_id: epoch.id,
+            rewards,
+            skipped: false,
+        });
     }
 
-    // update the last claimed epoch for the user
-    LAST_CLAIMED_EPOCH.save(deps.storage, &info.sender, &claimable_epochs[0].id)?;
+    // update the last claimed epoch for the user, which is the newest epoch claimed or skipped
+    if let Some(last_epoch) = claimable_epochs.last() {
+        LAST_CLAIMED_EPOCH.save(deps.storage, &address, &last_epoch.id)?;
+    }
+
+    // send funds to the reward recipient of the user, which is the user unless set otherwise
+    let reward_recipient: RewardRecipientResponse = deps.querier.query_wasm_smart(
+        config.bonding_contract_addr.to_string(),
+        &QueryMsg::RewardRecipient {
+            address: address.to_string(),
+        },
+    )?;
+    let recipient = deps.api.addr_validate(&reward_recipient.recipient)?;
 
-    // send funds to the user
     let mut messages = vec![];
-    for fee in claimable_fees {
-        messages.push(fee.into_msg(info.sender.clone())?);
+    for fee in claimable_fees.iter() {
+        messages.push(fee.clone().into_msg(recipient.clone())?);
+    }
+
+    let mut attributes = vec![
+        ("action", "claim".to_string()),
+        ("address", address.to_string()),
+        ("recipient", recipient.to_string()),
+    ];
+    // log the ids of the claimed and skipped epochs, attribute values can't be empty
+    for (key, skipped) in [("claimed_epochs", false), ("skipped_epochs", true)] {
+        let epoch_ids = epoch_claims
+            .iter()
+            .filter(|epoch_claim| epoch_claim.skipped == skipped)
+            .map(|epoch_claim| epoch_claim.epoch_id.to_string())
+            .collect::<Vec<_>>();
+
+        if !epoch_ids.is_empty() {
+            attributes.push((key, epoch_ids.join(", ")));
+        }
     }
 
     Ok(Response::new()
-        .add_attributes(vec![("action", "claim")])
-        .add_messages(messages))
+        .add_attributes(attributes)
+        .add_messages(messages)
+        .set_data(to_json_binary(&ClaimResponse {
+            recipient: recipient.to_string(),
+            rewards: claimable_fees,
+            epochs: epoch_claims,
+        })?))
 }
 
 /// Updates the [Config] of the contract
@@ -129,7 +129,11 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::NewEpoch {} => commands::create_new_epoch(deps, env),
-        ExecuteMsg::Claim {} => commands::claim(deps, info),
+        ExecuteMsg::Claim {
+            max_epochs,
+            until_epoch,
+        } => commands::claim(deps, info, max_epochs, until_epoch),
+        ExecuteMsg::ClaimFor { address } => commands::claim_for(deps, info, address),
         ExecuteMsg::UpdateConfig {
             owner,
             bonding_contract_addr,
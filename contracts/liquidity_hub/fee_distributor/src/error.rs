@@ -31,8 +31,8 @@ pub enum ContractError {
     #[error("There are no claimable rewards.")]
     NothingToClaim {},
 
-    #[error("The rewards cannot exceed the available claimable fees.")]
-    InvalidReward {},
+    #[error("The maximum number of epochs to claim must be greater than zero.")]
+    InvalidMaxEpochs {},
 
     #[error("The current epoch epoch has not expired yet.")]
     CurrentEpochNotExpired {},
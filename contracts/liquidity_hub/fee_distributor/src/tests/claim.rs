@@ -1,8 +1,14 @@
-use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::Uint64;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    coins, from_json, Addr, Attribute, BankMsg, CosmosMsg, Decimal, Timestamp, Uint128, Uint64,
+};
+
+use white_whale_std::fee_distributor::{ClaimResponse, Epoch, EpochClaim};
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 
 use crate::tests::robot::TestingRobot;
 use crate::tests::test_helpers;
+use crate::ContractError;
 
 #[test]
 fn test_claimable_epochs() {
@@ -29,3 +35,154 @@ fn test_claimable_epochs() {
             }
         });
 }
+
+fn uwhale(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn epoch(id: u64, total: u128, available: u128) -> Epoch {
+    Epoch {
+        id: Uint64::new(id),
+        start_time: Timestamp::from_seconds(1678726800 + id * 86_400),
+        total: vec![uwhale(total)],
+        available: vec![uwhale(available)],
+        claimed: vec![],
+        global_index: Default::default(),
+    }
+}
+
+#[test]
+fn test_claim_max_epochs() {
+    let mut robot = TestingRobot::new(mock_dependencies(), mock_env());
+
+    robot
+        .instantiate_default()
+        .mock_bonding_contract(Decimal::percent(10u64))
+        .add_epochs_to_state(vec![epoch(1, 1_000, 1_000), epoch(2, 2_000, 2_000)])
+        .claim(mock_info("bonder", &[]), Some(0u64), None, |res| {
+            assert_eq!(res.unwrap_err(), ContractError::InvalidMaxEpochs {});
+        })
+        // the oldest epoch is claimed first
+        .claim(mock_info("bonder", &[]), Some(1u64), None, |res| {
+            let res = res.unwrap();
+            assert_eq!(
+                from_json::<ClaimResponse>(res.data.unwrap()).unwrap(),
+                ClaimResponse {
+                    recipient: "bonder".to_string(),
+                    rewards: vec![uwhale(100)],
+                    epochs: vec![EpochClaim {
+                        epoch_id: Uint64::new(1u64),
+                        rewards: vec![uwhale(100)],
+                        skipped: false,
+                    }],
+                }
+            );
+            assert_eq!(
+                res.messages[0].msg,
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "bonder".to_string(),
+                    amount: coins(100u128, "uwhale"),
+                })
+            );
+        })
+        .query_claimable_epochs(Some(Addr::unchecked("bonder")), |res| {
+            let (_, epochs) = res.unwrap();
+            assert_eq!(epochs, vec![epoch(2, 2_000, 2_000)]);
+        })
+        .claim(mock_info("bonder", &[]), None, None, |res| {
+            let data: ClaimResponse = from_json(res.unwrap().data.unwrap()).unwrap();
+            assert_eq!(data.rewards, vec![uwhale(200)]);
+        })
+        .query_epoch(Uint64::new(2u64), |res| {
+            let (_, epoch) = res.unwrap();
+            assert_eq!(epoch.available, vec![uwhale(1_800)]);
+            assert_eq!(epoch.claimed, vec![uwhale(200)]);
+        })
+        .claim(mock_info("bonder", &[]), None, None, |res| {
+            assert_eq!(res.unwrap_err(), ContractError::NothingToClaim {});
+        });
+}
+
+#[test]
+fn test_claim_until_epoch() {
+    let mut robot = TestingRobot::new(mock_dependencies(), mock_env());
+
+    robot
+        .instantiate_default()
+        .mock_bonding_contract(Decimal::percent(10u64))
+        .add_epochs_to_state(vec![epoch(1, 1_000, 1_000), epoch(2, 2_000, 2_000)])
+        // no claimable epoch is that old
+        .claim(
+            mock_info("bonder", &[]),
+            None,
+            Some(Uint64::zero()),
+            |res| {
+                assert_eq!(res.unwrap_err(), ContractError::NothingToClaim {});
+            },
+        )
+        .claim(mock_info("bonder", &[]), None, Some(Uint64::one()), |res| {
+            let data: ClaimResponse = from_json(res.unwrap().data.unwrap()).unwrap();
+            assert_eq!(data.rewards, vec![uwhale(100)]);
+            assert_eq!(data.epochs.len(), 1);
+            assert_eq!(data.epochs[0].epoch_id, Uint64::one());
+        })
+        .query_claimable_epochs(Some(Addr::unchecked("bonder")), |res| {
+            let (_, epochs) = res.unwrap();
+            assert_eq!(epochs, vec![epoch(2, 2_000, 2_000)]);
+        });
+}
+
+#[test]
+fn test_claim_skips_unsound_epochs() {
+    let mut robot = TestingRobot::new(mock_dependencies(), mock_env());
+
+    robot
+        .instantiate_default()
+        .mock_bonding_contract(Decimal::percent(50u64))
+        // the reward of the first epoch exceeds what is left of it
+        .add_epochs_to_state(vec![epoch(1, 1_000, 400), epoch(2, 2_000, 2_000)])
+        .claim(mock_info("bonder", &[]), None, None, |res| {
+            let res = res.unwrap();
+            assert_eq!(
+                from_json::<ClaimResponse>(res.data.unwrap()).unwrap(),
+                ClaimResponse {
+                    recipient: "bonder".to_string(),
+                    rewards: vec![uwhale(1_000)],
+                    epochs: vec![
+                        EpochClaim {
+                            epoch_id: Uint64::new(1u64),
+                            rewards: vec![],
+                            skipped: true,
+                        },
+                        EpochClaim {
+                            epoch_id: Uint64::new(2u64),
+                            rewards: vec![uwhale(1_000)],
+                            skipped: false,
+                        },
+                    ],
+                }
+            );
+            assert!(res
+                .attributes
+                .contains(&Attribute::new("skipped_epochs", "1")));
+            assert!(res
+                .attributes
+                .contains(&Attribute::new("claimed_epochs", "2")));
+        })
+        // the skipped epoch is left untouched, its fees are forwarded to a new epoch once it
+        // expires instead of going to whoever claims first
+        .query_epoch(Uint64::new(1u64), |res| {
+            let (_, epoch) = res.unwrap();
+            assert_eq!(epoch.available, vec![uwhale(400)]);
+            assert!(epoch.claimed.is_empty());
+        })
+        .query_claimable_epochs(Some(Addr::unchecked("bonder")), |res| {
+            let (_, epochs) = res.unwrap();
+            assert!(epochs.is_empty());
+        });
+}
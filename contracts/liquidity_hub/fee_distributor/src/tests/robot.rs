@@ -1,14 +1,16 @@
 #![allow(dead_code)]
 use cosmwasm_std::testing::{mock_info, MockApi, MockQuerier, MockStorage};
 use cosmwasm_std::{
-    from_json, Addr, Empty, Env, MessageInfo, OwnedDeps, Response, StdResult, Uint64,
+    from_json, to_json_binary, Addr, ContractResult, Decimal, Empty, Env, MessageInfo, OwnedDeps,
+    Response, StdResult, SystemError, SystemResult, Uint64, WasmQuery,
 };
 use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
 
 use white_whale_std::fee_distributor::{
     ClaimableEpochsResponse, Config, Epoch, EpochResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
 };
-use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{BondedResponse, BondingWeightResponse, RewardRecipientResponse};
 
 use crate::contract::{execute, instantiate, query};
 use crate::state::{get_expiring_epoch, EPOCHS, LAST_CLAIMED_EPOCH};
@@ -121,6 +123,70 @@ impl TestingRobot {
         self
     }
 
+    /// Mocks the queries to the bonding contract, so every address has bonded since before the
+    /// first epoch with the given share of the weight, and gets its own rewards.
+    pub(crate) fn mock_bonding_contract(&mut self, share: Decimal) -> &mut Self {
+        self.owned_deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                return SystemResult::Err(SystemError::Unknown {});
+            };
+
+            let res = match from_json(msg).unwrap() {
+                white_whale_std::whale_lair::QueryMsg::Bonded { .. } => {
+                    to_json_binary(&BondedResponse {
+                        total_bonded: Default::default(),
+                        bonded_assets: vec![Asset {
+                            info: AssetInfo::NativeToken {
+                                denom: "ampWHALE".to_string(),
+                            },
+                            amount: Default::default(),
+                        }],
+                        first_bonded_epoch_id: Uint64::zero(),
+                    })
+                }
+                white_whale_std::whale_lair::QueryMsg::Weight {
+                    address, timestamp, ..
+                } => to_json_binary(&BondingWeightResponse {
+                    address,
+                    weight: Default::default(),
+                    global_weight: Default::default(),
+                    share,
+                    timestamp: timestamp.unwrap_or_default(),
+                }),
+                white_whale_std::whale_lair::QueryMsg::RewardRecipient { address } => {
+                    to_json_binary(&RewardRecipientResponse { recipient: address })
+                }
+                _ => return SystemResult::Err(SystemError::Unknown {}),
+            };
+
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+
+        self
+    }
+
+    pub(crate) fn claim(
+        &mut self,
+        info: MessageInfo,
+        max_epochs: Option<u64>,
+        until_epoch: Option<Uint64>,
+        response: impl Fn(Result<Response, ContractError>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::Claim {
+            max_epochs,
+            until_epoch,
+        };
+
+        response(execute(
+            self.owned_deps.as_mut(),
+            self.env.clone(),
+            info,
+            msg,
+        ));
+
+        self
+    }
+
     pub(crate) fn update_config(
         &mut self,
         info: MessageInfo,
@@ -10,8 +10,10 @@ pub(crate) fn get_epochs() -> Vec<Epoch> {
             global_index: GlobalIndex {
                 weight: Uint128::from(1u128),
                 bonded_amount: Default::default(),
+                weighted_amount: Default::default(),
                 bonded_assets: vec![],
                 timestamp: Default::default(),
+                vote_escrow: None,
             },
             id: Uint64::new(1u64),
             start_time: Timestamp::from_seconds(1678726800),
@@ -62,8 +64,10 @@ pub(crate) fn get_epochs() -> Vec<Epoch> {
             global_index: GlobalIndex {
                 weight: Uint128::from(1u128),
                 bonded_amount: Default::default(),
+                weighted_amount: Default::default(),
                 bonded_assets: vec![],
                 timestamp: Default::default(),
+                vote_escrow: None,
             },
             id: Uint64::new(2u64),
             start_time: Timestamp::from_seconds(1678813200),
@@ -85,8 +89,10 @@ pub(crate) fn get_epochs() -> Vec<Epoch> {
             global_index: GlobalIndex {
                 weight: Uint128::from(1u128),
                 bonded_amount: Default::default(),
+                weighted_amount: Default::default(),
                 bonded_assets: vec![],
                 timestamp: Default::default(),
+                vote_escrow: None,
             },
             id: Uint64::new(3u64),
             start_time: Timestamp::from_seconds(1678899600),
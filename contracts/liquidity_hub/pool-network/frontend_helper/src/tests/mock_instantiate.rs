@@ -162,6 +162,9 @@ pub fn app_mock_instantiate(app: &mut App, pool_assets: [AssetInfo; 2]) -> AppIn
                 max_unbonding_duration: 100000,
                 min_unbonding_duration: 86400,
                 fee_distributor_addr: fee_distributor.to_string(),
+                emergency_unlock_penalty: Decimal::percent(5),
+                max_emergency_unlock_penalty: Decimal::percent(50),
+                max_boost: Decimal::one(),
             },
             &[],
             "mock incentive factory",
@@ -1,237 +1,220 @@
-use cosmwasm_std::{
-    to_json_binary, BankMsg, Coin, CosmosMsg, Decimal256, DepsMut, MessageInfo, StdError, Uint128,
-    Uint256, WasmMsg,
-};
+use std::collections::hash_map::Entry;
 
-use white_whale_std::pool_network::asset::AssetInfo;
+use cosmwasm_std::{Addr, Decimal256, Deps, DepsMut, Order, StdResult, Uint128, Uint256};
 
-use crate::helpers::{get_flow_asset_amount_at_epoch, get_flow_current_end_epoch};
-use crate::state::{EpochId, ADDRESS_WEIGHT_HISTORY, GLOBAL_WEIGHT_SNAPSHOT, LAST_CLAIMED_EPOCH};
-use crate::{error::ContractError, helpers, state::FLOWS};
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::pool_network::incentive::Flow;
 
-//todo abstract code in this function as most of it is also used in get_rewards.rs
+use crate::helpers::get_flow_end_epoch;
+use crate::state::{
+    EpochId, FlowId, GLOBAL_WEIGHT_SNAPSHOT, LAST_CLAIMED_EPOCH, LAST_CLAIMED_FLOW_EPOCH,
+};
+use crate::{error::ContractError, helpers, state::FLOWS};
 
+/// The default amount of epochs claimed for each flow in a single claim. If there are more epochs
+/// to claim, the claim can be resumed in a following transaction.
+///
+/// The cap also applies to the claims done on behalf of the user, e.g. when transferring a
+/// position, so they can't run out of gas. That's intended: the weight history of the epochs left
+/// unclaimed is kept, so they stay claimable until the user's claim catches up.
 pub const EPOCH_CLAIM_CAP: u64 = 100u64;
 
-#[allow(unused_assignments)]
-/// Performs the claim function, returning all the [`CosmosMsg`]'s to run.
-pub fn claim(deps: &mut DepsMut, info: &MessageInfo) -> Result<Vec<CosmosMsg>, ContractError> {
-    let address = info.sender.clone();
+/// Performs the claim function for the given address, returning the rewards claimed on each flow.
+///
+/// Claims the rewards of every flow up to `until_epoch`, or the current epoch if not set, claiming at
+/// most `max_epochs` epochs for each flow. The progress is stored for every flow, so the claim can
+/// be resumed where it left off.
+pub fn claim(
+    deps: &mut DepsMut,
+    address: &Addr,
+    until_epoch: Option<u64>,
+    max_epochs: Option<u64>,
+) -> Result<Vec<Asset>, ContractError> {
     let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
-    let last_claimed_epoch = LAST_CLAIMED_EPOCH.may_load(deps.storage, &address)?;
+    let target_epoch = until_epoch.unwrap_or(current_epoch).min(current_epoch);
+    let max_epochs = max_epochs.unwrap_or(EPOCH_CLAIM_CAP);
+    if max_epochs == 0 {
+        return Err(ContractError::InvalidMaxEpochs {});
+    }
+
+    let last_claimed_epoch = LAST_CLAIMED_EPOCH.may_load(deps.storage, address)?;
 
     // Check if the user ever claimed before
     if let Some(last_claimed_epoch) = last_claimed_epoch {
-        // if the last claimed epoch is the same as the current epoch, then there is nothing to claim
-        if current_epoch == last_claimed_epoch {
+        // if the user already claimed up to the target epoch, then there is nothing to claim
+        if target_epoch <= last_claimed_epoch {
             return Err(ContractError::NothingToClaim {});
         }
     }
 
-    // calculate flow rewards
-    let mut messages: Vec<CosmosMsg> = vec![];
-    let mut flows = helpers::get_available_flows(deps.as_ref(), &current_epoch)?;
-
-    // last_user_weight_seen is a helper variable to keep track of the last user weight seen
-    let mut last_epoch_user_weight_update: EpochId = 0u64;
-    let mut last_user_weight_seen: Uint128 = Uint128::zero();
-    //let mut last_user_weight_seen: (EpochId, Uint128) = (064, Uint128::zero());
-    for flow in flows.iter_mut() {
-        let expanded_default_values = (flow.flow_asset.amount, flow.end_epoch);
+    let mut rewards: Vec<Asset> = vec![];
+    let mut flows = helpers::get_available_flows(deps.as_ref(), &target_epoch)?;
 
-        let (_, (expanded_asset_amount, expanded_end_epoch)) = flow
-            .asset_history
-            .last_key_value()
-            .unwrap_or((&0u64, &expanded_default_values));
+    // the epoch up to which all the flows have been claimed
+    let mut settled_epoch = target_epoch;
 
-        // check if flow already ended and if everything has been claimed for that flow.
-        if current_epoch > *expanded_end_epoch && flow.claimed_amount == expanded_asset_amount {
-            // if so, skip flow.
+    for flow in flows.iter_mut() {
+        if is_flow_depleted(flow, current_epoch) {
             continue;
         }
 
-        // reset last_epoch_user_weight_update and last_user_weight_seen
-        last_epoch_user_weight_update = 0u64;
-        last_user_weight_seen = Uint128::zero();
+        let first_epoch =
+            get_first_claimable_epoch(deps.as_ref(), address, flow, last_claimed_epoch)?;
+        let expanded_end_epoch = get_flow_end_epoch(flow);
 
-        // check what is the earliest available weight for the user
-        let earliest_available_weight_for_user =
-            helpers::get_earliest_available_weight_snapshot_for_user(deps.as_ref(), &&address)?;
+        // skip the flow if it has been claimed up to the target epoch, or it has finished
+        if first_epoch > target_epoch || first_epoch >= expanded_end_epoch {
+            continue;
+        }
 
-        if !earliest_available_weight_for_user.is_empty() {
-            (last_epoch_user_weight_update, last_user_weight_seen) =
-                earliest_available_weight_for_user[0];
+        let flow_target_epoch = target_epoch.min(expanded_end_epoch - 1u64);
+        let last_epoch = flow_target_epoch.min(first_epoch.saturating_add(max_epochs - 1u64));
+        if last_epoch < flow_target_epoch {
+            // the budget ran out before reaching the target for this flow
+            settled_epoch = settled_epoch.min(last_epoch);
         }
 
-        let first_claimable_epoch = if let Some(last_claimed_epoch) = last_claimed_epoch {
-            // start claiming from the last claimed epoch + 1
-            last_claimed_epoch + 1u64
-        } else {
-            // the user never claimed before
-            if flow.start_epoch > last_epoch_user_weight_update {
-                // it means the user locked tokens before the flow started. Start from there just to get
-                // the ADDRESS_WEIGHT_HISTORY right
-                last_epoch_user_weight_update
-            } else {
-                // it means the user locked tokens after the flow started, and last_epoch_user_weight_update has a value
-                flow.start_epoch
-            }
-        };
-
-        let mut epoch_count = 0;
-
-        // calculate the total reward for this flow, from the first claimable epoch to the current epoch
-        for epoch_id in first_claimable_epoch..=current_epoch {
-            epoch_count += 1;
-
-            if epoch_count > EPOCH_CLAIM_CAP {
-                break;
-            }
-
-            // check if the flow is active in this epoch
-            if epoch_id < flow.start_epoch {
-                // the flow is not active yet, skip
-                continue;
-            } else if epoch_id >= *expanded_end_epoch {
-                // this flow has finished
-                // todo maybe we should make end_epoch inclusive?
-                break;
-            }
-
-            // calculate emissions per epoch
-            let emitted_tokens = if flow.emitted_tokens.is_empty() {
-                // if the emitted_tokens map is empty, it means that this is the first time we
-                // are calculating an emission for this flow, return zero
-                Uint128::zero()
-            } else {
-                // otherwise we want to return the last emission, since this is used in the formula
-                // default to zero if the emission is not found, i.e. for cases when someone is claiming
-                // the very first epoch for the flow after someone else and there's already an
-                // emission stored in the map. So defaulting to zero emulates the case when the if
-                // statement above is true.
-                let previous_emission = *flow
-                    .emitted_tokens
-                    .get(&(epoch_id.saturating_sub(1u64)))
-                    .unwrap_or(&Uint128::zero());
-
-                previous_emission
-            };
-
-            // use the flow asset amount at the current epoch considering flow expansions
-            let flow_asset_amount = get_flow_asset_amount_at_epoch(flow, epoch_id);
-            let flow_expanded_end_epoch = get_flow_current_end_epoch(flow, epoch_id);
-
-            // emission = (total_tokens_for_epoch_considering_expansion - emitted_tokens_at_epoch) / (flow_start + flow_duration - epoch) = (total_tokens - emitted_tokens_at_epoch) / (flow_end - epoch)
-            let emission_per_epoch = flow_asset_amount
-                .saturating_sub(emitted_tokens)
-                .checked_div(Uint128::from(flow_expanded_end_epoch - epoch_id))?;
-
-            // record the emitted tokens for this epoch if it hasn't been recorded before.
-            // emitted tokens for this epoch is the total emitted tokens in previous epoch + the ones
-            // that where emitted in this epoch
-            if flow.emitted_tokens.get(&epoch_id).is_none() {
-                flow.emitted_tokens
-                    .insert(epoch_id, emission_per_epoch.checked_add(emitted_tokens)?);
-            }
-
-            // get user weight for this epoch
-            let user_weight_at_epoch =
-                ADDRESS_WEIGHT_HISTORY.may_load(deps.storage, (&address.clone(), epoch_id))?;
-
-            // this is done this way because we don't save the weight for every single epoch for the user,
-            // but rather keep a registry on when it changes. So we need to check if the user has a weight
-            // registered for this epoch, and if not, use the last one that was recorded since it means
-            // it hasn't changed since then.
-            let user_weight = if let Some(user_weight_at_epoch) = user_weight_at_epoch {
-                (last_epoch_user_weight_update, last_user_weight_seen) =
-                    (epoch_id, user_weight_at_epoch);
-                user_weight_at_epoch
-            } else if last_epoch_user_weight_update != 0u64
-                && last_epoch_user_weight_update <= epoch_id
-            {
-                // we have a weight for this user for a previous epoch, but not this one, so we use
-                // the last we saw as that's the current one
-                last_user_weight_seen
-            } else {
-                // we don't have a weight for this user for this epoch, or any previous epoch, so we
-                // skip this epoch. i.e. the user's weight is 0 for this epoch
-                continue;
-            };
-
-            // get global weight for this epoch
-            let global_weight_at_epoch = GLOBAL_WEIGHT_SNAPSHOT
-                .may_load(deps.storage, epoch_id)?
-                .unwrap_or_default();
-
-            if global_weight_at_epoch == Uint128::zero() {
-                // Nothing to compute here as the global weight is 0, we don't want to divide by 0
-                continue;
-            }
-
-            // calculate user share for this epoch
-            let user_share_at_epoch = Decimal256::from_ratio(user_weight, global_weight_at_epoch);
-            let user_reward_at_epoch: Uint128 =
-                (Uint256::from_uint128(emission_per_epoch) * user_share_at_epoch).try_into()?;
-
-            // sanity check for user_reward_at_epoch
-            if user_reward_at_epoch > emission_per_epoch
-                || user_reward_at_epoch.checked_add(flow.claimed_amount)? > *expanded_asset_amount
-            {
-                return Err(ContractError::InvalidReward {});
-            }
-
-            if user_reward_at_epoch.is_zero() {
-                // we don't want to construct a transfer message for the user
-                continue;
-            }
+        let reward = compute_flow_rewards(deps.as_ref(), address, flow, first_epoch, last_epoch)?;
+
+        LAST_CLAIMED_FLOW_EPOCH.save(deps.storage, (address, flow.flow_id), &last_epoch)?;
 
+        if !reward.is_zero() {
             // increase the amount of tokens claimed on this flow
-            flow.claimed_amount = flow.claimed_amount.checked_add(user_reward_at_epoch)?;
-
-            // construct transfer message for user
-            match &flow.flow_asset.info {
-                AssetInfo::NativeToken { denom } => messages.push(
-                    BankMsg::Send {
-                        to_address: address.clone().into_string(),
-                        amount: vec![Coin {
-                            amount: user_reward_at_epoch,
-                            denom: denom.to_owned(),
-                        }],
-                    }
-                    .into(),
-                ),
-                AssetInfo::Token { contract_addr } => messages.push(
-                    WasmMsg::Execute {
-                        contract_addr: contract_addr.to_owned(),
-                        msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
-                            recipient: address.clone().into_string(),
-                            amount: user_reward_at_epoch,
-                        })?,
-                        funds: vec![],
-                    }
-                    .into(),
-                ),
-            }
+            flow.claimed_amount = flow.claimed_amount.checked_add(reward)?;
+
+            rewards.push(Asset {
+                info: flow.flow_asset.info.clone(),
+                amount: reward,
+            });
         }
 
         // save current flow state
         FLOWS.save(deps.storage, (flow.start_epoch, flow.flow_id), flow)?;
     }
 
-    // now update the weight history for the users, deleting all the entries from the past since
-    // they are useless now since the user already claimed those epochs
-    helpers::delete_weight_history_for_user(deps, &&address)?;
+    // store the epoch up to which all the flows were claimed for the user, the flows that were
+    // claimed further keep their own cursor
+    LAST_CLAIMED_EPOCH.save(deps.storage, address, &settled_epoch)?;
+
+    let settled_flows = LAST_CLAIMED_FLOW_EPOCH
+        .prefix(address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((flow_id, epoch)) if epoch <= settled_epoch => Some(Ok(flow_id)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<StdResult<Vec<FlowId>>>()?;
+    for flow_id in settled_flows {
+        LAST_CLAIMED_FLOW_EPOCH.remove(deps.storage, (address, flow_id));
+    }
+
+    // the weight history before the settled epoch is not needed anymore
+    helpers::compact_weight_history_for_user(deps, address, settled_epoch + 1u64)?;
 
-    // update the last seen weight for the user, storing what the weight is gonna be from the next
-    // epoch (since current epoch was just claimed)
-    ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
-        deps.storage,
-        (&address, current_epoch + 1u64),
-        |_| Ok(last_user_weight_seen),
-    )?;
+    Ok(rewards)
+}
+
+/// Checks whether the flow has ended and everything on it has been claimed.
+pub fn is_flow_depleted(flow: &Flow, current_epoch: u64) -> bool {
+    let expanded_default_values = (flow.flow_asset.amount, flow.end_epoch);
+
+    let (_, (expanded_asset_amount, expanded_end_epoch)) = flow
+        .asset_history
+        .last_key_value()
+        .unwrap_or((&0u64, &expanded_default_values));
+
+    current_epoch > *expanded_end_epoch && flow.claimed_amount == expanded_asset_amount
+}
 
-    // store last claimed epoch for the user
-    LAST_CLAIMED_EPOCH.save(deps.storage, &address, &current_epoch)?;
+/// Gets the first epoch the address can claim rewards for on the given flow.
+pub fn get_first_claimable_epoch(
+    deps: Deps,
+    address: &Addr,
+    flow: &Flow,
+    last_claimed_epoch: Option<EpochId>,
+) -> Result<EpochId, ContractError> {
+    let last_claimed_flow_epoch =
+        LAST_CLAIMED_FLOW_EPOCH.may_load(deps.storage, (address, flow.flow_id))?;
+
+    let first_epoch = match last_claimed_flow_epoch.max(last_claimed_epoch) {
+        // resume from where the user left off
+        Some(last_claimed_epoch) => last_claimed_epoch + 1u64,
+        // the user never claimed before, start from the first epoch the user had weight
+        None => helpers::get_earliest_available_weight_snapshot_for_user(deps, &address)?
+            .first()
+            .map(|(epoch_id, _)| *epoch_id)
+            .unwrap_or(flow.start_epoch),
+    };
+
+    Ok(first_epoch.max(flow.start_epoch))
+}
+
+/// Computes the rewards of the address on the given flow from `first_epoch` up to `last_epoch`,
+/// both inclusive. The emissions of the flow for those epochs are recorded in the flow.
+pub fn compute_flow_rewards(
+    deps: Deps,
+    address: &Addr,
+    flow: &mut Flow,
+    first_epoch: EpochId,
+    last_epoch: EpochId,
+) -> Result<Uint128, ContractError> {
+    let expanded_asset_amount = helpers::get_flow_asset_amount_at_epoch(flow, u64::MAX);
+    let expanded_end_epoch = get_flow_end_epoch(flow);
+
+    let mut total_reward = Uint128::zero();
+
+    for epoch_id in first_epoch..=last_epoch {
+        // check if the flow is active in this epoch
+        if epoch_id < flow.start_epoch {
+            // the flow is not active yet, skip
+            continue;
+        } else if epoch_id >= expanded_end_epoch {
+            // this flow has finished
+            break;
+        }
+
+        let emitted_tokens = helpers::get_emitted_tokens_before_epoch(flow, epoch_id)?;
+        let emission_per_epoch =
+            helpers::get_flow_emission_at_epoch(flow, epoch_id, emitted_tokens)?;
+
+        // record the emitted tokens for this epoch if it hasn't been recorded before.
+        // emitted tokens for this epoch is the total emitted tokens in previous epoch + the ones
+        // that where emitted in this epoch
+        if let Entry::Vacant(entry) = flow.emitted_tokens.entry(epoch_id) {
+            entry.insert(emission_per_epoch.checked_add(emitted_tokens)?);
+        }
+
+        let user_weight = helpers::get_address_weight_at_epoch(deps, address, epoch_id)?;
+        if user_weight.is_zero() {
+            // the user had no weight in this epoch
+            continue;
+        }
+
+        // get global weight for this epoch
+        let global_weight_at_epoch = GLOBAL_WEIGHT_SNAPSHOT
+            .may_load(deps.storage, epoch_id)?
+            .unwrap_or_default();
+
+        if global_weight_at_epoch == Uint128::zero() {
+            // Nothing to compute here as the global weight is 0, we don't want to divide by 0
+            continue;
+        }
+
+        // calculate user share for this epoch
+        let user_share_at_epoch = Decimal256::from_ratio(user_weight, global_weight_at_epoch);
+        let user_reward_at_epoch: Uint128 =
+            (Uint256::from_uint128(emission_per_epoch) * user_share_at_epoch).try_into()?;
+
+        total_reward = total_reward.checked_add(user_reward_at_epoch)?;
+
+        // sanity check for user_reward_at_epoch
+        if user_reward_at_epoch > emission_per_epoch
+            || total_reward.checked_add(flow.claimed_amount)? > expanded_asset_amount
+        {
+            return Err(ContractError::InvalidReward {});
+        }
+    }
 
-    Ok(messages)
+    Ok(total_reward)
 }
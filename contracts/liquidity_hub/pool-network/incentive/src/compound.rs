@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, Decimal, Deps, Env, StdResult, WasmMsg};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo, PairInfo};
+use white_whale_std::pool_network::incentive::{AutoCompound, CallbackMsg, ExecuteMsg};
+use white_whale_std::pool_network::pair;
+
+use crate::error::ContractError;
+use crate::state::{CONFIG, OPEN_POSITIONS};
+
+/// The max spread of the compounding swaps and provision when the auto-compound preference doesn't
+/// set one. The claim can be timed to sandwich them, so it's kept tight.
+pub const DEFAULT_COMPOUND_MAX_SPREAD: Decimal = Decimal::permille(5);
+
+/// Creates the messages to compound the rewards into the owner's position.
+///
+/// Half of the rewards in any of the pair's assets are swapped for the other asset of the pair.
+/// The assets are then provided as liquidity with a [CallbackMsg::ProvideCompoundedRewards], so
+/// the amounts provided are computed against the reserves of the pair after the swaps. The
+/// rewards that can't be compounded are sent to the owner. Without a max spread in the
+/// auto-compound preference, [DEFAULT_COMPOUND_MAX_SPREAD] applies.
+pub fn compound_rewards_msgs(
+    deps: Deps,
+    env: &Env,
+    owner: &Addr,
+    rewards: Vec<Asset>,
+    auto_compound: AutoCompound,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let unbonding_duration = auto_compound.unbonding_duration;
+    let max_spread = Some(
+        auto_compound
+            .max_spread
+            .unwrap_or(DEFAULT_COMPOUND_MAX_SPREAD),
+    );
+
+    // the position to add the LP tokens to must exist
+    OPEN_POSITIONS
+        .may_load(deps.storage, owner.clone())?
+        .unwrap_or_default()
+        .iter()
+        .find(|position| position.unbonding_duration == unbonding_duration)
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let pair_address = get_pair_address(deps, &config.lp_asset)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair_address, &pair::QueryMsg::Pair {})
+        .map_err(|_| ContractError::AutoCompoundUnavailable {})?;
+
+    // aggregate the rewards by asset, as multiple flows can distribute the same asset
+    let mut aggregated_rewards: BTreeMap<String, Asset> = BTreeMap::new();
+    for reward in rewards {
+        aggregated_rewards
+            .entry(reward.info.to_string())
+            .and_modify(|asset| asset.amount += reward.amount)
+            .or_insert(reward);
+    }
+
+    // the balances of the pair's assets held by the contract, not counting the rewards
+    let mut balances_before = pair_info
+        .asset_infos
+        .iter()
+        .map(|asset_info| {
+            Ok(Asset {
+                info: asset_info.clone(),
+                amount: asset_info.query_pool(
+                    &deps.querier,
+                    deps.api,
+                    env.contract.address.clone(),
+                )?,
+            })
+        })
+        .collect::<StdResult<Vec<Asset>>>()?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut compounded = false;
+
+    for reward in aggregated_rewards.into_values() {
+        let Some(index) = pair_info
+            .asset_infos
+            .iter()
+            .position(|asset_info| asset_info.equal(&reward.info))
+        else {
+            // the reward is not one of the pair's assets, send it to the owner
+            messages.push(reward.into_msg(owner.clone())?);
+            continue;
+        };
+
+        balances_before[index].amount = balances_before[index].amount.checked_sub(reward.amount)?;
+
+        let offer_asset = Asset {
+            info: reward.info.clone(),
+            amount: reward.amount.multiply_ratio(1u128, 2u128),
+        };
+        let simulation: pair::SimulationResponse = deps.querier.query_wasm_smart(
+            &pair_address,
+            &pair::QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+            },
+        )?;
+
+        if offer_asset.amount.is_zero() || simulation.return_amount.is_zero() {
+            // the reward is too small to be provided as liquidity, send it to the owner
+            messages.push(reward.into_msg(owner.clone())?);
+            continue;
+        }
+
+        messages.push(swap_msg(&pair_address, offer_asset, max_spread)?);
+        compounded = true;
+    }
+
+    if compounded {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Callback(
+                    CallbackMsg::ProvideCompoundedRewards {
+                        owner: owner.clone(),
+                        unbonding_duration,
+                        max_spread,
+                        balances_before,
+                    },
+                ))?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    Ok(messages)
+}
+
+/// Creates the messages to provide the pair's assets received since `balances_before` was
+/// recorded as liquidity, and to add the resulting LP tokens to the owner's position with a
+/// [CallbackMsg::CompoundRewards].
+///
+/// The assets are provided at the ratio of the current reserves of the pair, the amounts that
+/// don't fit it are sent to the owner.
+pub fn provide_compounded_rewards_msgs(
+    deps: Deps,
+    env: &Env,
+    owner: &Addr,
+    unbonding_duration: u64,
+    max_spread: Option<Decimal>,
+    balances_before: Vec<Asset>,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pair_address = get_pair_address(deps, &config.lp_asset)?;
+    let pool: pair::PoolResponse = deps
+        .querier
+        .query_wasm_smart(&pair_address, &pair::QueryMsg::Pool {})?;
+
+    let mut assets = vec![];
+    let mut reserves = vec![];
+    for balance_before in balances_before {
+        let balance = balance_before.info.query_pool(
+            &deps.querier,
+            deps.api,
+            env.contract.address.clone(),
+        )?;
+        reserves.push(
+            pool.assets
+                .iter()
+                .find(|asset| asset.info.equal(&balance_before.info))
+                .map(|asset| asset.amount)
+                .unwrap_or_default(),
+        );
+        assets.push(Asset {
+            info: balance_before.info,
+            amount: balance.checked_sub(balance_before.amount)?,
+        });
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    if !reserves.iter().any(|reserve| reserve.is_zero()) {
+        let provided = [
+            assets[0]
+                .amount
+                .min(assets[1].amount.multiply_ratio(reserves[0], reserves[1])),
+            assets[1]
+                .amount
+                .min(assets[0].amount.multiply_ratio(reserves[1], reserves[0])),
+        ];
+
+        if !provided[0].is_zero() && !provided[1].is_zero() {
+            let lp_balance_before = config.lp_asset.query_pool(
+                &deps.querier,
+                deps.api,
+                env.contract.address.clone(),
+            )?;
+
+            messages.extend(provide_liquidity_msgs(
+                &pair_address,
+                vec![
+                    Asset {
+                        info: assets[0].info.clone(),
+                        amount: provided[0],
+                    },
+                    Asset {
+                        info: assets[1].info.clone(),
+                        amount: provided[1],
+                    },
+                ],
+                max_spread,
+            )?);
+
+            for (asset, provided) in assets.iter_mut().zip(provided) {
+                asset.amount = asset.amount.checked_sub(provided)?;
+            }
+
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::CompoundRewards {
+                        owner: owner.clone(),
+                        unbonding_duration,
+                        lp_balance_before,
+                    }))?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+    }
+
+    // send what couldn't be provided as liquidity to the owner
+    for asset in assets {
+        if !asset.amount.is_zero() {
+            messages.push(asset.into_msg(owner.clone())?);
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Gets the address of the pair the LP asset belongs to. The pair is the minter of cw20 LP tokens,
+/// and the creator of token factory LP tokens.
+fn get_pair_address(deps: Deps, lp_asset: &AssetInfo) -> Result<Addr, ContractError> {
+    let pair_address = match lp_asset {
+        AssetInfo::Token { contract_addr } => {
+            let minter: Option<MinterResponse> = deps
+                .querier
+                .query_wasm_smart(contract_addr, &Cw20QueryMsg::Minter {})?;
+            minter.map(|minter| minter.minter)
+        }
+        AssetInfo::NativeToken { denom } => denom
+            .strip_prefix("factory/")
+            .and_then(|denom| denom.split('/').next())
+            .map(|creator| creator.to_string()),
+    }
+    .ok_or(ContractError::AutoCompoundUnavailable {})?;
+
+    Ok(deps.api.addr_validate(&pair_address)?)
+}
+
+/// Creates the message to swap the given asset on the pair.
+fn swap_msg(
+    pair_address: &Addr,
+    offer_asset: Asset,
+    max_spread: Option<Decimal>,
+) -> StdResult<CosmosMsg> {
+    Ok(match &offer_asset.info {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: pair_address.to_string(),
+            msg: to_json_binary(&pair::ExecuteMsg::Swap {
+                offer_asset: offer_asset.clone(),
+                belief_price: None,
+                max_spread,
+                to: None,
+            })?,
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: offer_asset.amount,
+            }],
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: pair_address.to_string(),
+                amount: offer_asset.amount,
+                msg: to_json_binary(&pair::Cw20HookMsg::Swap {
+                    belief_price: None,
+                    max_spread,
+                    to: None,
+                })?,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+/// Creates the messages to provide the given assets as liquidity to the pair.
+fn provide_liquidity_msgs(
+    pair_address: &Addr,
+    assets: Vec<Asset>,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for asset in &assets {
+        match &asset.info {
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            }),
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_address.to_string(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: pair_address.to_string(),
+            msg: to_json_binary(&pair::ExecuteMsg::ProvideLiquidity {
+                assets: [assets[0].clone(), assets[1].clone()],
+                slippage_tolerance,
+                receiver: None,
+            })?,
+            funds,
+        }
+        .into(),
+    );
+
+    Ok(messages)
+}
@@ -79,6 +79,10 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::TakeGlobalWeightSnapshot {} => execute::take_global_weight_snapshot(deps),
+        ExecuteMsg::EpochChangedHook(msg) => execute::epoch_changed_hook(deps, info, msg),
+        ExecuteMsg::UpdateEpochManager { epoch_manager_addr } => {
+            execute::update_epoch_manager(deps, info, epoch_manager_addr)
+        }
         ExecuteMsg::OpenFlow {
             start_epoch,
             end_epoch,
@@ -111,13 +115,64 @@ pub fn execute(
         ExecuteMsg::ClosePosition { unbonding_duration } => {
             execute::close_position(deps, env, info, unbonding_duration)
         }
+        ExecuteMsg::TransferPosition {
+            unbonding_duration,
+            recipient,
+        } => execute::transfer_position(deps, info, unbonding_duration, recipient),
         ExecuteMsg::Withdraw {} => execute::withdraw(deps, env, info),
-        ExecuteMsg::Claim {} => execute::claim(deps, info),
+        ExecuteMsg::EmergencyUnlock { unbonding_duration } => {
+            execute::emergency_unlock(deps, env, info, unbonding_duration)
+        }
+        ExecuteMsg::PokeBoost { address } => execute::poke_boost(deps, address),
+        ExecuteMsg::TokenizePosition { unbonding_duration } => {
+            execute::tokenize_position(deps, info, unbonding_duration)
+        }
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => execute::transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute::send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute::approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute::revoke(deps, env, info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute::approve_all(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => execute::revoke_all(deps, info, operator),
+        ExecuteMsg::Burn { token_id } => execute::burn(deps, env, info, token_id),
+        ExecuteMsg::Claim {
+            until_epoch,
+            max_epochs,
+            owner,
+            auto_compound,
+        } => execute::claim(
+            deps,
+            env,
+            info,
+            until_epoch,
+            max_epochs,
+            owner,
+            auto_compound,
+        ),
+        ExecuteMsg::UpdateClaimOperator {
+            operator,
+            auto_compound,
+        } => execute::update_claim_operator(deps, info, operator, auto_compound),
         ExecuteMsg::ExpandFlow {
             flow_identifier,
             end_epoch,
             flow_asset,
         } => execute::expand_flow(deps, info, env, flow_identifier, end_epoch, flow_asset),
+        ExecuteMsg::Callback(msg) => execute::callback(deps, env, info, msg),
     }
 }
 
@@ -154,6 +209,94 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         QueryMsg::CurrentEpochRewardsShare { address } => Ok(to_json_binary(
             &queries::get_rewards_share(deps, deps.api.addr_validate(&address)?)?,
         )?),
+        QueryMsg::ClaimOperator { address } => Ok(to_json_binary(&queries::get_claim_operator(
+            deps, address,
+        )?)?),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => Ok(to_json_binary(&queries::get_owner_of(
+            deps,
+            env,
+            token_id,
+            include_expired,
+        )?)?),
+        QueryMsg::NftInfo { token_id } => {
+            Ok(to_json_binary(&queries::get_nft_info(deps, token_id)?)?)
+        }
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::get_tokens(
+            deps,
+            owner,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::Approval {
+            token_id,
+            spender,
+            include_expired,
+        } => Ok(to_json_binary(&queries::get_approval(
+            deps,
+            env,
+            token_id,
+            spender,
+            include_expired,
+        )?)?),
+        QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        } => Ok(to_json_binary(&queries::get_approvals(
+            deps,
+            env,
+            token_id,
+            include_expired,
+        )?)?),
+        QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::get_all_operators(
+            deps,
+            env,
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::NumTokens {} => Ok(to_json_binary(&queries::get_num_tokens(deps)?)?),
+        QueryMsg::ContractInfo {} => Ok(to_json_binary(&queries::get_contract_info())?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => Ok(to_json_binary(&queries::get_all_nft_info(
+            deps,
+            env,
+            token_id,
+            include_expired,
+        )?)?),
+        QueryMsg::AllTokens { start_after, limit } => Ok(to_json_binary(
+            &queries::get_all_tokens(deps, start_after, limit)?,
+        )?),
+        QueryMsg::ActiveFlows {
+            flow_asset,
+            creator,
+            start_epoch,
+            end_epoch,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&queries::get_active_flows(
+            deps,
+            flow_asset,
+            creator,
+            start_epoch,
+            end_epoch,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
@@ -54,6 +54,9 @@ pub enum ContractError {
     #[error("Specified flow asset was not transferred to incentive contract")]
     FlowAssetNotSent,
 
+    #[error("The asset {asset} is not whitelisted to open flows with")]
+    FlowAssetNotWhitelisted { asset: String },
+
     #[error("Flow end timestamp was set to a time in the past")]
     FlowExpirationInPast,
 
@@ -145,6 +148,9 @@ pub enum ContractError {
     #[error("There's nothing to claim for this address")]
     NothingToClaim {},
 
+    #[error("The maximum amount of epochs to claim must be at least one")]
+    InvalidMaxEpochs {},
+
     #[error("There're pending rewards to be claimed before you can execute this action")]
     PendingRewards {},
 
@@ -153,6 +159,38 @@ pub enum ContractError {
 
     #[error("The flow has already ended, can't be expanded")]
     FlowAlreadyEnded {},
+
+    #[error("The half life of an exponential decay curve must be at least one epoch")]
+    InvalidHalfLife {},
+
+    #[error(
+        "The step schedule must be sorted by epoch, without duplicates, and have a non-zero weight"
+    )]
+    InvalidStepSchedule {},
+
+    #[error("The rewards can't be auto-compounded, the LP asset is not tied to a pair")]
+    AutoCompoundUnavailable {},
+
+    #[error("A position can't be transferred to its own owner")]
+    InvalidTransferRecipient {},
+
+    #[error("The position with unbonding_duration of {unbonding_duration} is already tokenized")]
+    PositionAlreadyTokenized { unbonding_duration: u64 },
+
+    #[error("The position NFT {token_id} does not exist")]
+    NonExistentPositionToken { token_id: String },
+
+    #[error("The approval has already expired")]
+    ExpiredApproval {},
+
+    #[error("There is no approval of {spender} on the position NFT {token_id}")]
+    ApprovalNotFound { spender: String, token_id: String },
+
+    #[error("Callbacks can only be invoked by the contract itself")]
+    ExternalCallback {},
+
+    #[error("There are no unbonding positions to unlock")]
+    NothingToUnlock {},
 }
 
 impl From<semver::Error> for ContractError {
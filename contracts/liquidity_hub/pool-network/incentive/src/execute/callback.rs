@@ -0,0 +1,90 @@
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::pool_network::incentive::CallbackMsg;
+
+use crate::error::ContractError;
+use crate::execute::expand_position::add_to_position;
+use crate::state::CONFIG;
+
+/// Handles the callback messages, which can only be invoked by the contract itself.
+pub fn callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::ExternalCallback {});
+    }
+
+    match msg {
+        CallbackMsg::CompoundRewards {
+            owner,
+            unbonding_duration,
+            lp_balance_before,
+        } => compound_rewards(deps, env, owner, unbonding_duration, lp_balance_before),
+        CallbackMsg::ProvideCompoundedRewards {
+            owner,
+            unbonding_duration,
+            max_spread,
+            balances_before,
+        } => provide_compounded_rewards(
+            deps,
+            env,
+            owner,
+            unbonding_duration,
+            max_spread,
+            balances_before,
+        ),
+    }
+}
+
+/// Provides the swapped rewards as liquidity to the pair, against the reserves left by the swaps.
+fn provide_compounded_rewards(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    unbonding_duration: u64,
+    max_spread: Option<Decimal>,
+    balances_before: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let messages = crate::compound::provide_compounded_rewards_msgs(
+        deps.as_ref(),
+        &env,
+        &owner,
+        unbonding_duration,
+        max_spread,
+        balances_before,
+    )?;
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "provide_compounded_rewards".to_string()),
+            ("owner", owner.to_string()),
+        ])
+        .add_messages(messages))
+}
+
+/// Adds the LP tokens obtained by providing the rewards as liquidity to the owner's position.
+fn compound_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    unbonding_duration: u64,
+    lp_balance_before: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let lp_balance = config
+        .lp_asset
+        .query_pool(&deps.querier, deps.api, env.contract.address)?;
+    let amount = lp_balance.checked_sub(lp_balance_before)?;
+
+    add_to_position(&mut deps, &owner, amount, unbonding_duration)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "compound_rewards".to_string()),
+        ("owner", owner.to_string()),
+        ("amount", amount.to_string()),
+        ("unbonding_duration", unbonding_duration.to_string()),
+    ]))
+}
@@ -1,22 +1,67 @@
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cosmwasm_std::{CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult};
+use white_whale_std::pool_network::incentive::AutoCompound;
 
 use crate::error::ContractError;
 use crate::helpers;
-use crate::state::GLOBAL_WEIGHT_SNAPSHOT;
+use crate::state::{CLAIM_OPERATORS, CLAIM_OPERATOR_AUTO_COMPOUND, LAST_CLAIMED_EPOCH};
 
-/// Claim available rewards for the user.
-pub fn claim(mut deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    // check what's the last global weight epoch, and snapshot it if it's not already done
+/// Claim available rewards for the owner, which defaults to the sender. Only the owner or its
+/// claim operator can claim the rewards, which are always sent to the owner.
+///
+/// Only the owner can choose how to compound the rewards. The claims done by the claim operator
+/// are compounded with the parameters set by the owner along with the operator, if any.
+#[allow(clippy::too_many_arguments)]
+pub fn claim(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    until_epoch: Option<u64>,
+    max_epochs: Option<u64>,
+    owner: Option<String>,
+    auto_compound: Option<AutoCompound>,
+) -> Result<Response, ContractError> {
+    let owner = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+
+    let auto_compound = if owner != info.sender {
+        let operator = CLAIM_OPERATORS.may_load(deps.storage, &owner)?;
+        if operator != Some(info.sender) || auto_compound.is_some() {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        CLAIM_OPERATOR_AUTO_COMPOUND.may_load(deps.storage, &owner)?
+    } else {
+        auto_compound
+    };
+
+    // snapshot the global weight of the epochs missed since the last snapshot, if any
     let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
+
+    let rewards = crate::claim::claim(&mut deps, &owner, until_epoch, max_epochs)?;
+    let last_claimed_epoch = LAST_CLAIMED_EPOCH.load(deps.storage, &owner)?;
 
-    let global_weight_snapshot = GLOBAL_WEIGHT_SNAPSHOT.may_load(deps.storage, current_epoch)?;
-    if global_weight_snapshot.is_none() {
-        return Err(ContractError::GlobalWeightSnapshotNotTakenForEpoch {
-            epoch: current_epoch,
-        });
-    }
+    let messages: Vec<CosmosMsg> = match auto_compound {
+        Some(auto_compound) => crate::compound::compound_rewards_msgs(
+            deps.as_ref(),
+            &env,
+            &owner,
+            rewards,
+            auto_compound,
+        )?,
+        None => rewards
+            .into_iter()
+            .map(|reward| reward.into_msg(owner.clone()))
+            .collect::<StdResult<_>>()?,
+    };
 
     Ok(Response::default()
-        .add_attributes(vec![("action", "claim")])
-        .add_messages(crate::claim::claim(&mut deps, &info)?))
+        .add_attributes(vec![
+            ("action", "claim".to_string()),
+            ("owner", owner.to_string()),
+            ("last_claimed_epoch", last_claimed_epoch.to_string()),
+        ])
+        .add_messages(messages))
 }
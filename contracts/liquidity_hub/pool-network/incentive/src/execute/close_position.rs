@@ -1,9 +1,10 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdError};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError};
 
 use white_whale_std::pool_network::incentive::{ClosedPosition, OpenPosition};
 
+use crate::execute::poke_boost::rescale_boost;
 use crate::queries::get_rewards;
-use crate::state::ADDRESS_WEIGHT_HISTORY;
+use crate::state::{ADDRESS_WEIGHT_HISTORY, OWNER_POSITION_TOKENS, POSITION_TOKENS};
 use crate::{
     error::ContractError,
     helpers,
@@ -13,30 +14,12 @@ use crate::{
 
 /// Closes the position for the user with the given unbonding_duration.
 pub fn close_position(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     unbonding_duration: u64,
 ) -> Result<Response, ContractError> {
-    //query and check if the user has pending rewards
-    let rewards_query_result = get_rewards(deps.as_ref(), info.sender.clone().into_string());
-
-    if let Ok(rewards_response) = rewards_query_result {
-        // can't close a position if there are pending rewards
-        if !rewards_response.rewards.is_empty() {
-            return Err(ContractError::PendingRewards {});
-        }
-    }
-
-    // remove position
-    let mut open_positions = OPEN_POSITIONS
-        .may_load(deps.storage, info.sender.clone())?
-        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
-    let to_close_index = open_positions
-        .iter()
-        .position(|pos| pos.unbonding_duration == unbonding_duration)
-        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
-    let to_close_position = &open_positions[to_close_index];
+    let closing_position = remove_open_position(&mut deps, &info.sender, unbonding_duration)?;
 
     // move to a closed position
     CLOSED_POSITIONS.update::<_, ContractError>(
@@ -46,12 +29,12 @@ pub fn close_position(
             let mut closed_positions = closed_positions.unwrap_or_default();
 
             closed_positions.push(ClosedPosition {
-                amount: to_close_position.amount,
+                amount: closing_position.amount,
                 unbonding_timestamp: env
                     .block
                     .time
                     .seconds()
-                    .checked_add(to_close_position.unbonding_duration)
+                    .checked_add(closing_position.unbonding_duration)
                     .ok_or(ContractError::OverflowTimestamp)?,
             });
 
@@ -59,41 +42,77 @@ pub fn close_position(
         },
     )?;
 
+    Ok(Response::default().add_attributes(vec![
+        ("action", "close_position".to_string()),
+        ("closing_position", closing_position.to_string()),
+        ("unbonding_duration", unbonding_duration.to_string()),
+    ]))
+}
+
+/// Removes the open position of the owner with the given unbonding_duration, together with its
+/// weight. Fails if the owner has pending rewards.
+pub(crate) fn remove_open_position(
+    deps: &mut DepsMut,
+    owner: &Addr,
+    unbonding_duration: u64,
+) -> Result<OpenPosition, ContractError> {
+    //query and check if the user has pending rewards
+    let rewards_query_result = get_rewards(deps.as_ref(), owner.to_string());
+
+    if let Ok(rewards_response) = rewards_query_result {
+        // can't close a position if there are pending rewards
+        if !rewards_response.rewards.is_empty() {
+            return Err(ContractError::PendingRewards {});
+        }
+    }
+
+    // remove position
+    let mut open_positions = OPEN_POSITIONS
+        .may_load(deps.storage, owner.clone())?
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+    let to_close_index = open_positions
+        .iter()
+        .position(|pos| pos.unbonding_duration == unbonding_duration)
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+    let closing_position: OpenPosition = open_positions.remove(to_close_index);
+
+    // a closed position is no longer represented by an NFT
+    if let Some(token_id) =
+        helpers::get_position_token_id(deps.as_ref(), owner, unbonding_duration)?
+    {
+        POSITION_TOKENS.remove(deps.storage, &token_id);
+        OWNER_POSITION_TOKENS.remove(deps.storage, (owner, &token_id));
+    }
+
     // reduce weight
     // we reduce the weight to be equivalent to 1*amount, so we subtract by (weight - amount)
     // this should always be a valid operation as calculate_weight will return >= amount
-    let weight_to_reduce = calculate_weight(unbonding_duration, to_close_position.amount)?;
+    let weight_to_reduce = calculate_weight(unbonding_duration, closing_position.amount)?;
+
+    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
 
     // reduce the global weight
     GLOBAL_WEIGHT.update::<_, StdError>(deps.storage, |global_weight| {
         Ok(global_weight.saturating_sub(weight_to_reduce))
     })?;
 
-    // reduce the weight for the user
+    // reduce the weight for the user, rescaling its boost to the weight left
     let mut user_weight = ADDRESS_WEIGHT
-        .may_load(deps.storage, info.sender.clone())?
+        .may_load(deps.storage, owner.clone())?
         .unwrap_or_default();
-    user_weight = user_weight.saturating_sub(weight_to_reduce);
-    ADDRESS_WEIGHT.save(deps.storage, info.sender.clone(), &user_weight)?;
-
-    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    user_weight = rescale_boost(deps, owner, user_weight.saturating_sub(weight_to_reduce))?;
+    ADDRESS_WEIGHT.save(deps.storage, owner.clone(), &user_weight)?;
 
     // store new user weight in history for the next epoch
     ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
         deps.storage,
-        (&info.sender, current_epoch + 1u64),
+        (owner, current_epoch + 1u64),
         |_| Ok(user_weight),
     )?;
 
     // remove closed position from open positions map
-    let closing_position: OpenPosition = open_positions[to_close_index].clone();
+    OPEN_POSITIONS.save(deps.storage, owner.clone(), &open_positions)?;
 
-    open_positions.remove(to_close_index);
-    OPEN_POSITIONS.save(deps.storage, info.sender, &open_positions)?;
-
-    Ok(Response::default().add_attributes(vec![
-        ("action", "close_position".to_string()),
-        ("closing_position", closing_position.to_string()),
-        ("unbonding_duration", unbonding_duration.to_string()),
-    ]))
+    Ok(closing_position)
 }
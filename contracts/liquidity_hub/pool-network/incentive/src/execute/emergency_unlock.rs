@@ -0,0 +1,183 @@
+use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::pool_network::incentive_factory;
+
+use crate::execute::close_position::remove_open_position;
+use crate::{
+    error::ContractError,
+    state::{CLOSED_POSITIONS, CONFIG},
+    weight::calculate_weight,
+};
+
+/// Unlocks positions before their unbonding period is over, charging a penalty on the returned
+/// LP tokens.
+pub fn emergency_unlock(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let incentive_factory_config: incentive_factory::ConfigResponse =
+        deps.querier.query_wasm_smart(
+            config.factory_address.into_string(),
+            &incentive_factory::QueryMsg::Config {},
+        )?;
+
+    let mut unlocked_amount = Uint128::zero();
+    let mut penalty_amount = Uint128::zero();
+
+    if let Some(unbonding_duration) = unbonding_duration {
+        // unlock the open position with the given unbonding duration
+        let position = remove_open_position(&mut deps, &info.sender, unbonding_duration)?;
+        let weight = calculate_weight(unbonding_duration, position.amount)?;
+
+        unlocked_amount = position.amount;
+        penalty_amount = calculate_penalty(
+            &incentive_factory_config,
+            position.amount,
+            weight,
+            unbonding_duration,
+        )?;
+    } else {
+        // unlock all the closed positions that are still unbonding
+        let current_time = env.block.time.seconds();
+        let mut closed_positions = CLOSED_POSITIONS
+            .may_load(deps.storage, info.sender.clone())?
+            .unwrap_or_default();
+
+        for i in (0..closed_positions.len()).rev() {
+            let position = &closed_positions[i];
+
+            // positions past their unbonding timestamp can be withdrawn without penalty
+            if current_time <= position.unbonding_timestamp {
+                // closed positions have a weight multiplier of one
+                let penalty = calculate_penalty(
+                    &incentive_factory_config,
+                    position.amount,
+                    position.amount,
+                    position.unbonding_timestamp - current_time,
+                )?;
+
+                unlocked_amount = unlocked_amount.checked_add(position.amount)?;
+                penalty_amount = penalty_amount.checked_add(penalty)?;
+
+                closed_positions.remove(i);
+            }
+        }
+
+        if unlocked_amount.is_zero() {
+            return Err(ContractError::NothingToUnlock {});
+        }
+
+        CLOSED_POSITIONS.save(deps.storage, info.sender.clone(), &closed_positions)?;
+    }
+
+    let return_asset = Asset {
+        info: config.lp_asset.clone(),
+        amount: unlocked_amount.checked_sub(penalty_amount)?,
+    };
+    let penalty_asset = Asset {
+        info: config.lp_asset,
+        amount: penalty_amount,
+    };
+
+    let mut response = Response::default().add_attributes(vec![
+        ("action", "emergency_unlock".to_string()),
+        ("return_asset", return_asset.to_string()),
+        ("penalty", penalty_asset.to_string()),
+    ]);
+
+    if !return_asset.amount.is_zero() {
+        response = response.add_message(return_asset.into_msg(info.sender)?);
+    }
+
+    if !penalty_asset.amount.is_zero() {
+        response = response
+            .add_message(penalty_asset.into_msg(incentive_factory_config.fee_collector_addr)?);
+    }
+
+    Ok(response)
+}
+
+/// Calculates the penalty for unlocking `amount` LP tokens carrying the given `weight`, with
+/// `remaining_duration` seconds left to unbond.
+///
+/// The penalty scales linearly with the weight multiplier and the remaining unbonding time
+/// relative to the maximum unbonding duration, and is capped at the maximum penalty.
+fn calculate_penalty(
+    incentive_factory_config: &incentive_factory::ConfigResponse,
+    amount: Uint128,
+    weight: Uint128,
+    remaining_duration: u64,
+) -> Result<Uint128, ContractError> {
+    // there is nothing to take a penalty from
+    if amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let penalty = incentive_factory_config
+        .emergency_unlock_penalty
+        .checked_mul(Decimal::checked_from_ratio(weight, amount)?)?
+        .checked_mul(Decimal::checked_from_ratio(
+            remaining_duration,
+            incentive_factory_config.max_unbonding_duration,
+        )?)?
+        .min(incentive_factory_config.max_emergency_unlock_penalty);
+
+    Ok(amount * penalty)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Addr, Decimal, Uint128};
+
+    use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+    use white_whale_std::pool_network::incentive_factory;
+
+    use super::calculate_penalty;
+
+    fn mock_config() -> incentive_factory::ConfigResponse {
+        incentive_factory::Config {
+            owner: Addr::unchecked("owner"),
+            fee_collector_addr: Addr::unchecked("fee_collector"),
+            fee_distributor_addr: Addr::unchecked("fee_distributor"),
+            create_flow_fee: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::zero(),
+            },
+            max_concurrent_flows: 7,
+            incentive_code_id: 1,
+            max_flow_epoch_buffer: 14,
+            min_unbonding_duration: 86_400,
+            max_unbonding_duration: 31_536_000,
+            emergency_unlock_penalty: Decimal::percent(10),
+            max_emergency_unlock_penalty: Decimal::percent(90),
+            max_boost: Decimal::one(),
+            epoch_manager_addr: None,
+        }
+    }
+
+    #[test]
+    fn penalty_scales_with_weight_and_remaining_duration() {
+        let penalty = calculate_penalty(
+            &mock_config(),
+            Uint128::new(1_000),
+            Uint128::new(2_000),
+            31_536_000 / 2,
+        )
+        .unwrap();
+        assert_eq!(penalty, Uint128::new(100));
+    }
+
+    #[test]
+    fn no_penalty_for_empty_position() {
+        let penalty =
+            calculate_penalty(&mock_config(), Uint128::zero(), Uint128::zero(), 86_400).unwrap();
+        assert_eq!(penalty, Uint128::zero());
+    }
+}
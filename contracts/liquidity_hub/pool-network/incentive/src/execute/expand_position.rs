@@ -1,5 +1,6 @@
-use cosmwasm_std::{CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
 
+use crate::execute::poke_boost::rescale_boost;
 use crate::state::ADDRESS_WEIGHT_HISTORY;
 use crate::{
     error::ContractError,
@@ -11,7 +12,7 @@ use crate::{
 
 /// Expands a position a user already has by adding more funds to it.
 pub fn expand_position(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     amount: Uint128,
@@ -43,24 +44,43 @@ pub fn expand_position(
         })
         .unwrap_or_else(|| info.clone());
 
+    add_to_position(&mut deps, &receiver.sender, amount, unbonding_duration)?;
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "expand_position".to_string()),
+            ("receiver", receiver.sender.to_string()),
+            ("amount", amount.to_string()),
+            ("unbonding_duration", unbonding_duration.to_string()),
+        ])
+        .add_messages(messages))
+}
+
+/// Adds the given amount to the position with the given unbonding duration, updating the weights
+/// of the receiver accordingly.
+pub(crate) fn add_to_position(
+    deps: &mut DepsMut,
+    receiver: &Addr,
+    amount: Uint128,
+    unbonding_duration: u64,
+) -> Result<(), ContractError> {
     // increase position
-    OPEN_POSITIONS.update::<_, ContractError>(
-        deps.storage,
-        receiver.sender.clone(),
-        |positions| {
-            let mut positions =
-                positions.ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+    OPEN_POSITIONS.update::<_, ContractError>(deps.storage, receiver.clone(), |positions| {
+        let mut positions =
+            positions.ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
 
-            let pos = positions
-                .iter_mut()
-                .find(|position| position.unbonding_duration == unbonding_duration)
-                .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+        let pos = positions
+            .iter_mut()
+            .find(|position| position.unbonding_duration == unbonding_duration)
+            .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
 
-            pos.amount += amount;
+        pos.amount += amount;
 
-            Ok(positions)
-        },
-    )?;
+        Ok(positions)
+    })?;
+
+    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
 
     // add the weight to the global weight and the user's weight
     let weight = calculate_weight(unbonding_duration, amount)?;
@@ -69,25 +89,16 @@ pub fn expand_position(
     })?;
 
     let mut user_weight = ADDRESS_WEIGHT
-        .may_load(deps.storage, receiver.sender.clone())?
+        .may_load(deps.storage, receiver.clone())?
         .unwrap_or_default();
-    user_weight = user_weight.checked_add(weight)?;
-    ADDRESS_WEIGHT.save(deps.storage, receiver.sender.clone(), &user_weight)?;
-
-    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    user_weight = rescale_boost(deps, receiver, user_weight.checked_add(weight)?)?;
+    ADDRESS_WEIGHT.save(deps.storage, receiver.clone(), &user_weight)?;
 
     ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
         deps.storage,
-        (&receiver.sender, current_epoch + 1u64),
+        (receiver, current_epoch + 1u64),
         |_| Ok(user_weight),
     )?;
 
-    Ok(Response::default()
-        .add_attributes(vec![
-            ("action", "expand_position".to_string()),
-            ("receiver", receiver.sender.to_string()),
-            ("amount", amount.to_string()),
-            ("unbonding_duration", unbonding_duration.to_string()),
-        ])
-        .add_messages(messages))
+    Ok(())
 }
@@ -1,19 +1,33 @@
+mod callback;
 mod claim;
 mod close_flow;
-mod close_position;
+pub(crate) mod close_position;
+mod emergency_unlock;
 mod expand_flow;
-mod expand_position;
+pub(crate) mod expand_position;
 mod open_flow;
 mod open_position;
+pub(crate) mod poke_boost;
+mod position_nft;
 mod snapshot;
+pub(crate) mod transfer_position;
+mod update_claim_operator;
 mod withdraw;
 
+pub use callback::callback;
 pub use claim::claim;
 pub use close_flow::close_flow;
 pub use close_position::close_position;
+pub use emergency_unlock::emergency_unlock;
 pub use expand_flow::expand_flow;
 pub use expand_position::expand_position;
 pub use open_flow::open_flow;
 pub use open_position::open_position;
-pub use snapshot::take_global_weight_snapshot;
+pub use poke_boost::poke_boost;
+pub use position_nft::{
+    approve, approve_all, burn, revoke, revoke_all, send_nft, tokenize_position, transfer_nft,
+};
+pub use snapshot::{epoch_changed_hook, take_global_weight_snapshot, update_epoch_manager};
+pub use transfer_position::transfer_position;
+pub use update_claim_operator::update_claim_operator;
 pub use withdraw::withdraw;
@@ -7,6 +7,8 @@ use cosmwasm_std::{
 };
 
 use white_whale_std::pool_network::incentive::Flow;
+use white_whale_std::pool_network::incentive_factory;
+use white_whale_std::pool_network::incentive_factory::{FlowAssetAllowedResponse, FlowFeeResponse};
 use white_whale_std::pool_network::{
     asset::{Asset, AssetInfo},
     incentive::Curve,
@@ -40,152 +42,178 @@ pub fn open_flow(
         });
     }
 
+    let curve = curve.unwrap_or(Curve::Linear);
+    helpers::validate_curve(&curve)?;
+
     let config = CONFIG.load(deps.storage)?;
 
     let incentive_factory_config: white_whale_std::pool_network::incentive_factory::ConfigResponse =
         deps.querier.query_wasm_smart(
-            config.factory_address.into_string(),
+            config.factory_address.clone().into_string(),
             &white_whale_std::pool_network::incentive_factory::QueryMsg::Config {},
         )?;
 
-    let mut messages: Vec<CosmosMsg> = vec![];
+    // check the flow asset is whitelisted, if there's a whitelist
+    let flow_asset_allowed: FlowAssetAllowedResponse = deps.querier.query_wasm_smart(
+        config.factory_address.clone().into_string(),
+        &incentive_factory::QueryMsg::FlowAssetAllowed {
+            flow_asset: flow_asset.info.clone(),
+        },
+    )?;
+    if !flow_asset_allowed {
+        return Err(ContractError::FlowAssetNotWhitelisted {
+            asset: flow_asset.info.to_string(),
+        });
+    }
 
-    let flow_fee = incentive_factory_config.create_flow_fee;
-    // check the fee to create a flow is being paid
-    match flow_fee.info.clone() {
-        AssetInfo::NativeToken {
-            denom: flow_fee_denom,
-        } => {
-            // fee should be included inside message info
-            let paid_amount = info
-                .funds
-                .iter()
-                .find(|token| token.denom == flow_fee_denom)
-                .ok_or(ContractError::FlowFeeMissing)?
-                .amount;
-
-            // check if the user intends to open a flow with the same asset used to pay for the flow_fee
-            match flow_asset.info.clone() {
-                AssetInfo::Token { .. } => {}
-                AssetInfo::NativeToken {
-                    denom: flow_asset_denom,
-                } => {
-                    // if so, subtract the flow_fee from the flow_asset amount
-                    if flow_fee_denom == flow_asset_denom {
-                        flow_asset.amount = flow_asset.amount.saturating_sub(flow_fee.amount);
+    let flow_fee_response: FlowFeeResponse = deps.querier.query_wasm_smart(
+        config.factory_address.into_string(),
+        &incentive_factory::QueryMsg::FlowFee {
+            creator: info.sender.clone().into_string(),
+        },
+    )?;
 
-                        if flow_asset.amount < MIN_FLOW_AMOUNT {
-                            return Err(ContractError::EmptyFlowAfterFee {
-                                min: MIN_FLOW_AMOUNT,
-                            });
-                        }
-                    }
-                }
-            }
+    let mut messages: Vec<CosmosMsg> = vec![];
 
-            match paid_amount.cmp(&flow_fee.amount) {
-                Ordering::Equal => (), // do nothing if user paid correct amount,
-                Ordering::Less => {
-                    // user underpaid
-                    return Err(ContractError::FlowFeeNotPaid {
-                        paid_amount,
-                        required_amount: flow_fee.amount,
-                    });
-                }
-                Ordering::Greater => {
-                    // if the user is paying more than the flow_fee and is not trying to open a
-                    // flow with the same asset as the flow_fee, refund the difference
-                    match flow_asset.info.clone() {
-                        AssetInfo::Token { .. } => {}
-                        AssetInfo::NativeToken {
-                            denom: flow_asset_denom,
-                        } => {
-                            if flow_fee_denom != flow_asset_denom {
-                                messages.push(
-                                    BankMsg::Send {
-                                        to_address: info.sender.clone().into_string(),
-                                        amount: vec![Coin {
-                                            amount: paid_amount - flow_fee.amount,
-                                            denom: flow_fee_denom.clone(),
-                                        }],
-                                    }
-                                    .into(),
-                                );
+    let flow_fee = select_flow_fee(flow_fee_response.fees, &info.funds, &flow_asset.info)
+        .ok_or(ContractError::FlowFeeMissing)?;
+    // check the fee to create a flow is being paid, unless the creator doesn't need to pay any
+    if !flow_fee.amount.is_zero() {
+        match flow_fee.info.clone() {
+            AssetInfo::NativeToken {
+                denom: flow_fee_denom,
+            } => {
+                // fee should be included inside message info
+                let paid_amount = info
+                    .funds
+                    .iter()
+                    .find(|token| token.denom == flow_fee_denom)
+                    .ok_or(ContractError::FlowFeeMissing)?
+                    .amount;
+
+                // check if the user intends to open a flow with the same asset used to pay for the flow_fee
+                match flow_asset.info.clone() {
+                    AssetInfo::Token { .. } => {}
+                    AssetInfo::NativeToken {
+                        denom: flow_asset_denom,
+                    } => {
+                        // if so, subtract the flow_fee from the flow_asset amount
+                        if flow_fee_denom == flow_asset_denom {
+                            flow_asset.amount = flow_asset.amount.saturating_sub(flow_fee.amount);
+
+                            if flow_asset.amount < MIN_FLOW_AMOUNT {
+                                return Err(ContractError::EmptyFlowAfterFee {
+                                    min: MIN_FLOW_AMOUNT,
+                                });
                             }
                         }
                     }
                 }
-            }
 
-            // send fee to fee collector
-            messages.push(
-                BankMsg::Send {
-                    to_address: incentive_factory_config
-                        .fee_collector_addr
-                        .clone()
-                        .into_string(),
-                    amount: vec![Coin {
-                        amount: flow_fee.amount,
-                        denom: flow_fee_denom,
-                    }],
-                }
-                .into(),
-            );
-        }
-        AssetInfo::Token {
-            contract_addr: flow_fee_contract_addr,
-        } => {
-            // we should have been given permissions through allowances
-            let flow_fee_allowance: cw20::AllowanceResponse = deps.querier.query_wasm_smart(
-                flow_fee_contract_addr.clone(),
-                &cw20::Cw20QueryMsg::Allowance {
-                    owner: info.sender.clone().into_string(),
-                    spender: env.contract.address.clone().into_string(),
-                },
-            )?;
-
-            match flow_asset.info.clone() {
-                AssetInfo::Token {
-                    contract_addr: flow_asset_contract_addr,
-                } => {
-                    if flow_asset_contract_addr == flow_fee_contract_addr {
-                        if flow_fee_allowance.allowance
-                            < flow_fee.amount.checked_add(MIN_FLOW_AMOUNT)?
-                        {
-                            return Err(ContractError::EmptyFlowAfterFee {
-                                min: MIN_FLOW_AMOUNT,
-                            });
-                        }
-                    } else if flow_fee_allowance.allowance < flow_fee.amount {
+                match paid_amount.cmp(&flow_fee.amount) {
+                    Ordering::Equal => (), // do nothing if user paid correct amount,
+                    Ordering::Less => {
+                        // user underpaid
                         return Err(ContractError::FlowFeeNotPaid {
-                            paid_amount: flow_fee_allowance.allowance,
+                            paid_amount,
                             required_amount: flow_fee.amount,
                         });
                     }
-                }
-                AssetInfo::NativeToken { .. } => {
-                    if flow_fee_allowance.allowance < flow_fee.amount {
-                        return Err(ContractError::FlowFeeNotPaid {
-                            paid_amount: flow_fee_allowance.allowance,
-                            required_amount: flow_fee.amount,
-                        });
+                    Ordering::Greater => {
+                        // if the user is paying more than the flow_fee and is not trying to open a
+                        // flow with the same asset as the flow_fee, refund the difference
+                        match flow_asset.info.clone() {
+                            AssetInfo::Token { .. } => {}
+                            AssetInfo::NativeToken {
+                                denom: flow_asset_denom,
+                            } => {
+                                if flow_fee_denom != flow_asset_denom {
+                                    messages.push(
+                                        BankMsg::Send {
+                                            to_address: info.sender.clone().into_string(),
+                                            amount: vec![Coin {
+                                                amount: paid_amount - flow_fee.amount,
+                                                denom: flow_fee_denom.clone(),
+                                            }],
+                                        }
+                                        .into(),
+                                    );
+                                }
+                            }
+                        }
                     }
                 }
-            }
 
-            // send fee to fee collector
-            messages.push(
-                WasmMsg::Execute {
-                    contract_addr: flow_fee_contract_addr,
-                    msg: to_json_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                // send fee to fee collector
+                messages.push(
+                    BankMsg::Send {
+                        to_address: incentive_factory_config
+                            .fee_collector_addr
+                            .clone()
+                            .into_string(),
+                        amount: vec![Coin {
+                            amount: flow_fee.amount,
+                            denom: flow_fee_denom,
+                        }],
+                    }
+                    .into(),
+                );
+            }
+            AssetInfo::Token {
+                contract_addr: flow_fee_contract_addr,
+            } => {
+                // we should have been given permissions through allowances
+                let flow_fee_allowance: cw20::AllowanceResponse = deps.querier.query_wasm_smart(
+                    flow_fee_contract_addr.clone(),
+                    &cw20::Cw20QueryMsg::Allowance {
                         owner: info.sender.clone().into_string(),
-                        recipient: incentive_factory_config.fee_collector_addr.into_string(),
-                        amount: flow_fee.amount,
-                    })?,
-                    funds: vec![],
+                        spender: env.contract.address.clone().into_string(),
+                    },
+                )?;
+
+                match flow_asset.info.clone() {
+                    AssetInfo::Token {
+                        contract_addr: flow_asset_contract_addr,
+                    } => {
+                        if flow_asset_contract_addr == flow_fee_contract_addr {
+                            if flow_fee_allowance.allowance
+                                < flow_fee.amount.checked_add(MIN_FLOW_AMOUNT)?
+                            {
+                                return Err(ContractError::EmptyFlowAfterFee {
+                                    min: MIN_FLOW_AMOUNT,
+                                });
+                            }
+                        } else if flow_fee_allowance.allowance < flow_fee.amount {
+                            return Err(ContractError::FlowFeeNotPaid {
+                                paid_amount: flow_fee_allowance.allowance,
+                                required_amount: flow_fee.amount,
+                            });
+                        }
+                    }
+                    AssetInfo::NativeToken { .. } => {
+                        if flow_fee_allowance.allowance < flow_fee.amount {
+                            return Err(ContractError::FlowFeeNotPaid {
+                                paid_amount: flow_fee_allowance.allowance,
+                                required_amount: flow_fee.amount,
+                            });
+                        }
+                    }
                 }
-                .into(),
-            );
+
+                // send fee to fee collector
+                messages.push(
+                    WasmMsg::Execute {
+                        contract_addr: flow_fee_contract_addr,
+                        msg: to_json_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                            owner: info.sender.clone().into_string(),
+                            recipient: incentive_factory_config.fee_collector_addr.into_string(),
+                            amount: flow_fee.amount,
+                        })?,
+                        funds: vec![],
+                    }
+                    .into(),
+                );
+            }
         }
     }
 
@@ -352,8 +380,6 @@ pub fn open_flow(
     let flow_id =
         FLOW_COUNTER.update::<_, StdError>(deps.storage, |current_id| Ok(current_id + 1u64))?;
 
-    let curve = curve.unwrap_or(Curve::Linear);
-
     FLOWS.save(
         deps.storage,
         (start_epoch, flow_id),
@@ -392,3 +418,27 @@ pub fn open_flow(
         .add_attributes(attributes)
         .add_messages(messages))
 }
+
+/// Selects the fee the creator is paying among the ones it can choose from. A zero fee is always
+/// preferred, then native fees sent along in the funds, favoring those in a different denom than
+/// the flow asset, and then cw20 fees, which are paid through allowances.
+fn select_flow_fee(fees: Vec<Asset>, funds: &[Coin], flow_asset: &AssetInfo) -> Option<Asset> {
+    let is_sent = |fee: &Asset| match &fee.info {
+        AssetInfo::NativeToken { denom } => funds.iter().any(|coin| &coin.denom == denom),
+        AssetInfo::Token { .. } => false,
+    };
+
+    fees.iter()
+        .find(|fee| fee.amount.is_zero())
+        .or_else(|| {
+            fees.iter()
+                .find(|fee| is_sent(fee) && &fee.info != flow_asset)
+        })
+        .or_else(|| fees.iter().find(|fee| is_sent(fee)))
+        .or_else(|| {
+            fees.iter()
+                .find(|fee| matches!(fee.info, AssetInfo::Token { .. }))
+        })
+        .or_else(|| fees.first())
+        .cloned()
+}
@@ -2,6 +2,7 @@ use cosmwasm_std::{CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, Uin
 
 use white_whale_std::pool_network::incentive::OpenPosition;
 
+use crate::execute::poke_boost::rescale_boost;
 use crate::state::ADDRESS_WEIGHT_HISTORY;
 use crate::{
     error::ContractError,
@@ -13,7 +14,7 @@ use crate::{
 
 /// Opens a position for the user with the given unbonding_duration.
 pub fn open_position(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     amount: Uint128,
@@ -81,6 +82,9 @@ pub fn open_position(
         Ok(positions)
     })?;
 
+    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
+
     // add the weight to the global weight and the user's weight
     let weight = calculate_weight(unbonding_duration, amount)?;
     GLOBAL_WEIGHT.update::<_, StdError>(deps.storage, |global_weight| {
@@ -90,11 +94,13 @@ pub fn open_position(
     let mut user_weight = ADDRESS_WEIGHT
         .may_load(deps.storage, receiver.sender.clone())?
         .unwrap_or_default();
-    user_weight = user_weight.checked_add(weight)?;
+    user_weight = rescale_boost(
+        &mut deps,
+        &receiver.sender,
+        user_weight.checked_add(weight)?,
+    )?;
     ADDRESS_WEIGHT.save(deps.storage, receiver.sender.clone(), &user_weight)?;
 
-    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
-
     ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
         deps.storage,
         (&receiver.sender, current_epoch + 1u64),
@@ -0,0 +1,174 @@
+use cosmwasm_std::{Addr, Decimal, Deps, DepsMut, Response, StdError, StdResult, Uint128};
+
+use white_whale_std::pool_network::incentive_factory;
+use white_whale_std::whale_lair::BondingWeightResponse;
+
+use crate::state::{Boost, ADDRESS_BOOST, ADDRESS_WEIGHT_HISTORY};
+use crate::{
+    error::ContractError,
+    helpers,
+    state::{ADDRESS_WEIGHT, CONFIG, GLOBAL_WEIGHT},
+    weight::calculate_boost,
+};
+
+/// Recomputes the boost applied to the weight of the given address, based on its current bonding
+/// share in the whale lair.
+pub fn poke_boost(mut deps: DepsMut, address: String) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let incentive_factory_config: incentive_factory::ConfigResponse =
+        deps.querier.query_wasm_smart(
+            config.factory_address.clone().into_string(),
+            &incentive_factory::QueryMsg::Config {},
+        )?;
+
+    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
+
+    // take the current boost out of the weights, so the boost is computed on the weight of the
+    // positions alone
+    let previous_boost_weight = remove_boost(&mut deps, &address)?;
+    let address_weight = ADDRESS_WEIGHT
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default()
+        .saturating_sub(previous_boost_weight);
+    let global_weight = GLOBAL_WEIGHT.may_load(deps.storage)?.unwrap_or_default();
+
+    let bonding_share =
+        query_bonding_share(deps.as_ref(), &config.fee_distributor_address, &address)?;
+    let multiplier = calculate_boost(
+        incentive_factory_config.max_boost,
+        bonding_share,
+        address_weight,
+        global_weight,
+    )?;
+    let boost_weight = address_weight * (multiplier - Decimal::one());
+
+    if !boost_weight.is_zero() {
+        ADDRESS_BOOST.save(
+            deps.storage,
+            &address,
+            &Boost {
+                multiplier,
+                weight: boost_weight,
+            },
+        )?;
+    }
+
+    let address_weight = address_weight.checked_add(boost_weight)?;
+    ADDRESS_WEIGHT.save(deps.storage, address.clone(), &address_weight)?;
+    GLOBAL_WEIGHT.save(deps.storage, &global_weight.checked_add(boost_weight)?)?;
+
+    // store new user weight in history for the next epoch
+    ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
+        deps.storage,
+        (&address, current_epoch + 1u64),
+        |_| Ok(address_weight),
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "poke_boost".to_string()),
+        ("address", address.to_string()),
+        ("boost", multiplier.to_string()),
+        ("boost_weight", boost_weight.to_string()),
+    ]))
+}
+
+/// Rescales the boost of the given address after the weight of its positions changed, given the
+/// weight of the address with the change applied. Returns the new weight of the address.
+///
+/// The boost keeps its multiplier, but never adds more weight than it did when it was last poked,
+/// as the bonding share it was computed with can be outdated. The boost goes away together with
+/// the weight of the positions.
+pub(crate) fn rescale_boost(
+    deps: &mut DepsMut,
+    address: &Addr,
+    address_weight: Uint128,
+) -> StdResult<Uint128> {
+    let Some(boost) = ADDRESS_BOOST.may_load(deps.storage, address)? else {
+        return Ok(address_weight);
+    };
+
+    let positions_weight = address_weight.saturating_sub(boost.weight);
+    let scaled_boost_weight = positions_weight * (boost.multiplier - Decimal::one());
+    let boost_weight = std::cmp::min(scaled_boost_weight, boost.weight);
+
+    if boost_weight.is_zero() {
+        ADDRESS_BOOST.remove(deps.storage, address);
+    } else {
+        // the multiplier drops if the boost is capped to the weight it added before
+        let multiplier = if scaled_boost_weight > boost.weight {
+            Decimal::from_ratio(
+                positions_weight.checked_add(boost_weight)?,
+                positions_weight,
+            )
+        } else {
+            boost.multiplier
+        };
+
+        ADDRESS_BOOST.save(
+            deps.storage,
+            address,
+            &Boost {
+                multiplier,
+                weight: boost_weight,
+            },
+        )?;
+    }
+
+    GLOBAL_WEIGHT.update::<_, StdError>(deps.storage, |global_weight| {
+        Ok(global_weight
+            .saturating_sub(boost.weight)
+            .checked_add(boost_weight)?)
+    })?;
+
+    Ok(positions_weight.checked_add(boost_weight)?)
+}
+
+/// Removes the boost of the given address, taking the weight it added out of the global weight.
+/// Returns the weight the boost added to the address, which the caller must take out of the weight
+/// of the address.
+fn remove_boost(deps: &mut DepsMut, address: &Addr) -> StdResult<Uint128> {
+    let boost_weight = ADDRESS_BOOST
+        .may_load(deps.storage, address)?
+        .map(|boost| boost.weight)
+        .unwrap_or_default();
+
+    if !boost_weight.is_zero() {
+        ADDRESS_BOOST.remove(deps.storage, address);
+        GLOBAL_WEIGHT.update::<_, StdError>(deps.storage, |global_weight| {
+            Ok(global_weight.saturating_sub(boost_weight))
+        })?;
+    }
+
+    Ok(boost_weight)
+}
+
+/// Queries the bonding share of the address in the whale lair, which is found through the fee
+/// distributor. An address without bonds has no share.
+fn query_bonding_share(
+    deps: Deps,
+    fee_distributor_addr: &Addr,
+    address: &Addr,
+) -> StdResult<Decimal> {
+    let fee_distributor_config: white_whale_std::fee_distributor::Config =
+        deps.querier.query_wasm_smart(
+            fee_distributor_addr,
+            &white_whale_std::fee_distributor::QueryMsg::Config {},
+        )?;
+
+    // the whale lair fails to compute the weight while nothing is bonded
+    Ok(deps
+        .querier
+        .query_wasm_smart::<BondingWeightResponse>(
+            fee_distributor_config.bonding_contract_addr,
+            &white_whale_std::whale_lair::QueryMsg::Weight {
+                address: address.to_string(),
+                timestamp: None,
+                global_index: None,
+            },
+        )
+        .map(|weight| weight.share)
+        .unwrap_or_default())
+}
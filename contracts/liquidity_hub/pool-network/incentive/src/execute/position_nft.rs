@@ -0,0 +1,288 @@
+use cosmwasm_std::{Addr, Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw20::Expiration;
+
+use white_whale_std::pool_network::incentive::{Approval, Cw721ReceiveMsg};
+
+use crate::error::ContractError;
+use crate::execute::transfer_position::move_position;
+use crate::helpers;
+use crate::state::{
+    PositionToken, NFT_OPERATORS, OPEN_POSITIONS, OWNER_POSITION_TOKENS, POSITION_TOKENS,
+    POSITION_TOKEN_COUNTER,
+};
+
+/// Mints an NFT representing the open position of the sender with the given unbonding_duration.
+pub fn tokenize_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    unbonding_duration: u64,
+) -> Result<Response, ContractError> {
+    OPEN_POSITIONS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default()
+        .iter()
+        .find(|position| position.unbonding_duration == unbonding_duration)
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+
+    if helpers::get_position_token_id(deps.as_ref(), &info.sender, unbonding_duration)?.is_some() {
+        return Err(ContractError::PositionAlreadyTokenized { unbonding_duration });
+    }
+
+    let token_id = POSITION_TOKEN_COUNTER
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1u64;
+    POSITION_TOKEN_COUNTER.save(deps.storage, &token_id)?;
+
+    let token_id = token_id.to_string();
+    POSITION_TOKENS.save(
+        deps.storage,
+        &token_id,
+        &PositionToken {
+            owner: info.sender.clone(),
+            unbonding_duration,
+            approvals: vec![],
+        },
+    )?;
+    OWNER_POSITION_TOKENS.save(deps.storage, (&info.sender, &token_id), &unbonding_duration)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "tokenize_position".to_string()),
+        ("owner", info.sender.to_string()),
+        ("token_id", token_id),
+        ("unbonding_duration", unbonding_duration.to_string()),
+    ]))
+}
+
+/// Transfers the position NFT, and the position it represents, to the recipient.
+pub fn transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token = load_token(&deps, &token_id)?;
+    check_can_send(&deps, &env, &info.sender, &token)?;
+
+    let (messages, _) = move_position(deps, &token.owner, &recipient, token.unbonding_duration)?;
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "transfer_nft".to_string()),
+            ("sender", info.sender.to_string()),
+            ("recipient", recipient.to_string()),
+            ("token_id", token_id),
+        ])
+        .add_messages(messages))
+}
+
+/// Sends the position NFT, and the position it represents, to a contract, triggering a
+/// [Cw721ReceiveMsg] on it.
+pub fn send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let token = load_token(&deps, &token_id)?;
+    check_can_send(&deps, &env, &info.sender, &token)?;
+
+    let (mut messages, _) = move_position(deps, &token.owner, &contract, token.unbonding_duration)?;
+
+    messages.push(
+        Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg,
+        }
+        .into_cosmos_msg(contract.to_string())?,
+    );
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "send_nft".to_string()),
+            ("sender", info.sender.to_string()),
+            ("recipient", contract.to_string()),
+            ("token_id", token_id),
+        ])
+        .add_messages(messages))
+}
+
+/// Allows the spender to transfer, send or burn the position NFT on behalf of the owner. Can be
+/// executed by the owner or its operators.
+pub fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let mut token = load_token(&deps, &token_id)?;
+    check_can_approve(&deps, &env, &info.sender, &token)?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ExpiredApproval {});
+    }
+
+    // replace any previous approval of the spender
+    token
+        .approvals
+        .retain(|approval| approval.spender != spender.as_str());
+    token.approvals.push(Approval {
+        spender: spender.to_string(),
+        expires,
+    });
+    POSITION_TOKENS.save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "approve".to_string()),
+        ("sender", info.sender.to_string()),
+        ("spender", spender.to_string()),
+        ("token_id", token_id),
+    ]))
+}
+
+/// Removes the approval of the spender on the position NFT. Can be executed by the owner or its
+/// operators.
+pub fn revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let mut token = load_token(&deps, &token_id)?;
+    check_can_approve(&deps, &env, &info.sender, &token)?;
+
+    token
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    POSITION_TOKENS.save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "revoke".to_string()),
+        ("sender", info.sender.to_string()),
+        ("spender", spender),
+        ("token_id", token_id),
+    ]))
+}
+
+/// Allows the operator to transfer, send, burn or approve any of the position NFTs of the sender.
+pub fn approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ExpiredApproval {});
+    }
+
+    NFT_OPERATORS.save(deps.storage, (&info.sender, &operator), &expires)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "approve_all".to_string()),
+        ("sender", info.sender.to_string()),
+        ("operator", operator.to_string()),
+    ]))
+}
+
+/// Removes the operator of the sender.
+pub fn revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    NFT_OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "revoke_all".to_string()),
+        ("sender", info.sender.to_string()),
+        ("operator", operator.to_string()),
+    ]))
+}
+
+/// Burns the position NFT, the owner keeps the position as a regular one.
+pub fn burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let token = load_token(&deps, &token_id)?;
+    check_can_send(&deps, &env, &info.sender, &token)?;
+
+    POSITION_TOKENS.remove(deps.storage, &token_id);
+    OWNER_POSITION_TOKENS.remove(deps.storage, (&token.owner, &token_id));
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "burn".to_string()),
+        ("sender", info.sender.to_string()),
+        ("token_id", token_id),
+    ]))
+}
+
+/// Loads the position NFT with the given id.
+fn load_token(deps: &DepsMut, token_id: &str) -> Result<PositionToken, ContractError> {
+    POSITION_TOKENS
+        .may_load(deps.storage, token_id)?
+        .ok_or_else(|| ContractError::NonExistentPositionToken {
+            token_id: token_id.to_string(),
+        })
+}
+
+/// Checks that the sender is either the owner of the NFT or one of its operators.
+fn check_can_approve(
+    deps: &DepsMut,
+    env: &Env,
+    sender: &Addr,
+    token: &PositionToken,
+) -> Result<(), ContractError> {
+    if token.owner == sender || is_operator(deps, env, &token.owner, sender)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {})
+}
+
+/// Checks that the sender is either the owner of the NFT, one of its operators, or has an approval
+/// that hasn't expired.
+fn check_can_send(
+    deps: &DepsMut,
+    env: &Env,
+    sender: &Addr,
+    token: &PositionToken,
+) -> Result<(), ContractError> {
+    if token.owner == sender || is_operator(deps, env, &token.owner, sender)? {
+        return Ok(());
+    }
+
+    token
+        .approvals
+        .iter()
+        .find(|approval| {
+            approval.spender == sender.as_str() && !approval.expires.is_expired(&env.block)
+        })
+        .map(|_| ())
+        .ok_or(ContractError::Unauthorized {})
+}
+
+/// Whether the sender is an operator of the owner that hasn't expired.
+fn is_operator(deps: &DepsMut, env: &Env, owner: &Addr, sender: &Addr) -> StdResult<bool> {
+    Ok(NFT_OPERATORS
+        .may_load(deps.storage, (owner, sender))?
+        .is_some_and(|expires| !expires.is_expired(&env.block)))
+}
@@ -1,8 +1,10 @@
-use cosmwasm_std::{DepsMut, Response, StdError, Uint128};
+use cosmwasm_std::{DepsMut, MessageInfo, Response, Uint128};
+
+use white_whale_std::epoch_manager::hooks::EpochChangedHookMsg;
 
 use crate::error::ContractError;
 use crate::helpers;
-use crate::state::{GLOBAL_WEIGHT, GLOBAL_WEIGHT_SNAPSHOT};
+use crate::state::{CONFIG, EPOCH_MANAGER, GLOBAL_WEIGHT, GLOBAL_WEIGHT_SNAPSHOT};
 
 /// Takes a global weight snapshot based on the current epoch
 pub fn take_global_weight_snapshot(deps: DepsMut) -> Result<Response, ContractError> {
@@ -15,18 +17,78 @@ pub fn take_global_weight_snapshot(deps: DepsMut) -> Result<Response, ContractEr
         });
     }
 
-    // take the snapshot
+    // take the snapshot, together with the ones missed since the last snapshot
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
 
     let current_global_weight = GLOBAL_WEIGHT
         .may_load(deps.storage)?
         .unwrap_or(Uint128::zero());
 
-    GLOBAL_WEIGHT_SNAPSHOT
-        .update::<_, StdError>(deps.storage, current_epoch, |_| Ok(current_global_weight))?;
-
     Ok(Response::default().add_attributes(vec![
         ("action", "take_global_weight_snapshot".to_string()),
         ("epoch", current_epoch.to_string()),
         ("current_global_weight", current_global_weight.to_string()),
     ]))
 }
+
+/// Takes the global weight snapshots when the epoch manager creates a new epoch, so that claims
+/// don't depend on someone taking them manually.
+///
+/// The epochs are tracked by the fee distributor, so the snapshots are taken up to its current
+/// epoch rather than the one of the hook.
+///
+/// An error here would make the epoch manager fail to create the epoch, so the hook does nothing
+/// when it's not called by the epoch manager or the current epoch can't be retrieved. The
+/// snapshots are then taken by the next action on the contract.
+pub fn epoch_changed_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: EpochChangedHookMsg,
+) -> Result<Response, ContractError> {
+    let response = Response::default().add_attributes(vec![
+        ("action", "epoch_changed_hook".to_string()),
+        ("epoch_manager_epoch", msg.current_epoch.id.to_string()),
+    ]);
+
+    if EPOCH_MANAGER.may_load(deps.storage)? != Some(info.sender) {
+        return Ok(response);
+    }
+
+    let Ok(current_epoch) = helpers::get_current_epoch(deps.as_ref()) else {
+        return Ok(response);
+    };
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
+
+    Ok(response.add_attribute("epoch", current_epoch.to_string()))
+}
+
+/// Sets the epoch manager allowed to execute the epoch changed hook. Only callable by the
+/// incentive factory.
+pub fn update_epoch_manager(
+    deps: DepsMut,
+    info: MessageInfo,
+    epoch_manager_addr: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.factory_address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let epoch_manager_addr = epoch_manager_addr
+        .map(|epoch_manager_addr| deps.api.addr_validate(&epoch_manager_addr))
+        .transpose()?;
+    match &epoch_manager_addr {
+        Some(epoch_manager_addr) => EPOCH_MANAGER.save(deps.storage, epoch_manager_addr)?,
+        None => EPOCH_MANAGER.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_epoch_manager".to_string()),
+        (
+            "epoch_manager_addr",
+            epoch_manager_addr
+                .map(|epoch_manager_addr| epoch_manager_addr.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+    ]))
+}
@@ -0,0 +1,154 @@
+use cosmwasm_std::{Addr, CosmosMsg, DepsMut, MessageInfo, Response, StdError, StdResult, Uint128};
+
+use white_whale_std::pool_network::incentive::OpenPosition;
+
+use crate::execute::poke_boost::rescale_boost;
+use crate::state::{ADDRESS_WEIGHT_HISTORY, OWNER_POSITION_TOKENS, POSITION_TOKENS};
+use crate::{
+    error::ContractError,
+    helpers,
+    state::{ADDRESS_WEIGHT, OPEN_POSITIONS},
+    weight::calculate_weight,
+};
+
+/// Transfers the position with the given unbonding_duration to the recipient, together with its
+/// weight. The pending rewards of the sender are claimed before the transfer.
+///
+/// If the recipient already has a position with the same unbonding_duration, the transferred
+/// position is merged into it.
+pub fn transfer_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    unbonding_duration: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let (messages, transferred_position) =
+        move_position(deps, &info.sender, &recipient, unbonding_duration)?;
+
+    Ok(Response::default()
+        .add_attributes(vec![
+            ("action", "transfer_position".to_string()),
+            ("sender", info.sender.to_string()),
+            ("recipient", recipient.to_string()),
+            ("transferred_position", transferred_position.to_string()),
+        ])
+        .add_messages(messages))
+}
+
+/// Moves the position of the owner with the given unbonding_duration to the recipient, together
+/// with its weight and its NFT if it's tokenized. Returns the messages to send the pending rewards
+/// to the owner, and the moved position.
+pub(crate) fn move_position(
+    mut deps: DepsMut,
+    owner: &Addr,
+    recipient: &Addr,
+    unbonding_duration: u64,
+) -> Result<(Vec<CosmosMsg>, OpenPosition), ContractError> {
+    if recipient == owner {
+        return Err(ContractError::InvalidTransferRecipient {});
+    }
+
+    // the rewards can only be settled once the global weight snapshot for the current epoch is taken
+    let current_epoch = helpers::get_current_epoch(deps.as_ref())?;
+    helpers::fill_global_weight_snapshots(deps.storage, current_epoch)?;
+
+    // remove the position from the owner
+    let mut open_positions = OPEN_POSITIONS
+        .may_load(deps.storage, owner.clone())?
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+    let to_transfer_index = open_positions
+        .iter()
+        .position(|pos| pos.unbonding_duration == unbonding_duration)
+        .ok_or(ContractError::NonExistentPosition { unbonding_duration })?;
+    let transferred_position: OpenPosition = open_positions.remove(to_transfer_index);
+
+    // settle the pending rewards of the owner, which were earned with the position. The claim is
+    // capped at EPOCH_CLAIM_CAP epochs per flow, the rest stays claimable by the owner
+    let messages = match crate::claim::claim(&mut deps, owner, None, None) {
+        Ok(rewards) => rewards
+            .into_iter()
+            .map(|reward| reward.into_msg(owner.clone()))
+            .collect::<StdResult<Vec<CosmosMsg>>>()?,
+        Err(ContractError::NothingToClaim {}) => vec![],
+        Err(err) => return Err(err),
+    };
+
+    OPEN_POSITIONS.save(deps.storage, owner.clone(), &open_positions)?;
+
+    // a tokenized position can't be merged, the NFT goes with it to the recipient
+    let token_id = helpers::get_position_token_id(deps.as_ref(), owner, unbonding_duration)?;
+    if let Some(token_id) = token_id {
+        let recipient_has_position = OPEN_POSITIONS
+            .may_load(deps.storage, recipient.clone())?
+            .unwrap_or_default()
+            .iter()
+            .any(|position| position.unbonding_duration == unbonding_duration);
+        if recipient_has_position {
+            return Err(ContractError::DuplicatePosition);
+        }
+
+        POSITION_TOKENS.update::<_, ContractError>(deps.storage, &token_id, |token| {
+            let mut token = token.ok_or_else(|| ContractError::NonExistentPositionToken {
+                token_id: token_id.clone(),
+            })?;
+            token.owner = recipient.clone();
+            token.approvals = vec![];
+
+            Ok(token)
+        })?;
+        OWNER_POSITION_TOKENS.remove(deps.storage, (owner, &token_id));
+        OWNER_POSITION_TOKENS.save(deps.storage, (recipient, &token_id), &unbonding_duration)?;
+    }
+
+    // add the position to the recipient
+    OPEN_POSITIONS.update::<_, StdError>(deps.storage, recipient.clone(), |positions| {
+        let mut positions = positions.unwrap_or_default();
+
+        match positions
+            .iter_mut()
+            .find(|position| position.unbonding_duration == unbonding_duration)
+        {
+            Some(position) => position.amount += transferred_position.amount,
+            None => positions.push(transferred_position.clone()),
+        }
+
+        Ok(positions)
+    })?;
+
+    // move the weight of the position, the global weight stays the same
+    let weight = calculate_weight(unbonding_duration, transferred_position.amount)?;
+    update_address_weight(&mut deps, owner, current_epoch, |user_weight| {
+        Ok(user_weight.saturating_sub(weight))
+    })?;
+    update_address_weight(&mut deps, recipient, current_epoch, |user_weight| {
+        Ok(user_weight.checked_add(weight)?)
+    })?;
+
+    Ok((messages, transferred_position))
+}
+
+/// Updates the weight of the address, rescaling its boost to the new weight, and stores the new
+/// weight in the history for the next epoch.
+fn update_address_weight(
+    deps: &mut DepsMut,
+    address: &Addr,
+    current_epoch: u64,
+    update: impl FnOnce(Uint128) -> Result<Uint128, ContractError>,
+) -> Result<(), ContractError> {
+    let user_weight = update(
+        ADDRESS_WEIGHT
+            .may_load(deps.storage, address.clone())?
+            .unwrap_or_default(),
+    )?;
+    let user_weight = rescale_boost(deps, address, user_weight)?;
+    ADDRESS_WEIGHT.save(deps.storage, address.clone(), &user_weight)?;
+
+    ADDRESS_WEIGHT_HISTORY.update::<_, StdError>(
+        deps.storage,
+        (address, current_epoch + 1u64),
+        |_| Ok(user_weight),
+    )?;
+
+    Ok(())
+}
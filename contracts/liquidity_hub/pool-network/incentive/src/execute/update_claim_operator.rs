@@ -0,0 +1,44 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use white_whale_std::pool_network::incentive::AutoCompound;
+
+use crate::error::ContractError;
+use crate::state::{CLAIM_OPERATORS, CLAIM_OPERATOR_AUTO_COMPOUND};
+
+/// Sets the address allowed to claim the rewards on behalf of the sender, or removes it if no
+/// operator is given. The operator's claims are compounded with the given parameters, if any.
+pub fn update_claim_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: Option<String>,
+    auto_compound: Option<AutoCompound>,
+) -> Result<Response, ContractError> {
+    let operator = operator
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+
+    match &operator {
+        Some(operator) => CLAIM_OPERATORS.save(deps.storage, &info.sender, operator)?,
+        None => CLAIM_OPERATORS.remove(deps.storage, &info.sender),
+    }
+
+    // the auto-compound parameters only apply to the claims done by the operator
+    let auto_compound = operator.as_ref().and(auto_compound);
+    match &auto_compound {
+        Some(auto_compound) => {
+            CLAIM_OPERATOR_AUTO_COMPOUND.save(deps.storage, &info.sender, auto_compound)?
+        }
+        None => CLAIM_OPERATOR_AUTO_COMPOUND.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "update_claim_operator".to_string()),
+        ("owner", info.sender.to_string()),
+        (
+            "operator",
+            operator
+                .map(|operator| operator.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ),
+        ("auto_compound", auto_compound.is_some().to_string()),
+    ]))
+}
@@ -1,9 +1,15 @@
-use cosmwasm_std::{Addr, Deps, DepsMut, Order, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    Addr, Decimal, Decimal256, Deps, DepsMut, Order, StdError, StdResult, Storage, Uint128, Uint256,
+};
+use cw_storage_plus::Bound;
 
-use white_whale_std::pool_network::incentive::Flow;
+use white_whale_std::pool_network::incentive::{Curve, Flow};
 
 use crate::error::ContractError;
-use crate::state::{EpochId, ADDRESS_WEIGHT_HISTORY, CONFIG, FLOWS};
+use crate::state::{
+    EpochId, ADDRESS_BOOST, ADDRESS_WEIGHT_HISTORY, CONFIG, FLOWS, GLOBAL_WEIGHT,
+    GLOBAL_WEIGHT_SNAPSHOT, OWNER_POSITION_TOKENS,
+};
 
 /// Gets the current epoch from the fee distributor contract.
 pub fn get_current_epoch(deps: Deps) -> Result<u64, ContractError> {
@@ -17,6 +23,37 @@ pub fn get_current_epoch(deps: Deps) -> Result<u64, ContractError> {
     Ok(epoch_response.epoch.id.u64())
 }
 
+/// Takes the global weight snapshots of the epochs since the last snapshot up to the current one.
+///
+/// Every action changing the global weight fills the snapshots first, so the global weight hasn't
+/// changed since the start of the first missing epoch and holds for all the missing epochs.
+pub fn fill_global_weight_snapshots(
+    storage: &mut dyn Storage,
+    current_epoch: EpochId,
+) -> StdResult<()> {
+    let last_snapshot_epoch = GLOBAL_WEIGHT_SNAPSHOT
+        .keys(
+            storage,
+            None,
+            Some(Bound::inclusive(current_epoch)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+
+    let first_missing_epoch = match last_snapshot_epoch {
+        Some(epoch) => epoch + 1u64,
+        None => current_epoch,
+    };
+
+    let global_weight = GLOBAL_WEIGHT.may_load(storage)?.unwrap_or_default();
+    for epoch in first_missing_epoch..=current_epoch {
+        GLOBAL_WEIGHT_SNAPSHOT.save(storage, epoch, &global_weight)?;
+    }
+
+    Ok(())
+}
+
 /// Gets the flows that are available for the current epoch, i.e. those flows that started either on
 /// the epoch provided or before it.
 pub fn get_available_flows(deps: Deps, epoch: &u64) -> Result<Vec<Flow>, ContractError> {
@@ -41,14 +78,44 @@ pub fn get_earliest_available_weight_snapshot_for_user(
         .collect::<StdResult<Vec<(EpochId, Uint128)>>>()?)
 }
 
-// Deletes all the weight history entries for the given user
-pub fn delete_weight_history_for_user(
+/// Gets the weight the address had at the given epoch, i.e. the last weight recorded for it up to
+/// that epoch.
+pub fn get_address_weight_at_epoch(
+    deps: Deps,
+    address: &Addr,
+    epoch: EpochId,
+) -> StdResult<Uint128> {
+    Ok(ADDRESS_WEIGHT_HISTORY
+        .prefix(address)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(epoch)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, weight)| weight)
+        .unwrap_or_default())
+}
+
+/// Deletes the weight history entries of the given user that are not needed to compute the weight
+/// from `epoch` onwards, i.e. all the entries before the last one recorded up to `epoch`.
+pub fn compact_weight_history_for_user(
     deps: &mut DepsMut,
-    address: &&Addr,
+    address: &Addr,
+    epoch: EpochId,
 ) -> Result<(), ContractError> {
     let address_weight_history_epoch_keys_for_sender = ADDRESS_WEIGHT_HISTORY
-        .prefix(&(*address).clone())
-        .keys(deps.storage, None, None, Order::Ascending)
+        .prefix(address)
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(epoch)),
+            Order::Descending,
+        )
+        // keep the entry the weight at `epoch` is taken from
+        .skip(1)
         .collect::<StdResult<Vec<u64>>>()?;
 
     address_weight_history_epoch_keys_for_sender
@@ -92,6 +159,157 @@ pub fn get_flow_current_end_epoch(flow: &Flow, epoch: u64) -> u64 {
     end_epoch
 }
 
+/// Gets the amount of tokens the flow emitted up to the epoch before the given one. The emissions
+/// of the epochs in between that haven't been recorded yet, i.e. that no one has claimed, are
+/// computed and recorded in the flow.
+pub fn get_emitted_tokens_before_epoch(
+    flow: &mut Flow,
+    epoch: u64,
+) -> Result<Uint128, ContractError> {
+    if epoch <= flow.start_epoch {
+        return Ok(Uint128::zero());
+    }
+
+    // find the last epoch with recorded emissions
+    let mut from_epoch = epoch - 1u64;
+    while from_epoch > flow.start_epoch && !flow.emitted_tokens.contains_key(&from_epoch) {
+        from_epoch -= 1u64;
+    }
+
+    let mut emitted_tokens = flow
+        .emitted_tokens
+        .get(&from_epoch)
+        .copied()
+        .unwrap_or_default();
+    if !flow.emitted_tokens.contains_key(&from_epoch) {
+        // nothing was recorded for the flow yet, start from its first epoch
+        from_epoch = flow.start_epoch;
+        emitted_tokens = get_flow_emission_at_epoch(flow, from_epoch, Uint128::zero())?;
+        flow.emitted_tokens.insert(from_epoch, emitted_tokens);
+    }
+
+    for missing_epoch in from_epoch + 1u64..epoch {
+        emitted_tokens = emitted_tokens.checked_add(get_flow_emission_at_epoch(
+            flow,
+            missing_epoch,
+            emitted_tokens,
+        )?)?;
+        flow.emitted_tokens.insert(missing_epoch, emitted_tokens);
+    }
+
+    Ok(emitted_tokens)
+}
+
+/// Gets the amount of tokens the flow emits at the given epoch, given the amount of tokens it has
+/// emitted up to the previous epoch.
+///
+/// The tokens that haven't been emitted yet are distributed among the remaining epochs of the flow
+/// according to its curve, i.e. the epoch gets its weight in the curve over the sum of the weights
+/// of the remaining epochs. This way the emissions adapt to flow expansions, and the last epoch
+/// emits whatever is left.
+pub fn get_flow_emission_at_epoch(
+    flow: &Flow,
+    epoch: u64,
+    emitted_tokens: Uint128,
+) -> Result<Uint128, ContractError> {
+    // use the flow asset amount at the current epoch considering flow expansions
+    let flow_asset_amount = get_flow_asset_amount_at_epoch(flow, epoch);
+    let flow_expanded_end_epoch = get_flow_current_end_epoch(flow, epoch);
+    let remaining_tokens = flow_asset_amount.saturating_sub(emitted_tokens);
+
+    // emission = (total_tokens_for_epoch_considering_expansion - emitted_tokens_at_epoch) / (flow_end - epoch)
+    let linear_emission =
+        remaining_tokens.checked_div(Uint128::from(flow_expanded_end_epoch - epoch))?;
+
+    if flow.curve == Curve::Linear {
+        return Ok(linear_emission);
+    }
+
+    let mut remaining_weight = Decimal256::zero();
+    for remaining_epoch in epoch..flow_expanded_end_epoch {
+        let weight = get_curve_weight(flow, epoch, remaining_epoch, flow_expanded_end_epoch);
+        // the weights of the exponential decay only get smaller from here
+        if weight.is_zero() && matches!(flow.curve, Curve::ExponentialDecay { .. }) {
+            break;
+        }
+        remaining_weight = remaining_weight.checked_add(weight)?;
+    }
+
+    // if the curve has nothing left to emit, i.e. a step schedule ending with a zero weight,
+    // release what's left linearly so that no tokens get stuck in the flow
+    if remaining_weight.is_zero() {
+        return Ok(linear_emission);
+    }
+
+    let epoch_share = get_curve_weight(flow, epoch, epoch, flow_expanded_end_epoch)
+        .checked_div(remaining_weight)?;
+
+    Ok((Uint256::from_uint128(remaining_tokens) * epoch_share).try_into()?)
+}
+
+/// Gets the weight of the given epoch in the curve of the flow, relative to the epoch the emission
+/// is being computed for.
+fn get_curve_weight(flow: &Flow, current_epoch: u64, epoch: u64, end_epoch: u64) -> Decimal256 {
+    match &flow.curve {
+        Curve::Linear => Decimal256::one(),
+        Curve::ExponentialDecay { half_life_epochs } => {
+            // the decay is memoryless, so the weights can be computed from the current epoch,
+            // halving every half life and interpolating linearly in between
+            let elapsed_epochs = epoch - current_epoch;
+            let half_lives = elapsed_epochs / half_life_epochs;
+            if half_lives >= 128 {
+                return Decimal256::zero();
+            }
+
+            let half_life_epochs = u128::from(*half_life_epochs);
+            let interpolation = Decimal256::from_ratio(
+                2 * half_life_epochs - u128::from(elapsed_epochs) % half_life_epochs,
+                2 * half_life_epochs,
+            );
+            Decimal256::from_ratio(1u128, 1u128 << half_lives) * interpolation
+        }
+        Curve::Step { schedule } => {
+            let epoch_offset = epoch.saturating_sub(flow.start_epoch);
+            schedule
+                .iter()
+                .take_while(|(step_offset, _)| *step_offset <= epoch_offset)
+                .last()
+                .map(|(_, weight)| *weight)
+                .unwrap_or_default()
+        }
+        Curve::Cliff => {
+            if epoch + 1 == end_epoch {
+                Decimal256::one()
+            } else {
+                Decimal256::zero()
+            }
+        }
+    }
+}
+
+/// Validates the distribution curve of a flow.
+pub fn validate_curve(curve: &Curve) -> Result<(), ContractError> {
+    match curve {
+        Curve::Linear | Curve::Cliff => Ok(()),
+        Curve::ExponentialDecay { half_life_epochs } => {
+            if *half_life_epochs == 0 {
+                return Err(ContractError::InvalidHalfLife {});
+            }
+            Ok(())
+        }
+        Curve::Step { schedule } => {
+            // the steps must be sorted by epoch offset, without duplicates, and emit something
+            if schedule.is_empty()
+                || schedule.windows(2).any(|steps| steps[0].0 >= steps[1].0)
+                || schedule.iter().all(|(_, weight)| weight.is_zero())
+            {
+                return Err(ContractError::InvalidStepSchedule {});
+            }
+            Ok(())
+        }
+    }
+}
+
 pub const MAX_EPOCH_LIMIT: u64 = 100;
 
 /// Gets a [Flow] filtering the asset history and emitted tokens to the given range of epochs.
@@ -120,3 +338,28 @@ pub fn get_filtered_flow(
 
     Ok(flow)
 }
+
+/// Gets the id of the NFT representing the position of the owner with the given unbonding
+/// duration, if the position is tokenized.
+pub fn get_position_token_id(
+    deps: Deps,
+    owner: &Addr,
+    unbonding_duration: u64,
+) -> StdResult<Option<String>> {
+    Ok(OWNER_POSITION_TOKENS
+        .prefix(owner)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .find(|(_, duration)| *duration == unbonding_duration)
+        .map(|(token_id, _)| token_id))
+}
+
+/// Gets the boost multiplier applied to the weight of the address, which is one if the address
+/// isn't boosted.
+pub fn get_boost(deps: Deps, address: &Addr) -> StdResult<Decimal> {
+    Ok(ADDRESS_BOOST
+        .may_load(deps.storage, address)?
+        .map(|boost| boost.multiplier)
+        .unwrap_or(Decimal::one()))
+}
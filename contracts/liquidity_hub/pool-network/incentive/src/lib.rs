@@ -3,6 +3,7 @@ mod error;
 pub mod state;
 
 mod claim;
+mod compound;
 mod execute;
 mod funds_validation;
 mod helpers;
@@ -0,0 +1,121 @@
+use cosmwasm_std::{Decimal256, Deps, Order, StdResult, Uint128};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::incentive::{ActiveFlow, ActiveFlowsResponse, Flow};
+
+use crate::error::ContractError;
+use crate::helpers::{
+    get_current_epoch, get_emitted_tokens_before_epoch, get_flow_emission_at_epoch,
+    get_flow_end_epoch,
+};
+use crate::state::{CONFIG, FLOWS, GLOBAL_WEIGHT, GLOBAL_WEIGHT_SNAPSHOT};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Retrieves the flows that haven't finished emitting yet, matching the given filters.
+pub fn get_active_flows(
+    deps: Deps,
+    flow_asset: Option<AssetInfo>,
+    creator: Option<String>,
+    start_epoch: Option<u64>,
+    end_epoch: Option<u64>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<ActiveFlowsResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_epoch = get_current_epoch(deps)?;
+    let creator = creator
+        .map(|creator| deps.api.addr_validate(&creator))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // the flows are stored by start epoch, so they need to be sorted by id to paginate
+    let mut flows = FLOWS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, flow)| flow))
+        .collect::<StdResult<Vec<Flow>>>()?
+        .into_iter()
+        .filter(|flow| {
+            let flow_end_epoch = get_flow_end_epoch(flow);
+
+            flow_end_epoch > current_epoch
+                && start_after.map_or(true, |flow_id| flow.flow_id > flow_id)
+                && flow_asset
+                    .as_ref()
+                    .map_or(true, |asset| flow.flow_asset.info == *asset)
+                && creator
+                    .as_ref()
+                    .map_or(true, |creator| flow.flow_creator == *creator)
+                && start_epoch.map_or(true, |epoch| flow_end_epoch > epoch)
+                && end_epoch.map_or(true, |epoch| flow.start_epoch <= epoch)
+        })
+        .collect::<Vec<Flow>>();
+    flows.sort_by_key(|flow| flow.flow_id);
+
+    // the snapshot for the current epoch is taken with the first claim, fall back to the current
+    // global weight until then
+    let global_weight = match GLOBAL_WEIGHT_SNAPSHOT.may_load(deps.storage, current_epoch)? {
+        Some(global_weight) => global_weight,
+        None => GLOBAL_WEIGHT.may_load(deps.storage)?.unwrap_or_default(),
+    };
+
+    let flows = flows
+        .into_iter()
+        .take(limit)
+        .map(|flow| get_active_flow(flow, current_epoch, global_weight))
+        .collect::<Result<Vec<ActiveFlow>, ContractError>>()?;
+
+    Ok(ActiveFlowsResponse {
+        lp_asset: config.lp_asset,
+        epoch_id: current_epoch,
+        flows,
+    })
+}
+
+/// Summarizes the emissions of the given flow at the current epoch.
+fn get_active_flow(
+    mut flow: Flow,
+    current_epoch: u64,
+    global_weight: Uint128,
+) -> Result<ActiveFlow, ContractError> {
+    let end_epoch = get_flow_end_epoch(&flow);
+    let flow_amount = flow
+        .asset_history
+        .last_key_value()
+        .map_or(flow.flow_asset.amount, |(_, &(amount, _))| amount);
+
+    let (remaining_emissions, current_epoch_emissions) = if current_epoch < flow.start_epoch {
+        (flow_amount, Uint128::zero())
+    } else {
+        let emitted_tokens = get_emitted_tokens_before_epoch(&mut flow, current_epoch)?;
+
+        (
+            flow_amount.saturating_sub(emitted_tokens),
+            get_flow_emission_at_epoch(&flow, current_epoch, emitted_tokens)?,
+        )
+    };
+
+    let reward_per_weight = if global_weight.is_zero() {
+        Decimal256::zero()
+    } else {
+        Decimal256::checked_from_ratio(current_epoch_emissions, global_weight)?
+    };
+
+    Ok(ActiveFlow {
+        flow_id: flow.flow_id,
+        flow_label: flow.flow_label,
+        flow_creator: flow.flow_creator,
+        flow_asset: Asset {
+            info: flow.flow_asset.info,
+            amount: flow_amount,
+        },
+        curve: flow.curve,
+        start_epoch: flow.start_epoch,
+        end_epoch,
+        remaining_emissions,
+        current_epoch_emissions,
+        reward_per_weight,
+    })
+}
@@ -0,0 +1,19 @@
+use cosmwasm_std::Deps;
+use white_whale_std::pool_network::incentive::ClaimOperatorResponse;
+
+use crate::error::ContractError;
+use crate::state::{CLAIM_OPERATORS, CLAIM_OPERATOR_AUTO_COMPOUND};
+
+/// Gets the claim operator of the given address, together with the parameters its claims are
+/// compounded with. Returns a [ClaimOperatorResponse] struct.
+pub fn get_claim_operator(
+    deps: Deps,
+    address: String,
+) -> Result<ClaimOperatorResponse, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(ClaimOperatorResponse {
+        operator: CLAIM_OPERATORS.may_load(deps.storage, &address)?,
+        auto_compound: CLAIM_OPERATOR_AUTO_COMPOUND.may_load(deps.storage, &address)?,
+    })
+}
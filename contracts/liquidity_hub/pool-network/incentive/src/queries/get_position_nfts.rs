@@ -0,0 +1,218 @@
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+use white_whale_std::pool_network::incentive::{
+    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse,
+    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, PositionMetadata,
+    TokensResponse,
+};
+
+use crate::error::ContractError;
+use crate::state::{
+    PositionToken, NFT_OPERATORS, OPEN_POSITIONS, OWNER_POSITION_TOKENS, POSITION_TOKENS,
+};
+use crate::weight::calculate_weight;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 10;
+
+const NFT_NAME: &str = "White Whale Incentive Positions";
+const NFT_SYMBOL: &str = "wwPOSITION";
+
+/// Gets the owner of the position NFT and the approvals on it. Returns a [OwnerOfResponse] struct.
+pub fn get_owner_of(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> Result<OwnerOfResponse, ContractError> {
+    let token = load_token(deps, &token_id)?;
+
+    Ok(OwnerOfResponse {
+        owner: token.owner.to_string(),
+        approvals: filter_approvals(&env, token.approvals, include_expired),
+    })
+}
+
+/// Gets the approval of the spender on the position NFT. Returns a [ApprovalResponse] struct.
+pub fn get_approval(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    spender: String,
+    include_expired: Option<bool>,
+) -> Result<ApprovalResponse, ContractError> {
+    let token = load_token(deps, &token_id)?;
+
+    filter_approvals(&env, token.approvals, include_expired)
+        .into_iter()
+        .find(|approval| approval.spender == spender)
+        .map(|approval| ApprovalResponse { approval })
+        .ok_or(ContractError::ApprovalNotFound { spender, token_id })
+}
+
+/// Gets the approvals on the position NFT. Returns a [ApprovalsResponse] struct.
+pub fn get_approvals(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> Result<ApprovalsResponse, ContractError> {
+    let token = load_token(deps, &token_id)?;
+
+    Ok(ApprovalsResponse {
+        approvals: filter_approvals(&env, token.approvals, include_expired),
+    })
+}
+
+/// Gets the operators allowed to handle all the position NFTs of the owner. Returns a
+/// [OperatorsResponse] struct.
+pub fn get_all_operators(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<OperatorsResponse, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let include_expired = include_expired.unwrap_or(false);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let operators = NFT_OPERATORS
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|operator| match operator {
+            Ok((_, expires)) => include_expired || !expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|operator| {
+            operator.map(|(operator, expires)| Approval {
+                spender: operator.to_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<Vec<Approval>>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+/// Gets the metadata of the position NFT, i.e. the position it represents. Returns a
+/// [NftInfoResponse] struct.
+pub fn get_nft_info(deps: Deps, token_id: String) -> Result<NftInfoResponse, ContractError> {
+    let token = load_token(deps, &token_id)?;
+
+    let position = OPEN_POSITIONS
+        .may_load(deps.storage, token.owner)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|position| position.unbonding_duration == token.unbonding_duration)
+        .ok_or(ContractError::NonExistentPosition {
+            unbonding_duration: token.unbonding_duration,
+        })?;
+
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: PositionMetadata {
+            amount: position.amount,
+            unbonding_duration: position.unbonding_duration,
+            weight: calculate_weight(position.unbonding_duration, position.amount)?,
+        },
+    })
+}
+
+/// Gets the owner, approvals and metadata of the position NFT. Returns a [AllNftInfoResponse]
+/// struct.
+pub fn get_all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> Result<AllNftInfoResponse, ContractError> {
+    Ok(AllNftInfoResponse {
+        access: get_owner_of(deps, env, token_id.clone(), include_expired)?,
+        info: get_nft_info(deps, token_id)?,
+    })
+}
+
+/// Gets the ids of the position NFTs owned by the given address. Returns a [TokensResponse] struct.
+pub fn get_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<TokensResponse, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = OWNER_POSITION_TOKENS
+        .prefix(&owner)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+/// Gets the ids of all the position NFTs. Returns a [TokensResponse] struct.
+pub fn get_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<TokensResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = POSITION_TOKENS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+/// Gets the amount of position NFTs in existence. Returns a [NumTokensResponse] struct.
+pub fn get_num_tokens(deps: Deps) -> Result<NumTokensResponse, ContractError> {
+    let count = POSITION_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+
+    Ok(NumTokensResponse { count })
+}
+
+/// Gets the name and symbol of the position NFTs. Returns a [ContractInfoResponse] struct.
+pub fn get_contract_info() -> ContractInfoResponse {
+    ContractInfoResponse {
+        name: NFT_NAME.to_string(),
+        symbol: NFT_SYMBOL.to_string(),
+    }
+}
+
+/// Filters out the expired approvals, unless they are to be included.
+fn filter_approvals(
+    env: &Env,
+    approvals: Vec<Approval>,
+    include_expired: Option<bool>,
+) -> Vec<Approval> {
+    let include_expired = include_expired.unwrap_or(false);
+
+    approvals
+        .into_iter()
+        .filter(|approval| include_expired || !approval.expires.is_expired(&env.block))
+        .collect()
+}
+
+/// Loads the position NFT with the given id.
+fn load_token(deps: Deps, token_id: &str) -> Result<PositionToken, ContractError> {
+    POSITION_TOKENS
+        .may_load(deps.storage, token_id)?
+        .ok_or_else(|| ContractError::NonExistentPositionToken {
+            token_id: token_id.to_string(),
+        })
+}
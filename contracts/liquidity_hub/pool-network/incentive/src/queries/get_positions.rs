@@ -3,6 +3,7 @@ use white_whale_std::pool_network::incentive::{PositionsResponse, QueryPosition}
 
 use crate::{
     error::ContractError,
+    helpers,
     state::{CLOSED_POSITIONS, OPEN_POSITIONS},
     weight::calculate_weight,
 };
@@ -29,7 +30,7 @@ pub fn get_positions(
         .collect::<Result<Vec<_>, ContractError>>()?;
 
     let closed_positions = CLOSED_POSITIONS
-        .may_load(deps.storage, address)?
+        .may_load(deps.storage, address.clone())?
         .unwrap_or_default()
         .into_iter()
         .map(|position| QueryPosition::ClosedPosition {
@@ -42,6 +43,7 @@ pub fn get_positions(
     Ok(PositionsResponse {
         timestamp: env.block.time.seconds(),
         positions: open_positions.into_iter().chain(closed_positions).collect(),
+        boost: helpers::get_boost(deps, &address)?,
     })
 }
 
@@ -16,6 +16,7 @@ pub fn get_rewards_share(deps: Deps, address: Addr) -> Result<RewardsShareRespon
         helpers::get_earliest_available_weight_snapshot_for_user(deps, &&address)?;
 
     let current_epoch = helpers::get_current_epoch(deps)?;
+    let boost = helpers::get_boost(deps, &address)?;
 
     if !earliest_available_weight_for_user.is_empty() {
         (last_epoch_user_weight_update, last_user_weight_seen) =
@@ -31,6 +32,7 @@ pub fn get_rewards_share(deps: Deps, address: Addr) -> Result<RewardsShareRespon
             address_weight: Uint128::zero(),
             share: Decimal256::zero(),
             epoch_id: current_epoch,
+            boost,
         });
     }
 
@@ -57,6 +59,7 @@ pub fn get_rewards_share(deps: Deps, address: Addr) -> Result<RewardsShareRespon
             address_weight: last_user_weight_seen,
             share: user_share_at_epoch,
             epoch_id: current_epoch,
+            boost,
         })
     } else {
         Err(ContractError::GlobalWeightSnapshotNotTakenForEpoch {
@@ -1,15 +1,24 @@
+mod get_active_flows;
+mod get_claim_operator;
 mod get_config;
 mod get_flow;
 mod get_flows;
 mod get_global_weight;
+mod get_position_nfts;
 mod get_positions;
 mod get_rewards;
 mod get_rewards_share;
 
+pub use get_active_flows::get_active_flows;
+pub use get_claim_operator::get_claim_operator;
 pub use get_config::get_config;
 pub use get_flow::get_flow;
 pub use get_flows::get_flows;
 pub use get_global_weight::get_global_weight;
+pub use get_position_nfts::{
+    get_all_nft_info, get_all_operators, get_all_tokens, get_approval, get_approvals,
+    get_contract_info, get_nft_info, get_num_tokens, get_owner_of, get_tokens,
+};
 pub use get_positions::get_positions;
 pub use get_rewards::get_rewards;
 pub use get_rewards_share::get_rewards_share;
@@ -1,6 +1,10 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Expiration;
 use cw_storage_plus::{Item, Map};
-use white_whale_std::pool_network::incentive::{ClosedPosition, Config, Flow, OpenPosition};
+use white_whale_std::pool_network::incentive::{
+    Approval, AutoCompound, ClosedPosition, Config, Flow, OpenPosition,
+};
 
 /// The configuration for the incentive contract.
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -29,8 +33,51 @@ pub const GLOBAL_WEIGHT_SNAPSHOT: Map<EpochId, Uint128> = Map::new("global_weigh
 /// The address weight history, i.e. how much weight an address had at a given epoch. Key is (address, epoch_id)
 pub const ADDRESS_WEIGHT_HISTORY: Map<(&Addr, EpochId), Uint128> =
     Map::new("address_weight_snapshot");
-/// The last epoch an address claimed rewards
+/// The last epoch an address claimed rewards for on all the flows
 pub const LAST_CLAIMED_EPOCH: Map<&Addr, EpochId> = Map::new("last_claimed_epoch");
+/// The last epoch an address claimed rewards for on a given flow, when the flow was claimed further
+/// than [LAST_CLAIMED_EPOCH], i.e. while a paginated claim is in progress. Key is (address, flow_id)
+pub const LAST_CLAIMED_FLOW_EPOCH: Map<(&Addr, FlowId), EpochId> =
+    Map::new("last_claimed_flow_epoch");
+/// The address allowed to claim the rewards on behalf of an address
+pub const CLAIM_OPERATORS: Map<&Addr, Addr> = Map::new("claim_operators");
+/// The parameters the rewards claimed by the claim operator of an address are compounded with
+pub const CLAIM_OPERATOR_AUTO_COMPOUND: Map<&Addr, AutoCompound> =
+    Map::new("claim_operator_auto_compound");
+/// The epoch manager allowed to execute the epoch changed hook
+pub const EPOCH_MANAGER: Item<Addr> = Item::new("epoch_manager");
+/// The counter used to generate the ids of the position NFTs
+pub const POSITION_TOKEN_COUNTER: Item<u64> = Item::new("position_token_counter");
+/// The position NFTs, keyed by token id
+pub const POSITION_TOKENS: Map<&str, PositionToken> = Map::new("position_tokens");
+/// The position NFTs owned by an address. Key is (owner, token_id), value is the unbonding duration
+/// of the position
+pub const OWNER_POSITION_TOKENS: Map<(&Addr, &str), u64> = Map::new("owner_position_tokens");
+/// The operators allowed to handle all the position NFTs of an owner. Key is (owner, operator),
+/// value is when the approval expires
+pub const NFT_OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("nft_operators");
+/// The boost applied to the weight of an address, based on its bonding share in the whale lair
+pub const ADDRESS_BOOST: Map<&Addr, Boost> = Map::new("address_boost");
 
 pub type EpochId = u64;
 pub type FlowId = u64;
+
+/// A position represented by a cw721 NFT.
+#[cw_serde]
+pub struct PositionToken {
+    /// The owner of the NFT, and thus of the position.
+    pub owner: Addr,
+    /// The unbonding duration of the position.
+    pub unbonding_duration: u64,
+    /// The addresses allowed to transfer the NFT on behalf of the owner.
+    pub approvals: Vec<Approval>,
+}
+
+/// The boost applied to the weight of an address.
+#[cw_serde]
+pub struct Boost {
+    /// The multiplier applied to the weight of the address.
+    pub multiplier: Decimal,
+    /// The weight added to the address by the boost, on top of the weight of its positions.
+    pub weight: Uint128,
+}
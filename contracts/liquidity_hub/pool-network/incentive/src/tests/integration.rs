@@ -1,12 +1,12 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 
-use cosmwasm_std::{coin, coins, Addr, Decimal256, Timestamp, Uint128};
+use cosmwasm_std::{coin, coins, Addr, Decimal, Decimal256, Timestamp, Uint128};
 
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 use white_whale_std::pool_network::incentive;
 use white_whale_std::pool_network::incentive::{Curve, Flow, FlowIdentifier, RewardsShareResponse};
-use white_whale_std::pool_network::incentive_factory::IncentivesContract;
+use white_whale_std::pool_network::incentive_factory::{CreatorFlowFee, IncentivesContract};
 
 use crate::error::ContractError;
 use crate::tests::suite::TestingSuite;
@@ -3518,6 +3518,7 @@ fn open_expand_close_flows_positions_and_claim_native_token_incentive() {
                             Uint128::new(6_000u128),
                         ),
                         epoch_id: 16u64,
+                        boost: Decimal::one(),
                     }
                 );
             },
@@ -3692,6 +3693,7 @@ fn open_expand_close_flows_positions_and_claim_native_token_incentive() {
                             Uint128::new(6_000u128),
                         ),
                         epoch_id: 26u64,
+                        boost: Decimal::one(),
                     }
                 );
             },
@@ -3927,6 +3929,7 @@ fn open_expand_close_flows_positions_and_claim_native_token_incentive() {
                             Uint128::new(4_000u128),
                         ),
                         epoch_id: 27u64,
+                        boost: Decimal::one(),
                     }
                 );
             },
@@ -3944,6 +3947,7 @@ fn open_expand_close_flows_positions_and_claim_native_token_incentive() {
                         address_weight: Uint128::zero(),
                         share: Decimal256::zero(),
                         epoch_id: 27u64,
+                        boost: Decimal::one(),
                     }
                 );
             },
@@ -4449,27 +4453,40 @@ fn open_expand_position_with_optional_receiver() {
             // Bob has some rewards as Alice opened a position for him
             assert!(result.unwrap().rewards[0].amount > Uint128::zero());
         })
-        .query_rewards(incentive_addr.clone().into_inner(), alice.clone(), |result| {
-            // Alice should have 0, as she didn't open any position for herself but for Bob
-            assert_eq!(result.unwrap().rewards.len(), 0usize);
-        })
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                // Alice should have 0, as she didn't open any position for herself but for Bob
+                assert_eq!(result.unwrap().rewards.len(), 0usize);
+            },
+        )
         .create_epochs_on_fee_distributor_without_snapshot_on_incentive(3u64)
+        .query_incentive_global_weight(incentive_addr.clone().into_inner(), 19u64, |result| {
+            assert_eq!(
+                result.unwrap_err().to_string().rsplit_once(": ").unwrap().1,
+                (ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 19u64 }).to_string()
+            );
+        })
+        // the claim takes the missing snapshots instead of failing
         .claim(
             incentive_addr.clone().into_inner(),
             alice.clone(),
             |result| {
-                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
-
-                match err {
-                    ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch } => assert_eq!(epoch, 19),
-                    _ => panic!(
-                        "Wrong error type, should return ContractError::GlobalWeightSnapshotNotTakenForEpoch"
-                    ),
-                }
+                result.unwrap();
             },
         )
+        .query_incentive_global_weight(incentive_addr.clone().into_inner(), 17u64, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1000u128));
+        })
+        .query_incentive_global_weight(incentive_addr.clone().into_inner(), 19u64, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1000u128));
+        })
         .query_incentive_global_weight(incentive_addr.clone().into_inner(), 100u64, |result| {
-            assert_eq!(result.unwrap_err().to_string().rsplit_once(": ").unwrap().1, (ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 100u64 }).to_string());
+            assert_eq!(
+                result.unwrap_err().to_string().rsplit_once(": ").unwrap().1,
+                (ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 100u64 }).to_string()
+            );
         });
 }
 
@@ -4691,6 +4708,7 @@ fn close_position_if_empty_rewards() {
                         address_weight: Uint128::new(1u128),
                         share: Decimal256::from_ratio(Uint128::new(1u128), Uint128::new(5001u128)),
                         epoch_id: 26u64,
+                        boost: Decimal::one(),
                     }
                 );
             },
@@ -6609,3 +6627,2824 @@ fn open_expand_flow_over_expand_limit() {
             },
         );
 }
+
+#[test]
+fn open_flows_with_curves_and_claim() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+        coin(5_000_000_000u128, "bWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    let end_epoch = current_epoch.clone().into_inner() + 10u64;
+
+    let flow_asset = |denom: &str| Asset {
+        info: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::new(10_000u128),
+    };
+
+    // invalid curves are rejected
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            Some(Curve::ExponentialDecay {
+                half_life_epochs: 0u64,
+            }),
+            flow_asset("usdc"),
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::InvalidHalfLife {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidHalfLife"),
+                }
+            },
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            Some(Curve::Step {
+                schedule: vec![(5u64, Decimal256::one()), (2u64, Decimal256::percent(50))],
+            }),
+            flow_asset("usdc"),
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::InvalidStepSchedule {} => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::InvalidStepSchedule")
+                    }
+                }
+            },
+        );
+
+    // open a front-loaded flow and a cliff flow
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            Some(Curve::ExponentialDecay {
+                half_life_epochs: 2u64,
+            }),
+            flow_asset("usdc"),
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            Some(Curve::Cliff),
+            flow_asset("bWHALE"),
+            None,
+            &vec![coin(10_000u128, "bWHALE"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                // the decaying flow emits way more than the linear 1_000 per epoch in the first
+                // two epochs, the cliff flow hasn't emitted anything yet
+                assert_eq!(
+                    result.unwrap().rewards,
+                    vec![Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "usdc".to_string(),
+                        },
+                        amount: Uint128::new(5_057u128),
+                    }]
+                );
+            },
+        );
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    let carol_bwhale_funds = RefCell::new(Uint128::zero());
+
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *carol_usdc_funds.borrow_mut() = result;
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "bWHALE".to_string(),
+            },
+            |result| {
+                *carol_bwhale_funds.borrow_mut() = result;
+            },
+        )
+        .claim(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        // carol is the only one with a position, so she gets everything both flows emitted
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds
+                        .clone()
+                        .into_inner()
+                        .checked_add(Uint128::new(10_000u128))
+                        .unwrap(),
+                );
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "bWHALE".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_bwhale_funds
+                        .clone()
+                        .into_inner()
+                        .checked_add(Uint128::new(10_000u128))
+                        .unwrap(),
+                );
+            },
+        );
+}
+
+#[test]
+fn claim_rewards_across_multiple_transactions() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    let start_epoch = current_epoch.clone().into_inner();
+    let end_epoch = start_epoch + 10u64;
+
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(12, vec![incentive_addr.clone().into_inner()])
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let rewards = result.unwrap();
+                assert_eq!(rewards.rewards[0].amount, Uint128::new(10_000u128));
+                assert_eq!(
+                    rewards.pending_epochs,
+                    vec![incentive::PendingEpochs {
+                        flow_id: 1u64,
+                        start_epoch,
+                        end_epoch: end_epoch - 1u64,
+                    }]
+                );
+            },
+        );
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    suite.query_funds(
+        carol.clone(),
+        AssetInfo::NativeToken {
+            denom: "usdc".to_string(),
+        },
+        |result| {
+            *carol_usdc_funds.borrow_mut() = result;
+        },
+    );
+
+    // claim the first 3 epochs of the flow
+    suite
+        .claim_epochs(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            Some(0u64),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::InvalidMaxEpochs {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::InvalidMaxEpochs"),
+                }
+            },
+        )
+        .claim_epochs(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            Some(3u64),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(3_000u128)
+                );
+            },
+        )
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let rewards = result.unwrap();
+                assert_eq!(rewards.rewards[0].amount, Uint128::new(7_000u128));
+                assert_eq!(
+                    rewards.pending_epochs,
+                    vec![incentive::PendingEpochs {
+                        flow_id: 1u64,
+                        start_epoch: start_epoch + 3u64,
+                        end_epoch: end_epoch - 1u64,
+                    }]
+                );
+            },
+        )
+        // claim up to a given epoch
+        .claim_epochs(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            Some(start_epoch + 4u64),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(5_000u128)
+                );
+            },
+        )
+        // resume the claim until the end
+        .claim(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(10_000u128)
+                );
+            },
+        )
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let rewards = result.unwrap();
+                assert!(rewards.rewards.is_empty());
+                assert!(rewards.pending_epochs.is_empty());
+            },
+        )
+        .claim(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NothingToClaim {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToClaim"),
+                }
+            },
+        );
+}
+
+#[test]
+fn claim_rewards_on_behalf_of_owner() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(current_epoch.clone().into_inner() + 10u64),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(12, vec![incentive_addr.clone().into_inner()]);
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    let bob_usdc_funds = RefCell::new(Uint128::zero());
+    suite
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *carol_usdc_funds.borrow_mut() = result;
+            },
+        )
+        .query_funds(
+            bob.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *bob_usdc_funds.borrow_mut() = result;
+            },
+        );
+
+    suite
+        // bob is not the claim operator of carol
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            carol.clone(),
+            None,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .update_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            Some(bob.clone()),
+            Some(incentive::AutoCompound {
+                unbonding_duration: 86400u64,
+                max_spread: None,
+            }),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let response = result.unwrap();
+                assert_eq!(response.operator, Some(bob.clone()));
+                assert_eq!(
+                    response.auto_compound,
+                    Some(incentive::AutoCompound {
+                        unbonding_duration: 86400u64,
+                        max_spread: None,
+                    })
+                );
+            },
+        )
+        // only carol can choose how to compound her rewards
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            carol.clone(),
+            Some(incentive::AutoCompound {
+                unbonding_duration: 86400u64,
+                max_spread: Some(Decimal::one()),
+            }),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        // bob claims with carol's preference. The LP asset is not tied to a pair, so the
+        // rewards can't be compounded
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            carol.clone(),
+            None,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::AutoCompoundUnavailable {} => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::AutoCompoundUnavailable"
+                    ),
+                }
+            },
+        )
+        .update_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            Some(bob.clone()),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(result.unwrap().auto_compound, None);
+            },
+        )
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            carol.clone(),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        // the rewards go to carol
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(10_000u128)
+                );
+            },
+        )
+        .query_funds(
+            bob.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(result, bob_usdc_funds.clone().into_inner());
+            },
+        )
+        .update_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_claim_operator(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(result.unwrap().operator, None);
+            },
+        );
+}
+
+#[test]
+fn auto_compound_rewards_into_position() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee();
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "usdc".to_string(),
+        },
+    ];
+
+    let pair_addr = RefCell::new(Addr::unchecked(""));
+    let lp_asset = RefCell::new(AssetInfo::NativeToken {
+        denom: "".to_string(),
+    });
+
+    suite
+        .create_pair(asset_infos.clone(), |result| {
+            *pair_addr.borrow_mut() = result.unwrap();
+        })
+        .provide_liquidity(
+            carol.clone(),
+            pair_addr.clone().into_inner(),
+            asset_infos.clone().map(|info| Asset {
+                info,
+                amount: Uint128::new(1_000_000u128),
+            }),
+            &[coin(1_000_000u128, "usdc"), coin(1_000_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_pair(pair_addr.clone().into_inner(), |result| {
+            *lp_asset.borrow_mut() = result.unwrap().liquidity_token;
+        });
+
+    let lp_asset = lp_asset.into_inner();
+    let lp_token = match &lp_asset {
+        AssetInfo::Token { contract_addr } => Addr::unchecked(contract_addr),
+        AssetInfo::NativeToken { .. } => panic!("the LP token should be a cw20 token"),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(lp_asset.clone(), |result| {
+            *incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .increase_allowance(
+            carol.clone(),
+            lp_token.clone(),
+            Uint128::new(1_000u128),
+            incentive_addr.clone().into_inner(),
+        )
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    let end_epoch = current_epoch.clone().into_inner() + 10u64;
+
+    // one flow in one of the pair's assets, and another one in an asset outside the pair
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(end_epoch),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "ampWHALE"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(12, vec![incentive_addr.clone().into_inner()]);
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    let carol_ampwhale_funds = RefCell::new(Uint128::zero());
+    let carol_uwhale_funds = RefCell::new(Uint128::zero());
+    suite
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "uwhale".to_string(),
+            },
+            |result| {
+                *carol_uwhale_funds.borrow_mut() = result;
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *carol_usdc_funds.borrow_mut() = result;
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "ampWHALE".to_string(),
+            },
+            |result| {
+                *carol_ampwhale_funds.borrow_mut() = result;
+            },
+        );
+
+    suite
+        // the position to compound into must exist
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            carol.clone(),
+            Some(incentive::AutoCompound {
+                unbonding_duration: 172800u64,
+                max_spread: None,
+            }),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NonExistentPosition { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::NonExistentPosition")
+                    }
+                }
+            },
+        )
+        .claim_on_behalf(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            carol.clone(),
+            Some(incentive::AutoCompound {
+                unbonding_duration: 86400u64,
+                max_spread: None,
+            }),
+            |result| {
+                result.unwrap();
+            },
+        )
+        // half of the usdc rewards are swapped for uwhale, and both are provided as liquidity at
+        // the ratio of the pool after the swap
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(
+                    result.unwrap().positions.first().unwrap(),
+                    &incentive::QueryPosition::OpenPosition {
+                        amount: Uint128::new(5_974u128),
+                        unbonding_duration: 86400u64,
+                        weight: Uint128::new(5_974u128),
+                    }
+                );
+            },
+        )
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(result, carol_usdc_funds.clone().into_inner());
+            },
+        )
+        // the uwhale that doesn't fit the ratio of the pool is sent to carol
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "uwhale".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_uwhale_funds.clone().into_inner() + Uint128::new(25u128)
+                );
+            },
+        )
+        // the rewards outside the pair are sent to carol
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "ampWHALE".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_ampwhale_funds.clone().into_inner() + Uint128::new(10_000u128)
+                );
+            },
+        )
+        .query_funds(incentive_addr.clone().into_inner(), lp_asset, |result| {
+            assert_eq!(result, Uint128::new(5_974u128));
+        });
+}
+
+#[test]
+fn transfer_position_settles_rewards_and_moves_weight() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(current_epoch.clone().into_inner() + 10u64),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(5, vec![incentive_addr.clone().into_inner()]);
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    let bob_usdc_funds = RefCell::new(Uint128::zero());
+    suite
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *carol_usdc_funds.borrow_mut() = result;
+            },
+        )
+        .query_funds(
+            bob.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                *bob_usdc_funds.borrow_mut() = result;
+            },
+        );
+
+    suite
+        .transfer_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            carol.clone(),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::InvalidTransferRecipient {} => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::InvalidTransferRecipient"
+                    ),
+                }
+            },
+        )
+        .transfer_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            172800u64,
+            bob.clone(),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NonExistentPosition { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::NonExistentPosition")
+                    }
+                }
+            },
+        )
+        .transfer_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            bob.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        // carol got the rewards for the epochs she held the position
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(6_000u128)
+                );
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert!(result.unwrap().positions.is_empty());
+            },
+        )
+        .query_positions(incentive_addr.clone().into_inner(), bob.clone(), |result| {
+            assert_eq!(
+                result.unwrap().positions,
+                vec![incentive::QueryPosition::OpenPosition {
+                    amount: Uint128::new(1_000u128),
+                    unbonding_duration: 86400u64,
+                    weight: Uint128::new(1_000u128),
+                }]
+            );
+        })
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_rewards(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert!(result.unwrap().rewards.is_empty());
+            },
+        )
+        .claim(incentive_addr.clone().into_inner(), bob.clone(), |result| {
+            result.unwrap();
+        })
+        // bob got the rewards for the remaining epochs of the flow
+        .query_funds(
+            bob.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    bob_usdc_funds.clone().into_inner() + Uint128::new(4_000u128)
+                );
+            },
+        );
+}
+
+#[test]
+fn tokenized_position_follows_the_nft() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let time = Timestamp::from_seconds(1684766796u64);
+    suite.set_time(time);
+
+    let current_epoch = RefCell::new(0u64);
+    suite
+        .create_epochs_on_fee_distributor(10, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch(|result| {
+            *current_epoch.borrow_mut() = result.unwrap().epoch.id.u64();
+        });
+
+    suite
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            Some(current_epoch.clone().into_inner() + 10u64),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(5, vec![incentive_addr.clone().into_inner()]);
+
+    suite
+        .tokenize_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            172800u64,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NonExistentPosition { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::NonExistentPosition")
+                    }
+                }
+            },
+        )
+        .tokenize_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .tokenize_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::PositionAlreadyTokenized { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::PositionAlreadyTokenized"
+                    ),
+                }
+            },
+        )
+        .query_num_tokens(incentive_addr.clone().into_inner(), |result| {
+            assert_eq!(result.unwrap().count, 1u64);
+        })
+        .query_tokens(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(result.unwrap().tokens, vec!["1".to_string()]);
+            },
+        )
+        .query_nft_info(incentive_addr.clone().into_inner(), "1", |result| {
+            assert_eq!(
+                result.unwrap().extension,
+                incentive::PositionMetadata {
+                    amount: Uint128::new(1_000u128),
+                    unbonding_duration: 86400u64,
+                    weight: Uint128::new(1_000u128),
+                }
+            );
+        });
+
+    let carol_usdc_funds = RefCell::new(Uint128::zero());
+    suite.query_funds(
+        carol.clone(),
+        AssetInfo::NativeToken {
+            denom: "usdc".to_string(),
+        },
+        |result| {
+            *carol_usdc_funds.borrow_mut() = result;
+        },
+    );
+
+    suite
+        // bob can't transfer carol's NFT without an approval
+        .transfer_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            "1",
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .approve_nft(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            "1",
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_owner_of(incentive_addr.clone().into_inner(), "1", |result| {
+            let owner_of = result.unwrap();
+            assert_eq!(owner_of.owner, carol.to_string());
+            assert_eq!(owner_of.approvals.len(), 1);
+        })
+        .transfer_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            "1",
+            |result| {
+                result.unwrap();
+            },
+        )
+        // carol got the rewards for the epochs she held the position
+        .query_funds(
+            carol.clone(),
+            AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            },
+            |result| {
+                assert_eq!(
+                    result,
+                    carol_usdc_funds.clone().into_inner() + Uint128::new(6_000u128)
+                );
+            },
+        )
+        .query_owner_of(incentive_addr.clone().into_inner(), "1", |result| {
+            let owner_of = result.unwrap();
+            assert_eq!(owner_of.owner, bob.to_string());
+            assert!(owner_of.approvals.is_empty());
+        })
+        .query_tokens(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert!(result.unwrap().tokens.is_empty());
+            },
+        )
+        .query_positions(incentive_addr.clone().into_inner(), bob.clone(), |result| {
+            assert_eq!(
+                result.unwrap().positions,
+                vec![incentive::QueryPosition::OpenPosition {
+                    amount: Uint128::new(1_000u128),
+                    unbonding_duration: 86400u64,
+                    weight: Uint128::new(1_000u128),
+                }]
+            );
+        })
+        // closing the position burns the NFT
+        .close_incentive_position(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_num_tokens(incentive_addr.clone().into_inner(), |result| {
+            assert_eq!(result.unwrap().count, 0u64);
+        })
+        .query_owner_of(incentive_addr.clone().into_inner(), "1", |result| {
+            assert!(result.is_err());
+        });
+}
+
+#[test]
+fn position_nft_operators() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()]);
+
+    for unbonding_duration in [86400u64, 259200u64] {
+        suite
+            .open_incentive_position(
+                carol.clone(),
+                incentive_addr.clone().into_inner(),
+                Uint128::new(1_000u128),
+                unbonding_duration,
+                None,
+                vec![coin(1_000u128, "ampWHALE".to_string())],
+                |result| {
+                    result.unwrap();
+                },
+            )
+            .tokenize_position(
+                carol.clone(),
+                incentive_addr.clone().into_inner(),
+                unbonding_duration,
+                |result| {
+                    result.unwrap();
+                },
+            );
+    }
+
+    suite
+        .query_contract_info(incentive_addr.clone().into_inner(), |result| {
+            let contract_info = result.unwrap();
+            assert_eq!(contract_info.name, "White Whale Incentive Positions");
+            assert_eq!(contract_info.symbol, "wwPOSITION");
+        })
+        .query_all_tokens(incentive_addr.clone().into_inner(), |result| {
+            assert_eq!(
+                result.unwrap().tokens,
+                vec!["1".to_string(), "2".to_string()]
+            );
+        })
+        .query_all_nft_info(incentive_addr.clone().into_inner(), "2", |result| {
+            let all_nft_info = result.unwrap();
+            assert_eq!(all_nft_info.access.owner, carol.to_string());
+            assert!(all_nft_info.access.approvals.is_empty());
+            assert_eq!(
+                all_nft_info.info.extension,
+                incentive::PositionMetadata {
+                    amount: Uint128::new(1_000u128),
+                    unbonding_duration: 259200u64,
+                    weight: Uint128::new(1_006u128),
+                }
+            );
+        })
+        // bob can't approve others on carol's NFTs until he's her operator
+        .approve_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            "2",
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .approve_all_nfts(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_all_operators(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let operators = result.unwrap().operators;
+                assert_eq!(operators.len(), 1);
+                assert_eq!(operators[0].spender, bob.to_string());
+            },
+        )
+        .approve_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            "2",
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_approval(
+            incentive_addr.clone().into_inner(),
+            "2",
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().approval.spender, alice.to_string());
+            },
+        )
+        .query_approval(
+            incentive_addr.clone().into_inner(),
+            "1",
+            alice.clone(),
+            |result| {
+                assert!(result.is_err());
+            },
+        )
+        .query_approvals(incentive_addr.clone().into_inner(), "2", |result| {
+            assert_eq!(result.unwrap().approvals.len(), 1);
+        })
+        // the operator can transfer any of the NFTs
+        .transfer_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            "1",
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_owner_of(incentive_addr.clone().into_inner(), "1", |result| {
+            assert_eq!(result.unwrap().owner, bob.to_string());
+        })
+        .revoke_all_nfts(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_all_operators(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert!(result.unwrap().operators.is_empty());
+            },
+        )
+        .transfer_nft(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            bob.clone(),
+            "2",
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        // alice's approval on the NFT is kept
+        .transfer_nft(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            "2",
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_owner_of(incentive_addr.clone().into_inner(), "2", |result| {
+            assert_eq!(result.unwrap().owner, alice.to_string());
+        });
+}
+
+#[test]
+fn emergency_unlock_positions_with_penalty() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+    let fee_collector_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .query_incentive_factory_config(|result| {
+            *fee_collector_addr.borrow_mut() = result.unwrap().fee_collector_addr;
+        })
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            259200u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    let carol_lp_funds = RefCell::new(Uint128::zero());
+    suite
+        .query_funds(carol.clone(), incentive_asset.clone(), |result| {
+            *carol_lp_funds.borrow_mut() = result;
+        })
+        .emergency_unlock(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NothingToUnlock {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToUnlock"),
+                }
+            },
+        )
+        .emergency_unlock(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            Some(172800u64),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NonExistentPosition { .. } => {}
+                    _ => {
+                        panic!("Wrong error type, should return ContractError::NonExistentPosition")
+                    }
+                }
+            },
+        )
+        // unlocking the position with the max unbonding duration charges the base penalty,
+        // scaled by the weight multiplier of the position
+        .emergency_unlock(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            Some(259200u64),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(carol.clone(), incentive_asset.clone(), |result| {
+            assert_eq!(
+                result,
+                carol_lp_funds.clone().into_inner() + Uint128::new(1_000u128 - 50u128)
+            );
+        })
+        .query_funds(
+            fee_collector_addr.clone().into_inner(),
+            incentive_asset.clone(),
+            |result| {
+                assert_eq!(result, Uint128::new(50u128));
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                let positions = result.unwrap().positions;
+                assert_eq!(positions.len(), 1);
+                assert_eq!(
+                    positions[0],
+                    incentive::QueryPosition::OpenPosition {
+                        amount: Uint128::new(1_000u128),
+                        unbonding_duration: 86400u64,
+                        weight: Uint128::new(1_000u128),
+                    }
+                );
+            },
+        )
+        .close_incentive_position(
+            carol.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            |result| {
+                result.unwrap();
+            },
+        );
+
+    // half of the unbonding period is left, so the penalty is 5% * 43200 / 259200
+    let time = suite.get_time().plus_seconds(43200u64);
+    suite.set_time(time);
+
+    suite
+        .query_funds(carol.clone(), incentive_asset.clone(), |result| {
+            *carol_lp_funds.borrow_mut() = result;
+        })
+        .emergency_unlock(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(carol.clone(), incentive_asset.clone(), |result| {
+            assert_eq!(
+                result,
+                carol_lp_funds.clone().into_inner() + Uint128::new(1_000u128 - 8u128)
+            );
+        })
+        .query_funds(
+            fee_collector_addr.clone().into_inner(),
+            incentive_asset.clone(),
+            |result| {
+                assert_eq!(result, Uint128::new(58u128));
+            },
+        )
+        .emergency_unlock(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            None,
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::NothingToUnlock {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::NothingToUnlock"),
+                }
+            },
+        );
+}
+
+#[test]
+fn boost_weight_with_bonding_share() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+    let bonding_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .create_bonding_contract(|result| {
+            *bonding_addr.borrow_mut() = result.unwrap();
+        })
+        .update_max_boost(Decimal::from_ratio(5u128, 2u128), |result| {
+            result.unwrap();
+        })
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .open_incentive_position(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_position(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(9_000u128),
+            86400u64,
+            None,
+            vec![coin(9_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        // nothing is bonded yet, so there's no boost
+        .poke_boost(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::one());
+            },
+        );
+
+    // alice holds 10% of the incentive's weight and 5% of the bonding share, which gives her half
+    // of the extra boost, i.e. 1 + 1.5 * 0.5
+    suite
+        .bond(
+            alice.clone(),
+            bonding_addr.clone().into_inner(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::new(50u128),
+            },
+            &coins(50u128, "uwhale"),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .bond(
+            bob.clone(),
+            bonding_addr.clone().into_inner(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::new(950u128),
+            },
+            &coins(950u128, "uwhale"),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .poke_boost(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::from_ratio(7u128, 4u128));
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                let rewards_share = result.unwrap();
+                assert_eq!(rewards_share.address_weight, Uint128::new(1_750u128));
+                assert_eq!(rewards_share.global_weight, Uint128::new(10_750u128));
+                assert_eq!(rewards_share.boost, Decimal::from_ratio(7u128, 4u128));
+            },
+        )
+        // poking again with the same bonding share doesn't compound the boost
+        .poke_boost(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                let rewards_share = result.unwrap();
+                assert_eq!(rewards_share.address_weight, Uint128::new(1_750u128));
+                assert_eq!(rewards_share.global_weight, Uint128::new(10_750u128));
+            },
+        )
+        // the boost goes away with the last open position
+        .close_incentive_position(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::one());
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                let rewards_share = result.unwrap();
+                assert_eq!(rewards_share.address_weight, Uint128::zero());
+                assert_eq!(rewards_share.global_weight, Uint128::new(9_000u128));
+            },
+        );
+}
+
+#[test]
+fn boost_follows_position_weight_changes() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+    let carol = suite.senders[2].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+    let bonding_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            *incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .create_bonding_contract(|result| {
+            *bonding_addr.borrow_mut() = result.unwrap();
+        })
+        .update_max_boost(Decimal::from_ratio(5u128, 2u128), |result| {
+            result.unwrap();
+        })
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .open_incentive_position(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_position(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(9_000u128),
+            86400u64,
+            None,
+            vec![coin(9_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .bond(
+            alice.clone(),
+            bonding_addr.clone().into_inner(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::new(50u128),
+            },
+            &coins(50u128, "uwhale"),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .bond(
+            bob.clone(),
+            bonding_addr.clone().into_inner(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::new(950u128),
+            },
+            &coins(950u128, "uwhale"),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .poke_boost(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::from_ratio(7u128, 4u128));
+            },
+        )
+        // the boost doesn't grow with the position until it's poked again, so the multiplier
+        // drops to (2_000 + 750) / 2_000
+        .expand_incentive_position(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400u64,
+            None,
+            vec![coin(1_000u128, "ampWHALE".to_string())],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::from_ratio(11u128, 8u128));
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                let rewards_share = result.unwrap();
+                assert_eq!(rewards_share.address_weight, Uint128::new(2_750u128));
+                assert_eq!(rewards_share.global_weight, Uint128::new(11_750u128));
+            },
+        )
+        // the boost doesn't follow the position to another wallet, nor stays behind
+        .transfer_incentive_position(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            86400u64,
+            carol.clone(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::one());
+            },
+        )
+        .query_positions(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(result.unwrap().boost, Decimal::one());
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            alice.clone(),
+            |result| {
+                let rewards_share = result.unwrap();
+                assert_eq!(rewards_share.address_weight, Uint128::zero());
+                assert_eq!(rewards_share.global_weight, Uint128::new(11_000u128));
+            },
+        )
+        .query_current_epoch_rewards_share(
+            incentive_addr.clone().into_inner(),
+            carol.clone(),
+            |result| {
+                assert_eq!(result.unwrap().address_weight, Uint128::new(2_000u128));
+            },
+        );
+}
+
+#[test]
+fn open_flow_with_whitelisted_assets_and_fee_overrides() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let incentive_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let incentive_addr = RefCell::new(Addr::unchecked(""));
+    let fee_collector_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), incentive_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(incentive_asset.clone(), |result| {
+            let incentive = result.unwrap();
+            assert!(incentive.is_some());
+            *incentive_addr.borrow_mut() = incentive.unwrap();
+        })
+        .query_incentive_factory_config(|result| {
+            *fee_collector_addr.borrow_mut() = result.unwrap().fee_collector_addr;
+        })
+        // only usdc flows are allowed, the fee can be paid with 500 ampWHALE instead of
+        // 1_000 uwhale, and bob doesn't pay any fee
+        .update_flow_assets_and_fees(
+            vec![AssetInfo::NativeToken {
+                denom: "usdc".to_string(),
+            }],
+            vec![Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(500u128),
+            }],
+            vec![CreatorFlowFee {
+                creator: bob.to_string(),
+                fee: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uwhale".to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+            }],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(1, vec![incentive_addr.clone().into_inner()])
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            None,
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(11_000u128, "uwhale")],
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::FlowAssetNotWhitelisted { .. } => {}
+                    _ => panic!(
+                        "Wrong error type, should return ContractError::FlowAssetNotWhitelisted"
+                    ),
+                }
+            },
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            None,
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(500u128, "ampWHALE")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(
+            fee_collector_addr.clone().into_inner(),
+            AssetInfo::NativeToken {
+                denom: "ampWHALE".to_string(),
+            },
+            |result| {
+                assert_eq!(result, Uint128::new(500u128));
+            },
+        )
+        .open_incentive_flow(
+            bob.clone(),
+            incentive_addr.clone().into_inner(),
+            None,
+            None,
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_funds(
+            fee_collector_addr.clone().into_inner(),
+            AssetInfo::NativeToken {
+                denom: "uwhale".to_string(),
+            },
+            |result| {
+                assert_eq!(result, Uint128::zero());
+            },
+        )
+        .query_flows(incentive_addr.clone().into_inner(), None, None, |result| {
+            assert_eq!(result.unwrap().len(), 2);
+        });
+}
+
+#[test]
+fn query_active_flows_across_incentives() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let amp_lp_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+    let cw20_lp_asset = AssetInfo::Token {
+        contract_addr: suite.cw20_tokens[1].to_string(),
+    };
+    let usdc = AssetInfo::NativeToken {
+        denom: "usdc".to_string(),
+    };
+    let uwhale = AssetInfo::NativeToken {
+        denom: "uwhale".to_string(),
+    };
+
+    let amp_incentive_addr = RefCell::new(Addr::unchecked(""));
+    let cw20_incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), amp_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .create_incentive(alice.clone(), cw20_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(amp_lp_asset.clone(), |result| {
+            *amp_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .query_incentive(cw20_lp_asset.clone(), |result| {
+            *cw20_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .open_incentive_position(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400,
+            None,
+            vec![coin(1_000u128, "ampWHALE")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor(
+            1,
+            vec![
+                amp_incentive_addr.clone().into_inner(),
+                cw20_incentive_addr.clone().into_inner(),
+            ],
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            None,
+            Some(12),
+            None,
+            Asset {
+                info: usdc.clone(),
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_flow(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            Some(6),
+            Some(11),
+            None,
+            Asset {
+                info: usdc.clone(),
+                amount: Uint128::new(5_000u128),
+            },
+            None,
+            &vec![coin(5_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .open_incentive_flow(
+            bob.clone(),
+            cw20_incentive_addr.clone().into_inner(),
+            None,
+            Some(6),
+            None,
+            Asset {
+                info: uwhale.clone(),
+                amount: Uint128::new(20_000u128),
+            },
+            None,
+            &vec![coin(20_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_active_flows(None, None, None, None, None, None, None, |result| {
+            let flows = result.unwrap().flows;
+            assert_eq!(flows.len(), 3);
+
+            let bob_flow = flows
+                .iter()
+                .find(|flow| flow.flow.flow_creator == bob)
+                .unwrap();
+            assert_eq!(
+                bob_flow.incentive_address,
+                cw20_incentive_addr.clone().into_inner()
+            );
+            assert_eq!(bob_flow.lp_asset, cw20_lp_asset);
+            assert_eq!(bob_flow.epoch_id, 2);
+            // the fee was taken out of the flow
+            assert_eq!(bob_flow.flow.remaining_emissions, Uint128::new(19_000u128));
+            assert_eq!(
+                bob_flow.flow.current_epoch_emissions,
+                Uint128::new(4_750u128)
+            );
+            // nobody has a position in the incentive
+            assert_eq!(bob_flow.flow.reward_per_weight, Decimal256::zero());
+        })
+        .query_active_flows(
+            Some(amp_lp_asset.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            |result| {
+                let flows = result.unwrap().flows;
+                assert_eq!(flows.len(), 2);
+
+                assert_eq!(flows[0].flow.flow_id, 1);
+                assert_eq!(flows[0].flow.end_epoch, 12);
+                assert_eq!(flows[0].flow.remaining_emissions, Uint128::new(10_000u128));
+                assert_eq!(
+                    flows[0].flow.current_epoch_emissions,
+                    Uint128::new(1_000u128)
+                );
+                assert_eq!(flows[0].flow.reward_per_weight, Decimal256::one());
+
+                // the second flow hasn't started yet
+                assert_eq!(flows[1].flow.flow_id, 2);
+                assert_eq!(flows[1].flow.remaining_emissions, Uint128::new(5_000u128));
+                assert_eq!(flows[1].flow.current_epoch_emissions, Uint128::zero());
+            },
+        )
+        .query_active_flows(
+            None,
+            Some(uwhale.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            |result| {
+                let flows = result.unwrap().flows;
+                assert_eq!(flows.len(), 1);
+                assert_eq!(flows[0].flow.flow_creator, bob);
+            },
+        )
+        .query_active_flows(
+            None,
+            None,
+            Some(alice.to_string()),
+            None,
+            Some(3),
+            None,
+            None,
+            |result| {
+                let flows = result.unwrap().flows;
+                assert_eq!(flows.len(), 1);
+                assert_eq!(flows[0].flow.flow_id, 1);
+                assert_eq!(flows[0].lp_asset, amp_lp_asset);
+            },
+        )
+        .query_active_flows(
+            Some(amp_lp_asset.clone()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(1),
+            |result| {
+                let flows = result.unwrap().flows;
+                assert_eq!(flows.len(), 1);
+                assert_eq!(flows[0].flow.flow_id, 1);
+            },
+        )
+        .query_active_flows(
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some((amp_lp_asset.clone(), 1)),
+            Some(1),
+            |result| {
+                let flows = result.unwrap().flows;
+                assert_eq!(flows.len(), 1);
+                assert_eq!(flows[0].lp_asset, amp_lp_asset);
+                assert_eq!(flows[0].flow.flow_id, 2);
+            },
+        )
+        // with a limit of one, a page only scans one incentive contract
+        .query_active_flows(
+            None,
+            Some(uwhale.clone()),
+            None,
+            None,
+            None,
+            None,
+            Some(1),
+            |result| {
+                let response = result.unwrap();
+                assert!(response.flows.len() <= 1);
+                assert!(response.next_start_after.is_some());
+            },
+        );
+
+    // keep paginating until the scan is over
+    let start_after = RefCell::new(None);
+    let paginated_flows = RefCell::new(vec![]);
+    let pages = RefCell::new(0u32);
+    loop {
+        suite.query_active_flows(
+            None,
+            Some(uwhale.clone()),
+            None,
+            None,
+            None,
+            start_after.clone().into_inner(),
+            Some(1),
+            |result| {
+                let response = result.unwrap();
+                paginated_flows.borrow_mut().extend(response.flows);
+                *start_after.borrow_mut() = response.next_start_after;
+                *pages.borrow_mut() += 1;
+            },
+        );
+
+        if start_after.borrow().is_none() {
+            break;
+        }
+    }
+
+    let paginated_flows = paginated_flows.into_inner();
+    assert_eq!(paginated_flows.len(), 1);
+    assert_eq!(paginated_flows[0].flow.flow_creator, bob);
+    assert!(pages.into_inner() >= 2);
+
+    suite
+        .create_epochs_on_fee_distributor(
+            5,
+            vec![
+                amp_incentive_addr.clone().into_inner(),
+                cw20_incentive_addr.clone().into_inner(),
+            ],
+        )
+        .query_active_flows(None, None, None, None, None, None, None, |result| {
+            let flows = result.unwrap().flows;
+
+            // bob's flow has ended
+            assert_eq!(flows.len(), 2);
+            assert!(flows.iter().all(|flow| flow.lp_asset == amp_lp_asset));
+            assert_eq!(flows[0].epoch_id, 7);
+
+            assert_eq!(flows[0].flow.remaining_emissions, Uint128::new(5_000u128));
+            assert_eq!(
+                flows[0].flow.current_epoch_emissions,
+                Uint128::new(1_000u128)
+            );
+            assert_eq!(flows[1].flow.remaining_emissions, Uint128::new(4_000u128));
+            assert_eq!(
+                flows[1].flow.current_epoch_emissions,
+                Uint128::new(1_000u128)
+            );
+        });
+}
+
+#[test]
+fn query_active_flows_skips_incentives_failing_to_answer() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let amp_lp_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+    let cw20_lp_asset = AssetInfo::Token {
+        contract_addr: suite.cw20_tokens[1].to_string(),
+    };
+
+    let amp_incentive_addr = RefCell::new(Addr::unchecked(""));
+    let cw20_incentive_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), amp_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .create_incentive(alice.clone(), cw20_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(amp_lp_asset.clone(), |result| {
+            *amp_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .query_incentive(cw20_lp_asset.clone(), |result| {
+            *cw20_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .create_epochs_on_fee_distributor(
+            1,
+            vec![
+                amp_incentive_addr.clone().into_inner(),
+                cw20_incentive_addr.clone().into_inner(),
+            ],
+        );
+
+    for incentive_addr in [
+        amp_incentive_addr.clone().into_inner(),
+        cw20_incentive_addr.clone().into_inner(),
+    ] {
+        suite.open_incentive_flow(
+            alice.clone(),
+            incentive_addr,
+            None,
+            Some(12),
+            None,
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "usdc".to_string(),
+                },
+                amount: Uint128::new(10_000u128),
+            },
+            None,
+            &vec![coin(10_000u128, "usdc"), coin(1_000u128, "uwhale")],
+            |result| {
+                result.unwrap();
+            },
+        );
+    }
+
+    suite
+        .migrate_incentive_to_legacy_code(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_active_flows(None, None, None, None, None, None, None, |result| {
+            let response = result.unwrap();
+            assert_eq!(response.flows.len(), 1);
+            assert_eq!(
+                response.flows[0].incentive_address,
+                cw20_incentive_addr.clone().into_inner()
+            );
+            assert_eq!(response.next_start_after, None);
+        });
+
+    // paginating one incentive contract at a time moves past the one failing to answer
+    let start_after = RefCell::new(None);
+    let paginated_flows = RefCell::new(vec![]);
+    for _ in 0..2 {
+        suite.query_active_flows(
+            None,
+            None,
+            None,
+            None,
+            None,
+            start_after.clone().into_inner(),
+            Some(1),
+            |result| {
+                let response = result.unwrap();
+                paginated_flows.borrow_mut().extend(response.flows);
+                *start_after.borrow_mut() = response.next_start_after;
+            },
+        );
+    }
+
+    let paginated_flows = paginated_flows.into_inner();
+    assert_eq!(paginated_flows.len(), 1);
+    assert_eq!(paginated_flows[0].lp_asset, cw20_lp_asset);
+}
+
+#[test]
+fn take_global_weight_snapshots_on_epoch_changed_hook() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let amp_lp_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+    let cw20_lp_asset = AssetInfo::Token {
+        contract_addr: suite.cw20_tokens[1].to_string(),
+    };
+
+    let amp_incentive_addr = RefCell::new(Addr::unchecked(""));
+    let cw20_incentive_addr = RefCell::new(Addr::unchecked(""));
+    let epoch_manager_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        .create_incentive(alice.clone(), amp_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(amp_lp_asset.clone(), |result| {
+            *amp_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .add_epoch_hooks(alice.clone(), None, |result| {
+            let err = result
+                .unwrap_err()
+                .downcast::<incentive_factory::error::ContractError>()
+                .unwrap();
+
+            match err {
+                incentive_factory::error::ContractError::EpochManagerNotSet => {}
+                _ => panic!("Wrong error type, should return ContractError::EpochManagerNotSet"),
+            }
+        })
+        .create_epoch_manager(true, |result| {
+            *epoch_manager_addr.borrow_mut() = result.unwrap();
+        })
+        // the incentive contract created before the epoch manager was set must be registered
+        .add_epoch_hooks(bob.clone(), None, |result| {
+            let err = result
+                .unwrap_err()
+                .downcast::<incentive_factory::error::ContractError>()
+                .unwrap();
+
+            match err {
+                incentive_factory::error::ContractError::Unauthorized => {}
+                _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+            }
+        })
+        .add_epoch_hooks(alice.clone(), None, |result| {
+            result.unwrap();
+        })
+        // the incentive contracts created from now on are registered automatically
+        .create_incentive(alice.clone(), cw20_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(cw20_lp_asset.clone(), |result| {
+            *cw20_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .open_incentive_position(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400,
+            None,
+            vec![coin(1_000u128, "ampWHALE")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor_without_snapshot_on_incentive(2)
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 3, |result| {
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains(
+                &ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 3 }.to_string()
+            ));
+        })
+        // the hook does nothing when it's not called by the epoch manager
+        .epoch_changed_hook(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 3, |result| {
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains(
+                &ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 3 }.to_string()
+            ));
+        })
+        .create_epoch_on_epoch_manager(epoch_manager_addr.clone().into_inner(), |result| {
+            result.unwrap();
+        })
+        // the snapshots of both missed epochs are taken
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 2, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1_000u128));
+        })
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 3, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1_000u128));
+        })
+        .query_incentive_global_weight(cw20_incentive_addr.clone().into_inner(), 3, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::zero());
+        })
+        // taking the snapshot again is harmless
+        .create_epoch_on_epoch_manager(epoch_manager_addr.clone().into_inner(), |result| {
+            result.unwrap();
+        })
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 3, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1_000u128));
+        });
+}
+
+#[test]
+fn epoch_hooks_are_left_to_the_epoch_manager_admin() {
+    let mut suite = TestingSuite::default_with_balances(vec![
+        coin(5_000_000_000u128, "uwhale".to_string()),
+        coin(50_000_000_000u128, "usdc".to_string()),
+        coin(5_000_000_000u128, "ampWHALE".to_string()),
+    ]);
+    let alice = suite.creator();
+    let bob = suite.senders[1].clone();
+
+    suite.instantiate_default_native_fee().create_lp_tokens();
+
+    let amp_lp_asset = AssetInfo::NativeToken {
+        denom: "ampWHALE".to_string(),
+    };
+
+    let amp_incentive_addr = RefCell::new(Addr::unchecked(""));
+    let epoch_manager_addr = RefCell::new(Addr::unchecked(""));
+
+    suite
+        // alice is the admin of the epoch manager, not the factory
+        .create_epoch_manager(false, |result| {
+            *epoch_manager_addr.borrow_mut() = result.unwrap();
+        })
+        // the factory can't add the hook, which doesn't prevent the creation of the incentive
+        .create_incentive(alice.clone(), amp_lp_asset.clone(), |result| {
+            result.unwrap();
+        })
+        .query_incentive(amp_lp_asset.clone(), |result| {
+            *amp_incentive_addr.borrow_mut() = result.unwrap().unwrap();
+        })
+        .add_epoch_hooks(alice.clone(), None, |result| {
+            result.unwrap();
+        })
+        .open_incentive_position(
+            alice.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            Uint128::new(1_000u128),
+            86400,
+            None,
+            vec![coin(1_000u128, "ampWHALE")],
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor_without_snapshot_on_incentive(1)
+        .create_epoch_on_epoch_manager(epoch_manager_addr.clone().into_inner(), |result| {
+            result.unwrap();
+        })
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 2, |result| {
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains(
+                &ContractError::GlobalWeightSnapshotNotTakenForEpoch { epoch: 2 }.to_string()
+            ));
+        })
+        // only the factory sets the epoch manager on the incentive
+        .update_epoch_manager(
+            bob.clone(),
+            amp_incentive_addr.clone().into_inner(),
+            Some(bob.clone()),
+            |result| {
+                let err = result.unwrap_err().downcast::<ContractError>().unwrap();
+
+                match err {
+                    ContractError::Unauthorized {} => {}
+                    _ => panic!("Wrong error type, should return ContractError::Unauthorized"),
+                }
+            },
+        )
+        .add_hook_on_epoch_manager(
+            alice.clone(),
+            epoch_manager_addr.clone().into_inner(),
+            amp_incentive_addr.clone().into_inner(),
+            |result| {
+                result.unwrap();
+            },
+        )
+        .create_epochs_on_fee_distributor_without_snapshot_on_incentive(1)
+        .create_epoch_on_epoch_manager(epoch_manager_addr.clone().into_inner(), |result| {
+            result.unwrap();
+        })
+        .query_incentive_global_weight(amp_incentive_addr.clone().into_inner(), 3, |result| {
+            assert_eq!(result.unwrap().global_weight, Uint128::new(1_000u128));
+        });
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage},
-    to_json_binary, Addr, Env, OwnedDeps, Uint128, Uint64, WasmMsg,
+    to_json_binary, Addr, Decimal, Env, OwnedDeps, Uint128, Uint64, WasmMsg,
 };
 use cw20::Cw20Coin;
 use cw_multi_test::{App, Executor};
@@ -117,6 +117,9 @@ pub fn app_mock_instantiate(app: &mut App, lp_balance: Uint128) -> AppInstantiat
                 max_unbonding_duration: 31556926,
                 min_unbonding_duration: 86400,
                 fee_distributor_addr: fee_distributor.to_string(),
+                emergency_unlock_penalty: Decimal::percent(5),
+                max_emergency_unlock_penalty: Decimal::percent(50),
+                max_boost: Decimal::one(),
             },
             &[],
             "mock incentive factory",
@@ -1,20 +1,25 @@
-use cosmwasm_std::{Addr, Coin, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Empty, StdResult, Timestamp, Uint128, Uint64};
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse};
 use cw_multi_test::{App, AppBuilder, AppResponse, BankKeeper, Executor};
 
+use white_whale_std::epoch_manager::epoch_manager::{EpochConfig, EpochV2};
+use white_whale_std::epoch_manager::hooks::EpochChangedHookMsg;
 use white_whale_std::fee_distributor::EpochResponse;
-use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::asset::{Asset, AssetInfo, PairInfo, PairType};
 use white_whale_std::pool_network::incentive::{
-    Curve, Flow, FlowIdentifier, FlowResponse, GlobalWeightResponse, PositionsResponse,
-    RewardsResponse, RewardsShareResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, AutoCompound, ClaimOperatorResponse,
+    ContractInfoResponse, Curve, Flow, FlowIdentifier, FlowResponse, GlobalWeightResponse,
+    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, PositionsResponse,
+    RewardsResponse, RewardsShareResponse, TokensResponse,
 };
 use white_whale_std::pool_network::incentive_factory::{
-    IncentiveResponse, IncentivesResponse, InstantiateMsg,
+    ActiveFlowsResponse, CreatorFlowFee, IncentiveResponse, IncentivesResponse, InstantiateMsg,
 };
 
 use crate::tests::suite_contracts::{
-    cw20_token_contract, fee_collector_contract, fee_distributor_mock_contract, incentive_contract,
-    incentive_factory_contract,
+    bonding_mock_contract, cw20_token_contract, epoch_manager_contract, fee_collector_contract,
+    fee_distributor_mock_contract, incentive_contract, incentive_factory_contract,
+    legacy_incentive_mock_contract, pair_contract,
 };
 
 pub struct TestingSuite {
@@ -446,6 +451,110 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn transfer_incentive_position(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        unbonding_duration: u64,
+        recipient: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::TransferPosition {
+            unbonding_duration,
+            recipient: recipient.to_string(),
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn tokenize_position(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        unbonding_duration: u64,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::TokenizePosition {
+            unbonding_duration,
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn transfer_nft(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        recipient: Addr,
+        token_id: &str,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.to_string(),
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn approve_nft(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        spender: Addr,
+        token_id: &str,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn approve_all_nfts(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        operator: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::ApproveAll {
+            operator: operator.to_string(),
+            expires: None,
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn revoke_all_nfts(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        operator: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::RevokeAll {
+            operator: operator.to_string(),
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
     pub(crate) fn expand_incentive_position(
         &mut self,
         sender: Addr,
@@ -476,7 +585,12 @@ impl TestingSuite {
         sender: Addr,
         result: impl Fn(Result<AppResponse, anyhow::Error>),
     ) -> &mut Self {
-        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::Claim {};
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::Claim {
+            until_epoch: None,
+            max_epochs: None,
+            owner: None,
+            auto_compound: None,
+        };
         println!("-------------- claiming {}", sender);
         result(
             self.app
@@ -486,6 +600,398 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn claim_epochs(
+        &mut self,
+        incentive_addr: Addr,
+        sender: Addr,
+        until_epoch: Option<u64>,
+        max_epochs: Option<u64>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::Claim {
+            until_epoch,
+            max_epochs,
+            owner: None,
+            auto_compound: None,
+        };
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn claim_on_behalf(
+        &mut self,
+        incentive_addr: Addr,
+        sender: Addr,
+        owner: Addr,
+        auto_compound: Option<AutoCompound>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::Claim {
+            until_epoch: None,
+            max_epochs: None,
+            owner: Some(owner.to_string()),
+            auto_compound,
+        };
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn update_claim_operator(
+        &mut self,
+        incentive_addr: Addr,
+        sender: Addr,
+        operator: Option<Addr>,
+        auto_compound: Option<AutoCompound>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::UpdateClaimOperator {
+            operator: operator.map(|operator| operator.to_string()),
+            auto_compound,
+        };
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn create_pair(
+        &mut self,
+        asset_infos: [AssetInfo; 2],
+        result: impl Fn(anyhow::Result<Addr>),
+    ) -> &mut Self {
+        result(instantiate_contract(
+            self,
+            InstatiateContract::Pair { asset_infos },
+        ));
+
+        self
+    }
+
+    /// Creates a whale lair mock and sets it as the bonding contract of the fee distributor.
+    pub(crate) fn create_bonding_contract(
+        &mut self,
+        result: impl Fn(anyhow::Result<Addr>),
+    ) -> &mut Self {
+        let bonding_addr = instantiate_contract(self, InstatiateContract::Bonding).unwrap();
+
+        let msg = white_whale_std::fee_distributor::ExecuteMsg::UpdateConfig {
+            owner: None,
+            bonding_contract_addr: Some(bonding_addr.to_string()),
+            fee_collector_addr: None,
+            grace_period: None,
+            distribution_asset: None,
+            epoch_config: None,
+        };
+        self.app
+            .execute_contract(
+                self.senders[0].clone(),
+                self.fee_distributor_addr.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+
+        result(Ok(bonding_addr));
+
+        self
+    }
+
+    pub(crate) fn bond(
+        &mut self,
+        sender: Addr,
+        bonding_addr: Addr,
+        asset: Asset,
+        funds: &[Coin],
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::whale_lair::ExecuteMsg::Bond {
+            asset,
+            lock_duration: None,
+            owner: None,
+        };
+        result(self.app.execute_contract(sender, bonding_addr, &msg, funds));
+
+        self
+    }
+
+    /// Creates an epoch manager and sets it in the factory. The epoch manager is administered by the
+    /// incentive factory if `factory_is_admin`, otherwise by the creator.
+    pub(crate) fn create_epoch_manager(
+        &mut self,
+        factory_is_admin: bool,
+        result: impl Fn(anyhow::Result<Addr>),
+    ) -> &mut Self {
+        let epoch_manager_addr =
+            instantiate_contract(self, InstatiateContract::EpochManager).unwrap();
+
+        if factory_is_admin {
+            self.app
+                .execute_contract(
+                    self.senders[0].clone(),
+                    epoch_manager_addr.clone(),
+                    &white_whale_std::epoch_manager::epoch_manager::ExecuteMsg::UpdateConfig {
+                        owner: Some(self.incentive_factory_addr.to_string()),
+                        epoch_config: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        let msg = white_whale_std::pool_network::incentive_factory::ExecuteMsg::UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: Some(epoch_manager_addr.to_string()),
+        };
+        self.app
+            .execute_contract(
+                self.senders[0].clone(),
+                self.incentive_factory_addr.clone(),
+                &msg,
+                &[],
+            )
+            .unwrap();
+
+        result(Ok(epoch_manager_addr));
+
+        self
+    }
+
+    pub(crate) fn add_epoch_hooks(
+        &mut self,
+        sender: Addr,
+        incentive_address: Option<String>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive_factory::ExecuteMsg::AddEpochHooks {
+            incentive_address,
+        };
+
+        result(
+            self.app
+                .execute_contract(sender, self.incentive_factory_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn add_hook_on_epoch_manager(
+        &mut self,
+        sender: Addr,
+        epoch_manager_addr: Addr,
+        contract_addr: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        result(self.app.execute_contract(
+            sender,
+            epoch_manager_addr,
+            &white_whale_std::epoch_manager::epoch_manager::ExecuteMsg::AddHook {
+                contract_addr: contract_addr.to_string(),
+            },
+            &[],
+        ));
+
+        self
+    }
+
+    /// Creates a new epoch on the epoch manager, a day after the current one.
+    pub(crate) fn create_epoch_on_epoch_manager(
+        &mut self,
+        epoch_manager_addr: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let time = self.get_time().plus_days(1);
+        self.set_time(time);
+
+        result(self.app.execute_contract(
+            self.senders[0].clone(),
+            epoch_manager_addr,
+            &white_whale_std::epoch_manager::epoch_manager::ExecuteMsg::CreateEpoch {},
+            &[],
+        ));
+
+        self
+    }
+
+    pub(crate) fn epoch_changed_hook(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::EpochChangedHook(
+            EpochChangedHookMsg {
+                current_epoch: EpochV2::default(),
+            },
+        );
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn update_epoch_manager(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        epoch_manager_addr: Option<Addr>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::UpdateEpochManager {
+            epoch_manager_addr: epoch_manager_addr.map(|addr| addr.to_string()),
+        };
+
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    /// Migrates the given incentive contract to a code that answers no query, as an incentive
+    /// contract that wasn't migrated yet would.
+    pub(crate) fn migrate_incentive_to_legacy_code(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let code_id = self.app.store_code(legacy_incentive_mock_contract());
+        let msg = white_whale_std::pool_network::incentive_factory::ExecuteMsg::MigrateIncentives {
+            incentive_address: Some(incentive_addr.to_string()),
+            code_id,
+        };
+
+        result(
+            self.app
+                .execute_contract(sender, self.incentive_factory_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn update_max_boost(
+        &mut self,
+        max_boost: Decimal,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive_factory::ExecuteMsg::UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: Some(max_boost),
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
+        };
+        result(self.app.execute_contract(
+            self.senders[0].clone(),
+            self.incentive_factory_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    pub(crate) fn update_flow_assets_and_fees(
+        &mut self,
+        flow_assets_to_add: Vec<AssetInfo>,
+        flow_fee_overrides_to_set: Vec<Asset>,
+        creator_flow_fees_to_set: Vec<CreatorFlowFee>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive_factory::ExecuteMsg::UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: Some(flow_assets_to_add),
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: Some(flow_fee_overrides_to_set),
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: Some(creator_flow_fees_to_set),
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
+        };
+        result(self.app.execute_contract(
+            self.senders[0].clone(),
+            self.incentive_factory_addr.clone(),
+            &msg,
+            &[],
+        ));
+
+        self
+    }
+
+    pub(crate) fn poke_boost(
+        &mut self,
+        sender: Addr,
+        incentive_addr: Addr,
+        address: Addr,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::PokeBoost {
+            address: address.to_string(),
+        };
+        result(self.app.execute_contract(sender, incentive_addr, &msg, &[]));
+
+        self
+    }
+
+    pub(crate) fn provide_liquidity(
+        &mut self,
+        sender: Addr,
+        pair_addr: Addr,
+        assets: [Asset; 2],
+        funds: &[Coin],
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::pair::ExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance: None,
+            receiver: None,
+        };
+        result(self.app.execute_contract(sender, pair_addr, &msg, funds));
+
+        self
+    }
+
     pub(crate) fn withdraw(
         &mut self,
         incentive_addr: Addr,
@@ -501,6 +1007,24 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn emergency_unlock(
+        &mut self,
+        incentive_addr: Addr,
+        sender: Addr,
+        unbonding_duration: Option<u64>,
+        result: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = white_whale_std::pool_network::incentive::ExecuteMsg::EmergencyUnlock {
+            unbonding_duration,
+        };
+        result(
+            self.app
+                .execute_contract(sender, incentive_addr, &msg, &vec![]),
+        );
+
+        self
+    }
+
     pub(crate) fn create_epochs_on_fee_distributor(
         &mut self,
         epoch_amount: u64,
@@ -644,6 +1168,37 @@ impl TestingSuite {
         self
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn query_active_flows(
+        &mut self,
+        lp_asset: Option<AssetInfo>,
+        flow_asset: Option<AssetInfo>,
+        creator: Option<String>,
+        start_epoch: Option<u64>,
+        end_epoch: Option<u64>,
+        start_after: Option<(AssetInfo, u64)>,
+        limit: Option<u32>,
+        result: impl Fn(StdResult<ActiveFlowsResponse>),
+    ) -> &mut Self {
+        let active_flows_response: StdResult<ActiveFlowsResponse> =
+            self.app.wrap().query_wasm_smart(
+                &self.incentive_factory_addr,
+                &white_whale_std::pool_network::incentive_factory::QueryMsg::ActiveFlows {
+                    lp_asset,
+                    flow_asset,
+                    creator,
+                    start_epoch,
+                    end_epoch,
+                    start_after,
+                    limit,
+                },
+            );
+
+        result(active_flows_response);
+
+        self
+    }
+
     pub(crate) fn query_incentive_global_weight(
         &mut self,
         incentive_addr: Addr,
@@ -786,6 +1341,215 @@ impl TestingSuite {
         self
     }
 
+    pub(crate) fn query_claim_operator(
+        &mut self,
+        incentive_addr: Addr,
+        address: Addr,
+        result: impl Fn(StdResult<ClaimOperatorResponse>),
+    ) -> &mut Self {
+        let claim_operator_response: StdResult<ClaimOperatorResponse> =
+            self.app.wrap().query_wasm_smart(
+                incentive_addr,
+                &white_whale_std::pool_network::incentive::QueryMsg::ClaimOperator {
+                    address: address.to_string(),
+                },
+            );
+
+        result(claim_operator_response);
+        self
+    }
+
+    pub(crate) fn query_owner_of(
+        &mut self,
+        incentive_addr: Addr,
+        token_id: &str,
+        result: impl Fn(StdResult<OwnerOfResponse>),
+    ) -> &mut Self {
+        let owner_of_response: StdResult<OwnerOfResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        );
+
+        result(owner_of_response);
+        self
+    }
+
+    pub(crate) fn query_nft_info(
+        &mut self,
+        incentive_addr: Addr,
+        token_id: &str,
+        result: impl Fn(StdResult<NftInfoResponse>),
+    ) -> &mut Self {
+        let nft_info_response: StdResult<NftInfoResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::NftInfo {
+                token_id: token_id.to_string(),
+            },
+        );
+
+        result(nft_info_response);
+        self
+    }
+
+    pub(crate) fn query_tokens(
+        &mut self,
+        incentive_addr: Addr,
+        owner: Addr,
+        result: impl Fn(StdResult<TokensResponse>),
+    ) -> &mut Self {
+        let tokens_response: StdResult<TokensResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::Tokens {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        );
+
+        result(tokens_response);
+        self
+    }
+
+    pub(crate) fn query_approval(
+        &mut self,
+        incentive_addr: Addr,
+        token_id: &str,
+        spender: Addr,
+        result: impl Fn(StdResult<ApprovalResponse>),
+    ) -> &mut Self {
+        let approval_response: StdResult<ApprovalResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::Approval {
+                token_id: token_id.to_string(),
+                spender: spender.to_string(),
+                include_expired: None,
+            },
+        );
+
+        result(approval_response);
+        self
+    }
+
+    pub(crate) fn query_approvals(
+        &mut self,
+        incentive_addr: Addr,
+        token_id: &str,
+        result: impl Fn(StdResult<ApprovalsResponse>),
+    ) -> &mut Self {
+        let approvals_response: StdResult<ApprovalsResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::Approvals {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        );
+
+        result(approvals_response);
+        self
+    }
+
+    pub(crate) fn query_all_operators(
+        &mut self,
+        incentive_addr: Addr,
+        owner: Addr,
+        result: impl Fn(StdResult<OperatorsResponse>),
+    ) -> &mut Self {
+        let operators_response: StdResult<OperatorsResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::AllOperators {
+                owner: owner.to_string(),
+                include_expired: None,
+                start_after: None,
+                limit: None,
+            },
+        );
+
+        result(operators_response);
+        self
+    }
+
+    pub(crate) fn query_contract_info(
+        &mut self,
+        incentive_addr: Addr,
+        result: impl Fn(StdResult<ContractInfoResponse>),
+    ) -> &mut Self {
+        let contract_info_response: StdResult<ContractInfoResponse> =
+            self.app.wrap().query_wasm_smart(
+                incentive_addr,
+                &white_whale_std::pool_network::incentive::QueryMsg::ContractInfo {},
+            );
+
+        result(contract_info_response);
+        self
+    }
+
+    pub(crate) fn query_all_nft_info(
+        &mut self,
+        incentive_addr: Addr,
+        token_id: &str,
+        result: impl Fn(StdResult<AllNftInfoResponse>),
+    ) -> &mut Self {
+        let all_nft_info_response: StdResult<AllNftInfoResponse> =
+            self.app.wrap().query_wasm_smart(
+                incentive_addr,
+                &white_whale_std::pool_network::incentive::QueryMsg::AllNftInfo {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            );
+
+        result(all_nft_info_response);
+        self
+    }
+
+    pub(crate) fn query_all_tokens(
+        &mut self,
+        incentive_addr: Addr,
+        result: impl Fn(StdResult<TokensResponse>),
+    ) -> &mut Self {
+        let tokens_response: StdResult<TokensResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::AllTokens {
+                start_after: None,
+                limit: None,
+            },
+        );
+
+        result(tokens_response);
+        self
+    }
+
+    pub(crate) fn query_num_tokens(
+        &mut self,
+        incentive_addr: Addr,
+        result: impl Fn(StdResult<NumTokensResponse>),
+    ) -> &mut Self {
+        let num_tokens_response: StdResult<NumTokensResponse> = self.app.wrap().query_wasm_smart(
+            incentive_addr,
+            &white_whale_std::pool_network::incentive::QueryMsg::NumTokens {},
+        );
+
+        result(num_tokens_response);
+        self
+    }
+
+    pub(crate) fn query_pair(
+        &mut self,
+        pair_addr: Addr,
+        result: impl Fn(StdResult<PairInfo>),
+    ) -> &mut Self {
+        let pair_info: StdResult<PairInfo> = self.app.wrap().query_wasm_smart(
+            pair_addr,
+            &white_whale_std::pool_network::pair::QueryMsg::Pair {},
+        );
+
+        result(pair_info);
+        self
+    }
+
     pub(crate) fn query_funds(
         &mut self,
         address: Addr,
@@ -835,6 +1599,11 @@ enum InstatiateContract {
         initial_balances: Vec<Cw20Coin>,
         mint: Option<MinterResponse>,
     },
+    Pair {
+        asset_infos: [AssetInfo; 2],
+    },
+    Bonding,
+    EpochManager,
 }
 
 fn instantiate_contract(
@@ -861,6 +1630,9 @@ fn instantiate_contract(
                 max_flow_epoch_buffer,
                 min_unbonding_duration,
                 max_unbonding_duration,
+                emergency_unlock_penalty: Decimal::percent(5),
+                max_emergency_unlock_penalty: Decimal::percent(50),
+                max_boost: Decimal::one(),
             };
 
             let incentive_factory_id = suite.app.store_code(incentive_factory_contract());
@@ -914,6 +1686,66 @@ fn instantiate_contract(
                 Some(suite.senders[0].clone().into_string()),
             )
         }
+        InstatiateContract::Pair { asset_infos } => {
+            let fee = white_whale_std::fee::Fee {
+                share: Decimal::zero(),
+            };
+            let msg = white_whale_std::pool_network::pair::InstantiateMsg {
+                asset_infos,
+                token_code_id: suite.app.store_code(cw20_token_contract()),
+                asset_decimals: [6, 6],
+                pool_fees: white_whale_std::pool_network::pair::PoolFee {
+                    protocol_fee: fee.clone(),
+                    swap_fee: fee.clone(),
+                    burn_fee: fee,
+                },
+                fee_collector_addr: "fee_collector".to_string(),
+                pair_type: PairType::ConstantProduct,
+                token_factory_lp: false,
+            };
+
+            let pair_id = suite.app.store_code(pair_contract());
+
+            suite.app.instantiate_contract(
+                pair_id,
+                suite.senders[0].clone(),
+                &msg,
+                &[],
+                "mock pair",
+                Some(suite.senders[0].clone().into_string()),
+            )
+        }
+        InstatiateContract::Bonding => {
+            let bonding_id = suite.app.store_code(bonding_mock_contract());
+
+            suite.app.instantiate_contract(
+                bonding_id,
+                suite.senders[0].clone(),
+                &Empty {},
+                &[],
+                "mock bonding",
+                Some(suite.senders[0].clone().into_string()),
+            )
+        }
+        InstatiateContract::EpochManager => {
+            let epoch_manager_id = suite.app.store_code(epoch_manager_contract());
+            let start_time = suite.app.block_info().time;
+
+            suite.app.instantiate_contract(
+                epoch_manager_id,
+                suite.senders[0].clone(),
+                &white_whale_std::epoch_manager::epoch_manager::InstantiateMsg {
+                    start_epoch: EpochV2 { id: 1, start_time },
+                    epoch_config: EpochConfig {
+                        duration: Uint64::new(86_400_000_000_000u64),
+                        genesis_epoch: Uint64::new(start_time.nanos()),
+                    },
+                },
+                &[],
+                "epoch manager",
+                Some(suite.senders[0].clone().into_string()),
+            )
+        }
         InstatiateContract::FeeDistributor => {
             let msg = fee_distributor_mock::msg::InstantiateMsg {};
 
@@ -1,5 +1,10 @@
-use cosmwasm_std::Empty;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
 use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::{Item, Map};
+use white_whale_std::whale_lair::{BondingWeightResponse, ExecuteMsg, QueryMsg};
 
 pub fn incentive_factory_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
@@ -24,6 +29,32 @@ pub fn incentive_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+/// A stand-in for an incentive contract that wasn't migrated yet, which answers no query.
+pub fn legacy_incentive_mock_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("unsupported query"))
+    }
+
+    fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_migrate(migrate))
+}
+
 pub fn fee_collector_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         fee_collector::contract::execute,
@@ -55,3 +86,82 @@ pub fn fee_distributor_mock_contract() -> Box<dyn Contract<Empty>> {
 
     Box::new(contract)
 }
+
+pub fn epoch_manager_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        epoch_manager::contract::execute,
+        epoch_manager::contract::instantiate,
+        epoch_manager::contract::query,
+    );
+
+    Box::new(contract)
+}
+
+pub fn pair_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        terraswap_pair::contract::execute,
+        terraswap_pair::contract::instantiate,
+        terraswap_pair::contract::query,
+    )
+    .with_reply(terraswap_pair::contract::reply);
+
+    Box::new(contract)
+}
+
+const BONDS: Map<&Addr, Uint128> = Map::new("bonds");
+const TOTAL_BONDED: Item<Uint128> = Item::new("total_bonded");
+
+/// A minimal stand-in for the whale lair, where the bonding weight of an address is the amount it
+/// bonded.
+pub fn bonding_mock_contract() -> Box<dyn Contract<Empty>> {
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        TOTAL_BONDED.save(deps.storage, &Uint128::zero())?;
+
+        Ok(Response::default())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        if let ExecuteMsg::Bond { asset, .. } = msg {
+            BONDS.update(deps.storage, &info.sender, |bond| -> StdResult<_> {
+                Ok(bond.unwrap_or_default() + asset.amount)
+            })?;
+            TOTAL_BONDED.update(deps.storage, |total| -> StdResult<_> {
+                Ok(total + asset.amount)
+            })?;
+        }
+
+        Ok(Response::default())
+    }
+
+    fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Weight { address, .. } => {
+                let address = deps.api.addr_validate(&address)?;
+                let weight = BONDS.may_load(deps.storage, &address)?.unwrap_or_default();
+                let global_weight = TOTAL_BONDED.load(deps.storage)?;
+
+                to_json_binary(&BondingWeightResponse {
+                    address: address.to_string(),
+                    weight,
+                    global_weight,
+                    share: Decimal::checked_from_ratio(weight, global_weight)
+                        .map_err(|err| StdError::generic_err(err.to_string()))?,
+                    timestamp: env.block.time,
+                })
+            }
+            _ => Err(StdError::generic_err("unsupported query")),
+        }
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Decimal256, Uint128};
+use cosmwasm_std::{Decimal, Decimal256, Uint128};
 
 use crate::error::ContractError;
 
@@ -50,11 +50,39 @@ pub fn calculate_weight(
     Ok(weight.max(amount_uint))
 }
 
+/// Calculates the boost multiplier for an address with the given `address_weight`, based on its
+/// share of the WHALE bonded in the whale lair.
+///
+/// Similar to Curve's boost, the multiplier is `1 + (max_boost - 1) * bonding_share * global_weight / address_weight`,
+/// capped at `max_boost`. That is, the full boost is reached once the bonding share of the address
+/// is at least as big as its share of the incentive's weight.
+pub fn calculate_boost(
+    max_boost: Decimal,
+    bonding_share: Decimal,
+    address_weight: Uint128,
+    global_weight: Uint128,
+) -> Result<Decimal, ContractError> {
+    if max_boost <= Decimal::one() || address_weight.is_zero() {
+        return Ok(Decimal::one());
+    }
+
+    // the weight the address would have if its share of the incentive matched its bonding share
+    let bonded_weight = global_weight * bonding_share;
+    if bonded_weight >= address_weight {
+        return Ok(max_boost);
+    }
+
+    Ok(Decimal::one().checked_add(
+        (max_boost - Decimal::one())
+            .checked_mul(Decimal::checked_from_ratio(bonded_weight, address_weight)?)?,
+    )?)
+}
+
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::Uint128;
+    use cosmwasm_std::{Decimal, Uint128};
 
-    use super::calculate_weight;
+    use super::{calculate_boost, calculate_weight};
 
     #[test]
     fn first_step() {
@@ -94,4 +122,51 @@ mod tests {
         // range starts at 86400
         calculate_weight(100, Uint128::new(64)).unwrap();
     }
+
+    #[test]
+    fn boost_is_disabled_with_max_boost_of_one() {
+        let boost = calculate_boost(
+            Decimal::one(),
+            Decimal::percent(50),
+            Uint128::new(1_000),
+            Uint128::new(10_000),
+        )
+        .unwrap();
+        assert_eq!(boost, Decimal::one());
+    }
+
+    #[test]
+    fn boost_scales_with_bonding_share() {
+        let max_boost = Decimal::from_ratio(5u128, 2u128);
+
+        // no bonding share, no boost
+        let boost = calculate_boost(
+            max_boost,
+            Decimal::zero(),
+            Uint128::new(1_000),
+            Uint128::new(10_000),
+        )
+        .unwrap();
+        assert_eq!(boost, Decimal::one());
+
+        // 10% of the incentive's weight and 5% of the bonding share, half of the extra boost
+        let boost = calculate_boost(
+            max_boost,
+            Decimal::percent(5),
+            Uint128::new(1_000),
+            Uint128::new(10_000),
+        )
+        .unwrap();
+        assert_eq!(boost, Decimal::from_ratio(7u128, 4u128));
+
+        // the boost is capped once the bonding share reaches the share of the incentive's weight
+        let boost = calculate_boost(
+            max_boost,
+            Decimal::percent(20),
+            Uint128::new(1_000),
+            Uint128::new(10_000),
+        )
+        .unwrap();
+        assert_eq!(boost, max_boost);
+    }
 }
@@ -12,9 +12,10 @@ use white_whale_std::pool_network::incentive_factory::{
 
 use crate::error::ContractError;
 use crate::error::ContractError::MigrateInvalidVersion;
+use crate::execute::{validate_emergency_unlock_penalty, validate_max_boost};
 use crate::reply::create_incentive_reply::CREATE_INCENTIVE_REPLY_ID;
 use crate::state::CONFIG;
-use crate::{execute, queries, reply};
+use crate::{execute, migrations, queries, reply};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "white_whale-incentive_factory";
@@ -41,6 +42,10 @@ pub fn instantiate(
         });
     }
 
+    validate_emergency_unlock_penalty(msg.emergency_unlock_penalty)?;
+    validate_emergency_unlock_penalty(msg.max_emergency_unlock_penalty)?;
+    validate_max_boost(msg.max_boost)?;
+
     let config = Config {
         owner: deps.api.addr_validate(info.sender.as_str())?,
         fee_collector_addr: deps.api.addr_validate(msg.fee_collector_addr.as_str())?,
@@ -51,6 +56,10 @@ pub fn instantiate(
         max_flow_epoch_buffer: msg.max_flow_epoch_buffer,
         min_unbonding_duration: msg.min_unbonding_duration,
         max_unbonding_duration: msg.max_unbonding_duration,
+        emergency_unlock_penalty: msg.emergency_unlock_penalty,
+        max_emergency_unlock_penalty: msg.max_emergency_unlock_penalty,
+        max_boost: msg.max_boost,
+        epoch_manager_addr: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -81,6 +90,15 @@ pub fn instantiate(
             "max_unbonding_duration",
             config.max_unbonding_duration.to_string(),
         ),
+        (
+            "emergency_unlock_penalty",
+            config.emergency_unlock_penalty.to_string(),
+        ),
+        (
+            "max_emergency_unlock_penalty",
+            config.max_emergency_unlock_penalty.to_string(),
+        ),
+        ("max_boost", config.max_boost.to_string()),
     ]))
 }
 
@@ -109,6 +127,16 @@ pub fn execute(
             max_flow_start_time_buffer,
             min_unbonding_duration,
             max_unbonding_duration,
+            emergency_unlock_penalty,
+            max_emergency_unlock_penalty,
+            max_boost,
+            flow_assets_to_add,
+            flow_assets_to_remove,
+            flow_fee_overrides_to_set,
+            flow_fee_overrides_to_remove,
+            creator_flow_fees_to_set,
+            creator_flow_fees_to_remove,
+            epoch_manager_addr,
         } => execute::update_config(
             deps,
             owner,
@@ -120,20 +148,33 @@ pub fn execute(
             max_flow_start_time_buffer,
             min_unbonding_duration,
             max_unbonding_duration,
+            emergency_unlock_penalty,
+            max_emergency_unlock_penalty,
+            max_boost,
+            flow_assets_to_add,
+            flow_assets_to_remove,
+            flow_fee_overrides_to_set,
+            flow_fee_overrides_to_remove,
+            creator_flow_fees_to_set,
+            creator_flow_fees_to_remove,
+            epoch_manager_addr,
         ),
         ExecuteMsg::MigrateIncentives {
             incentive_address,
             code_id,
         } => execute::migrate_incentives(deps, incentive_address, code_id),
+        ExecuteMsg::AddEpochHooks { incentive_address } => {
+            execute::add_epoch_hooks(deps, env, incentive_address)
+        }
     }
 }
 
 /// Handles reply messages from submessages sent out by the incentive factory.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         CREATE_INCENTIVE_REPLY_ID => {
-            reply::create_incentive_reply::create_incentive_reply(deps, msg)
+            reply::create_incentive_reply::create_incentive_reply(deps, env, msg)
         }
         id => Err(ContractError::UnknownReplyId { id }),
     }
@@ -150,12 +191,39 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Incentives { start_after, limit } => {
             to_json_binary(&queries::get_incentives(deps, start_after, limit)?)
         }
+        QueryMsg::FlowAssets {} => to_json_binary(&queries::get_flow_assets(deps)?),
+        QueryMsg::FlowAssetAllowed { flow_asset } => {
+            to_json_binary(&queries::get_flow_asset_allowed(deps, flow_asset)?)
+        }
+        QueryMsg::FlowFeeOverrides {} => to_json_binary(&queries::get_flow_fee_overrides(deps)?),
+        QueryMsg::CreatorFlowFees { start_after, limit } => {
+            to_json_binary(&queries::get_creator_flow_fees(deps, start_after, limit)?)
+        }
+        QueryMsg::FlowFee { creator } => to_json_binary(&queries::get_flow_fee(deps, creator)?),
+        QueryMsg::ActiveFlows {
+            lp_asset,
+            flow_asset,
+            creator,
+            start_epoch,
+            end_epoch,
+            start_after,
+            limit,
+        } => to_json_binary(&queries::get_active_flows(
+            deps,
+            lp_asset,
+            flow_asset,
+            creator,
+            start_epoch,
+            end_epoch,
+            start_after,
+            limit,
+        )?),
     }
 }
 
 #[cfg(not(tarpaulin_include))]
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let version: Version = CONTRACT_VERSION.parse()?;
     let storage_version: Version = get_contract_version(deps.storage)?.version.parse()?;
 
@@ -166,6 +234,13 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         });
     }
 
+    // migrate_to_v110 already stores the config in its latest form
+    if storage_version < Version::parse("1.1.0")? {
+        migrations::migrate_to_v110(deps.branch())?;
+    } else if storage_version < Version::parse("1.2.0")? {
+        migrations::migrate_to_v120(deps.branch())?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default().add_attributes(vec![("action", "migrate".to_string())]))
 }
@@ -41,6 +41,15 @@ pub enum ContractError {
         /// The maximum unbonding time
         max: u64,
     },
+
+    #[error("The emergency unlock penalties must not be greater than one")]
+    InvalidEmergencyUnlockPenalty,
+
+    #[error("The max boost must not be lower than one")]
+    InvalidMaxBoost,
+
+    #[error("The epoch manager is not set in the config")]
+    EpochManagerNotSet,
 }
 
 impl From<semver::Error> for ContractError {
@@ -0,0 +1,91 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, Order, Response, StdResult, WasmMsg,
+};
+
+use white_whale_std::epoch_manager::epoch_manager::{
+    ConfigResponse as EpochManagerConfigResponse, ExecuteMsg as EpochManagerExecuteMsg,
+    QueryMsg as EpochManagerQueryMsg,
+};
+use white_whale_std::pool_network::incentive;
+
+use crate::error::ContractError;
+use crate::state::{CONFIG, INCENTIVE_MAPPINGS};
+
+pub fn add_epoch_hooks(
+    deps: DepsMut,
+    env: Env,
+    incentive_address: Option<String>,
+) -> Result<Response, ContractError> {
+    let epoch_manager_addr = CONFIG
+        .load(deps.storage)?
+        .epoch_manager_addr
+        .ok_or(ContractError::EpochManagerNotSet)?;
+
+    // register only the provided incentive address, otherwise register all incentives
+    let incentives = if let Some(incentive_address) = incentive_address {
+        vec![deps.api.addr_validate(incentive_address.as_str())?]
+    } else {
+        INCENTIVE_MAPPINGS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (_, incentive_address) = item?;
+                Ok(incentive_address)
+            })
+            .collect::<StdResult<Vec<Addr>>>()?
+    };
+
+    let is_admin = is_epoch_manager_admin(deps.as_ref(), &env, &epoch_manager_addr);
+
+    let mut res = Response::new().add_attributes(vec![
+        ("method", "add_epoch_hooks".to_string()),
+        ("epoch_manager_addr", epoch_manager_addr.to_string()),
+        ("hooks_added", is_admin.to_string()),
+    ]);
+    for incentive in incentives {
+        res = res
+            .add_attribute("incentive", incentive.to_string())
+            .add_messages(epoch_hook_msgs(&epoch_manager_addr, &incentive, is_admin)?);
+    }
+
+    Ok(res)
+}
+
+/// Checks whether the factory is the admin of the epoch manager, which is required to add hooks
+/// to it. Treats a failing query as the factory not being the admin.
+pub(crate) fn is_epoch_manager_admin(deps: Deps, env: &Env, epoch_manager_addr: &Addr) -> bool {
+    deps.querier
+        .query_wasm_smart::<EpochManagerConfigResponse>(
+            epoch_manager_addr,
+            &EpochManagerQueryMsg::Config {},
+        )
+        .map(|config| config.owner == env.contract.address)
+        .unwrap_or(false)
+}
+
+/// Creates the messages setting the epoch manager on the given incentive contract, and
+/// registering the incentive contract as a hook on the epoch manager if `add_hook` is set.
+pub(crate) fn epoch_hook_msgs(
+    epoch_manager_addr: &Addr,
+    incentive_address: &Addr,
+    add_hook: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: incentive_address.to_string(),
+        msg: to_json_binary(&incentive::ExecuteMsg::UpdateEpochManager {
+            epoch_manager_addr: Some(epoch_manager_addr.to_string()),
+        })?,
+        funds: vec![],
+    })];
+
+    if add_hook {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: epoch_manager_addr.to_string(),
+            msg: to_json_binary(&EpochManagerExecuteMsg::AddHook {
+                contract_addr: incentive_address.to_string(),
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(messages)
+}
@@ -57,7 +57,7 @@ mod tests {
     // create test to check the update_config function works properly
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{attr, Addr, Uint128};
+    use cosmwasm_std::{attr, Addr, Decimal, Uint128};
 
     use white_whale_std::pool_network::asset::{Asset, AssetInfo};
     use white_whale_std::pool_network::incentive_factory::ExecuteMsg::MigrateIncentives;
@@ -85,6 +85,9 @@ mod tests {
             max_flow_epoch_buffer: 3600u64,
             min_unbonding_duration: 86400u64,
             max_unbonding_duration: 259200u64,
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
         };
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -136,6 +139,9 @@ mod tests {
             max_flow_epoch_buffer: 3600u64,
             min_unbonding_duration: 86400u64,
             max_unbonding_duration: 259200u64,
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
         };
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -1,7 +1,10 @@
+mod add_epoch_hooks;
 mod create_incentive;
 mod migrate_incentive;
 mod update_config;
 
+pub use add_epoch_hooks::add_epoch_hooks;
+pub(crate) use add_epoch_hooks::{epoch_hook_msgs, is_epoch_manager_admin};
 pub use create_incentive::create_incentive;
 pub use migrate_incentive::migrate_incentives;
-pub use update_config::update_config;
+pub use update_config::{update_config, validate_emergency_unlock_penalty, validate_max_boost};
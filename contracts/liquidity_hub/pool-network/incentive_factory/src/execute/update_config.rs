@@ -1,6 +1,8 @@
-use cosmwasm_std::{DepsMut, Response};
-use white_whale_std::pool_network::asset::Asset;
+use cosmwasm_std::{Decimal, DepsMut, Response};
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::incentive_factory::CreatorFlowFee;
 
+use crate::state::{CREATOR_FLOW_FEES, FLOW_ASSETS, FLOW_FEE_OVERRIDES};
 use crate::{error::ContractError, state::CONFIG};
 
 #[allow(clippy::too_many_arguments)]
@@ -15,6 +17,16 @@ pub fn update_config(
     max_flow_start_time_buffer: Option<u64>,
     min_unbonding_duration: Option<u64>,
     max_unbonding_duration: Option<u64>,
+    emergency_unlock_penalty: Option<Decimal>,
+    max_emergency_unlock_penalty: Option<Decimal>,
+    max_boost: Option<Decimal>,
+    flow_assets_to_add: Option<Vec<AssetInfo>>,
+    flow_assets_to_remove: Option<Vec<AssetInfo>>,
+    flow_fee_overrides_to_set: Option<Vec<Asset>>,
+    flow_fee_overrides_to_remove: Option<Vec<AssetInfo>>,
+    creator_flow_fees_to_set: Option<Vec<CreatorFlowFee>>,
+    creator_flow_fees_to_remove: Option<Vec<String>>,
+    epoch_manager_addr: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -72,6 +84,59 @@ pub fn update_config(
         config.min_unbonding_duration = min_unbonding_duration;
     }
 
+    if let Some(emergency_unlock_penalty) = emergency_unlock_penalty {
+        validate_emergency_unlock_penalty(emergency_unlock_penalty)?;
+        config.emergency_unlock_penalty = emergency_unlock_penalty;
+    }
+
+    if let Some(max_emergency_unlock_penalty) = max_emergency_unlock_penalty {
+        validate_emergency_unlock_penalty(max_emergency_unlock_penalty)?;
+        config.max_emergency_unlock_penalty = max_emergency_unlock_penalty;
+    }
+
+    if let Some(max_boost) = max_boost {
+        validate_max_boost(max_boost)?;
+        config.max_boost = max_boost;
+    }
+
+    for flow_asset in flow_assets_to_add.unwrap_or_default() {
+        FLOW_ASSETS.save(
+            deps.storage,
+            flow_asset.to_raw(deps.api)?.as_bytes(),
+            &flow_asset,
+        )?;
+    }
+
+    for flow_asset in flow_assets_to_remove.unwrap_or_default() {
+        FLOW_ASSETS.remove(deps.storage, flow_asset.to_raw(deps.api)?.as_bytes());
+    }
+
+    for flow_fee in flow_fee_overrides_to_set.unwrap_or_default() {
+        FLOW_FEE_OVERRIDES.save(
+            deps.storage,
+            flow_fee.info.to_raw(deps.api)?.as_bytes(),
+            &flow_fee,
+        )?;
+    }
+
+    for fee_asset in flow_fee_overrides_to_remove.unwrap_or_default() {
+        FLOW_FEE_OVERRIDES.remove(deps.storage, fee_asset.to_raw(deps.api)?.as_bytes());
+    }
+
+    for creator_flow_fee in creator_flow_fees_to_set.unwrap_or_default() {
+        let creator = deps.api.addr_validate(&creator_flow_fee.creator)?;
+        CREATOR_FLOW_FEES.save(deps.storage, &creator, &creator_flow_fee.fee)?;
+    }
+
+    for creator in creator_flow_fees_to_remove.unwrap_or_default() {
+        let creator = deps.api.addr_validate(&creator)?;
+        CREATOR_FLOW_FEES.remove(deps.storage, &creator);
+    }
+
+    if let Some(epoch_manager_addr) = epoch_manager_addr {
+        config.epoch_manager_addr = Some(deps.api.addr_validate(&epoch_manager_addr)?);
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attributes(vec![
@@ -100,9 +165,43 @@ pub fn update_config(
             "max_unbonding_duration",
             config.max_unbonding_duration.to_string(),
         ),
+        (
+            "emergency_unlock_penalty",
+            config.emergency_unlock_penalty.to_string(),
+        ),
+        (
+            "max_emergency_unlock_penalty",
+            config.max_emergency_unlock_penalty.to_string(),
+        ),
+        ("max_boost", config.max_boost.to_string()),
+        (
+            "epoch_manager_addr",
+            config
+                .epoch_manager_addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        ),
     ]))
 }
 
+/// Validates that the emergency unlock penalty is not greater than one.
+pub fn validate_emergency_unlock_penalty(penalty: Decimal) -> Result<(), ContractError> {
+    if penalty > Decimal::one() {
+        return Err(ContractError::InvalidEmergencyUnlockPenalty);
+    }
+
+    Ok(())
+}
+
+/// Validates that the max boost is not lower than one.
+pub fn validate_max_boost(max_boost: Decimal) -> Result<(), ContractError> {
+    if max_boost < Decimal::one() {
+        return Err(ContractError::InvalidMaxBoost);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     // create test to check the update_config function works properly
@@ -110,10 +209,13 @@ mod tests {
     use crate::contract::{execute, instantiate, query};
     use crate::error::ContractError;
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{from_json, Addr, Uint128};
+    use cosmwasm_std::{from_json, Addr, Decimal, Uint128};
     use white_whale_std::pool_network::asset::{Asset, AssetInfo};
     use white_whale_std::pool_network::incentive_factory::ExecuteMsg::UpdateConfig;
-    use white_whale_std::pool_network::incentive_factory::{Config, InstantiateMsg, QueryMsg};
+    use white_whale_std::pool_network::incentive_factory::{
+        Config, CreatorFlowFee, CreatorFlowFeesResponse, FlowAssetAllowedResponse,
+        FlowAssetsResponse, FlowFeeResponse, InstantiateMsg, QueryMsg,
+    };
 
     #[test]
     fn update_config_successfully() {
@@ -134,6 +236,9 @@ mod tests {
             max_flow_epoch_buffer: 3600u64,
             min_unbonding_duration: 86400u64,
             max_unbonding_duration: 259200u64,
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
         };
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -157,6 +262,10 @@ mod tests {
                 max_flow_epoch_buffer: 3600u64,
                 min_unbonding_duration: 86400u64,
                 max_unbonding_duration: 259200u64,
+                emergency_unlock_penalty: Decimal::percent(5),
+                max_emergency_unlock_penalty: Decimal::percent(50),
+                max_boost: Decimal::one(),
+                epoch_manager_addr: None,
             }
         );
 
@@ -175,6 +284,16 @@ mod tests {
             max_flow_start_time_buffer: Some(60u64),
             min_unbonding_duration: Some(1000u64),
             max_unbonding_duration: Some(86400u64),
+            emergency_unlock_penalty: Some(Decimal::percent(10)),
+            max_emergency_unlock_penalty: Some(Decimal::percent(80)),
+            max_boost: Some(Decimal::from_ratio(5u128, 2u128)),
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: Some("epoch_manager".to_string()),
         };
 
         let info = mock_info("owner", &[]);
@@ -200,6 +319,10 @@ mod tests {
                 max_flow_epoch_buffer: 60u64,
                 min_unbonding_duration: 1000u64,
                 max_unbonding_duration: 86400u64,
+                emergency_unlock_penalty: Decimal::percent(10),
+                max_emergency_unlock_penalty: Decimal::percent(80),
+                max_boost: Decimal::from_ratio(5u128, 2u128),
+                epoch_manager_addr: Some(Addr::unchecked("epoch_manager")),
             }
         );
     }
@@ -223,6 +346,9 @@ mod tests {
             max_flow_epoch_buffer: 3600u64,
             min_unbonding_duration: 86400u64,
             max_unbonding_duration: 259200u64,
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
         };
         instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -237,6 +363,16 @@ mod tests {
             max_flow_start_time_buffer: None,
             min_unbonding_duration: None,
             max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
         };
 
         let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
@@ -263,6 +399,16 @@ mod tests {
             max_flow_start_time_buffer: None,
             min_unbonding_duration: Some(300000u64),
             max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
         };
 
         let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
@@ -281,6 +427,16 @@ mod tests {
             max_flow_start_time_buffer: None,
             min_unbonding_duration: None,
             max_unbonding_duration: Some(1000u64),
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
         };
 
         let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
@@ -288,5 +444,265 @@ mod tests {
             ContractError::InvalidUnbondingRange { .. } => {}
             _ => panic!("should return ContractError::InvalidUnbondingRange"),
         }
+
+        let msg = UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: Some(Decimal::percent(101)),
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
+        };
+
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        match err {
+            ContractError::InvalidEmergencyUnlockPenalty => {}
+            _ => panic!("should return ContractError::InvalidEmergencyUnlockPenalty"),
+        }
+
+        let msg = UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: Some(Decimal::percent(50)),
+            flow_assets_to_add: None,
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
+        };
+
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        match err {
+            ContractError::InvalidMaxBoost => {}
+            _ => panic!("should return ContractError::InvalidMaxBoost"),
+        }
+    }
+
+    #[test]
+    fn update_flow_assets_and_fees() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("owner", &[]);
+
+        let msg = InstantiateMsg {
+            fee_collector_addr: "fee_collector_addr".to_string(),
+            fee_distributor_addr: "fee_distributor_addr".to_string(),
+            create_flow_fee: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusdc".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            max_concurrent_flows: 1u64,
+            incentive_code_id: 123,
+            max_flow_epoch_buffer: 3600u64,
+            min_unbonding_duration: 86400u64,
+            max_unbonding_duration: 259200u64,
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
+        };
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let uwhale = AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        };
+        let uusdc = AssetInfo::NativeToken {
+            denom: "uusdc".to_string(),
+        };
+
+        // any asset is allowed while the whitelist is empty
+        let flow_asset_allowed: FlowAssetAllowedResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::FlowAssetAllowed {
+                    flow_asset: uusdc.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(flow_asset_allowed);
+
+        let msg = UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: Some(vec![uwhale.clone(), uusdc.clone()]),
+            flow_assets_to_remove: None,
+            flow_fee_overrides_to_set: Some(vec![Asset {
+                info: uwhale.clone(),
+                amount: Uint128::new(500u128),
+            }]),
+            flow_fee_overrides_to_remove: None,
+            creator_flow_fees_to_set: Some(vec![CreatorFlowFee {
+                creator: "treasury".to_string(),
+                fee: Asset {
+                    info: uwhale.clone(),
+                    amount: Uint128::zero(),
+                },
+            }]),
+            creator_flow_fees_to_remove: None,
+            epoch_manager_addr: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let flow_assets: FlowAssetsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::FlowAssets {}).unwrap()).unwrap();
+        assert_eq!(flow_assets.len(), 2);
+        assert!(flow_assets.contains(&uwhale) && flow_assets.contains(&uusdc));
+
+        let flow_fee: FlowFeeResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::FlowFee {
+                    creator: "alice".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            flow_fee.fees,
+            vec![
+                Asset {
+                    info: uusdc.clone(),
+                    amount: Uint128::new(1_000u128),
+                },
+                Asset {
+                    info: uwhale.clone(),
+                    amount: Uint128::new(500u128),
+                },
+            ]
+        );
+
+        let flow_fee: FlowFeeResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::FlowFee {
+                    creator: "treasury".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            flow_fee.fees,
+            vec![Asset {
+                info: uwhale.clone(),
+                amount: Uint128::zero(),
+            }]
+        );
+
+        let msg = UpdateConfig {
+            owner: None,
+            fee_collector_addr: None,
+            fee_distributor_addr: None,
+            create_flow_fee: None,
+            max_concurrent_flows: None,
+            incentive_code_id: None,
+            max_flow_start_time_buffer: None,
+            min_unbonding_duration: None,
+            max_unbonding_duration: None,
+            emergency_unlock_penalty: None,
+            max_emergency_unlock_penalty: None,
+            max_boost: None,
+            flow_assets_to_add: None,
+            flow_assets_to_remove: Some(vec![uusdc.clone()]),
+            flow_fee_overrides_to_set: None,
+            flow_fee_overrides_to_remove: Some(vec![uwhale.clone()]),
+            creator_flow_fees_to_set: None,
+            creator_flow_fees_to_remove: Some(vec!["treasury".to_string()]),
+            epoch_manager_addr: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let flow_assets: FlowAssetsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::FlowAssets {}).unwrap()).unwrap();
+        assert_eq!(flow_assets, vec![uwhale.clone()]);
+
+        let flow_asset_allowed = |flow_asset: &AssetInfo| -> FlowAssetAllowedResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::FlowAssetAllowed {
+                        flow_asset: flow_asset.clone(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        assert!(flow_asset_allowed(&uwhale));
+        assert!(!flow_asset_allowed(&uusdc));
+
+        let creator_flow_fees: CreatorFlowFeesResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::CreatorFlowFees {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(creator_flow_fees.is_empty());
+
+        let flow_fee: FlowFeeResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::FlowFee {
+                    creator: "treasury".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            flow_fee.fees,
+            vec![Asset {
+                info: uusdc,
+                amount: Uint128::new(1_000u128),
+            }]
+        );
     }
 }
@@ -1,2 +1,94 @@
-// currently a stub file
-// until migrations are needed in the future
+#![cfg(not(tarpaulin_include))]
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, DepsMut, StdError};
+use cw_storage_plus::Item;
+
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::pool_network::incentive_factory::Config;
+
+use crate::state::CONFIG;
+
+/// Migrates to version 1.1.0, which introduces the emergency unlock penalties to the [Config].
+pub(crate) fn migrate_to_v110(deps: DepsMut) -> Result<(), StdError> {
+    #[cw_serde]
+    pub struct ConfigV102 {
+        pub owner: Addr,
+        pub fee_collector_addr: Addr,
+        pub fee_distributor_addr: Addr,
+        pub create_flow_fee: Asset,
+        pub max_concurrent_flows: u64,
+        pub incentive_code_id: u64,
+        pub max_flow_epoch_buffer: u64,
+        pub min_unbonding_duration: u64,
+        pub max_unbonding_duration: u64,
+    }
+
+    const CONFIG_V102: Item<ConfigV102> = Item::new("config");
+    let config = CONFIG_V102.load(deps.storage)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: config.owner,
+            fee_collector_addr: config.fee_collector_addr,
+            fee_distributor_addr: config.fee_distributor_addr,
+            create_flow_fee: config.create_flow_fee,
+            max_concurrent_flows: config.max_concurrent_flows,
+            incentive_code_id: config.incentive_code_id,
+            max_flow_epoch_buffer: config.max_flow_epoch_buffer,
+            min_unbonding_duration: config.min_unbonding_duration,
+            max_unbonding_duration: config.max_unbonding_duration,
+            // a position with the maximum unbonding duration pays the maximum penalty
+            emergency_unlock_penalty: Decimal::percent(5),
+            max_emergency_unlock_penalty: Decimal::percent(50),
+            max_boost: Decimal::one(),
+            epoch_manager_addr: None,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Migrates to version 1.2.0, which introduces the max boost to the [Config].
+pub(crate) fn migrate_to_v120(deps: DepsMut) -> Result<(), StdError> {
+    #[cw_serde]
+    pub struct ConfigV110 {
+        pub owner: Addr,
+        pub fee_collector_addr: Addr,
+        pub fee_distributor_addr: Addr,
+        pub create_flow_fee: Asset,
+        pub max_concurrent_flows: u64,
+        pub incentive_code_id: u64,
+        pub max_flow_epoch_buffer: u64,
+        pub min_unbonding_duration: u64,
+        pub max_unbonding_duration: u64,
+        pub emergency_unlock_penalty: Decimal,
+        pub max_emergency_unlock_penalty: Decimal,
+    }
+
+    const CONFIG_V110: Item<ConfigV110> = Item::new("config");
+    let config = CONFIG_V110.load(deps.storage)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: config.owner,
+            fee_collector_addr: config.fee_collector_addr,
+            fee_distributor_addr: config.fee_distributor_addr,
+            create_flow_fee: config.create_flow_fee,
+            max_concurrent_flows: config.max_concurrent_flows,
+            incentive_code_id: config.incentive_code_id,
+            max_flow_epoch_buffer: config.max_flow_epoch_buffer,
+            min_unbonding_duration: config.min_unbonding_duration,
+            max_unbonding_duration: config.max_unbonding_duration,
+            emergency_unlock_penalty: config.emergency_unlock_penalty,
+            max_emergency_unlock_penalty: config.max_emergency_unlock_penalty,
+            // boosting stays disabled until it's configured
+            max_boost: Decimal::one(),
+            epoch_manager_addr: None,
+        },
+    )?;
+
+    Ok(())
+}
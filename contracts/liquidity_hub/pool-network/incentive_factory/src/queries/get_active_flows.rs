@@ -0,0 +1,145 @@
+use cosmwasm_std::{Addr, CanonicalAddr, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::pool_network::incentive;
+use white_whale_std::pool_network::incentive_factory::{ActiveFlowsResponse, IncentiveFlow};
+
+use crate::state::INCENTIVE_MAPPINGS;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+/// The maximum amount of incentive contracts queried in a single page
+const MAX_INCENTIVES_PER_PAGE: usize = 10;
+
+/// Retrieves the flows that haven't finished emitting yet across the incentive contracts, matching
+/// the given filters.
+///
+/// At most `limit` incentive contracts, capped at [MAX_INCENTIVES_PER_PAGE], are queried per page.
+/// The returned cursor points at where the scan stopped, which is the last flow returned or the
+/// end of the last incentive contract queried. Incentive contracts failing to answer are skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn get_active_flows(
+    deps: Deps,
+    lp_asset: Option<AssetInfo>,
+    flow_asset: Option<AssetInfo>,
+    creator: Option<String>,
+    start_epoch: Option<u64>,
+    end_epoch: Option<u64>,
+    start_after: Option<(AssetInfo, u64)>,
+    limit: Option<u32>,
+) -> StdResult<ActiveFlowsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let max_incentives = limit.min(MAX_INCENTIVES_PER_PAGE);
+    // an empty page leaves the cursor where it was
+    let mut next_start_after = start_after.clone();
+    let start_after = start_after
+        .map(|(lp_asset, flow_id)| {
+            lp_asset
+                .to_raw(deps.api)
+                .map(|lp_asset| (lp_asset.as_bytes().to_vec(), flow_id))
+        })
+        .transpose()?;
+
+    let incentives: Box<dyn Iterator<Item = StdResult<(Vec<u8>, Addr)>>> = match lp_asset {
+        Some(lp_asset) => {
+            let lp_reference = lp_asset.to_raw(deps.api)?.as_bytes().to_vec();
+            let incentive = INCENTIVE_MAPPINGS
+                .may_load(deps.storage, &lp_reference)?
+                .map(|incentive_address| Ok((lp_reference, incentive_address)));
+
+            Box::new(incentive.into_iter())
+        }
+        None => {
+            // start at the incentive contract of the flow to start after, it may have more flows
+            let start = start_after
+                .as_ref()
+                .map(|(lp_reference, _)| Bound::InclusiveRaw(lp_reference.clone()));
+
+            Box::new(INCENTIVE_MAPPINGS.range(deps.storage, start, None, Order::Ascending))
+        }
+    };
+
+    let mut flows = vec![];
+    let mut scanned_incentives = 0usize;
+    let mut scan_over = true;
+    for incentive in incentives {
+        let (lp_reference, incentive_address) = incentive?;
+        let flow_start_after = match &start_after {
+            // all the flows of the incentive contract to start after were already scanned
+            Some((start_lp_reference, u64::MAX)) if start_lp_reference == &lp_reference => continue,
+            Some((start_lp_reference, flow_id)) if start_lp_reference == &lp_reference => {
+                Some(*flow_id)
+            }
+            // the incentive contracts before the one to start after are skipped
+            Some((start_lp_reference, _)) if start_lp_reference > &lp_reference => continue,
+            _ => None,
+        };
+
+        if flows.len() >= limit || scanned_incentives >= max_incentives {
+            scan_over = false;
+            break;
+        }
+        scanned_incentives += 1;
+
+        let remaining = limit - flows.len();
+        let response: incentive::ActiveFlowsResponse = match deps.querier.query_wasm_smart(
+            &incentive_address,
+            &incentive::QueryMsg::ActiveFlows {
+                flow_asset: flow_asset.clone(),
+                creator: creator.clone(),
+                start_epoch,
+                end_epoch,
+                start_after: flow_start_after,
+                limit: Some(remaining as u32),
+            },
+        ) {
+            Ok(response) => response,
+            // incentive contracts that can't answer, i.e. not migrated yet, are skipped
+            Err(_) => {
+                if let Some(lp_asset) = lp_asset_from_reference(deps, &lp_reference) {
+                    next_start_after = Some((lp_asset, u64::MAX));
+                }
+                continue;
+            }
+        };
+
+        // the incentive contract may have more flows if it filled the page
+        let last_flow_id = match response.flows.last() {
+            Some(flow) if response.flows.len() >= remaining => {
+                scan_over = false;
+                flow.flow_id
+            }
+            _ => u64::MAX,
+        };
+        next_start_after = Some((response.lp_asset.clone(), last_flow_id));
+
+        flows.extend(response.flows.into_iter().map(|flow| IncentiveFlow {
+            incentive_address: incentive_address.clone(),
+            lp_asset: response.lp_asset.clone(),
+            epoch_id: response.epoch_id,
+            flow,
+        }));
+    }
+
+    Ok(ActiveFlowsResponse {
+        flows,
+        next_start_after: if scan_over { None } else { next_start_after },
+    })
+}
+
+/// Rebuilds the LP asset of an incentive contract from its reference in [INCENTIVE_MAPPINGS], so
+/// the cursor can point past it. Any asset with the same raw bytes will do for the cursor.
+fn lp_asset_from_reference(deps: Deps, lp_reference: &[u8]) -> Option<AssetInfo> {
+    match String::from_utf8(lp_reference.to_vec()) {
+        Ok(denom) => Some(AssetInfo::NativeToken { denom }),
+        Err(_) => deps
+            .api
+            .addr_humanize(&CanonicalAddr::from(lp_reference))
+            .ok()
+            .map(|contract_addr| AssetInfo::Token {
+                contract_addr: contract_addr.into_string(),
+            }),
+    }
+}
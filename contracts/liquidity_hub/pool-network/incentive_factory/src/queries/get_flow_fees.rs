@@ -0,0 +1,87 @@
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::pool_network::incentive_factory::{
+    CreatorFlowFee, CreatorFlowFeesResponse, FlowAssetAllowedResponse, FlowAssetsResponse,
+    FlowFeeOverridesResponse, FlowFeeResponse,
+};
+
+use crate::state::{CONFIG, CREATOR_FLOW_FEES, FLOW_ASSETS, FLOW_FEE_OVERRIDES};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Retrieves the whitelist of assets flows can be opened with.
+pub fn get_flow_assets(deps: Deps) -> StdResult<FlowAssetsResponse> {
+    FLOW_ASSETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Retrieves whether flows can be opened with the given asset, looking it up in the whitelist
+/// rather than loading the whole of it.
+pub fn get_flow_asset_allowed(
+    deps: Deps,
+    flow_asset: AssetInfo,
+) -> StdResult<FlowAssetAllowedResponse> {
+    Ok(FLOW_ASSETS.is_empty(deps.storage)
+        || FLOW_ASSETS.has(deps.storage, flow_asset.to_raw(deps.api)?.as_bytes()))
+}
+
+/// Retrieves the alternative fees to create a flow.
+pub fn get_flow_fee_overrides(deps: Deps) -> StdResult<FlowFeeOverridesResponse> {
+    FLOW_FEE_OVERRIDES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Retrieves the fees charged to specific flow creators.
+pub fn get_creator_flow_fees(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CreatorFlowFeesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|creator| deps.api.addr_validate(&creator))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    CREATOR_FLOW_FEES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (creator, fee) = item?;
+
+            Ok(CreatorFlowFee {
+                creator: creator.into_string(),
+                fee,
+            })
+        })
+        .collect()
+}
+
+/// Retrieves the fees the given creator can choose from to create a flow. A fee set for the creator
+/// takes precedence over the `create_flow_fee` and its overrides.
+pub fn get_flow_fee(deps: Deps, creator: String) -> StdResult<FlowFeeResponse> {
+    let creator = deps.api.addr_validate(&creator)?;
+
+    if let Some(fee) = CREATOR_FLOW_FEES.may_load(deps.storage, &creator)? {
+        return Ok(FlowFeeResponse { fees: vec![fee] });
+    }
+
+    let mut fees = vec![CONFIG.load(deps.storage)?.create_flow_fee];
+    for fee in get_flow_fee_overrides(deps)? {
+        // an override in the asset of the create_flow_fee replaces it
+        if fee.info == fees[0].info {
+            fees[0] = fee;
+        } else {
+            fees.push(fee);
+        }
+    }
+
+    Ok(FlowFeeResponse { fees })
+}
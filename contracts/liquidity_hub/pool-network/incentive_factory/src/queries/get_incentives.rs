@@ -57,8 +57,8 @@ fn calc_range_start(start_after: Option<AssetInfoRaw>) -> Option<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
-        testing::mock_dependencies, to_json_binary, Addr, Binary, DepsMut, Reply, SubMsgResponse,
-        SubMsgResult,
+        testing::{mock_dependencies, mock_env},
+        to_json_binary, Addr, Binary, DepsMut, Reply, SubMsgResponse, SubMsgResult,
     };
     use protobuf::{Message, SpecialFields};
     use white_whale_std::pool_network::{asset::AssetInfo, incentive_factory::IncentivesContract};
@@ -74,6 +74,7 @@ mod tests {
     fn create_incentive(deps: DepsMut, id: u64) {
         create_incentive_reply(
             deps,
+            mock_env(),
             Reply {
                 id: CREATE_INCENTIVE_REPLY_ID,
                 result: SubMsgResult::Ok(SubMsgResponse {
@@ -1,7 +1,14 @@
+mod get_active_flows;
 mod get_config;
+mod get_flow_fees;
 mod get_incentive;
 mod get_incentives;
 
+pub use get_active_flows::get_active_flows;
 pub use get_config::get_config;
+pub use get_flow_fees::{
+    get_creator_flow_fees, get_flow_asset_allowed, get_flow_assets, get_flow_fee,
+    get_flow_fee_overrides,
+};
 pub use get_incentive::get_incentive;
 pub use get_incentives::get_incentives;
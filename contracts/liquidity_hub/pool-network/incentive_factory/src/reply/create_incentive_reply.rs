@@ -1,8 +1,11 @@
-use cosmwasm_std::{from_json, DepsMut, Reply, Response};
+use cosmwasm_std::{from_json, DepsMut, Env, Reply, Response};
 use protobuf::Message;
 
+use crate::execute::{epoch_hook_msgs, is_epoch_manager_admin};
 use crate::{
-    error::ContractError, response::MsgInstantiateContractResponse, state::INCENTIVE_MAPPINGS,
+    error::ContractError,
+    response::MsgInstantiateContractResponse,
+    state::{CONFIG, INCENTIVE_MAPPINGS},
 };
 
 /// The reply ID for submessages when creating the incentive contract from the factory.
@@ -11,7 +14,11 @@ pub const CREATE_INCENTIVE_REPLY_ID: u64 = 1;
 /// Triggered after a new incentive contract is created.
 ///
 /// Triggered to allow us to register the new contract in state.
-pub fn create_incentive_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+pub fn create_incentive_reply(
+    deps: DepsMut,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
     let res: MsgInstantiateContractResponse = Message::parse_from_bytes(
         msg.result
             .into_result()
@@ -37,9 +44,27 @@ pub fn create_incentive_reply(deps: DepsMut, msg: Reply) -> Result<Response, Con
         &incentive_address,
     )?;
 
-    Ok(Response::default().add_attributes(vec![
+    let mut response = Response::default().add_attributes(vec![
         ("action", "create_incentive_reply".to_string()),
         ("incentive_address", incentive_address.to_string()),
         ("lp_asset", incentive_data.lp_asset.to_string()),
-    ]))
+    ]);
+
+    // register the new incentive contract to take the global weight snapshots on every new epoch.
+    // Only the admin of the epoch manager can add hooks, otherwise it's left for the admin to do
+    if let Some(epoch_manager_addr) = CONFIG
+        .may_load(deps.storage)?
+        .and_then(|config| config.epoch_manager_addr)
+    {
+        let is_admin = is_epoch_manager_admin(deps.as_ref(), &env, &epoch_manager_addr);
+        response = response
+            .add_attribute("epoch_hook_added", is_admin.to_string())
+            .add_messages(epoch_hook_msgs(
+                &epoch_manager_addr,
+                &incentive_address,
+                is_admin,
+            )?);
+    }
+
+    Ok(response)
 }
@@ -1,8 +1,17 @@
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 use white_whale_std::pool_network::incentive_factory::Config;
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
 /// Maps the address of the LP token to the incentive contract address
 pub const INCENTIVE_MAPPINGS: Map<&[u8], Addr> = Map::new("incentive_mappings");
+
+/// The assets flows can be opened with, keyed by the raw asset info. While empty, flows can be
+/// opened with any asset.
+pub const FLOW_ASSETS: Map<&[u8], AssetInfo> = Map::new("flow_assets");
+/// Alternative fees to create a flow, keyed by the raw asset info of the fee.
+pub const FLOW_FEE_OVERRIDES: Map<&[u8], Asset> = Map::new("flow_fee_overrides");
+/// Fees to create a flow charged to specific creators, overriding any other fee.
+pub const CREATOR_FLOW_FEES: Map<&Addr, Asset> = Map::new("creator_flow_fees");
@@ -16,9 +16,16 @@ use white_whale_std::vault_network::vault::{
 use crate::state::{initialize_fee, ALL_TIME_BURNED_FEES};
 use crate::{
     error::VaultError,
-    execute::{callback, collect_protocol_fees, deposit, flash_loan, receive, update_config},
+    execute::{
+        callback, cancel_queued_withdrawal, collect_protocol_fees, deposit, flash_loan,
+        process_withdrawal_queue, rebalance, receive, update_config,
+    },
     migrations,
-    queries::{get_config, get_fees, get_payback_amount, get_share},
+    queries::{
+        get_config, get_deposit_cap, get_fee_rebates, get_fees, get_flash_loan_quote,
+        get_payback_amount, get_share, get_share_price_history, get_strategy, get_withdrawal_queue,
+        get_withdrawal_queue_position,
+    },
     state::{ALL_TIME_COLLECTED_PROTOCOL_FEES, COLLECTED_PROTOCOL_FEES, CONFIG, LOAN_COUNTER},
 };
 
@@ -171,7 +178,7 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, VaultError> {
     match msg {
-        ExecuteMsg::UpdateConfig(params) => update_config(deps, info, params),
+        ExecuteMsg::UpdateConfig(params) => update_config(deps, env, info, params),
         ExecuteMsg::Deposit { amount } => deposit(deps, env, info, amount),
         ExecuteMsg::Withdraw {} => {
             // validate that the asset sent is the token factory LP token
@@ -187,10 +194,21 @@ pub fn execute(
 
             withdraw(deps, env, info.sender.into_string(), info.funds[0].amount)
         }
-        ExecuteMsg::FlashLoan { amount, msg } => flash_loan(deps, env, info, amount, msg),
+        ExecuteMsg::FlashLoan {
+            amount,
+            msg,
+            initiator,
+        } => flash_loan(deps, env, info, amount, msg, initiator),
         ExecuteMsg::CollectProtocolFees {} => collect_protocol_fees(deps),
         ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
         ExecuteMsg::Callback(msg) => callback(deps, env, info, msg),
+        ExecuteMsg::Rebalance {} => rebalance(deps, env, info),
+        ExecuteMsg::ProcessWithdrawalQueue { limit } => {
+            // only the owner can make the vault unwind its strategy position, like when rebalancing
+            let unwind_strategy = CONFIG.load(deps.storage)?.owner == info.sender;
+            process_withdrawal_queue(deps, env, limit, unwind_strategy)
+        }
+        ExecuteMsg::CancelQueuedWithdrawal { id } => cancel_queued_withdrawal(deps, info, id),
     }
 }
 
@@ -251,7 +269,28 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, VaultError>
             ALL_TIME_COLLECTED_PROTOCOL_FEES,
             Some(COLLECTED_PROTOCOL_FEES),
         ),
-        QueryMsg::GetPaybackAmount { amount } => get_payback_amount(deps, amount),
+        QueryMsg::GetPaybackAmount { amount, initiator } => {
+            get_payback_amount(deps, amount, initiator)
+        }
+        QueryMsg::FlashLoanQuote {
+            amount,
+            sender,
+            initiator,
+        } => get_flash_loan_quote(deps, env, amount, sender, initiator),
         QueryMsg::BurnedFees {} => get_fees(deps, true, ALL_TIME_BURNED_FEES, None),
+        QueryMsg::Strategy {} => get_strategy(deps, env),
+        QueryMsg::SharePriceHistory { start_after, limit } => {
+            get_share_price_history(deps, env, start_after, limit)
+        }
+        QueryMsg::DepositCap {} => get_deposit_cap(deps, env),
+        QueryMsg::FeeRebates {} => get_fee_rebates(deps),
+        QueryMsg::WithdrawalQueue { start_after, limit } => {
+            get_withdrawal_queue(deps, start_after, limit)
+        }
+        QueryMsg::WithdrawalQueuePosition {
+            address,
+            start_after,
+            limit,
+        } => get_withdrawal_queue_position(deps, address, start_after, limit),
     }
 }
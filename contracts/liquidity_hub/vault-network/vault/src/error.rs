@@ -1,4 +1,7 @@
-use cosmwasm_std::{ConversionOverflowError, DivideByZeroError, OverflowError, StdError, Uint128};
+use cosmwasm_std::{
+    CheckedFromRatioError, ConversionOverflowError, DivideByZeroError, OverflowError, StdError,
+    Uint128,
+};
 use semver::Version;
 use thiserror::Error;
 
@@ -19,6 +22,9 @@ pub enum VaultError {
     #[error("{0}")]
     DivideByZeroError(#[from] DivideByZeroError),
 
+    #[error("{0}")]
+    CheckedFromRatioError(#[from] CheckedFromRatioError),
+
     #[error("Deposits are not enabled")]
     DepositsDisabled {},
 
@@ -72,4 +78,34 @@ pub enum VaultError {
 
     #[error("The asset doesn't match the asset stored in contract")]
     AssetMismatch {},
+
+    #[error("The vault doesn't have a strategy")]
+    NoStrategy {},
+
+    #[error("The strategy pool must contain the vault asset")]
+    InvalidStrategyPool {},
+
+    #[error("The strategy deploy share must be between 0 and 1")]
+    InvalidDeployShare {},
+
+    #[error("The strategy pool can't be changed while there is an open position in it")]
+    StrategyPositionOpen {},
+
+    #[error("Cannot rebalance while flash-loaning")]
+    RebalanceDuringLoan {},
+
+    #[error("Cannot process the withdrawal queue while flash-loaning")]
+    ProcessQueueDuringLoan {},
+
+    #[error("There is no queued withdrawal with id {id}")]
+    QueuedWithdrawalNotFound { id: u64 },
+
+    #[error("The fee rebate discount must be between 0 and 1")]
+    InvalidFeeRebateDiscount {},
+
+    #[error("The fee rebate minimum share must be between 0 and 1")]
+    InvalidFeeRebateShare {},
+
+    #[error("The deposit would exceed the deposit cap of {cap}, the vault holds {total_assets}")]
+    DepositCapExceeded { cap: Uint128, total_assets: Uint128 },
 }
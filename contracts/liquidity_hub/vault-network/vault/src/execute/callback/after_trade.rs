@@ -1,7 +1,9 @@
-use cosmwasm_std::{DepsMut, Env, Response, StdError, Uint128, Uint256};
+use cosmwasm_std::{Decimal, DepsMut, Env, Response, StdError, Uint128, Uint256};
 use cw20::{BalanceResponse, Cw20QueryMsg};
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 
+use crate::fee_rebates::apply_fee_discount;
+use crate::share_price::record_share_price;
 use crate::state::{store_fee, ALL_TIME_BURNED_FEES};
 use crate::{
     error::VaultError,
@@ -9,10 +11,11 @@ use crate::{
 };
 
 pub fn after_trade(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     old_balance: Uint128,
     loan_amount: Uint128,
+    fee_discount: Option<Decimal>,
 ) -> Result<Response, VaultError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -20,14 +23,14 @@ pub fn after_trade(
     let new_balance = match config.asset_info.clone() {
         AssetInfo::NativeToken { denom } => {
             deps.querier
-                .query_balance(env.contract.address.into_string(), denom)?
+                .query_balance(env.contract.address.to_string(), denom)?
                 .amount
         }
         AssetInfo::Token { contract_addr } => {
             let res: BalanceResponse = deps.querier.query_wasm_smart(
                 contract_addr,
                 &Cw20QueryMsg::Balance {
-                    address: env.contract.address.into_string(),
+                    address: env.contract.address.to_string(),
                 },
             )?;
             res.balance
@@ -37,11 +40,14 @@ pub fn after_trade(
     // check that balance is greater than expected
     let protocol_fee =
         Uint128::try_from(config.fees.protocol_fee.compute(Uint256::from(loan_amount)))?;
-    let flash_loan_fee = Uint128::try_from(
-        config
-            .fees
-            .flash_loan_fee
-            .compute(Uint256::from(loan_amount)),
+    let flash_loan_fee = apply_fee_discount(
+        Uint128::try_from(
+            config
+                .fees
+                .flash_loan_fee
+                .compute(Uint256::from(loan_amount)),
+        )?,
+        fee_discount,
     )?;
     let burn_fee = Uint128::try_from(config.fees.burn_fee.compute(Uint256::from(loan_amount)))?;
 
@@ -71,6 +77,9 @@ pub fn after_trade(
     // deduct loan counter
     LOAN_COUNTER.update::<_, StdError>(deps.storage, |c| Ok(c.saturating_sub(1)))?;
 
+    // the burn fee is still held by the vault until the burn message is executed
+    record_share_price(deps.branch(), &env, &config, burn_fee)?;
+
     let mut response = Response::new();
     if !burn_fee.is_zero() {
         let burn_asset = Asset {
@@ -161,6 +170,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
         )
@@ -300,6 +310,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
         )
@@ -402,6 +413,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
         )
@@ -458,6 +470,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
         )
@@ -541,6 +554,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
         )
@@ -1,11 +1,16 @@
 mod after_trade;
+mod strategy;
 
 pub use after_trade::after_trade;
+pub use strategy::{provide_strategy_liquidity, swap_strategy_assets};
 
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use white_whale_std::vault_network::vault::CallbackMsg;
 
 use crate::error::VaultError;
+use crate::execute::flash_loan::perform_flash_loan;
+use crate::execute::process_withdrawal_queue::process_withdrawal_queue;
+use crate::execute::receive::withdraw::perform_withdraw;
 
 pub fn callback(
     deps: DepsMut,
@@ -22,7 +27,32 @@ pub fn callback(
         CallbackMsg::AfterTrade {
             old_balance,
             loan_amount,
-        } => after_trade(deps, env, old_balance, loan_amount),
+            fee_discount,
+        } => after_trade(deps, env, old_balance, loan_amount, fee_discount),
+        CallbackMsg::ProvideStrategyLiquidity {
+            max_amount,
+            swapped_amount,
+        } => provide_strategy_liquidity(deps, env, max_amount, swapped_amount),
+        CallbackMsg::SwapStrategyAssets {
+            liquid_balance,
+            unwound_cost,
+        } => swap_strategy_assets(deps, env, liquid_balance, unwound_cost),
+        CallbackMsg::ContinueFlashLoan {
+            sender,
+            amount,
+            msg,
+            fee_discount,
+        } => {
+            let sender = deps.api.addr_validate(&sender)?;
+            perform_flash_loan(deps, env, sender, amount, msg, fee_discount)
+        }
+        CallbackMsg::ContinueWithdraw { sender, amount } => {
+            let sender = deps.api.addr_validate(&sender)?;
+            perform_withdraw(deps, env, sender, amount)
+        }
+        CallbackMsg::ContinueWithdrawalQueue { limit } => {
+            process_withdrawal_queue(deps, env, limit, false)
+        }
     }
 }
 
@@ -54,6 +84,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(5_000),
                     loan_amount: Uint128::new(2_500),
+                    fee_discount: None,
                 },
             ),
         );
@@ -97,6 +128,7 @@ mod test {
                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                     old_balance: Uint128::new(0),
                     loan_amount: Uint128::new(1_000),
+                    fee_discount: None,
                 },
             ),
             &[],
@@ -0,0 +1,164 @@
+use cosmwasm_std::{CosmosMsg, Decimal, DepsMut, Env, Response, Uint128};
+
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::pool_network::pair::PoolResponse;
+use white_whale_std::pool_network::pair::QueryMsg;
+use white_whale_std::vault_network::vault::StrategyPool;
+
+use crate::strategy::{get_liquid_balance, increase_cost_basis, provide_liquidity_msgs, swap_msg};
+use crate::{
+    error::VaultError,
+    state::{CONFIG, STRATEGY},
+};
+
+/// Provides the strategy assets held by the vault as liquidity to the strategy pool, together
+/// with up to `max_amount` of the vault asset. The `swapped_amount` of vault asset spent on the
+/// provided strategy assets is added to the cost basis of the position.
+pub fn provide_strategy_liquidity(
+    deps: DepsMut,
+    env: Env,
+    max_amount: Uint128,
+    swapped_amount: Uint128,
+) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+    let strategy = STRATEGY
+        .may_load(deps.storage)?
+        .ok_or(VaultError::NoStrategy {})?;
+
+    let mut assets = strategy
+        .pool_assets
+        .iter()
+        .filter(|asset_info| **asset_info != config.asset_info)
+        .map(|asset_info| {
+            Ok(Asset {
+                info: asset_info.clone(),
+                amount: asset_info.query_pool(
+                    &deps.querier,
+                    deps.api,
+                    env.contract.address.clone(),
+                )?,
+            })
+        })
+        .collect::<Result<Vec<_>, VaultError>>()?;
+
+    let mut vault_amount = max_amount;
+    let mut swapped_cost = swapped_amount;
+    if let StrategyPool::Pair { contract_addr } = &strategy.pool {
+        // match the ratio of the pool, otherwise the excess would be donated to the pool
+        let pool: PoolResponse = deps
+            .querier
+            .query_wasm_smart(contract_addr, &QueryMsg::Pool {})?;
+        let find_reserve = |asset: &Asset| {
+            pool.assets
+                .iter()
+                .find(|pool_asset| pool_asset.info == asset.info)
+                .map(|pool_asset| pool_asset.amount)
+                .unwrap_or_default()
+        };
+
+        let other_asset = &mut assets[0];
+        let vault_reserve = find_reserve(&Asset {
+            info: config.asset_info.clone(),
+            amount: Uint128::zero(),
+        });
+        let other_reserve = find_reserve(other_asset);
+
+        if !vault_reserve.is_zero() && !other_reserve.is_zero() {
+            vault_amount = other_asset
+                .amount
+                .multiply_ratio(vault_reserve, other_reserve)
+                .min(max_amount);
+            let provided_amount = other_asset
+                .amount
+                .min(vault_amount.multiply_ratio(other_reserve, vault_reserve));
+
+            // the excess of the other asset stays idle in the vault, so it isn't part of the cost
+            if !other_asset.amount.is_zero() {
+                swapped_cost = swapped_amount.multiply_ratio(provided_amount, other_asset.amount);
+            }
+            other_asset.amount = provided_amount;
+        }
+    }
+
+    if vault_amount.is_zero() || assets.iter().any(|asset| asset.amount.is_zero()) {
+        return Ok(Response::new().add_attributes(vec![
+            ("method", "provide_strategy_liquidity"),
+            ("provided", "false"),
+        ]));
+    }
+
+    assets.push(Asset {
+        info: config.asset_info,
+        amount: vault_amount,
+    });
+
+    let cost_basis = increase_cost_basis(deps.storage, swapped_cost.checked_add(vault_amount)?)?;
+
+    Ok(Response::new()
+        .add_messages(provide_liquidity_msgs(&strategy, assets)?)
+        .add_attributes(vec![
+            ("method", "provide_strategy_liquidity"),
+            ("provided", "true"),
+            ("cost_basis", &cost_basis.to_string()),
+        ]))
+}
+
+/// Swaps the strategy assets held by the vault, other than the vault asset, back into the vault
+/// asset. The vault asset withdrawn from the pool, i.e. the balance on top of `liquid_balance`,
+/// counts towards the `unwound_cost`, and the swaps are expected to return the rest of it.
+pub fn swap_strategy_assets(
+    deps: DepsMut,
+    env: Env,
+    liquid_balance: Uint128,
+    unwound_cost: Uint128,
+) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+    let strategy = STRATEGY
+        .may_load(deps.storage)?
+        .ok_or(VaultError::NoStrategy {})?;
+
+    let mut offer_assets: Vec<Asset> = vec![];
+    for asset_info in strategy
+        .pool_assets
+        .iter()
+        .filter(|asset_info| **asset_info != config.asset_info)
+    {
+        let amount =
+            asset_info.query_pool(&deps.querier, deps.api, env.contract.address.clone())?;
+        if amount.is_zero() {
+            continue;
+        }
+
+        offer_assets.push(Asset {
+            info: asset_info.clone(),
+            amount,
+        });
+    }
+
+    let withdrawn_amount =
+        get_liquid_balance(deps.as_ref(), &env, &config)?.saturating_sub(liquid_balance);
+    // the vault asset was split evenly among the other assets when deploying the position, so
+    // each swap is expected to return an even part of the remaining cost
+    let expected_return = unwound_cost
+        .saturating_sub(withdrawn_amount)
+        .multiply_ratio(1u128, offer_assets.len().max(1) as u128);
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for offer_asset in offer_assets {
+        let belief_price = Some(expected_return)
+            .filter(|expected_return| !expected_return.is_zero())
+            .map(|expected_return| Decimal::from_ratio(offer_asset.amount, expected_return));
+
+        messages.push(swap_msg(
+            &strategy,
+            offer_asset,
+            config.asset_info.clone(),
+            belief_price,
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("method", "swap_strategy_assets"),
+        ("expected_return", &expected_return.to_string()),
+    ]))
+}
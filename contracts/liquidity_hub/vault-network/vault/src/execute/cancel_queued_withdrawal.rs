@@ -0,0 +1,40 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+use white_whale_std::pool_network::asset::Asset;
+
+use crate::{
+    error::VaultError,
+    state::{CONFIG, WITHDRAWAL_QUEUE},
+};
+
+/// Cancels a queued withdrawal of the sender, returning the LP tokens the vault holds for it.
+pub fn cancel_queued_withdrawal(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, VaultError> {
+    let withdrawal = WITHDRAWAL_QUEUE
+        .may_load(deps.storage, id)?
+        .ok_or(VaultError::QueuedWithdrawalNotFound { id })?;
+
+    if withdrawal.sender != info.sender {
+        return Err(VaultError::Unauthorized {});
+    }
+
+    WITHDRAWAL_QUEUE.remove(deps.storage, id);
+
+    let config = CONFIG.load(deps.storage)?;
+    let return_lp_msg = Asset {
+        info: config.lp_asset,
+        amount: withdrawal.lp_amount,
+    }
+    .into_msg(withdrawal.sender)?;
+
+    Ok(Response::new()
+        .add_message(return_lp_msg)
+        .add_attributes(vec![
+            ("method", "cancel_queued_withdrawal"),
+            ("id", &id.to_string()),
+            ("lp_amount", &withdrawal.lp_amount.to_string()),
+        ]))
+}
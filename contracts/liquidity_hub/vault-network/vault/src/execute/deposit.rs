@@ -14,13 +14,15 @@ use white_whale_std::pool_network::denom_injective::{Coin, MsgMint};
 #[cfg(feature = "osmosis_token_factory")]
 use white_whale_std::pool_network::denom_osmosis::{Coin, MsgMint};
 
+use crate::share_price::record_share_price;
+use crate::strategy::get_total_assets;
 use crate::{
     error::VaultError,
-    state::{COLLECTED_PROTOCOL_FEES, CONFIG, LOAN_COUNTER},
+    state::{CONFIG, DEPOSIT_CAP, LOAN_COUNTER},
 };
 
 pub fn deposit(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     amount: Uint128,
@@ -65,6 +67,26 @@ pub fn deposit(
         });
     }
 
+    // If the asset is native token, the balance has already increased in the vault
+    // To calculate it properly we should subtract user deposit from the vault.
+    // If the asset is a cw20 token, the balance has not changed yet so we don't need to subtract it
+    let deposit_amount = match config.asset_info {
+        AssetInfo::NativeToken { .. } => amount,
+        AssetInfo::Token { .. } => Uint128::zero(),
+    };
+
+    record_share_price(deps.branch(), &env, &config, deposit_amount)?;
+
+    // check that the deposit doesn't exceed the deposit cap
+    if let Some(cap) = DEPOSIT_CAP.may_load(deps.storage)? {
+        let total_assets = get_total_assets(deps.as_ref(), &env, &config)?
+            .checked_sub(deposit_amount)?
+            .checked_add(amount)?;
+        if total_assets > cap {
+            return Err(VaultError::DepositCapExceeded { cap, total_assets });
+        }
+    }
+
     let mut messages: Vec<CosmosMsg> = vec![];
     // add cw20 transfer message if needed
     if let AssetInfo::Token { contract_addr } = config.asset_info.clone() {
@@ -113,21 +135,9 @@ pub fn deposit(
 
         share
     } else {
-        // If the asset is native token, the balance has already increased in the vault
-        // To calculate it properly we should subtract user deposit from the vault.
-        // If the asset is a cw20 token, the balance has not changed yet so we don't need to subtract it
-        let deposit_amount = match config.asset_info {
-            AssetInfo::NativeToken { .. } => amount,
-            AssetInfo::Token { .. } => Uint128::zero(),
-        };
-
-        // return based on a share of the total pool
-        let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
-        let total_deposits = config
-            .asset_info
-            .query_pool(&deps.querier, deps.api, env.contract.address.clone())?
-            .checked_sub(collected_protocol_fees.amount)?
-            .checked_sub(deposit_amount)?;
+        // return based on a share of the total pool, including the strategy position
+        let total_deposits =
+            get_total_assets(deps.as_ref(), &env, &config)?.checked_sub(deposit_amount)?;
 
         Uint256::from_uint128(amount)
             .checked_mul(Uint256::from_uint128(total_share))?
@@ -202,7 +212,7 @@ mod test {
     use cw_multi_test::Executor;
 
     use white_whale_std::pool_network::asset::AssetInfo;
-    use white_whale_std::vault_network::vault::Config;
+    use white_whale_std::vault_network::vault::{Config, UpdateConfigParams};
 
     use crate::tests::mock_app::mock_app_with_balance;
     use crate::tests::mock_instantiate::app_mock_instantiate;
@@ -519,6 +529,77 @@ mod test {
         assert_eq!(res.unwrap_err(), VaultError::DepositDuringLoan {});
     }
 
+    #[test]
+    fn cannot_deposit_over_cap() {
+        let mut app = mock_app_with_balance(vec![(mock_creator().sender, coins(100_000, "uluna"))]);
+
+        let vault_addr = app_mock_instantiate(
+            &mut app,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &white_whale_std::vault_network::vault::ExecuteMsg::UpdateConfig(UpdateConfigParams {
+                flash_loan_enabled: None,
+                deposit_enabled: None,
+                withdraw_enabled: None,
+                new_owner: None,
+                new_vault_fees: None,
+                new_fee_collector_addr: None,
+                new_strategy: None,
+                new_deposit_cap: Some(Uint128::new(15_000)),
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &white_whale_std::vault_network::vault::ExecuteMsg::Deposit {
+                amount: Uint128::new(10_000),
+            },
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                mock_creator().sender,
+                vault_addr.clone(),
+                &white_whale_std::vault_network::vault::ExecuteMsg::Deposit {
+                    amount: Uint128::new(6_000),
+                },
+                &coins(6_000, "uluna"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<VaultError>().unwrap(),
+            VaultError::DepositCapExceeded {
+                cap: Uint128::new(15_000),
+                total_assets: Uint128::new(16_000),
+            }
+        );
+
+        // can deposit up to the cap
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr,
+            &white_whale_std::vault_network::vault::ExecuteMsg::Deposit {
+                amount: Uint128::new(5_000),
+            },
+            &coins(5_000, "uluna"),
+        )
+        .unwrap();
+    }
+
     #[test]
     fn does_not_dilute_early_holders() {
         // simulate a vault with first depositor having 10,000 LP tokens
@@ -1,14 +1,19 @@
 use cosmwasm_std::{
-    coins, to_json_binary, Binary, CosmosMsg, DepsMut, Env, MessageInfo, OverflowError, Response,
-    StdError, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, Binary, CosmosMsg, Decimal, DepsMut, Env, MessageInfo,
+    OverflowError, Response, StdError, Uint128, WasmMsg,
 };
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 use white_whale_std::pool_network::asset::AssetInfo;
 use white_whale_std::vault_network::vault::{CallbackMsg, ExecuteMsg};
 
+use crate::fee_rebates::{get_fee_discount, get_loan_initiator};
+use crate::strategy::{
+    callback_msg, get_liquid_balance, get_strategy_position, get_unwind_lp_amount,
+    unwind_strategy_msgs,
+};
 use crate::{
     error::VaultError,
-    state::{CONFIG, LOAN_COUNTER},
+    state::{CONFIG, LOAN_COUNTER, STRATEGY},
 };
 
 pub fn flash_loan(
@@ -17,6 +22,7 @@ pub fn flash_loan(
     info: MessageInfo,
     amount: Uint128,
     msg: Binary,
+    initiator: Option<String>,
 ) -> Result<Response, VaultError> {
     // check that flash loans are enabled
     let config = CONFIG.load(deps.storage)?;
@@ -24,6 +30,65 @@ pub fn flash_loan(
         return Err(VaultError::FlashLoansDisabled {});
     }
 
+    // get the flash-loan fee rebate of the initiator, if any
+    let initiator = get_loan_initiator(deps.as_ref(), &info.sender, initiator)?;
+    let fee_discount = Some(get_fee_discount(deps.as_ref(), &initiator)?)
+        .filter(|fee_discount| !fee_discount.is_zero());
+
+    // if the liquid balance can't cover the loan, unwind the shortfall from the strategy position
+    // first
+    if let Some(strategy) = STRATEGY.may_load(deps.storage)? {
+        let liquid_balance = get_liquid_balance(deps.as_ref(), &env, &config)?;
+        if liquid_balance < amount {
+            let (lp_amount, position_value) = get_strategy_position(deps.as_ref(), &env)?;
+            if !lp_amount.is_zero() {
+                let unwind_lp_amount = get_unwind_lp_amount(
+                    &strategy,
+                    lp_amount,
+                    position_value,
+                    amount.checked_sub(liquid_balance)?,
+                )?;
+                let mut messages = unwind_strategy_msgs(
+                    deps,
+                    &env,
+                    &config,
+                    &strategy,
+                    unwind_lp_amount,
+                    lp_amount,
+                )?;
+                messages.push(callback_msg(
+                    &env,
+                    CallbackMsg::ContinueFlashLoan {
+                        sender: info.sender.into_string(),
+                        amount,
+                        msg,
+                        fee_discount,
+                    },
+                )?);
+
+                return Ok(Response::new().add_messages(messages).add_attributes(vec![
+                    ("method", "flash_loan"),
+                    ("amount", &amount.to_string()),
+                    ("unwind_strategy", &unwind_lp_amount.to_string()),
+                ]));
+            }
+        }
+    }
+
+    perform_flash_loan(deps, env, info.sender, amount, msg, fee_discount)
+}
+
+/// Lends the given amount to `sender`, calling back `msg` and checking the profit afterwards.
+pub fn perform_flash_loan(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    amount: Uint128,
+    msg: Binary,
+    fee_discount: Option<Decimal>,
+) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+
     // increment loan counter
     LOAN_COUNTER.update::<_, StdError>(deps.storage, |c| {
         Ok(c.checked_add(1)
@@ -55,7 +120,7 @@ pub fn flash_loan(
         let loan_msg = WasmMsg::Execute {
             contract_addr,
             msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.clone().into_string(),
+                recipient: sender.clone().into_string(),
                 amount,
             })?,
             funds: vec![],
@@ -73,7 +138,7 @@ pub fn flash_loan(
     // add callback msg to messages
     messages.push(
         WasmMsg::Execute {
-            contract_addr: info.sender.into_string(),
+            contract_addr: sender.into_string(),
             msg,
             funds: callback_funds,
         }
@@ -87,6 +152,7 @@ pub fn flash_loan(
             msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::AfterTrade {
                 old_balance,
                 loan_amount: amount,
+                fee_discount,
             }))?,
             funds: vec![],
         }
@@ -148,6 +214,7 @@ mod test {
             white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                 amount: Uint128::new(5_000),
                 msg: to_json_binary(&BankMsg::Burn { amount: vec![] }).unwrap(),
+                initiator: None,
             },
         );
 
@@ -188,6 +255,7 @@ mod test {
             white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                 amount: Uint128::new(5_000),
                 msg: callback_msg,
+                initiator: None,
             },
         )
         .unwrap();
@@ -227,6 +295,7 @@ mod test {
             white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                 amount: Uint128::new(5_000),
                 msg: callback_msg.clone(),
+                initiator: None,
             },
         );
 
@@ -248,7 +317,8 @@ mod test {
                             &white_whale_std::vault_network::vault::ExecuteMsg::Callback(
                                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                                     old_balance: Uint128::new(10_000),
-                                    loan_amount: Uint128::new(5_000)
+                                    loan_amount: Uint128::new(5_000),
+                                    fee_discount: None,
                                 }
                             )
                         )
@@ -303,6 +373,7 @@ mod test {
             white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                 amount: Uint128::new(5_000),
                 msg: callback_msg.clone(),
+                initiator: None,
             },
         );
 
@@ -333,7 +404,8 @@ mod test {
                             &white_whale_std::vault_network::vault::ExecuteMsg::Callback(
                                 white_whale_std::vault_network::vault::CallbackMsg::AfterTrade {
                                     old_balance: Uint128::new(10_000),
-                                    loan_amount: Uint128::new(5_000)
+                                    loan_amount: Uint128::new(5_000),
+                                    fee_discount: None,
                                 }
                             )
                         )
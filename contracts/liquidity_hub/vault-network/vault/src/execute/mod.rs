@@ -1,13 +1,19 @@
 mod callback;
+mod cancel_queued_withdrawal;
 mod collect_protocol_fee;
 mod deposit;
 mod flash_loan;
+pub(crate) mod process_withdrawal_queue;
+mod rebalance;
 pub(crate) mod receive;
 mod update_config;
 
 pub use callback::callback;
+pub use cancel_queued_withdrawal::cancel_queued_withdrawal;
 pub use collect_protocol_fee::collect_protocol_fees;
 pub use deposit::deposit;
 pub use flash_loan::flash_loan;
+pub use process_withdrawal_queue::process_withdrawal_queue;
+pub use rebalance::rebalance;
 pub use receive::receive;
 pub use update_config::update_config;
@@ -0,0 +1,414 @@
+use cosmwasm_std::{CosmosMsg, Decimal, DepsMut, Env, Order, Response, StdResult, Uint128};
+
+use white_whale_std::vault_network::vault::{CallbackMsg, QueuedWithdrawal};
+
+use crate::execute::receive::withdraw::withdraw_msgs;
+use crate::share_price::get_vault_size;
+use crate::strategy::{
+    callback_msg, get_liquid_balance, get_strategy_position, get_unwind_lp_amount,
+    unwind_strategy_msgs,
+};
+use crate::{
+    error::VaultError,
+    state::{COLLECTED_PROTOCOL_FEES, CONFIG, LOAN_COUNTER, STRATEGY, WITHDRAWAL_QUEUE},
+};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Pays out the queued withdrawals in FIFO order, stopping at the first withdrawal the liquid
+/// balance of the vault can't cover. In that case, if `unwind_strategy` is set, the shortfall is
+/// unwound from the strategy position and the queue processed again once the unwound assets are
+/// liquid.
+pub fn process_withdrawal_queue(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+    unwind_strategy: bool,
+) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // check that withdrawals are enabled
+    if !config.withdraw_enabled {
+        return Err(VaultError::WithdrawsDisabled {});
+    }
+
+    // check that we are not currently in a flash-loan, as the loaned amount would be missing
+    // from the vault
+    if LOAN_COUNTER.load(deps.storage)? != 0 {
+        return Err(VaultError::ProcessQueueDuringLoan {});
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let queued_withdrawals = WITHDRAWAL_QUEUE
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<QueuedWithdrawal>>>()?;
+
+    let (mut total_share, mut total_assets) =
+        get_vault_size(deps.as_ref(), &env, &config, Uint128::zero())?;
+    let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
+    let mut liquid_balance = get_liquid_balance(deps.as_ref(), &env, &config)?
+        .saturating_sub(collected_protocol_fees.amount);
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut processed = 0u64;
+    let mut shortfall = Uint128::zero();
+    for withdrawal in queued_withdrawals {
+        let withdraw_amount = Decimal::from_ratio(withdrawal.lp_amount, total_share) * total_assets;
+        if withdraw_amount > liquid_balance {
+            shortfall = withdraw_amount.checked_sub(liquid_balance)?;
+            break;
+        }
+
+        messages.append(&mut withdraw_msgs(
+            &env,
+            &config,
+            withdrawal.sender,
+            withdrawal.lp_amount,
+            withdraw_amount,
+        )?);
+        WITHDRAWAL_QUEUE.remove(deps.storage, withdrawal.id);
+
+        // the payout messages are executed after this, so keep track of the vault size here
+        liquid_balance = liquid_balance.checked_sub(withdraw_amount)?;
+        total_assets = total_assets.checked_sub(withdraw_amount)?;
+        total_share = total_share.checked_sub(withdrawal.lp_amount)?;
+        processed += 1;
+    }
+
+    let mut attributes = vec![
+        ("method", "process_withdrawal_queue".to_string()),
+        ("processed", processed.to_string()),
+    ];
+
+    // unwind the shortfall of the withdrawal the liquid balance can't cover from the strategy
+    // position
+    if !shortfall.is_zero() && unwind_strategy {
+        if let Some(strategy) = STRATEGY.may_load(deps.storage)? {
+            let (lp_amount, position_value) = get_strategy_position(deps.as_ref(), &env)?;
+            if !lp_amount.is_zero() {
+                let unwind_lp_amount =
+                    get_unwind_lp_amount(&strategy, lp_amount, position_value, shortfall)?;
+                // the unwind looks at the balance of the vault asset, so it goes before the
+                // payouts
+                let mut unwind_messages = unwind_strategy_msgs(
+                    deps,
+                    &env,
+                    &config,
+                    &strategy,
+                    unwind_lp_amount,
+                    lp_amount,
+                )?;
+                unwind_messages.append(&mut messages);
+                messages = unwind_messages;
+                messages.push(callback_msg(
+                    &env,
+                    CallbackMsg::ContinueWithdrawalQueue {
+                        limit: Some(limit as u32),
+                    },
+                )?);
+                attributes.push(("unwind_strategy", unwind_lp_amount.to_string()));
+            }
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    use white_whale_std::pool_network::asset::AssetInfo;
+    use white_whale_std::vault_network::vault::{
+        Config, Cw20HookMsg, ExecuteMsg, QueryMsg, StrategyParams, StrategyPool, StrategyResponse,
+        UpdateConfigParams, WithdrawalQueuePositionResponse, WithdrawalQueueResponse,
+    };
+
+    use crate::error::VaultError;
+    use crate::tests::{
+        mock_app::mock_app_with_balance,
+        mock_creator,
+        mock_instantiate::{app_mock_instantiate, app_mock_instantiate_pair},
+    };
+
+    fn update_config(app: &mut App, vault_addr: &Addr, params: UpdateConfigParams) {
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::UpdateConfig(params),
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn withdraw(app: &mut App, vault_addr: &Addr, sender: &Addr, amount: u128) {
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(vault_addr, &QueryMsg::Config {})
+            .unwrap();
+        let AssetInfo::Token { contract_addr } = config.lp_asset else {
+            panic!("expected a cw20 lp token")
+        };
+
+        app.execute_contract(
+            sender.clone(),
+            Addr::unchecked(contract_addr),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: vault_addr.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn can_queue_and_process_withdrawals_in_order() {
+        let other = Addr::unchecked("other");
+        let mut app = mock_app_with_balance(vec![
+            (
+                mock_creator().sender,
+                vec![coin(10_000_000, "uluna"), coin(10_000_000, "uwhale")],
+            ),
+            (other.clone(), coins(10_000, "uluna")),
+        ]);
+
+        let pair_addr = app_mock_instantiate_pair(&mut app, ["uluna", "uwhale"], 1_000_000);
+        let vault_addr = app_mock_instantiate(
+            &mut app,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        );
+
+        for (sender, amount) in [
+            (mock_creator().sender, 100_000u128),
+            (other.clone(), 10_000),
+        ] {
+            app.execute_contract(
+                sender,
+                vault_addr.clone(),
+                &ExecuteMsg::Deposit {
+                    amount: Uint128::new(amount),
+                },
+                &coins(amount, "uluna"),
+            )
+            .unwrap();
+        }
+
+        // deploy most of the vault
+        let strategy_params = |deploy_share| UpdateConfigParams {
+            flash_loan_enabled: None,
+            deposit_enabled: None,
+            withdraw_enabled: None,
+            new_owner: None,
+            new_fee_collector_addr: None,
+            new_vault_fees: None,
+            new_strategy: Some(StrategyParams {
+                pool: StrategyPool::Pair {
+                    contract_addr: pair_addr.to_string(),
+                },
+                deploy_share,
+                max_spread: Some(Decimal::percent(10)),
+            }),
+            new_deposit_cap: None,
+            withdrawal_queue_enabled: Some(true),
+            new_fee_rebates: None,
+            new_epoch_manager_addr: None,
+        };
+        update_config(&mut app, &vault_addr, strategy_params(Decimal::percent(80)));
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::Rebalance {},
+            &[],
+        )
+        .unwrap();
+
+        // the first withdrawal exceeds the liquid balance, the others queue behind it
+        withdraw(&mut app, &vault_addr, &mock_creator().sender, 90_000);
+        withdraw(&mut app, &vault_addr, &other, 1_000);
+        withdraw(&mut app, &vault_addr, &other, 500);
+
+        let query_queue = |app: &App| -> WithdrawalQueueResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &vault_addr,
+                    &QueryMsg::WithdrawalQueue {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+        let query_position_page = |app: &App,
+                                   start_after: Option<u64>,
+                                   limit: Option<u32>|
+         -> WithdrawalQueuePositionResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    &vault_addr,
+                    &QueryMsg::WithdrawalQueuePosition {
+                        address: other.to_string(),
+                        start_after,
+                        limit,
+                    },
+                )
+                .unwrap()
+        };
+        let query_position = |app: &App| query_position_page(app, None, None);
+
+        let queue = query_queue(&app);
+        assert!(queue.enabled);
+        assert_eq!(queue.withdrawals.len(), 3);
+        assert_eq!(queue.withdrawals[0].sender, mock_creator().sender);
+
+        let position = query_position(&app);
+        assert_eq!(position.withdrawals.len(), 2);
+        assert_eq!(position.withdrawals[0].position, 1);
+        assert_eq!(
+            position.withdrawals[0].withdrawal.lp_amount,
+            Uint128::new(1_000)
+        );
+        assert_eq!(position.withdrawals[1].position, 2);
+        assert_eq!(position.next_start_after, None);
+
+        // the position query pages through the queue, counting positions from `start_after`
+        let page = query_position_page(&app, None, Some(1));
+        assert!(page.withdrawals.is_empty());
+        assert_eq!(page.next_start_after, Some(0));
+        let page = query_position_page(&app, page.next_start_after, Some(1));
+        assert_eq!(page.withdrawals.len(), 1);
+        assert_eq!(page.withdrawals[0].withdrawal.id, 1);
+        assert_eq!(page.withdrawals[0].position, 0);
+        assert_eq!(page.next_start_after, Some(1));
+
+        // only the sender of a queued withdrawal can cancel it, getting the LP tokens back
+        let err = app
+            .execute_contract(
+                Addr::unchecked("anyone"),
+                vault_addr.clone(),
+                &ExecuteMsg::CancelQueuedWithdrawal { id: 1 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<VaultError>().unwrap(),
+            VaultError::Unauthorized {}
+        );
+
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(&vault_addr, &QueryMsg::Config {})
+            .unwrap();
+        let AssetInfo::Token {
+            contract_addr: lp_token,
+        } = config.lp_asset
+        else {
+            panic!("expected a cw20 lp token")
+        };
+        let lp_balance = |app: &App| -> Uint128 {
+            let res: cw20::BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &lp_token,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: other.to_string(),
+                    },
+                )
+                .unwrap();
+            res.balance
+        };
+
+        let lp_balance_before = lp_balance(&app);
+        app.execute_contract(
+            other.clone(),
+            vault_addr.clone(),
+            &ExecuteMsg::CancelQueuedWithdrawal { id: 1 },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(lp_balance(&app) - lp_balance_before, Uint128::new(1_000));
+
+        let err = app
+            .execute_contract(
+                other.clone(),
+                vault_addr.clone(),
+                &ExecuteMsg::CancelQueuedWithdrawal { id: 1 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<VaultError>().unwrap(),
+            VaultError::QueuedWithdrawalNotFound { id: 1 }
+        );
+
+        // the position accounts for the cancelled withdrawal
+        let position = query_position(&app);
+        assert_eq!(position.withdrawals.len(), 1);
+        assert_eq!(position.withdrawals[0].withdrawal.id, 2);
+        assert_eq!(position.withdrawals[0].position, 1);
+
+        // the liquid balance can't cover the front of the queue, and only the owner can make the
+        // vault unwind its strategy position
+        let strategy_before: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(&vault_addr, &QueryMsg::Strategy {})
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked("anyone"),
+            vault_addr.clone(),
+            &ExecuteMsg::ProcessWithdrawalQueue { limit: None },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(query_queue(&app).withdrawals.len(), 2);
+        let strategy: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(&vault_addr, &QueryMsg::Strategy {})
+            .unwrap();
+        assert_eq!(strategy.lp_amount, strategy_before.lp_amount);
+
+        // the owner unwinds the shortfall of the front of the queue to pay out the queue
+        let creator_balance_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        let other_balance_before = app.wrap().query_balance(&other, "uluna").unwrap().amount;
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::ProcessWithdrawalQueue { limit: None },
+            &[],
+        )
+        .unwrap();
+
+        assert!(query_queue(&app).withdrawals.is_empty());
+
+        // the rest of the strategy position stays deployed
+        let strategy: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(&vault_addr, &QueryMsg::Strategy {})
+            .unwrap();
+        assert!(!strategy.lp_amount.is_zero());
+        assert!(strategy.lp_amount < strategy_before.lp_amount);
+
+        let creator_balance_after = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        assert!(creator_balance_after - creator_balance_before > Uint128::new(85_000));
+        let other_balance_after = app.wrap().query_balance(&other, "uluna").unwrap().amount;
+        assert!(other_balance_after - other_balance_before > Uint128::new(475));
+    }
+}
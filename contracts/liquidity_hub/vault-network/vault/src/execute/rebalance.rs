@@ -0,0 +1,350 @@
+use cosmwasm_std::{CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::vault_network::vault::{CallbackMsg, StrategyPool};
+
+use crate::strategy::{
+    callback_msg, get_liquid_balance, get_strategy_position, swap_msg, unwind_strategy_msgs,
+};
+use crate::{
+    error::VaultError,
+    state::{COLLECTED_PROTOCOL_FEES, CONFIG, LOAN_COUNTER, STRATEGY, WITHDRAWAL_QUEUE},
+};
+
+/// Deploys or unwinds the idle liquidity of the vault, so that the value of the strategy position
+/// matches the strategy's `deploy_share` of the total assets of the vault. Only the owner can
+/// rebalance, as the swaps and the provided liquidity are exposed to the price of the pool.
+pub fn rebalance(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(VaultError::Unauthorized {});
+    }
+
+    // check that we are not currently in a flash-loan
+    if LOAN_COUNTER.load(deps.storage)? != 0 {
+        return Err(VaultError::RebalanceDuringLoan {});
+    }
+
+    let strategy = STRATEGY
+        .may_load(deps.storage)?
+        .ok_or(VaultError::NoStrategy {})?;
+
+    let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
+    let liquid_balance = get_liquid_balance(deps.as_ref(), &env, &config)?
+        .saturating_sub(collected_protocol_fees.amount);
+    let (lp_amount, position_value) = get_strategy_position(deps.as_ref(), &env)?;
+
+    let target_value = liquid_balance.checked_add(position_value)? * strategy.deploy_share;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut action = "none";
+
+    // don't deploy liquidity the queued withdrawals are waiting for
+    if position_value < target_value && WITHDRAWAL_QUEUE.is_empty(deps.storage) {
+        let deploy_amount = target_value
+            .checked_sub(position_value)?
+            .min(liquid_balance);
+
+        // the vault asset is split evenly among the assets of the pool, swapping the share of
+        // the other assets before providing the liquidity
+        let split = match strategy.pool {
+            StrategyPool::Pair { .. } => Decimal::percent(50),
+            StrategyPool::Trio { .. } => Decimal::from_ratio(1u128, 3u128),
+        };
+        let swap_amount = deploy_amount * split;
+
+        if !swap_amount.is_zero() {
+            let mut swapped_amount = Uint128::zero();
+            for ask_asset in strategy
+                .pool_assets
+                .iter()
+                .filter(|asset_info| **asset_info != config.asset_info)
+            {
+                messages.push(swap_msg(
+                    &strategy,
+                    Asset {
+                        info: config.asset_info.clone(),
+                        amount: swap_amount,
+                    },
+                    ask_asset.clone(),
+                    None,
+                )?);
+                swapped_amount = swapped_amount.checked_add(swap_amount)?;
+            }
+
+            messages.push(callback_msg(
+                &env,
+                CallbackMsg::ProvideStrategyLiquidity {
+                    max_amount: deploy_amount.checked_sub(swapped_amount)?,
+                    swapped_amount,
+                },
+            )?);
+            action = "deploy";
+        }
+    } else if position_value > target_value {
+        // withdraw the share of the position exceeding the target
+        let withdraw_lp_amount = if target_value.is_zero() {
+            lp_amount
+        } else {
+            lp_amount.multiply_ratio(position_value.checked_sub(target_value)?, position_value)
+        };
+
+        if !withdraw_lp_amount.is_zero() {
+            messages.append(&mut unwind_strategy_msgs(
+                deps,
+                &env,
+                &config,
+                &strategy,
+                withdraw_lp_amount,
+                lp_amount,
+            )?);
+            action = "unwind";
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("method", "rebalance"),
+        ("action", action),
+        ("position_value", &position_value.to_string()),
+        ("target_value", &target_value.to_string()),
+    ]))
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Uint128};
+    use cw_multi_test::Executor;
+
+    use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+    use white_whale_std::vault_network::vault::{
+        Config, Cw20HookMsg, ExecuteMsg, QueryMsg, StrategyParams, StrategyPool, StrategyResponse,
+        UpdateConfigParams,
+    };
+
+    use crate::{
+        error::VaultError,
+        tests::{
+            mock_app::mock_app_with_balance,
+            mock_creator, mock_execute,
+            mock_instantiate::{app_mock_instantiate, app_mock_instantiate_pair},
+        },
+    };
+
+    #[test]
+    fn does_fail_without_strategy() {
+        let (res, ..) = mock_execute(
+            5,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            false,
+            ExecuteMsg::Rebalance {},
+        );
+
+        assert_eq!(res.unwrap_err(), VaultError::NoStrategy {});
+    }
+
+    #[test]
+    fn can_deploy_and_unwind_strategy() {
+        let mut app = mock_app_with_balance(vec![(
+            mock_creator().sender,
+            vec![coin(10_000_000, "uluna"), coin(10_000_000, "uwhale")],
+        )]);
+
+        // create a uluna-uwhale pair with some liquidity
+        let pair_addr = app_mock_instantiate_pair(&mut app, ["uluna", "uwhale"], 1_000_000);
+
+        // create the vault and deposit into it
+        let vault_addr = app_mock_instantiate(
+            &mut app,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        );
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::Deposit {
+                amount: Uint128::new(100_000),
+            },
+            &coins(100_000, "uluna"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::UpdateConfig(UpdateConfigParams {
+                flash_loan_enabled: None,
+                deposit_enabled: None,
+                withdraw_enabled: None,
+                new_owner: None,
+                new_fee_collector_addr: None,
+                new_vault_fees: None,
+                new_strategy: Some(StrategyParams {
+                    pool: StrategyPool::Pair {
+                        contract_addr: pair_addr.to_string(),
+                    },
+                    deploy_share: Decimal::percent(50),
+                    max_spread: Some(Decimal::percent(10)),
+                }),
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+        // only the owner can rebalance
+        let err = app
+            .execute_contract(
+                Addr::unchecked("anyone"),
+                vault_addr.clone(),
+                &ExecuteMsg::Rebalance {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<VaultError>().unwrap(),
+            VaultError::Unauthorized {}
+        );
+
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::Rebalance {},
+            &[],
+        )
+        .unwrap();
+
+        let strategy: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(vault_addr.clone(), &QueryMsg::Strategy {})
+            .unwrap();
+        assert!(!strategy.lp_amount.is_zero());
+        // half of the vault is deployed, valued at the reserves of the pool
+        assert!(strategy.value > Uint128::new(45_000) && strategy.value <= Uint128::new(50_000));
+
+        // dumping the other asset of the pool prices the loss into the position
+        let swap = |app: &mut cw_multi_test::App, offer_denom: &str, amount: Uint128| {
+            app.execute_contract(
+                mock_creator().sender,
+                pair_addr.clone(),
+                &white_whale_std::pool_network::pair::ExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: offer_denom.to_string(),
+                        },
+                        amount,
+                    },
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(50)),
+                    to: None,
+                },
+                &coins(amount.u128(), offer_denom),
+            )
+            .unwrap();
+        };
+
+        let uluna_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        swap(&mut app, "uwhale", Uint128::new(100_000));
+        let moved_strategy: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(vault_addr.clone(), &QueryMsg::Strategy {})
+            .unwrap();
+        assert!(moved_strategy.value < strategy.value);
+
+        // move the price back
+        let uluna_after = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        swap(&mut app, "uluna", uluna_after - uluna_before);
+
+        let liquid_balance = app
+            .wrap()
+            .query_balance(vault_addr.clone(), "uluna")
+            .unwrap()
+            .amount;
+        assert!(liquid_balance >= Uint128::new(50_000));
+
+        // withdrawing more than the liquid balance unwinds the strategy position
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(vault_addr.clone(), &QueryMsg::Config {})
+            .unwrap();
+        let lp_token = match config.lp_asset {
+            AssetInfo::Token { contract_addr } => contract_addr,
+            AssetInfo::NativeToken { .. } => panic!("expected a cw20 lp token"),
+        };
+
+        let withdraw = |app: &mut cw_multi_test::App| {
+            app.execute_contract(
+                mock_creator().sender,
+                Addr::unchecked(lp_token.clone()),
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: vault_addr.to_string(),
+                    amount: Uint128::new(99_000),
+                    msg: to_json_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+                },
+                &[],
+            )
+        };
+
+        // the unwind can't be sandwiched, as it must return the cost basis of the unwound
+        // position within the max spread
+        let uluna_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        swap(&mut app, "uwhale", Uint128::new(300_000));
+        let err = withdraw(&mut app).unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("Spread limit exceeded"));
+        let uluna_after = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        swap(&mut app, "uluna", uluna_after - uluna_before);
+
+        let balance_before = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+
+        withdraw(&mut app).unwrap();
+
+        let strategy: StrategyResponse = app
+            .wrap()
+            .query_wasm_smart(vault_addr.clone(), &QueryMsg::Strategy {})
+            .unwrap();
+        assert!(strategy.lp_amount.is_zero());
+
+        let balance_after = app
+            .wrap()
+            .query_balance(mock_creator().sender, "uluna")
+            .unwrap()
+            .amount;
+        assert!(balance_after - balance_before > Uint128::new(95_000));
+
+        // nothing but dust is left in the vault besides the share of the minimum liquidity
+        let vault_balance = app
+            .wrap()
+            .query_balance(vault_addr, "uwhale")
+            .unwrap()
+            .amount;
+        assert!(vault_balance < Uint128::new(10));
+    }
+}
@@ -1,7 +1,8 @@
 use cosmwasm_std::{
-    coins, to_json_binary, BankMsg, CosmosMsg, Decimal, DepsMut, Env, Response, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Env, Response,
+    Uint128, WasmMsg,
 };
-use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20::Cw20ExecuteMsg;
 
 #[cfg(any(feature = "osmosis_token_factory", feature = "injective"))]
 use white_whale_std::pool_network::asset::is_factory_token;
@@ -11,11 +12,21 @@ use white_whale_std::pool_network::denom_injective::{Coin, MsgBurn};
 #[cfg(feature = "osmosis_token_factory")]
 use white_whale_std::pool_network::denom_osmosis::{Coin, MsgBurn};
 
-use crate::state::COLLECTED_PROTOCOL_FEES;
+use white_whale_std::vault_network::vault::{CallbackMsg, Config, QueuedWithdrawal};
+
+use crate::share_price::record_share_price;
+use crate::state::{
+    COLLECTED_PROTOCOL_FEES, STRATEGY, WITHDRAWAL_QUEUE, WITHDRAWAL_QUEUE_ENABLED,
+    WITHDRAWAL_QUEUE_NEXT_ID,
+};
+use crate::strategy::{
+    callback_msg, get_liquid_balance, get_strategy_position, get_total_assets,
+    get_unwind_lp_amount, unwind_strategy_msgs,
+};
 use crate::{error::VaultError, state::CONFIG};
 
 pub fn withdraw(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     sender: String,
     amount: Uint128,
@@ -30,45 +41,133 @@ pub fn withdraw(
     // parse sender
     let sender = deps.api.addr_validate(&sender)?;
 
-    // calculate the size of vault and the amount of assets to withdraw
-    let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
-    let total_asset_amount = match &config.asset_info {
-        AssetInfo::NativeToken { denom } => {
-            deps.querier
-                .query_balance(env.contract.address.clone(), denom)?
-                .amount
-        }
-        AssetInfo::Token { contract_addr } => {
-            let balance: BalanceResponse = deps.querier.query_wasm_smart(
-                contract_addr,
-                &Cw20QueryMsg::Balance {
-                    address: env.contract.address.clone().into_string(),
+    record_share_price(deps.branch(), &env, &config, Uint128::zero())?;
+
+    // queue the withdrawal if the liquid balance can't cover it, or other withdrawals are
+    // already waiting to be paid
+    if WITHDRAWAL_QUEUE_ENABLED
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+    {
+        let withdraw_amount = get_withdraw_amount(deps.as_ref(), &env, &config, amount)?;
+        let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
+        let liquid_balance = get_liquid_balance(deps.as_ref(), &env, &config)?
+            .saturating_sub(collected_protocol_fees.amount);
+        let queue_is_empty = WITHDRAWAL_QUEUE.is_empty(deps.storage);
+
+        if !queue_is_empty || liquid_balance < withdraw_amount {
+            let id = WITHDRAWAL_QUEUE_NEXT_ID
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            WITHDRAWAL_QUEUE_NEXT_ID.save(deps.storage, &(id + 1))?;
+            WITHDRAWAL_QUEUE.save(
+                deps.storage,
+                id,
+                &QueuedWithdrawal {
+                    id,
+                    sender,
+                    lp_amount: amount,
+                    queued_at: env.block.time,
                 },
             )?;
-            balance.balance
+
+            return Ok(Response::new().add_attributes(vec![
+                ("method", "withdraw"),
+                ("lp_amount", &amount.to_string()),
+                ("queued_withdrawal_id", &id.to_string()),
+            ]));
         }
-    } // deduct protocol fees
-    .checked_sub(collected_protocol_fees.amount)?;
+    }
 
-    let liquidity_asset = match config.lp_asset {
-        AssetInfo::Token { contract_addr } => contract_addr,
-        AssetInfo::NativeToken { denom } => denom,
-    };
+    // if the liquid balance can't cover the withdrawal, unwind the shortfall from the strategy
+    // position first
+    if let Some(strategy) = STRATEGY.may_load(deps.storage)? {
+        let withdraw_amount = get_withdraw_amount(deps.as_ref(), &env, &config, amount)?;
+        let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
+        let liquid_balance = get_liquid_balance(deps.as_ref(), &env, &config)?
+            .saturating_sub(collected_protocol_fees.amount);
+
+        if liquid_balance < withdraw_amount {
+            let (lp_amount, position_value) = get_strategy_position(deps.as_ref(), &env)?;
+            if !lp_amount.is_zero() {
+                let unwind_lp_amount = get_unwind_lp_amount(
+                    &strategy,
+                    lp_amount,
+                    position_value,
+                    withdraw_amount.checked_sub(liquid_balance)?,
+                )?;
+                let mut messages = unwind_strategy_msgs(
+                    deps,
+                    &env,
+                    &config,
+                    &strategy,
+                    unwind_lp_amount,
+                    lp_amount,
+                )?;
+                messages.push(callback_msg(
+                    &env,
+                    CallbackMsg::ContinueWithdraw {
+                        sender: sender.into_string(),
+                        amount,
+                    },
+                )?);
 
-    let total_share = get_total_share(&deps.as_ref(), liquidity_asset.clone())?;
+                return Ok(Response::new().add_messages(messages).add_attributes(vec![
+                    ("method", "withdraw"),
+                    ("lp_amount", &amount.to_string()),
+                    ("unwind_strategy", &unwind_lp_amount.to_string()),
+                ]));
+            }
+        }
+    }
+
+    perform_withdraw(deps, env, sender, amount)
+}
 
-    let withdraw_amount = Decimal::from_ratio(amount, total_share) * total_asset_amount;
+/// Pays out the share of the vault assets corresponding to the given amount of LP tokens to
+/// `sender`, burning the LP tokens.
+pub fn perform_withdraw(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+    let withdraw_amount = get_withdraw_amount(deps.as_ref(), &env, &config, amount)?;
+
+    let messages = withdraw_msgs(&env, &config, sender, amount, withdraw_amount)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("method", "withdraw"),
+        ("lp_amount", &amount.to_string()),
+        ("asset_amount", &withdraw_amount.to_string()),
+    ]))
+}
+
+/// Creates the messages to pay out `withdraw_amount` to `sender`, burning `lp_amount` of the LP
+/// tokens held by the vault.
+pub(crate) fn withdraw_msgs(
+    env: &Env,
+    config: &Config,
+    sender: Addr,
+    lp_amount: Uint128,
+    withdraw_amount: Uint128,
+) -> Result<Vec<CosmosMsg>, VaultError> {
+    let liquidity_asset = match &config.lp_asset {
+        AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+        AssetInfo::NativeToken { denom } => denom.to_string(),
+    };
 
     // create message to send back to user if cw20
-    let messages: Vec<CosmosMsg> = vec![
-        match config.asset_info {
+    Ok(vec![
+        match &config.asset_info {
             AssetInfo::NativeToken { denom } => BankMsg::Send {
                 to_address: sender.into_string(),
                 amount: coins(withdraw_amount.u128(), denom),
             }
             .into(),
             AssetInfo::Token { contract_addr } => WasmMsg::Execute {
-                contract_addr,
+                contract_addr: contract_addr.to_string(),
                 msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
                     recipient: sender.into_string(),
                     amount: withdraw_amount,
@@ -77,14 +176,27 @@ pub fn withdraw(
             }
             .into(),
         },
-        burn_lp_asset_msg(liquidity_asset, env.contract.address.to_string(), amount)?,
-    ];
+        burn_lp_asset_msg(liquidity_asset, env.contract.address.to_string(), lp_amount)?,
+    ])
+}
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("method", "withdraw"),
-        ("lp_amount", &amount.to_string()),
-        ("asset_amount", &withdraw_amount.to_string()),
-    ]))
+/// Calculates the amount of assets corresponding to the given amount of LP tokens.
+fn get_withdraw_amount(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    amount: Uint128,
+) -> Result<Uint128, VaultError> {
+    // calculate the size of vault, deducting protocol fees
+    let total_asset_amount = get_total_assets(deps, env, config)?;
+
+    let liquidity_asset = match &config.lp_asset {
+        AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+        AssetInfo::NativeToken { denom } => denom.to_string(),
+    };
+    let total_share = get_total_share(&deps, liquidity_asset)?;
+
+    Ok(Decimal::from_ratio(amount, total_share) * total_asset_amount)
 }
 
 /// Creates the Burn LP message
@@ -204,6 +316,11 @@ mod tests {
                 new_owner: None,
                 new_fee_collector_addr: None,
                 new_vault_fees: None,
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
             }),
         );
 
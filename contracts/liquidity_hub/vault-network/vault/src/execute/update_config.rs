@@ -1,12 +1,21 @@
-use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response};
+use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Response};
 
-use white_whale_std::pool_network::asset::has_factory_token;
-use white_whale_std::vault_network::vault::UpdateConfigParams;
+use white_whale_std::pool_network::asset::{has_factory_token, PairInfo, TrioInfo};
+use white_whale_std::pool_network::{pair, trio};
+use white_whale_std::vault_network::vault::{
+    Strategy, StrategyParams, StrategyPool, UpdateConfigParams,
+};
 
-use crate::{error::VaultError, state::CONFIG};
+use crate::fee_rebates::validate_fee_rebates;
+use crate::strategy::get_strategy_position;
+use crate::{
+    error::VaultError,
+    state::{CONFIG, DEPOSIT_CAP, EPOCH_MANAGER, FEE_REBATES, STRATEGY, WITHDRAWAL_QUEUE_ENABLED},
+};
 
 pub fn update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     params: UpdateConfigParams,
 ) -> Result<Response, VaultError> {
@@ -17,6 +26,11 @@ pub fn update_config(
         new_owner,
         new_fee_collector_addr,
         new_vault_fees,
+        new_strategy,
+        new_deposit_cap,
+        withdrawal_queue_enabled,
+        new_fee_rebates,
+        new_epoch_manager_addr,
     } = params;
 
     let mut config = CONFIG.load(deps.storage)?;
@@ -53,6 +67,47 @@ pub fn update_config(
         config.fees = new_fees;
     }
 
+    if let Some(new_strategy) = new_strategy {
+        let strategy = validate_strategy(&deps, &config, new_strategy)?;
+
+        // the pool can't be changed while liquidity is provided to the current one
+        if let Some(current_strategy) = STRATEGY.may_load(deps.storage)? {
+            if current_strategy.pool != strategy.pool {
+                let (lp_amount, _) = get_strategy_position(deps.as_ref(), &env)?;
+                if !lp_amount.is_zero() {
+                    return Err(VaultError::StrategyPositionOpen {});
+                }
+            }
+        }
+
+        STRATEGY.save(deps.storage, &strategy)?;
+    }
+
+    if let Some(new_deposit_cap) = new_deposit_cap {
+        if new_deposit_cap.is_zero() {
+            DEPOSIT_CAP.remove(deps.storage);
+        } else {
+            DEPOSIT_CAP.save(deps.storage, &new_deposit_cap)?;
+        }
+    }
+    if let Some(withdrawal_queue_enabled) = withdrawal_queue_enabled {
+        WITHDRAWAL_QUEUE_ENABLED.save(deps.storage, &withdrawal_queue_enabled)?;
+    }
+
+    if let Some(new_fee_rebates) = new_fee_rebates {
+        match validate_fee_rebates(deps.as_ref(), new_fee_rebates)? {
+            Some(fee_rebates) => FEE_REBATES.save(deps.storage, &fee_rebates)?,
+            None => FEE_REBATES.remove(deps.storage),
+        }
+    }
+
+    if let Some(new_epoch_manager_addr) = new_epoch_manager_addr {
+        EPOCH_MANAGER.save(
+            deps.storage,
+            &deps.api.addr_validate(&new_epoch_manager_addr)?,
+        )?;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![
@@ -68,6 +123,55 @@ pub fn update_config(
     ]))
 }
 
+/// Validates the strategy params against the pool, returning the [Strategy] to store.
+fn validate_strategy(
+    deps: &DepsMut,
+    config: &white_whale_std::vault_network::vault::Config,
+    params: StrategyParams,
+) -> Result<Strategy, VaultError> {
+    if params.deploy_share > Decimal::one() {
+        return Err(VaultError::InvalidDeployShare {});
+    }
+
+    let (pool, pool_assets, lp_asset) = match params.pool {
+        StrategyPool::Pair { contract_addr } => {
+            let contract_addr = deps.api.addr_validate(&contract_addr)?.into_string();
+            let pair_info: PairInfo = deps
+                .querier
+                .query_wasm_smart(&contract_addr, &pair::QueryMsg::Pair {})?;
+            (
+                StrategyPool::Pair { contract_addr },
+                pair_info.asset_infos.to_vec(),
+                pair_info.liquidity_token,
+            )
+        }
+        StrategyPool::Trio { contract_addr } => {
+            let contract_addr = deps.api.addr_validate(&contract_addr)?.into_string();
+            let trio_info: TrioInfo = deps
+                .querier
+                .query_wasm_smart(&contract_addr, &trio::QueryMsg::Trio {})?;
+            (
+                StrategyPool::Trio { contract_addr },
+                trio_info.asset_infos.to_vec(),
+                trio_info.liquidity_token,
+            )
+        }
+    };
+
+    // the vault asset needs to be traded in the pool
+    if !pool_assets.contains(&config.asset_info) {
+        return Err(VaultError::InvalidStrategyPool {});
+    }
+
+    Ok(Strategy {
+        pool,
+        deploy_share: params.deploy_share,
+        max_spread: params.max_spread,
+        pool_assets,
+        lp_asset,
+    })
+}
+
 #[cfg(test)]
 mod test {
     use cosmwasm_std::{
@@ -77,7 +181,9 @@ mod test {
 
     use white_whale_std::fee::{Fee, VaultFee};
     use white_whale_std::pool_network::asset::AssetInfo;
-    use white_whale_std::vault_network::vault::{Config, UpdateConfigParams};
+    use white_whale_std::vault_network::vault::{
+        Config, FeeRebateParams, FeeRebateTier, StrategyParams, StrategyPool, UpdateConfigParams,
+    };
 
     use crate::{
         contract::execute,
@@ -107,6 +213,11 @@ mod test {
                 new_owner: None,
                 new_fee_collector_addr: None,
                 new_vault_fees: None,
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
             }),
         );
 
@@ -146,6 +257,11 @@ mod test {
                 new_owner: None,
                 new_fee_collector_addr: None,
                 new_vault_fees: None,
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
             }),
         )
         .unwrap();
@@ -210,6 +326,11 @@ mod test {
                         share: Decimal::zero(),
                     },
                 }),
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
             }),
         )
         .unwrap_err();
@@ -265,6 +386,11 @@ mod test {
                 new_owner: Some("new_owner".to_string()),
                 new_fee_collector_addr: Some("new_fee_collector".to_string()),
                 new_vault_fees: Some(new_fee.clone()),
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
             }),
         )
         .unwrap();
@@ -301,4 +427,81 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn fails_on_invalid_deploy_share() {
+        let (mut deps, env) = mock_instantiate(
+            2,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            false,
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_creator(),
+            white_whale_std::vault_network::vault::ExecuteMsg::UpdateConfig(UpdateConfigParams {
+                flash_loan_enabled: None,
+                deposit_enabled: None,
+                withdraw_enabled: None,
+                new_owner: None,
+                new_fee_collector_addr: None,
+                new_vault_fees: None,
+                new_strategy: Some(StrategyParams {
+                    pool: StrategyPool::Pair {
+                        contract_addr: "pair".to_string(),
+                    },
+                    deploy_share: Decimal::percent(101),
+                    max_spread: None,
+                }),
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: None,
+            }),
+        );
+
+        assert_eq!(res.unwrap_err(), VaultError::InvalidDeployShare {});
+    }
+
+    #[test]
+    fn fails_on_invalid_fee_rebate_discount() {
+        let (mut deps, env) = mock_instantiate(
+            2,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            false,
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_creator(),
+            white_whale_std::vault_network::vault::ExecuteMsg::UpdateConfig(UpdateConfigParams {
+                flash_loan_enabled: None,
+                deposit_enabled: None,
+                withdraw_enabled: None,
+                new_owner: None,
+                new_fee_collector_addr: None,
+                new_vault_fees: None,
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: Some(FeeRebateParams {
+                    whale_lair_addr: "whale_lair".to_string(),
+                    router_addr: None,
+                    tiers: vec![FeeRebateTier {
+                        min_share: Decimal::percent(1),
+                        discount: Decimal::percent(101),
+                    }],
+                }),
+                new_epoch_manager_addr: None,
+            }),
+        );
+
+        assert_eq!(res.unwrap_err(), VaultError::InvalidFeeRebateDiscount {});
+    }
 }
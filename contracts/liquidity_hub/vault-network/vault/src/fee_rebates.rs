@@ -0,0 +1,107 @@
+use cosmwasm_std::{Addr, Decimal, Deps, Uint128};
+
+use white_whale_std::vault_network::vault::{FeeRebateParams, FeeRebates};
+use white_whale_std::whale_lair::{BondingWeightResponse, QueryMsg as WhaleLairQueryMsg};
+
+use crate::error::VaultError;
+use crate::state::FEE_REBATES;
+
+/// Validates the fee rebates params. Returns `None` if no tiers are given, removing the rebates.
+pub fn validate_fee_rebates(
+    deps: Deps,
+    params: FeeRebateParams,
+) -> Result<Option<FeeRebates>, VaultError> {
+    if params.tiers.is_empty() {
+        return Ok(None);
+    }
+
+    if params
+        .tiers
+        .iter()
+        .any(|tier| tier.discount > Decimal::one())
+    {
+        return Err(VaultError::InvalidFeeRebateDiscount {});
+    }
+
+    if params
+        .tiers
+        .iter()
+        .any(|tier| tier.min_share > Decimal::one())
+    {
+        return Err(VaultError::InvalidFeeRebateShare {});
+    }
+
+    let mut tiers = params.tiers;
+    tiers.sort_by_key(|tier| tier.min_share);
+
+    Ok(Some(FeeRebates {
+        whale_lair_addr: deps.api.addr_validate(&params.whale_lair_addr)?,
+        router_addr: params
+            .router_addr
+            .map(|router_addr| deps.api.addr_validate(&router_addr))
+            .transpose()?,
+        tiers,
+    }))
+}
+
+/// Gets the account a flash-loan is taken on behalf of. The `initiator` passed along the loan is
+/// only trusted if the sender is the router set in the fee rebates.
+pub fn get_loan_initiator(
+    deps: Deps,
+    sender: &Addr,
+    initiator: Option<String>,
+) -> Result<Addr, VaultError> {
+    let Some(initiator) = initiator else {
+        return Ok(sender.clone());
+    };
+
+    match FEE_REBATES.may_load(deps.storage)? {
+        Some(FeeRebates {
+            router_addr: Some(router_addr),
+            ..
+        }) if router_addr == sender => Ok(deps.api.addr_validate(&initiator)?),
+        _ => Ok(sender.clone()),
+    }
+}
+
+/// Gets the discount on the flash-loan fee the given initiator is entitled to, based on its share
+/// of the global bonding weight in the whale lair. The weight is comparable across the bonding
+/// assets, unlike the raw bonded amounts. If the weight can't be queried, there is no discount.
+pub fn get_fee_discount(deps: Deps, initiator: &Addr) -> Result<Decimal, VaultError> {
+    let Some(fee_rebates) = FEE_REBATES.may_load(deps.storage)? else {
+        return Ok(Decimal::zero());
+    };
+
+    // the whale lair fails to compute the weight while nothing is bonded, which must not make
+    // the flash-loan fail
+    let share = deps
+        .querier
+        .query_wasm_smart::<BondingWeightResponse>(
+            fee_rebates.whale_lair_addr,
+            &WhaleLairQueryMsg::Weight {
+                address: initiator.to_string(),
+                timestamp: None,
+                global_index: None,
+            },
+        )
+        .map(|weight| weight.share)
+        .unwrap_or_default();
+
+    // the best discount among the tiers reached applies
+    Ok(fee_rebates
+        .tiers
+        .iter()
+        .filter(|tier| tier.min_share <= share)
+        .map(|tier| tier.discount)
+        .max()
+        .unwrap_or_default())
+}
+
+/// Applies the given discount to the flash-loan fee.
+pub fn apply_fee_discount(
+    flash_loan_fee: Uint128,
+    fee_discount: Option<Decimal>,
+) -> Result<Uint128, VaultError> {
+    let discount = flash_loan_fee * fee_discount.unwrap_or_default();
+    Ok(flash_loan_fee.checked_sub(discount)?)
+}
@@ -1,10 +1,13 @@
 pub mod contract;
 pub mod execute;
+pub mod fee_rebates;
 mod migrations;
 pub mod queries;
 pub mod reply;
 pub mod response;
+pub mod share_price;
 pub mod state;
+pub mod strategy;
 
 pub mod error;
 
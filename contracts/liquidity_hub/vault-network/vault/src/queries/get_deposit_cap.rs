@@ -0,0 +1,16 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env};
+
+use white_whale_std::vault_network::vault::DepositCapResponse;
+
+use crate::error::VaultError;
+use crate::state::{CONFIG, DEPOSIT_CAP};
+use crate::strategy::get_total_assets;
+
+pub fn get_deposit_cap(deps: Deps, env: Env) -> Result<Binary, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(to_json_binary(&DepositCapResponse {
+        deposit_cap: DEPOSIT_CAP.may_load(deps.storage)?,
+        total_assets: get_total_assets(deps, &env, &config)?,
+    })?)
+}
@@ -0,0 +1,12 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps};
+
+use white_whale_std::vault_network::vault::FeeRebatesResponse;
+
+use crate::error::VaultError;
+use crate::state::FEE_REBATES;
+
+pub fn get_fee_rebates(deps: Deps) -> Result<Binary, VaultError> {
+    Ok(to_json_binary(&FeeRebatesResponse {
+        fee_rebates: FEE_REBATES.may_load(deps.storage)?,
+    })?)
+}
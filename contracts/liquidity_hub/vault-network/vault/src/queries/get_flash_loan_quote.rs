@@ -0,0 +1,121 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Uint128};
+use white_whale_std::vault_network::vault::FlashLoanQuoteResponse;
+
+use crate::error::VaultError;
+use crate::fee_rebates::{get_fee_discount, get_loan_initiator};
+use crate::queries::get_payback_amount::compute_payback_amount;
+use crate::state::CONFIG;
+use crate::strategy::{get_liquid_balance, get_strategy_position};
+
+/// Quotes a flash-loan of `amount` requested by `sender` on behalf of `initiator`, i.e. whether
+/// the vault can serve it and the amount that must be paid back for it.
+pub fn get_flash_loan_quote(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+    sender: String,
+    initiator: Option<String>,
+) -> Result<Binary, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // the strategy position is unwound when the liquid balance can't cover the loan
+    let (_, strategy_value) = get_strategy_position(deps, &env)?;
+    let available_liquidity =
+        get_liquid_balance(deps, &env, &config)?.checked_add(strategy_value)?;
+
+    let initiator = get_loan_initiator(deps, &deps.api.addr_validate(&sender)?, initiator)?;
+    let fee_discount =
+        Some(get_fee_discount(deps, &initiator)?).filter(|fee_discount| !fee_discount.is_zero());
+    let payback_amount = compute_payback_amount(&config, amount, fee_discount)?.payback_amount;
+
+    Ok(to_json_binary(&FlashLoanQuoteResponse {
+        flash_loan_enabled: config.flash_loan_enabled,
+        available_liquidity,
+        payback_amount,
+    })?)
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{coins, from_json, Addr, Decimal, Uint128};
+    use white_whale_std::fee::{Fee, VaultFee};
+    use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+    use white_whale_std::vault_network::vault::{Config, FlashLoanQuoteResponse, QueryMsg};
+
+    use crate::contract::query;
+    use crate::state::{COLLECTED_PROTOCOL_FEES, CONFIG};
+    use crate::tests::mock_creator;
+
+    #[test]
+    fn returns_flash_loan_quote() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        deps.querier
+            .update_balance(env.contract.address.clone(), coins(5_000, "uluna"));
+
+        let mut config = Config {
+            owner: mock_creator().sender,
+            lp_asset: AssetInfo::Token {
+                contract_addr: "lp_token".to_string(),
+            },
+            asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            deposit_enabled: true,
+            flash_loan_enabled: true,
+            withdraw_enabled: true,
+            fee_collector_addr: Addr::unchecked("fee_collector"),
+            fees: VaultFee {
+                flash_loan_fee: Fee {
+                    share: Decimal::permille(5),
+                },
+                protocol_fee: Fee {
+                    share: Decimal::permille(5),
+                },
+                burn_fee: Fee {
+                    share: Decimal::zero(),
+                },
+            },
+        };
+        CONFIG.save(&mut deps.storage, &config).unwrap();
+        COLLECTED_PROTOCOL_FEES
+            .save(
+                &mut deps.storage,
+                &Asset {
+                    info: config.asset_info.clone(),
+                    amount: Uint128::zero(),
+                },
+            )
+            .unwrap();
+
+        let quote = |deps: &cosmwasm_std::OwnedDeps<_, _, _>| -> FlashLoanQuoteResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    env.clone(),
+                    QueryMsg::FlashLoanQuote {
+                        amount: Uint128::new(1_000),
+                        sender: "router".to_string(),
+                        initiator: Some("initiator".to_string()),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            quote(&deps),
+            FlashLoanQuoteResponse {
+                flash_loan_enabled: true,
+                available_liquidity: Uint128::new(5_000),
+                payback_amount: Uint128::new(1_010),
+            }
+        );
+
+        config.flash_loan_enabled = false;
+        CONFIG.save(&mut deps.storage, &config).unwrap();
+        assert!(!quote(&deps).flash_loan_enabled);
+    }
+}
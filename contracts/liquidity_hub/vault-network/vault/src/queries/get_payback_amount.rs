@@ -1,16 +1,45 @@
-use cosmwasm_std::{to_json_binary, Binary, Deps, Uint128, Uint256};
-use white_whale_std::vault_network::vault::PaybackAmountResponse;
+use cosmwasm_std::{to_json_binary, Binary, Decimal, Deps, Uint128, Uint256};
+use white_whale_std::vault_network::vault::{Config, PaybackAmountResponse};
 
 use crate::error::VaultError;
+use crate::fee_rebates::{apply_fee_discount, get_fee_discount};
 use crate::state::CONFIG;
 
-pub fn get_payback_amount(deps: Deps, amount: Uint128) -> Result<Binary, VaultError> {
+pub fn get_payback_amount(
+    deps: Deps,
+    amount: Uint128,
+    initiator: Option<String>,
+) -> Result<Binary, VaultError> {
     let config = CONFIG.load(deps.storage)?;
 
+    let fee_discount = match initiator {
+        Some(initiator) => Some(get_fee_discount(
+            deps,
+            &deps.api.addr_validate(&initiator)?,
+        )?),
+        None => None,
+    };
+
+    Ok(to_json_binary(&compute_payback_amount(
+        &config,
+        amount,
+        fee_discount,
+    )?)?)
+}
+
+/// Computes the amount that must be paid back for a flash-loan of `amount`, with the given discount
+/// on the flash-loan fee.
+pub(crate) fn compute_payback_amount(
+    config: &Config,
+    amount: Uint128,
+    fee_discount: Option<Decimal>,
+) -> Result<PaybackAmountResponse, VaultError> {
     // check that balance is greater than expected
     let protocol_fee = Uint128::try_from(config.fees.protocol_fee.compute(Uint256::from(amount)))?;
-    let flash_loan_fee =
-        Uint128::try_from(config.fees.flash_loan_fee.compute(Uint256::from(amount)))?;
+    let flash_loan_fee = apply_fee_discount(
+        Uint128::try_from(config.fees.flash_loan_fee.compute(Uint256::from(amount)))?,
+        fee_discount,
+    )?;
     let burn_fee = Uint128::try_from(config.fees.burn_fee.compute(Uint256::from(amount)))?;
 
     let required_amount = amount
@@ -18,58 +47,63 @@ pub fn get_payback_amount(deps: Deps, amount: Uint128) -> Result<Binary, VaultEr
         .checked_add(flash_loan_fee)?
         .checked_add(burn_fee)?;
 
-    Ok(to_json_binary(&PaybackAmountResponse {
+    Ok(PaybackAmountResponse {
         payback_amount: required_amount,
         protocol_fee,
         flash_loan_fee,
         burn_fee,
-    })?)
+    })
 }
 
 #[cfg(test)]
 mod test {
     use crate::contract::query;
-    use crate::state::CONFIG;
+    use crate::state::{CONFIG, FEE_REBATES};
     use crate::tests::mock_creator;
     use cosmwasm_std::testing::{mock_dependencies, mock_env};
-    use cosmwasm_std::{from_json, Addr, Decimal, Uint128};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult, Decimal, SystemResult, Timestamp, Uint128,
+        WasmQuery,
+    };
     use white_whale_std::fee::{Fee, VaultFee};
     use white_whale_std::pool_network::asset::AssetInfo;
-    use white_whale_std::vault_network::vault::{Config, PaybackAmountResponse, QueryMsg};
+    use white_whale_std::vault_network::vault::{
+        Config, FeeRebateTier, FeeRebates, PaybackAmountResponse, QueryMsg,
+    };
+    use white_whale_std::whale_lair::{BondingWeightResponse, QueryMsg as WhaleLairQueryMsg};
+
+    fn mock_config() -> Config {
+        Config {
+            owner: mock_creator().sender,
+            lp_asset: AssetInfo::Token {
+                contract_addr: "lp_token".to_string(),
+            },
+            asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            deposit_enabled: true,
+            flash_loan_enabled: true,
+            withdraw_enabled: true,
+            fee_collector_addr: Addr::unchecked("fee_collector"),
+            fees: VaultFee {
+                flash_loan_fee: Fee {
+                    share: Decimal::permille(5),
+                },
+                protocol_fee: Fee {
+                    share: Decimal::permille(5),
+                },
+                burn_fee: Fee {
+                    share: Decimal::permille(1),
+                },
+            },
+        }
+    }
 
     #[test]
     fn returns_payback_amount() {
         let mut deps = mock_dependencies();
 
-        CONFIG
-            .save(
-                &mut deps.storage,
-                &Config {
-                    owner: mock_creator().sender,
-                    lp_asset: AssetInfo::Token {
-                        contract_addr: "lp_token".to_string(),
-                    },
-                    asset_info: AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                    deposit_enabled: true,
-                    flash_loan_enabled: true,
-                    withdraw_enabled: true,
-                    fee_collector_addr: Addr::unchecked("fee_collector"),
-                    fees: VaultFee {
-                        flash_loan_fee: Fee {
-                            share: Decimal::permille(5),
-                        },
-                        protocol_fee: Fee {
-                            share: Decimal::permille(5),
-                        },
-                        burn_fee: Fee {
-                            share: Decimal::permille(1),
-                        },
-                    },
-                },
-            )
-            .unwrap();
+        CONFIG.save(&mut deps.storage, &mock_config()).unwrap();
 
         let res: PaybackAmountResponse = from_json(
             &query(
@@ -77,6 +111,7 @@ mod test {
                 mock_env(),
                 QueryMsg::GetPaybackAmount {
                     amount: Uint128::new(1000),
+                    initiator: None,
                 },
             )
             .unwrap(),
@@ -93,4 +128,129 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn applies_fee_rebates_of_initiator() {
+        let mut deps = mock_dependencies();
+        CONFIG.save(&mut deps.storage, &mock_config()).unwrap();
+        FEE_REBATES
+            .save(
+                &mut deps.storage,
+                &FeeRebates {
+                    whale_lair_addr: Addr::unchecked("whale_lair"),
+                    router_addr: None,
+                    tiers: vec![
+                        FeeRebateTier {
+                            min_share: Decimal::percent(1),
+                            discount: Decimal::percent(20),
+                        },
+                        FeeRebateTier {
+                            min_share: Decimal::percent(10),
+                            discount: Decimal::percent(60),
+                        },
+                    ],
+                },
+            )
+            .unwrap();
+
+        // the bonder holds 10% of the global bonding weight, everyone else nothing
+        deps.querier.update_wasm(|request| match request {
+            WasmQuery::Smart { msg, .. } => {
+                let WhaleLairQueryMsg::Weight { address, .. } = from_json(msg).unwrap() else {
+                    panic!("unexpected query");
+                };
+                let (weight, share) = if address == "bonder" {
+                    (Uint128::new(10_000), Decimal::percent(10))
+                } else {
+                    (Uint128::zero(), Decimal::zero())
+                };
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&BondingWeightResponse {
+                        address,
+                        weight,
+                        global_weight: Uint128::new(100_000),
+                        share,
+                        timestamp: Timestamp::from_seconds(0),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => panic!("unexpected query"),
+        });
+
+        let get_payback_amount = |initiator: &str| -> PaybackAmountResponse {
+            from_json(
+                query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::GetPaybackAmount {
+                        amount: Uint128::new(10_000),
+                        initiator: Some(initiator.to_string()),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        // the highest tier reached applies, only discounting the flash-loan fee
+        assert_eq!(
+            get_payback_amount("bonder"),
+            PaybackAmountResponse {
+                payback_amount: Uint128::new(10_080),
+                protocol_fee: Uint128::new(50),
+                flash_loan_fee: Uint128::new(20),
+                burn_fee: Uint128::new(10),
+            }
+        );
+        assert_eq!(
+            get_payback_amount("someone_else"),
+            PaybackAmountResponse {
+                payback_amount: Uint128::new(10_110),
+                protocol_fee: Uint128::new(50),
+                flash_loan_fee: Uint128::new(50),
+                burn_fee: Uint128::new(10),
+            }
+        );
+    }
+
+    #[test]
+    fn does_not_discount_if_weight_query_fails() {
+        let mut deps = mock_dependencies();
+        CONFIG.save(&mut deps.storage, &mock_config()).unwrap();
+        FEE_REBATES
+            .save(
+                &mut deps.storage,
+                &FeeRebates {
+                    whale_lair_addr: Addr::unchecked("whale_lair"),
+                    router_addr: None,
+                    tiers: vec![FeeRebateTier {
+                        min_share: Decimal::percent(1),
+                        discount: Decimal::percent(20),
+                    }],
+                },
+            )
+            .unwrap();
+
+        // the whale lair can't compute the weight before anyone has bonded
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Err("Global index not found".to_string()))
+        });
+
+        let res: PaybackAmountResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetPaybackAmount {
+                    amount: Uint128::new(10_000),
+                    initiator: Some("bonder".to_string()),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.flash_loan_fee, Uint128::new(50));
+        assert_eq!(res.payback_amount, Uint128::new(10_110));
+    }
 }
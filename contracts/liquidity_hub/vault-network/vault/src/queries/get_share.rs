@@ -1,11 +1,10 @@
 use cosmwasm_std::{to_json_binary, Binary, Decimal, Deps, Env, Uint128};
-use cw20::{BalanceResponse, Cw20QueryMsg};
 
 use white_whale_std::pool_network::asset::{get_total_share, AssetInfo};
 
 use crate::error::VaultError;
-use crate::state::COLLECTED_PROTOCOL_FEES;
 use crate::state::CONFIG;
+use crate::strategy::get_total_assets;
 
 pub fn get_share(deps: Deps, env: Env, amount: Uint128) -> Result<Binary, VaultError> {
     let config = CONFIG.load(deps.storage)?;
@@ -17,25 +16,8 @@ pub fn get_share(deps: Deps, env: Env, amount: Uint128) -> Result<Binary, VaultE
 
     let lp_amount = get_total_share(&deps, liquidity_asset)?;
 
-    let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
-
-    let balance = match config.asset_info {
-        AssetInfo::NativeToken { denom } => {
-            deps.querier
-                .query_balance(env.contract.address, denom)?
-                .amount
-        }
-        AssetInfo::Token { contract_addr } => {
-            let balance: BalanceResponse = deps.querier.query_wasm_smart(
-                contract_addr,
-                &Cw20QueryMsg::Balance {
-                    address: env.contract.address.into_string(),
-                },
-            )?;
-            balance.balance
-        }
-    } // deduct protocol fees
-    .checked_sub(collected_protocol_fees.amount)?;
+    // includes the strategy position, deducting protocol fees
+    let balance = get_total_assets(deps, &env, &config)?;
 
     // lp_share = amount / lp_amount
     // asset_share = lp_share * balance
@@ -0,0 +1,188 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env};
+
+use white_whale_std::vault_network::vault::SharePriceHistoryResponse;
+
+use crate::error::VaultError;
+use crate::share_price::{get_current_share_price, get_trailing_apr, read_share_price_history};
+use crate::state::CONFIG;
+
+pub fn get_share_price_history(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Binary, VaultError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let snapshots = read_share_price_history(deps, start_after, limit)?;
+    let current_share_price = get_current_share_price(deps, &env, &config)?;
+    let apr = get_trailing_apr(deps, &env, current_share_price)?;
+
+    Ok(to_json_binary(&SharePriceHistoryResponse {
+        snapshots,
+        current_share_price,
+        apr,
+    })?)
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coins, Decimal, Uint128, Uint64};
+    use cw_multi_test::Executor;
+
+    use white_whale_std::epoch_manager::epoch_manager::{
+        EpochConfig, EpochV2, ExecuteMsg as EpochManagerExecuteMsg,
+        InstantiateMsg as EpochManagerInstantiateMsg,
+    };
+    use white_whale_std::pool_network::asset::AssetInfo;
+    use white_whale_std::vault_network::vault::{
+        ExecuteMsg, QueryMsg, SharePriceHistoryResponse, UpdateConfigParams,
+    };
+
+    use crate::tests::{
+        mock_app::mock_app_with_balance, mock_creator, mock_instantiate::app_mock_instantiate,
+        store_code::store_epoch_manager_code,
+    };
+
+    const SECONDS_PER_EPOCH: u64 = 86_400;
+
+    #[test]
+    fn can_get_share_price_history_and_apr() {
+        let mut app = mock_app_with_balance(vec![(mock_creator().sender, coins(100_000, "uluna"))]);
+
+        let vault_addr = app_mock_instantiate(
+            &mut app,
+            AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        );
+
+        // daily epochs, starting now
+        let start_time = app.block_info().time;
+        let epoch_manager_id = store_epoch_manager_code(&mut app);
+        let epoch_manager_addr = app
+            .instantiate_contract(
+                epoch_manager_id,
+                mock_creator().sender,
+                &EpochManagerInstantiateMsg {
+                    start_epoch: EpochV2 { id: 1, start_time },
+                    epoch_config: EpochConfig {
+                        duration: Uint64::new(SECONDS_PER_EPOCH * 1_000_000_000),
+                        genesis_epoch: Uint64::new(start_time.nanos()),
+                    },
+                },
+                &[],
+                "epoch manager",
+                None,
+            )
+            .unwrap();
+        let next_epoch = |app: &mut cw_multi_test::App| {
+            app.update_block(|block| block.time = block.time.plus_seconds(SECONDS_PER_EPOCH));
+            app.execute_contract(
+                mock_creator().sender,
+                epoch_manager_addr.clone(),
+                &EpochManagerExecuteMsg::CreateEpoch {},
+                &[],
+            )
+            .unwrap();
+        };
+
+        app.execute_contract(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &ExecuteMsg::UpdateConfig(UpdateConfigParams {
+                flash_loan_enabled: None,
+                deposit_enabled: None,
+                withdraw_enabled: None,
+                new_owner: None,
+                new_fee_collector_addr: None,
+                new_vault_fees: None,
+                new_strategy: None,
+                new_deposit_cap: None,
+                withdrawal_queue_enabled: None,
+                new_fee_rebates: None,
+                new_epoch_manager_addr: Some(epoch_manager_addr.to_string()),
+            }),
+            &[],
+        )
+        .unwrap();
+
+        let deposit = |app: &mut cw_multi_test::App, amount: u128| {
+            app.execute_contract(
+                mock_creator().sender,
+                vault_addr.clone(),
+                &ExecuteMsg::Deposit {
+                    amount: Uint128::new(amount),
+                },
+                &coins(amount, "uluna"),
+            )
+            .unwrap();
+        };
+
+        // nothing is recorded on the first deposit, the vault is empty
+        deposit(&mut app, 10_000);
+        next_epoch(&mut app);
+
+        // a single snapshot is recorded per epoch
+        deposit(&mut app, 5_000);
+        deposit(&mut app, 5_000);
+        next_epoch(&mut app);
+
+        let query_history = |app: &cw_multi_test::App| -> SharePriceHistoryResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    vault_addr.clone(),
+                    &QueryMsg::SharePriceHistory {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+
+        let res = query_history(&app);
+        assert_eq!(res.snapshots.len(), 1);
+        assert_eq!(res.snapshots[0].epoch_id, 2);
+        assert_eq!(res.snapshots[0].lp_supply, Uint128::new(10_000));
+        assert_eq!(res.snapshots[0].total_assets, Uint128::new(10_000));
+        assert_eq!(res.snapshots[0].share_price, Decimal::one());
+        assert_eq!(res.current_share_price, Decimal::one());
+        assert_eq!(res.apr, Decimal::zero());
+
+        // the vault earns 1% in a day
+        app.send_tokens(
+            mock_creator().sender,
+            vault_addr.clone(),
+            &coins(200, "uluna"),
+        )
+        .unwrap();
+
+        let res = query_history(&app);
+        assert_eq!(res.current_share_price, Decimal::percent(101));
+        assert_eq!(res.apr, Decimal::percent(365));
+
+        // the APR doesn't depend on the page of snapshots queried
+        let res: SharePriceHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                vault_addr.clone(),
+                &QueryMsg::SharePriceHistory {
+                    start_after: Some(2),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(res.snapshots.is_empty());
+        assert_eq!(res.apr, Decimal::percent(365));
+
+        // once the snapshot falls out of the trailing window, there's nothing to compute the APR
+        // from
+        for _ in 0..30 {
+            next_epoch(&mut app);
+        }
+
+        let res = query_history(&app);
+        assert_eq!(res.snapshots.len(), 1);
+        assert_eq!(res.apr, Decimal::zero());
+    }
+}
@@ -0,0 +1,17 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env};
+
+use white_whale_std::vault_network::vault::StrategyResponse;
+
+use crate::error::VaultError;
+use crate::state::STRATEGY;
+use crate::strategy::get_strategy_position;
+
+pub fn get_strategy(deps: Deps, env: Env) -> Result<Binary, VaultError> {
+    let (lp_amount, value) = get_strategy_position(deps, &env)?;
+
+    Ok(to_json_binary(&StrategyResponse {
+        strategy: STRATEGY.may_load(deps.storage)?,
+        lp_amount,
+        value,
+    })?)
+}
@@ -0,0 +1,89 @@
+use cosmwasm_std::{to_json_binary, Binary, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use white_whale_std::vault_network::vault::{
+    QueuedWithdrawalPosition, WithdrawalQueuePositionResponse, WithdrawalQueueResponse,
+};
+
+use crate::error::VaultError;
+use crate::state::{WITHDRAWAL_QUEUE, WITHDRAWAL_QUEUE_ENABLED};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+// the position query only returns the withdrawals of an address, so it can scan more at once
+const MAX_SCAN_LIMIT: u32 = 100;
+const DEFAULT_SCAN_LIMIT: u32 = 30;
+
+pub fn get_withdrawal_queue(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Binary, VaultError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let withdrawals = WITHDRAWAL_QUEUE
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(to_json_binary(&WithdrawalQueueResponse {
+        enabled: WITHDRAWAL_QUEUE_ENABLED
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        withdrawals,
+    })?)
+}
+
+/// Gets the queued withdrawals of the given address among the next `limit` withdrawals of the
+/// queue after `start_after`, so the scan is bounded however long the queue gets.
+pub fn get_withdrawal_queue_position(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Binary, VaultError> {
+    let address = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_SCAN_LIMIT).min(MAX_SCAN_LIMIT) as usize;
+
+    let scanned = WITHDRAWAL_QUEUE
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // the scan isn't over if it hit the limit
+    let next_start_after = if scanned.len() == limit {
+        scanned.last().map(|withdrawal| withdrawal.id)
+    } else {
+        None
+    };
+
+    // withdrawals can be cancelled from anywhere in the queue, so the position of a withdrawal
+    // is given by the number of withdrawals ahead of it
+    let withdrawals = scanned
+        .into_iter()
+        .enumerate()
+        .filter(|(_, withdrawal)| withdrawal.sender == address)
+        .map(|(position, withdrawal)| QueuedWithdrawalPosition {
+            withdrawal,
+            position: position as u64,
+        })
+        .collect();
+
+    Ok(to_json_binary(&WithdrawalQueuePositionResponse {
+        withdrawals,
+        next_start_after,
+    })?)
+}
@@ -1,9 +1,21 @@
 mod get_config;
+mod get_deposit_cap;
+mod get_fee_rebates;
+mod get_flash_loan_quote;
 mod get_payback_amount;
 mod get_protocol_fees;
 mod get_share;
+mod get_share_price_history;
+mod get_strategy;
+mod get_withdrawal_queue;
 
 pub use get_config::get_config;
+pub use get_deposit_cap::get_deposit_cap;
+pub use get_fee_rebates::get_fee_rebates;
+pub use get_flash_loan_quote::get_flash_loan_quote;
 pub use get_payback_amount::get_payback_amount;
 pub use get_protocol_fees::get_fees;
 pub use get_share::get_share;
+pub use get_share_price_history::get_share_price_history;
+pub use get_strategy::get_strategy;
+pub use get_withdrawal_queue::{get_withdrawal_queue, get_withdrawal_queue_position};
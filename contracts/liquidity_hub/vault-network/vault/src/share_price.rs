@@ -0,0 +1,183 @@
+use cosmwasm_std::{Decimal, Deps, DepsMut, Env, Order, StdResult, Uint128};
+use cw_storage_plus::Bound;
+
+use white_whale_std::epoch_manager::epoch_manager::{
+    EpochResponse, EpochV2, QueryMsg as EpochManagerQueryMsg,
+};
+use white_whale_std::pool_network::asset::{get_total_share, AssetInfo};
+use white_whale_std::vault_network::vault::{Config, SharePriceSnapshot};
+
+use crate::error::VaultError;
+use crate::state::{EPOCH_MANAGER, SHARE_PRICE_HISTORY};
+use crate::strategy::get_total_assets;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAYS_PER_YEAR: u64 = 365;
+/// The number of epochs the trailing APR is computed over.
+const APR_WINDOW_EPOCHS: u64 = 30;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+
+/// Gets the current LP supply and total assets of the vault. `pending_amount` is deducted from
+/// the total assets, i.e. assets that are already held by the vault but not yet accounted for.
+pub fn get_vault_size(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    pending_amount: Uint128,
+) -> Result<(Uint128, Uint128), VaultError> {
+    let liquidity_asset = match &config.lp_asset {
+        AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+        AssetInfo::NativeToken { denom } => denom.to_string(),
+    };
+    // the LP asset is set once its creation is replied to, nothing has been deposited before that
+    if liquidity_asset.is_empty() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    let lp_supply = get_total_share(&deps, liquidity_asset)?;
+    if lp_supply.is_zero() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    let total_assets = get_total_assets(deps, env, config)?.saturating_sub(pending_amount);
+
+    Ok((lp_supply, total_assets))
+}
+
+/// Gets the current epoch of the epoch manager, if the vault has one.
+fn get_current_epoch(deps: Deps) -> Result<Option<EpochV2>, VaultError> {
+    let Some(epoch_manager) = EPOCH_MANAGER.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+
+    let EpochResponse { epoch } = deps
+        .querier
+        .query_wasm_smart(epoch_manager, &EpochManagerQueryMsg::CurrentEpoch {})?;
+
+    Ok(Some(epoch))
+}
+
+/// Records a snapshot of the share price if none was taken in the current epoch yet.
+pub fn record_share_price(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    pending_amount: Uint128,
+) -> Result<(), VaultError> {
+    let Some(epoch) = get_current_epoch(deps.as_ref())? else {
+        return Ok(());
+    };
+    if SHARE_PRICE_HISTORY.has(deps.storage, epoch.id) {
+        return Ok(());
+    }
+
+    let (lp_supply, total_assets) = get_vault_size(deps.as_ref(), env, config, pending_amount)?;
+    // nothing to record on an empty vault
+    if lp_supply.is_zero() {
+        return Ok(());
+    }
+
+    SHARE_PRICE_HISTORY.save(
+        deps.storage,
+        epoch.id,
+        &SharePriceSnapshot {
+            epoch_id: epoch.id,
+            timestamp: env.block.time,
+            lp_supply,
+            total_assets,
+            share_price: Decimal::from_ratio(total_assets, lp_supply),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Gets the current share price of the vault.
+pub fn get_current_share_price(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> Result<Decimal, VaultError> {
+    let (lp_supply, total_assets) = get_vault_size(deps, env, config, Uint128::zero())?;
+    if lp_supply.is_zero() {
+        return Ok(Decimal::zero());
+    }
+
+    Ok(Decimal::from_ratio(total_assets, lp_supply))
+}
+
+/// Reads the share price snapshots, in ascending order.
+pub fn read_share_price_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SharePriceSnapshot>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    SHARE_PRICE_HISTORY
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Computes the trailing APR of the vault, from the oldest snapshot within the last
+/// [APR_WINDOW_EPOCHS] epochs up to the current share price.
+pub fn get_trailing_apr(
+    deps: Deps,
+    env: &Env,
+    current_share_price: Decimal,
+) -> Result<Decimal, VaultError> {
+    let Some(epoch) = get_current_epoch(deps)? else {
+        return Ok(Decimal::zero());
+    };
+
+    let window_start = epoch.id.saturating_sub(APR_WINDOW_EPOCHS);
+    let snapshot = SHARE_PRICE_HISTORY
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(window_start)),
+            None,
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?;
+
+    match snapshot {
+        Some((_, snapshot)) => compute_apr(env, &snapshot, current_share_price),
+        None => Ok(Decimal::zero()),
+    }
+}
+
+/// Computes the annualized, non-compounded return between the given snapshot and the current
+/// share price. Returns zero if the share price didn't grow.
+fn compute_apr(
+    env: &Env,
+    snapshot: &SharePriceSnapshot,
+    current_share_price: Decimal,
+) -> Result<Decimal, VaultError> {
+    let elapsed_seconds = env.block.time.seconds() - snapshot.timestamp.seconds();
+    if elapsed_seconds == 0
+        || snapshot.share_price.is_zero()
+        || current_share_price <= snapshot.share_price
+    {
+        return Ok(Decimal::zero());
+    }
+
+    let growth = current_share_price
+        .checked_div(snapshot.share_price)?
+        .checked_sub(Decimal::one())?;
+
+    Ok(growth.checked_mul(Decimal::from_ratio(
+        SECONDS_PER_DAY * DAYS_PER_YEAR,
+        elapsed_seconds,
+    ))?)
+}
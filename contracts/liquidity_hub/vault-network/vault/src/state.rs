@@ -1,11 +1,32 @@
-use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 
-use white_whale_std::vault_network::vault::Config;
+use white_whale_std::vault_network::vault::{
+    Config, FeeRebates, QueuedWithdrawal, SharePriceSnapshot, Strategy,
+};
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+// The strategy the idle liquidity of the vault is deployed with, if any
+pub const STRATEGY: Item<Strategy> = Item::new("strategy");
+// The amount of vault asset spent on the strategy position that hasn't been unwound yet. Unwinds
+// are expected to return their share of it, so they can't be sandwiched
+pub const STRATEGY_COST_BASIS: Item<Uint128> = Item::new("strategy_cost_basis");
+
+// The cap on the total assets of the vault, beyond which deposits are rejected
+pub const DEPOSIT_CAP: Item<Uint128> = Item::new("deposit_cap");
+
+// If withdrawals exceeding the liquid balance of the vault are queued
+pub const WITHDRAWAL_QUEUE_ENABLED: Item<bool> = Item::new("withdrawal_queue_enabled");
+// The queued withdrawals, keyed by id. Withdrawals are paid from the front, or cancelled
+pub const WITHDRAWAL_QUEUE: Map<u64, QueuedWithdrawal> = Map::new("withdrawal_queue");
+// The id of the next withdrawal to be queued
+pub const WITHDRAWAL_QUEUE_NEXT_ID: Item<u64> = Item::new("withdrawal_queue_next_id");
+
+// The flash-loan fee rebates for WHALE bonders, if any
+pub const FEE_REBATES: Item<FeeRebates> = Item::new("fee_rebates");
+
 // Fees that have been accrued by the vault, still unclaimed by the fee collector
 pub const COLLECTED_PROTOCOL_FEES: Item<Asset> = Item::new("collected_protocol_fees");
 // Fees that have been accrued by the vault since the vault's inception
@@ -17,6 +38,11 @@ pub const ALL_TIME_BURNED_FEES: Item<Asset> = Item::new("all_time_burned_fees");
 // A counter for how many active loans are being performed
 pub const LOAN_COUNTER: Item<u32> = Item::new("loan_counter");
 
+// The epoch manager the share price snapshots are taken from, if any
+pub const EPOCH_MANAGER: Item<Addr> = Item::new("epoch_manager");
+// Snapshots of the share price of the vault, one per epoch, keyed by epoch id
+pub const SHARE_PRICE_HISTORY: Map<u64, SharePriceSnapshot> = Map::new("share_price_history");
+
 /// Stores a fee in the given fees_storage_item
 pub fn store_fee(
     storage: &mut dyn Storage,
@@ -0,0 +1,348 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    coins, to_json_binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, StdError, StdResult,
+    Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::swap::DEFAULT_SLIPPAGE;
+use white_whale_std::pool_network::{pair, trio};
+use white_whale_std::vault_network::vault::{
+    CallbackMsg, Config, ExecuteMsg, Strategy, StrategyPool,
+};
+
+use crate::error::VaultError;
+use crate::state::{COLLECTED_PROTOCOL_FEES, CONFIG, STRATEGY, STRATEGY_COST_BASIS};
+
+/// Gets the amount of the vault asset held by the vault, i.e. the liquid balance.
+pub fn get_liquid_balance(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
+    config
+        .asset_info
+        .query_pool(&deps.querier, deps.api, env.contract.address.clone())
+}
+
+/// Gets the total amount of assets managed by the vault, i.e. the liquid balance plus the value of
+/// the strategy position, minus the protocol fees that haven't been collected yet.
+pub fn get_total_assets(deps: Deps, env: &Env, config: &Config) -> Result<Uint128, VaultError> {
+    let collected_protocol_fees = COLLECTED_PROTOCOL_FEES.load(deps.storage)?;
+    let (_, strategy_value) = get_strategy_position(deps, env)?;
+
+    Ok(get_liquid_balance(deps, env, config)?
+        .checked_add(strategy_value)?
+        .checked_sub(collected_protocol_fees.amount)?)
+}
+
+/// Gets the amount of LP tokens held in the strategy pool, and the value of that position
+/// denominated in the vault asset. The position is valued at the current reserves of the pool, i.e.
+/// its share of the vault asset reserve plus what its share of the other reserves returns when
+/// swapped into the vault asset, so losses of the position are priced in right away.
+pub fn get_strategy_position(deps: Deps, env: &Env) -> Result<(Uint128, Uint128), VaultError> {
+    let Some(strategy) = STRATEGY.may_load(deps.storage)? else {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    };
+
+    let lp_amount =
+        strategy
+            .lp_asset
+            .query_pool(&deps.querier, deps.api, env.contract.address.clone())?;
+    if lp_amount.is_zero() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let pool = strategy.pool.contract_addr();
+    let (reserves, total_share) = match &strategy.pool {
+        StrategyPool::Pair { .. } => {
+            let response: pair::PoolResponse = deps
+                .querier
+                .query_wasm_smart(pool, &pair::QueryMsg::Pool {})?;
+            (response.assets, response.total_share)
+        }
+        StrategyPool::Trio { .. } => {
+            let response: trio::PoolResponse = deps
+                .querier
+                .query_wasm_smart(pool, &trio::QueryMsg::Pool {})?;
+            (response.assets, response.total_share)
+        }
+    };
+    if total_share.is_zero() {
+        return Ok((lp_amount, Uint128::zero()));
+    }
+
+    let mut value = Uint128::zero();
+    for reserve in reserves {
+        let amount = reserve.amount.multiply_ratio(lp_amount, total_share);
+        if reserve.info == config.asset_info {
+            value = value.checked_add(amount)?;
+            continue;
+        }
+        if amount.is_zero() {
+            continue;
+        }
+
+        let offer_asset = Asset {
+            info: reserve.info,
+            amount,
+        };
+        let return_amount = match &strategy.pool {
+            StrategyPool::Pair { .. } => {
+                let simulation: pair::SimulationResponse = deps
+                    .querier
+                    .query_wasm_smart(pool, &pair::QueryMsg::Simulation { offer_asset })?;
+                simulation.return_amount
+            }
+            StrategyPool::Trio { .. } => {
+                let simulation: trio::SimulationResponse = deps.querier.query_wasm_smart(
+                    pool,
+                    &trio::QueryMsg::Simulation {
+                        offer_asset,
+                        ask_asset: Asset {
+                            info: config.asset_info.clone(),
+                            amount: Uint128::zero(),
+                        },
+                    },
+                )?;
+                simulation.return_amount
+            }
+        };
+        value = value.checked_add(return_amount)?;
+    }
+
+    Ok((lp_amount, value))
+}
+
+/// Gets the amount of LP tokens to withdraw out of the `lp_amount` held in the strategy pool, worth
+/// `position_value`, to cover the given shortfall of vault asset. The max spread of the strategy is
+/// added to the shortfall as a margin for the swaps.
+pub fn get_unwind_lp_amount(
+    strategy: &Strategy,
+    lp_amount: Uint128,
+    position_value: Uint128,
+    shortfall: Uint128,
+) -> StdResult<Uint128> {
+    let max_spread = match strategy.max_spread {
+        Some(max_spread) => max_spread,
+        None => Decimal::from_str(DEFAULT_SLIPPAGE)?,
+    };
+    let shortfall = shortfall * (Decimal::one() + max_spread);
+    if shortfall >= position_value {
+        return Ok(lp_amount);
+    }
+
+    // round up, so the withdrawn share covers the shortfall
+    Ok(lp_amount
+        .multiply_ratio(shortfall, position_value)
+        .checked_add(Uint128::one())?
+        .min(lp_amount))
+}
+
+/// Adds the given amount of vault asset to the cost basis of the strategy position.
+pub fn increase_cost_basis(storage: &mut dyn Storage, amount: Uint128) -> StdResult<Uint128> {
+    let cost_basis = STRATEGY_COST_BASIS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    STRATEGY_COST_BASIS.save(storage, &cost_basis)?;
+
+    Ok(cost_basis)
+}
+
+/// Creates the messages to withdraw `lp_amount` out of the `total_lp_amount` LP tokens held in the
+/// strategy pool, and swap the withdrawn assets back into the vault asset. The cost basis of the
+/// position is reduced pro rata, and the unwind is expected to return the withdrawn share of it
+/// within the max spread of the strategy, so it can't be sandwiched. As the swaps look at the
+/// balance of the vault asset, the messages must run before any other message moving it.
+pub fn unwind_strategy_msgs(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    strategy: &Strategy,
+    lp_amount: Uint128,
+    total_lp_amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let cost_basis = STRATEGY_COST_BASIS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let remaining_cost_basis = if lp_amount >= total_lp_amount {
+        Uint128::zero()
+    } else {
+        cost_basis.multiply_ratio(total_lp_amount.checked_sub(lp_amount)?, total_lp_amount)
+    };
+    STRATEGY_COST_BASIS.save(deps.storage, &remaining_cost_basis)?;
+
+    let liquid_balance = get_liquid_balance(deps.as_ref(), env, config)?;
+
+    let pool = strategy.pool.contract_addr().to_string();
+
+    let withdraw_msg: CosmosMsg = match &strategy.lp_asset {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: pool,
+            msg: match &strategy.pool {
+                StrategyPool::Pair { .. } => {
+                    to_json_binary(&pair::ExecuteMsg::WithdrawLiquidity {})?
+                }
+                StrategyPool::Trio { .. } => {
+                    to_json_binary(&trio::ExecuteMsg::WithdrawLiquidity {})?
+                }
+            },
+            funds: coins(lp_amount.u128(), denom),
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: pool,
+                amount: lp_amount,
+                msg: match &strategy.pool {
+                    StrategyPool::Pair { .. } => {
+                        to_json_binary(&pair::Cw20HookMsg::WithdrawLiquidity {})?
+                    }
+                    StrategyPool::Trio { .. } => {
+                        to_json_binary(&trio::Cw20HookMsg::WithdrawLiquidity {})?
+                    }
+                },
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(vec![
+        withdraw_msg,
+        callback_msg(
+            env,
+            CallbackMsg::SwapStrategyAssets {
+                liquid_balance,
+                unwound_cost: cost_basis.checked_sub(remaining_cost_basis)?,
+            },
+        )?,
+    ])
+}
+
+/// Creates the message to swap the given asset into `ask_asset` through the strategy pool. Without
+/// a `belief_price`, the max spread of the strategy is measured against the price of the pool.
+pub fn swap_msg(
+    strategy: &Strategy,
+    offer_asset: Asset,
+    ask_asset: AssetInfo,
+    belief_price: Option<Decimal>,
+) -> StdResult<CosmosMsg> {
+    let pool = strategy.pool.contract_addr().to_string();
+
+    Ok(match offer_asset.info.clone() {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: pool,
+            funds: coins(offer_asset.amount.u128(), denom),
+            msg: match &strategy.pool {
+                StrategyPool::Pair { .. } => to_json_binary(&pair::ExecuteMsg::Swap {
+                    offer_asset,
+                    belief_price,
+                    max_spread: strategy.max_spread,
+                    to: None,
+                })?,
+                StrategyPool::Trio { .. } => to_json_binary(&trio::ExecuteMsg::Swap {
+                    offer_asset,
+                    ask_asset,
+                    belief_price,
+                    max_spread: strategy.max_spread,
+                    to: None,
+                })?,
+            },
+        }
+        .into(),
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr,
+            funds: vec![],
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: pool,
+                amount: offer_asset.amount,
+                msg: match &strategy.pool {
+                    StrategyPool::Pair { .. } => to_json_binary(&pair::Cw20HookMsg::Swap {
+                        belief_price,
+                        max_spread: strategy.max_spread,
+                        to: None,
+                    })?,
+                    StrategyPool::Trio { .. } => to_json_binary(&trio::Cw20HookMsg::Swap {
+                        ask_asset,
+                        belief_price,
+                        max_spread: strategy.max_spread,
+                        to: None,
+                    })?,
+                },
+            })?,
+        }
+        .into(),
+    })
+}
+
+/// Creates the messages to provide the given assets as liquidity to the strategy pool.
+pub fn provide_liquidity_msgs(
+    strategy: &Strategy,
+    assets: Vec<Asset>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let pool = strategy.pool.contract_addr().to_string();
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for asset in &assets {
+        match &asset.info {
+            AssetInfo::NativeToken { denom } => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            }),
+            AssetInfo::Token { contract_addr } => messages.push(
+                WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pool.clone(),
+                        amount: asset.amount,
+                        expires: None,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            ),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    let msg = match &strategy.pool {
+        StrategyPool::Pair { .. } => to_json_binary(&pair::ExecuteMsg::ProvideLiquidity {
+            assets: assets
+                .try_into()
+                .map_err(|_| StdError::generic_err("Expected 2 assets"))?,
+            slippage_tolerance: strategy.max_spread,
+            receiver: None,
+        })?,
+        StrategyPool::Trio { .. } => to_json_binary(&trio::ExecuteMsg::ProvideLiquidity {
+            assets: assets
+                .try_into()
+                .map_err(|_| StdError::generic_err("Expected 3 assets"))?,
+            slippage_tolerance: strategy.max_spread,
+            receiver: None,
+        })?,
+    };
+
+    messages.push(
+        WasmMsg::Execute {
+            contract_addr: pool,
+            msg,
+            funds,
+        }
+        .into(),
+    );
+
+    Ok(messages)
+}
+
+/// Creates a message to call the given [CallbackMsg] on the vault itself.
+pub fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_json_binary(&ExecuteMsg::Callback(msg))?,
+        funds: vec![],
+    }
+    .into())
+}
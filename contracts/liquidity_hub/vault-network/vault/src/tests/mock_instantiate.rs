@@ -1,15 +1,20 @@
 use cosmwasm_std::{
+    coin,
     testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage},
-    Addr, Env, OwnedDeps,
+    Addr, Decimal, Env, OwnedDeps, Uint128,
 };
 use cw_multi_test::{App, Executor};
-use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::fee::Fee;
+use white_whale_std::pool_network::asset::{Asset, AssetInfo, PairType};
+use white_whale_std::pool_network::pair::PoolFee;
 
 use crate::contract::instantiate;
 
 use super::{
     get_fees, mock_creator,
-    store_code::{store_cw20_token_code, store_fee_collector_code, store_vault_code},
+    store_code::{
+        store_cw20_token_code, store_fee_collector_code, store_pair_code, store_vault_code,
+    },
 };
 
 use crate::tests::mock_app::mock_app;
@@ -79,6 +84,65 @@ pub fn app_mock_instantiate(app: &mut App, asset_info: AssetInfo) -> Addr {
     .unwrap()
 }
 
+/// Instantiates a fee-less pair between two native denoms, providing `liquidity` of each.
+pub fn app_mock_instantiate_pair(app: &mut App, denoms: [&str; 2], liquidity: u128) -> Addr {
+    let pair_id = store_pair_code(app);
+    let token_id = store_cw20_token_code(app);
+
+    let asset_infos = denoms.map(|denom| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    });
+
+    let pair_addr = app
+        .instantiate_contract(
+            pair_id,
+            mock_creator().sender,
+            &white_whale_std::pool_network::pair::InstantiateMsg {
+                asset_infos: asset_infos.clone(),
+                token_code_id: token_id,
+                asset_decimals: [6, 6],
+                pool_fees: PoolFee {
+                    protocol_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                    swap_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                    burn_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                },
+                fee_collector_addr: "fee_collector".to_string(),
+                pair_type: PairType::ConstantProduct,
+                token_factory_lp: false,
+            },
+            &[],
+            "pair",
+            None,
+        )
+        .unwrap();
+
+    let mut funds = denoms.map(|denom| coin(liquidity, denom)).to_vec();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    app.execute_contract(
+        mock_creator().sender,
+        pair_addr.clone(),
+        &white_whale_std::pool_network::pair::ExecuteMsg::ProvideLiquidity {
+            assets: asset_infos.map(|info| Asset {
+                info,
+                amount: Uint128::new(liquidity),
+            }),
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &funds,
+    )
+    .unwrap();
+
+    pair_addr
+}
+
 #[test]
 fn can_instantiate_with_different_tokens() {
     let mut app = mock_app();
@@ -35,3 +35,28 @@ pub fn store_fee_collector_code(app: &mut App) -> u64 {
 
     app.store_code(contract)
 }
+
+/// Stores the pair contract to the app
+pub fn store_pair_code(app: &mut App) -> u64 {
+    let contract = Box::new(
+        ContractWrapper::new(
+            terraswap_pair::contract::execute,
+            terraswap_pair::contract::instantiate,
+            terraswap_pair::contract::query,
+        )
+        .with_reply(terraswap_pair::contract::reply),
+    );
+
+    app.store_code(contract)
+}
+
+/// Stores the epoch manager contract to the app
+pub fn store_epoch_manager_code(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        epoch_manager::contract::execute,
+        epoch_manager::contract::instantiate,
+        epoch_manager::contract::query,
+    ));
+
+    app.store_code(contract)
+}
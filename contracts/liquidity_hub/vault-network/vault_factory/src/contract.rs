@@ -11,7 +11,7 @@ use crate::execute::{
     create_vault, migrate_vaults, remove_vault, update_config, update_vault_config,
 };
 use crate::migrations;
-use crate::queries::{get_config, get_vault, get_vaults};
+use crate::queries::{get_asset_vaults, get_config, get_vault, get_vaults};
 use crate::state::CONFIG;
 
 const CONTRACT_NAME: &str = "white_whale-vault_factory";
@@ -50,7 +50,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             asset_info,
             fees,
             token_factory_lp,
-        } => create_vault(deps, env, info, asset_info, fees, token_factory_lp),
+            vault_identifier,
+        } => create_vault(
+            deps,
+            env,
+            info,
+            asset_info,
+            fees,
+            token_factory_lp,
+            vault_identifier,
+        ),
         ExecuteMsg::UpdateVaultConfig { vault_addr, params } => {
             update_vault_config(deps, vault_addr, params)
         }
@@ -58,7 +67,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             vault_addr,
             vault_code_id,
         } => migrate_vaults(deps, vault_addr, vault_code_id),
-        ExecuteMsg::RemoveVault { asset_info } => remove_vault(deps, asset_info),
+        ExecuteMsg::RemoveVault {
+            asset_info,
+            vault_identifier,
+        } => remove_vault(deps, asset_info, vault_identifier),
         ExecuteMsg::UpdateConfig {
             owner,
             fee_collector_addr,
@@ -89,6 +101,10 @@ pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Resp
         migrations::migrate_to_v110(deps.branch())?;
     }
 
+    if storage_version < Version::parse("1.2.0")? {
+        migrations::migrate_to_v120(deps.branch())?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
@@ -121,7 +137,15 @@ mod test {
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => get_config(deps),
-        QueryMsg::Vault { asset_info } => get_vault(deps, asset_info),
-        QueryMsg::Vaults { start_after, limit } => get_vaults(deps, start_after, limit),
+        QueryMsg::Vault {
+            asset_info,
+            vault_identifier,
+        } => get_vault(deps, asset_info, vault_identifier),
+        QueryMsg::AssetVaults { asset_info } => get_asset_vaults(deps, asset_info),
+        QueryMsg::Vaults {
+            start_after,
+            start_after_identifier,
+            limit,
+        } => get_vaults(deps, start_after, start_after_identifier, limit),
     }
 }
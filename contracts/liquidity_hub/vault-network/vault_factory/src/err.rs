@@ -15,9 +15,15 @@ pub enum VaultFactoryError {
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("This asset already has a vault at \"{addr}\"")]
+    #[error("This asset already has a vault with the given identifier at \"{addr}\"")]
     ExistingVault { addr: Addr },
 
+    #[error("The vault identifier can't be empty")]
+    InvalidVaultIdentifier {},
+
+    #[error("This asset already has the maximum amount of vaults, {max}")]
+    TooManyVaults { max: usize },
+
     #[error("Attempt to migrate to version {new_version}, but contract is on a higher version {current_version}")]
     MigrateInvalidVersion {
         new_version: Version,
@@ -26,6 +32,9 @@ pub enum VaultFactoryError {
 
     #[error("Vault doesn't exist given the vault address provided")]
     NonExistentVault {},
+
+    #[error("Both start_after and start_after_identifier must be provided to paginate the vaults")]
+    InvalidPaginationCursor {},
 }
 
 impl From<semver::Error> for VaultFactoryError {
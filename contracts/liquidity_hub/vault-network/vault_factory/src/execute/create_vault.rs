@@ -1,13 +1,17 @@
-use cosmwasm_std::{to_json_binary, DepsMut, Env, MessageInfo, ReplyOn, Response, SubMsg, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, DepsMut, Env, MessageInfo, Order, ReplyOn, Response, SubMsg, WasmMsg,
+};
 use white_whale_std::fee::VaultFee;
 use white_whale_std::pool_network::asset::AssetInfo;
 use white_whale_std::vault_network::vault::InstantiateMsg;
-use white_whale_std::vault_network::vault_factory::INSTANTIATE_VAULT_REPLY_ID;
+use white_whale_std::vault_network::vault_factory::{
+    DEFAULT_VAULT_IDENTIFIER, INSTANTIATE_VAULT_REPLY_ID,
+};
 
 use crate::{
     asset::AssetReference,
     err::{StdResult, VaultFactoryError},
-    state::{CONFIG, TMP_VAULT_ASSET, VAULTS},
+    state::{CONFIG, MAX_VAULTS_PER_ASSET, TMP_VAULT_ASSET, VAULTS},
 };
 
 pub fn create_vault(
@@ -17,16 +21,44 @@ pub fn create_vault(
     asset_info: AssetInfo,
     fees: VaultFee,
     token_factory_lp: bool,
+    vault_identifier: Option<String>,
 ) -> StdResult<Response> {
     // check that owner is creating vault
     let config = CONFIG.load(deps.storage)?;
 
-    // check that existing vault does not exist
-    let existing_addr = VAULTS.may_load(deps.storage, asset_info.get_reference())?;
+    let vault_identifier = vault_identifier.unwrap_or_else(|| DEFAULT_VAULT_IDENTIFIER.to_string());
+    if vault_identifier.is_empty() {
+        return Err(VaultFactoryError::InvalidVaultIdentifier {});
+    }
+
+    // check that a vault with the same identifier does not exist for the asset
+    let existing_addr = VAULTS.may_load(
+        deps.storage,
+        (asset_info.get_reference(), vault_identifier.as_str()),
+    )?;
     if let Some((addr, _)) = existing_addr {
         return Err(VaultFactoryError::ExistingVault { addr });
     }
 
+    // cap the vaults of the asset, so the router can read them all when picking one to loan from
+    let asset_vaults = VAULTS
+        .prefix(asset_info.get_reference())
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .take(MAX_VAULTS_PER_ASSET)
+        .count();
+    if asset_vaults >= MAX_VAULTS_PER_ASSET {
+        return Err(VaultFactoryError::TooManyVaults {
+            max: MAX_VAULTS_PER_ASSET,
+        });
+    }
+
+    let asset_label = asset_info.clone().get_label(&deps.as_ref())?;
+    let label = if vault_identifier == DEFAULT_VAULT_IDENTIFIER {
+        format!("White Whale {asset_label} Vault")
+    } else {
+        format!("White Whale {asset_label} {vault_identifier} Vault")
+    };
+
     // check the fees are valid
     fees.flash_loan_fee.is_valid()?;
     fees.protocol_fee.is_valid()?;
@@ -46,20 +78,21 @@ pub fn create_vault(
                 token_factory_lp,
             })?,
             funds: info.funds,
-            label: format!(
-                "White Whale {} Vault",
-                asset_info.clone().get_label(&deps.as_ref())?
-            ),
+            label,
         }
         .into(),
         gas_limit: None,
         reply_on: ReplyOn::Success,
     };
 
-    // store asset for use in reply callback
+    // store asset and identifier for use in reply callback
     TMP_VAULT_ASSET.save(
         deps.storage,
-        &(asset_info.get_reference().to_vec(), asset_info),
+        &(
+            asset_info.get_reference().to_vec(),
+            vault_identifier,
+            asset_info,
+        ),
     )?;
 
     Ok(Response::new()
@@ -73,14 +106,17 @@ mod tests {
         testing::mock_info, to_json_binary, Addr, Decimal, ReplyOn, Response, StdError, SubMsg,
         WasmMsg,
     };
-    use cw_multi_test::Executor;
+    use cw_multi_test::{App, Executor};
     use white_whale_std::fee::{Fee, VaultFee};
     use white_whale_std::pool_network::asset::AssetInfo;
-    use white_whale_std::vault_network::vault_factory::INSTANTIATE_VAULT_REPLY_ID;
+    use white_whale_std::vault_network::vault_factory::{
+        VaultsResponse, DEFAULT_VAULT_IDENTIFIER, INSTANTIATE_VAULT_REPLY_ID,
+    };
 
     use crate::{
         contract::execute,
         err::VaultFactoryError,
+        state::MAX_VAULTS_PER_ASSET,
         tests::{
             get_fees, mock_app, mock_creator, mock_execute,
             mock_instantiate::{app_mock_instantiate, mock_instantiate},
@@ -101,6 +137,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
 
@@ -148,6 +185,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: true,
+                vault_identifier: None,
             },
         );
 
@@ -200,6 +238,7 @@ mod tests {
                 asset_info,
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
 
@@ -226,6 +265,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
             &[],
         )
@@ -238,6 +278,7 @@ mod tests {
                 factory_addr.clone(),
                 &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
                     asset_info: asset_info.clone(),
+                    vault_identifier: None,
                 },
             )
             .unwrap();
@@ -250,6 +291,7 @@ mod tests {
                 asset_info,
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
             &[],
         );
@@ -265,6 +307,151 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_create_multiple_vaults_for_asset() {
+        let mut app = mock_app();
+
+        let factory_addr = app_mock_instantiate(&mut app);
+
+        let asset_info = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        let creator = mock_creator();
+
+        for vault_identifier in [None, Some("premium".to_string())] {
+            app.execute_contract(
+                creator.sender.clone(),
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::ExecuteMsg::CreateVault {
+                    asset_info: asset_info.clone(),
+                    fees: get_fees(),
+                    token_factory_lp: false,
+                    vault_identifier,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let default_vault: Option<String> = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info: asset_info.clone(),
+                    vault_identifier: None,
+                },
+            )
+            .unwrap();
+        let premium_vault: Option<String> = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info: asset_info.clone(),
+                    vault_identifier: Some("premium".to_string()),
+                },
+            )
+            .unwrap();
+        assert_ne!(default_vault, premium_vault);
+
+        let asset_vaults: VaultsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::QueryMsg::AssetVaults {
+                    asset_info: asset_info.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            asset_vaults
+                .vaults
+                .into_iter()
+                .map(|v| (v.vault_identifier, v.vault))
+                .collect::<Vec<_>>(),
+            vec![
+                (DEFAULT_VAULT_IDENTIFIER.to_string(), default_vault.unwrap()),
+                ("premium".to_string(), premium_vault.unwrap()),
+            ]
+        );
+
+        // an empty identifier is not allowed
+        let res = app.execute_contract(
+            creator.sender,
+            factory_addr,
+            &white_whale_std::vault_network::vault_factory::ExecuteMsg::CreateVault {
+                asset_info,
+                fees: get_fees(),
+                token_factory_lp: false,
+                vault_identifier: Some(String::new()),
+            },
+            &[],
+        );
+        assert_eq!(
+            res.unwrap_err()
+                .root_cause()
+                .downcast_ref::<VaultFactoryError>()
+                .unwrap(),
+            &VaultFactoryError::InvalidVaultIdentifier {}
+        );
+    }
+
+    #[test]
+    fn cannot_create_more_vaults_than_the_cap_for_asset() {
+        let mut app = mock_app();
+
+        let factory_addr = app_mock_instantiate(&mut app);
+
+        let asset_info = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+
+        let creator = mock_creator();
+
+        let create_vault = |app: &mut App, vault_identifier: usize| {
+            app.execute_contract(
+                creator.sender.clone(),
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::ExecuteMsg::CreateVault {
+                    asset_info: asset_info.clone(),
+                    fees: get_fees(),
+                    token_factory_lp: false,
+                    vault_identifier: Some(vault_identifier.to_string()),
+                },
+                &[],
+            )
+        };
+
+        for vault_identifier in 0..MAX_VAULTS_PER_ASSET {
+            create_vault(&mut app, vault_identifier).unwrap();
+        }
+
+        let res = create_vault(&mut app, MAX_VAULTS_PER_ASSET);
+        assert_eq!(
+            res.unwrap_err()
+                .root_cause()
+                .downcast_ref::<VaultFactoryError>()
+                .unwrap(),
+            &VaultFactoryError::TooManyVaults {
+                max: MAX_VAULTS_PER_ASSET
+            }
+        );
+
+        // all the vaults of the asset are read at once
+        let asset_vaults: VaultsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr,
+                &white_whale_std::vault_network::vault_factory::QueryMsg::AssetVaults {
+                    asset_info,
+                },
+            )
+            .unwrap();
+        assert_eq!(asset_vaults.vaults.len(), MAX_VAULTS_PER_ASSET);
+    }
+
     #[test]
     fn does_error_if_invalid_fee() {
         let (mut deps, env) = mock_instantiate(1, 2);
@@ -290,6 +477,7 @@ mod tests {
                     },
                 },
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
         assert_eq!(
@@ -320,6 +508,7 @@ mod tests {
                     },
                 },
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
         assert_eq!(
@@ -345,6 +534,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
 
@@ -393,6 +583,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
         );
 
@@ -1,17 +1,25 @@
 use cosmwasm_std::{DepsMut, Response};
 
 use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::vault_network::vault_factory::DEFAULT_VAULT_IDENTIFIER;
 
 use crate::asset::AssetReference;
 use crate::err::{StdResult, VaultFactoryError};
 use crate::state::VAULTS;
 
-pub fn remove_vault(deps: DepsMut, asset_info: AssetInfo) -> StdResult<Response> {
-    if let Ok(None) = VAULTS.may_load(deps.storage, asset_info.get_reference()) {
+pub fn remove_vault(
+    deps: DepsMut,
+    asset_info: AssetInfo,
+    vault_identifier: Option<String>,
+) -> StdResult<Response> {
+    let vault_identifier = vault_identifier.unwrap_or_else(|| DEFAULT_VAULT_IDENTIFIER.to_string());
+    let key = (asset_info.get_reference(), vault_identifier.as_str());
+
+    if let Ok(None) = VAULTS.may_load(deps.storage, key) {
         return Err(VaultFactoryError::NonExistentVault {});
     }
 
-    VAULTS.remove(deps.storage, asset_info.get_reference());
+    VAULTS.remove(deps.storage, key);
 
     Ok(Response::new().add_attributes(vec![("method", "remove_vault")]))
 }
@@ -49,6 +57,7 @@ mod tests {
                 asset_info: asset_info_1.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
             &[],
         )
@@ -61,6 +70,7 @@ mod tests {
                 factory_addr.clone(),
                 &white_whale_std::vault_network::vault_factory::ExecuteMsg::RemoveVault {
                     asset_info: asset_info_1,
+                    vault_identifier: None,
                 },
                 &[],
             )
@@ -100,7 +110,10 @@ mod tests {
             deps.as_mut(),
             env,
             bad_actor,
-            white_whale_std::vault_network::vault_factory::ExecuteMsg::RemoveVault { asset_info },
+            white_whale_std::vault_network::vault_factory::ExecuteMsg::RemoveVault {
+                asset_info,
+                vault_identifier: None,
+            },
         );
 
         assert_eq!(res.unwrap_err(), VaultFactoryError::Unauthorized {})
@@ -119,7 +132,10 @@ mod tests {
             deps.as_mut(),
             env,
             creator,
-            white_whale_std::vault_network::vault_factory::ExecuteMsg::RemoveVault { asset_info },
+            white_whale_std::vault_network::vault_factory::ExecuteMsg::RemoveVault {
+                asset_info,
+                vault_identifier: None,
+            },
         );
 
         assert_eq!(res.unwrap_err(), VaultFactoryError::NonExistentVault {})
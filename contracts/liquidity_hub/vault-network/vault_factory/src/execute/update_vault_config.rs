@@ -48,6 +48,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
             &[],
         )
@@ -58,7 +59,10 @@ mod tests {
             .wrap()
             .query_wasm_smart(
                 factory_addr.clone(),
-                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault { asset_info },
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info,
+                    vault_identifier: None,
+                },
             )
             .unwrap();
 
@@ -90,6 +94,11 @@ mod tests {
                     new_owner: None,
                     new_vault_fees: None,
                     new_fee_collector_addr: None,
+                    new_strategy: None,
+                    new_deposit_cap: None,
+                    withdrawal_queue_enabled: None,
+                    new_fee_rebates: None,
+                    new_epoch_manager_addr: None,
                 },
             },
             &[],
@@ -128,6 +137,7 @@ mod tests {
                 asset_info: asset_info.clone(),
                 fees: get_fees(),
                 token_factory_lp: false,
+                vault_identifier: None,
             },
             &[],
         )
@@ -138,7 +148,10 @@ mod tests {
             .wrap()
             .query_wasm_smart(
                 factory_addr.clone(),
-                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault { asset_info },
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info,
+                    vault_identifier: None,
+                },
             )
             .unwrap();
 
@@ -158,6 +171,11 @@ mod tests {
                     new_owner: Some("new_owner".to_string()),
                     new_vault_fees: None,
                     new_fee_collector_addr: None,
+                    new_strategy: None,
+                    new_deposit_cap: None,
+                    withdrawal_queue_enabled: None,
+                    new_fee_rebates: None,
+                    new_epoch_manager_addr: None,
                 },
             },
             &[],
@@ -2,9 +2,13 @@ use cosmwasm_std::{Addr, DepsMut, Order, StdError};
 use cw_storage_plus::Map;
 
 use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::vault_network::vault_factory::DEFAULT_VAULT_IDENTIFIER;
 
 use crate::state::{TMP_VAULT_ASSET, VAULTS};
 
+/// The VAULTS state used from v1.1.0 up to v1.2.0, where a single vault could exist per asset.
+const VAULTSV110: Map<&[u8], (Addr, AssetInfo)> = Map::new("vaults");
+
 /// Migrates the VAULTS state from v1.0.9 and lower to v1.1.0, which includes the asset info.
 pub fn migrate_to_v110(deps: DepsMut) -> Result<(), StdError> {
     const VAULTSV109: Map<&[u8], Addr> = Map::new("vaults");
@@ -24,7 +28,30 @@ pub fn migrate_to_v110(deps: DepsMut) -> Result<(), StdError> {
                 denom: String::from_utf8(key.clone())?,
             };
 
-            VAULTS.save(deps.storage, &key, &(vault_addr, asset_info))?;
+            VAULTSV110.save(deps.storage, &key, &(vault_addr, asset_info))?;
+
+            Ok(())
+        })?;
+
+    TMP_VAULT_ASSET.remove(deps.storage);
+
+    Ok(())
+}
+
+/// Migrates the VAULTS state to v1.2.0, which allows multiple vaults per asset. Existing vaults
+/// are stored under the [DEFAULT_VAULT_IDENTIFIER].
+pub fn migrate_to_v120(deps: DepsMut) -> Result<(), StdError> {
+    // There are less than 30 vaults in each network at this point
+    let vaults_v110 = VAULTSV110
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(30)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    vaults_v110
+        .into_iter()
+        .try_for_each(|(key, vault)| -> Result<(), StdError> {
+            VAULTSV110.remove(deps.storage, &key);
+            VAULTS.save(deps.storage, (&key, DEFAULT_VAULT_IDENTIFIER), &vault)?;
 
             Ok(())
         })?;
@@ -1,6 +1,7 @@
 mod config;
 mod vault;
 
+pub use self::vault::get_asset_vaults;
 pub use self::vault::get_vault;
 pub use self::vault::get_vaults;
 pub use config::get_config;
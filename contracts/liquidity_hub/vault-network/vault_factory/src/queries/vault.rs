@@ -1,13 +1,28 @@
 use cosmwasm_std::{to_json_binary, Binary, Deps};
 
 use white_whale_std::pool_network::asset::AssetInfo;
-use white_whale_std::vault_network::vault_factory::{VaultInfo, VaultsResponse};
-
-use crate::state::read_vaults;
-use crate::{asset::AssetReference, err::StdResult, state::VAULTS};
+use white_whale_std::vault_network::vault_factory::{
+    VaultInfo, VaultsResponse, DEFAULT_VAULT_IDENTIFIER,
+};
+
+use crate::state::{read_asset_vaults, read_vaults};
+use crate::{
+    asset::AssetReference,
+    err::{StdResult, VaultFactoryError},
+    state::VAULTS,
+};
+
+pub fn get_vault(
+    deps: Deps,
+    asset_info: AssetInfo,
+    vault_identifier: Option<String>,
+) -> StdResult<Binary> {
+    let vault_identifier = vault_identifier.unwrap_or_else(|| DEFAULT_VAULT_IDENTIFIER.to_string());
 
-pub fn get_vault(deps: Deps, asset_info: AssetInfo) -> StdResult<Binary> {
-    let vault_option = VAULTS.may_load(deps.storage, asset_info.get_reference())?;
+    let vault_option = VAULTS.may_load(
+        deps.storage,
+        (asset_info.get_reference(), vault_identifier.as_str()),
+    )?;
     if let Some((vault_addr, _)) = vault_option {
         return Ok(to_json_binary(&vault_addr)?);
     }
@@ -15,11 +30,27 @@ pub fn get_vault(deps: Deps, asset_info: AssetInfo) -> StdResult<Binary> {
     Ok(to_json_binary(&vault_option)?)
 }
 
+pub fn get_asset_vaults(deps: Deps, asset_info: AssetInfo) -> StdResult<Binary> {
+    let vaults: Vec<VaultInfo> = read_asset_vaults(deps.storage, asset_info.get_reference())?;
+    Ok(to_json_binary(&VaultsResponse { vaults })?)
+}
+
 pub fn get_vaults(
     deps: Deps,
     start_after: Option<Vec<u8>>,
+    start_after_identifier: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
+    // the cursor is the (asset, identifier) key of the last vault returned, as an asset can have
+    // several vaults
+    let start_after = match (start_after, start_after_identifier) {
+        (Some(asset_info_reference), Some(vault_identifier)) => {
+            Some((asset_info_reference, vault_identifier))
+        }
+        (None, None) => None,
+        _ => return Err(VaultFactoryError::InvalidPaginationCursor {}),
+    };
+
     let vaults: Vec<VaultInfo> = read_vaults(deps.storage, deps.api, start_after, limit)?;
     Ok(to_json_binary(&VaultsResponse { vaults })?)
 }
@@ -31,6 +62,7 @@ mod tests {
 
     use white_whale_std::vault_network::vault_factory::{ExecuteMsg, QueryMsg, VaultsResponse};
 
+    use crate::err::VaultFactoryError;
     use crate::tests::{
         get_fees, mock_app, mock_creator, mock_instantiate::app_mock_instantiate, mock_query,
     };
@@ -44,6 +76,7 @@ mod tests {
                 asset_info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
+                vault_identifier: None,
             },
         );
 
@@ -70,6 +103,7 @@ mod tests {
                     asset_info: asset_info.clone(),
                     fees: get_fees(),
                     token_factory_lp: false,
+                    vault_identifier: None,
                 },
                 &[],
             )
@@ -85,7 +119,13 @@ mod tests {
         // check that the address was stored
         let vault_addr: Option<String> = app
             .wrap()
-            .query_wasm_smart(factory_addr, &QueryMsg::Vault { asset_info })
+            .query_wasm_smart(
+                factory_addr,
+                &QueryMsg::Vault {
+                    asset_info,
+                    vault_identifier: None,
+                },
+            )
             .unwrap();
 
         assert_eq!(vault_addr, Some(created_vault_addr.value.clone()));
@@ -113,6 +153,7 @@ mod tests {
                         asset_info: asset_info.clone(),
                         fees: get_fees().clone(),
                         token_factory_lp: false,
+                        vault_identifier: None,
                     },
                     &[],
                 )
@@ -136,6 +177,7 @@ mod tests {
                 factory_addr.clone(),
                 &QueryMsg::Vaults {
                     start_after: None,
+                    start_after_identifier: None,
                     limit: None,
                 },
             )
@@ -153,6 +195,7 @@ mod tests {
         // check that the addresses were stored, with pagination
         let mut paginated_vaults: Vec<(String, AssetInfo)> = Vec::new();
         let mut start_after: Option<Vec<u8>> = None;
+        let mut start_after_identifier: Option<String> = None;
         // there are 7 vaults in the factory, let's take 4 vaults at a time so we query 2 times
         for _ in 0..2 {
             let vaults_response: VaultsResponse = app
@@ -161,20 +204,15 @@ mod tests {
                     factory_addr.clone(),
                     &QueryMsg::Vaults {
                         start_after: start_after.clone(),
+                        start_after_identifier: start_after_identifier.clone(),
                         limit: Some(u32::try_from(4).unwrap()),
                     },
                 )
                 .unwrap();
 
-            start_after = Some(
-                vaults_response
-                    .clone()
-                    .vaults
-                    .last()
-                    .unwrap()
-                    .asset_info_reference
-                    .clone(),
-            );
+            let last_vault = vaults_response.vaults.last().unwrap();
+            start_after = Some(last_vault.asset_info_reference.clone());
+            start_after_identifier = Some(last_vault.vault_identifier.clone());
 
             let vaults = vaults_response
                 .vaults
@@ -188,5 +226,21 @@ mod tests {
         }
 
         assert_eq!(paginated_vaults, vaults);
+
+        // the cursor requires both the asset and the vault identifier
+        let err = app
+            .wrap()
+            .query_wasm_smart::<VaultsResponse>(
+                factory_addr,
+                &QueryMsg::Vaults {
+                    start_after,
+                    start_after_identifier: None,
+                    limit: None,
+                },
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&VaultFactoryError::InvalidPaginationCursor {}.to_string()));
     }
 }
@@ -29,19 +29,20 @@ pub fn vault_instantiate(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Resp
 
     let vault_address = deps.api.addr_validate(&res.contract_address)?;
 
-    // retrieve stored key from temp storage
-    let (asset_info_key, asset_info) = TMP_VAULT_ASSET.load(deps.storage)?;
+    // retrieve stored key and identifier from temp storage
+    let (asset_info_key, vault_identifier, asset_info) = TMP_VAULT_ASSET.load(deps.storage)?;
 
     // save to vault storage
     VAULTS.save(
         deps.storage,
-        asset_info_key.as_slice(),
+        (asset_info_key.as_slice(), vault_identifier.as_str()),
         &(vault_address.clone(), asset_info),
     )?;
 
     Ok(Response::new().add_attributes(vec![
         ("action", "reply_vault_instantiate"),
         ("vault_address", &vault_address.into_string()),
+        ("vault_identifier", &vault_identifier),
     ]))
 }
 
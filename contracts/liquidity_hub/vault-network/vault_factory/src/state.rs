@@ -6,45 +6,75 @@ use white_whale_std::vault_network::vault_factory::{Config, VaultInfo};
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const VAULTS: Map<&[u8], (Addr, AssetInfo)> = Map::new("vaults");
+/// The vaults created by the factory, keyed by (asset info reference, vault identifier).
+pub const VAULTS: Map<(&[u8], &str), (Addr, AssetInfo)> = Map::new("asset_vaults");
 
-/// Used to temporarily store the asset being instantiated between `create_vault` and `reply` callback
-pub const TMP_VAULT_ASSET: Item<(Vec<u8>, AssetInfo)> = Item::new("tmp_vault_asset");
+/// Used to temporarily store the asset and the vault identifier being instantiated between
+/// `create_vault` and `reply` callback
+pub const TMP_VAULT_ASSET: Item<(Vec<u8>, String, AssetInfo)> = Item::new("tmp_vault_asset");
+
+/// The maximum amount of vaults the factory creates for an asset, so they can all be read at once.
+pub const MAX_VAULTS_PER_ASSET: usize = 30;
 
 // settings for pagination
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
 
+/// Reads the vaults created by the factory, starting after the vault with the given asset info
+/// reference and vault identifier.
 pub fn read_vaults(
     storage: &dyn Storage,
     _api: &dyn Api,
-    start_after: Option<Vec<u8>>,
+    start_after: Option<(Vec<u8>, String)>,
     limit: Option<u32>,
 ) -> StdResult<Vec<VaultInfo>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = calc_range_start(start_after).map(Bound::ExclusiveRaw);
 
-    VAULTS
-        .range(storage, start, None, Order::Ascending)
+    let vaults = VAULTS.range(
+        storage,
+        start_after
+            .as_ref()
+            .map(|(asset_info_reference, vault_identifier)| {
+                Bound::exclusive((asset_info_reference.as_slice(), vault_identifier.as_str()))
+            }),
+        None,
+        Order::Ascending,
+    );
+
+    vaults
         .take(limit)
         .map(|item| {
-            let (key, vault_data) = item?;
-            let (vault_addr, asset_info) = vault_data;
+            let ((asset_info_reference, vault_identifier), (vault_addr, asset_info)) = item?;
 
             Ok(VaultInfo {
                 vault: vault_addr.to_string(),
                 asset_info,
-                asset_info_reference: key,
+                asset_info_reference,
+                vault_identifier,
             })
         })
         .collect()
 }
 
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start(start_after: Option<Vec<u8>>) -> Option<Vec<u8>> {
-    start_after.map(|asset_info| {
-        let mut v = asset_info;
-        v.push(1);
-        v
-    })
+/// Reads all the vaults managing the given asset, of which there are at most
+/// [MAX_VAULTS_PER_ASSET].
+pub fn read_asset_vaults(
+    storage: &dyn Storage,
+    asset_info_reference: &[u8],
+) -> StdResult<Vec<VaultInfo>> {
+    VAULTS
+        .prefix(asset_info_reference)
+        .range(storage, None, None, Order::Ascending)
+        .take(MAX_VAULTS_PER_ASSET)
+        .map(|item| {
+            let (vault_identifier, (vault_addr, asset_info)) = item?;
+
+            Ok(VaultInfo {
+                vault: vault_addr.to_string(),
+                asset_info,
+                asset_info_reference: asset_info_reference.to_vec(),
+                vault_identifier,
+            })
+        })
+        .collect()
 }
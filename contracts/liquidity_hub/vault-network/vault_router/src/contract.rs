@@ -34,7 +34,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
-        ExecuteMsg::FlashLoan { assets, msgs } => flash_loan(deps, info, assets, msgs),
+        ExecuteMsg::FlashLoan { assets, msgs } => flash_loan(deps, env, info, assets, msgs),
         ExecuteMsg::NextLoan {
             initiator,
             source_vault,
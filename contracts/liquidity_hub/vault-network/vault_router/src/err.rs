@@ -25,6 +25,9 @@ pub enum VaultRouterError {
     #[error("Attempt to flash-loan asset \"{asset}\" that does not have a vault")]
     InvalidAsset { asset: Asset },
 
+    #[error("No vault has enough liquidity to flash-loan \"{asset}\"")]
+    InsufficientLiquidity { asset: Asset },
+
     #[error("Negative profits when attempting to flash-loan asset \"{input}\" (got {output_amount}, needed {required_amount})")]
     NegativeProfit {
         input: Asset,
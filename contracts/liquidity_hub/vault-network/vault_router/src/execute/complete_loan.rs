@@ -1,12 +1,43 @@
 use cosmwasm_std::{
-    attr, coins, to_json_binary, Addr, BankMsg, CosmosMsg, DepsMut, Env, MessageInfo, Response,
-    WasmMsg,
+    attr, coins, to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Response, Uint128, WasmMsg,
 };
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
-use white_whale_std::vault_network::vault::PaybackAmountResponse;
+use white_whale_std::vault_network::vault::{FeeRebatesResponse, PaybackAmountResponse};
 
 use crate::err::{StdResult, VaultRouterError};
 
+/// Queries the amount the router has to pay back to the vault for a loan taken on behalf of the
+/// initiator. The vault only grants the fee rebates of the initiator if it trusts this router,
+/// otherwise the rebates of the router itself apply.
+fn query_payback_amount(
+    deps: Deps,
+    env: &Env,
+    vault: &str,
+    amount: Uint128,
+    initiator: &Addr,
+) -> StdResult<Uint128> {
+    let FeeRebatesResponse { fee_rebates } = deps.querier.query_wasm_smart(
+        vault,
+        &white_whale_std::vault_network::vault::QueryMsg::FeeRebates {},
+    )?;
+
+    let initiator = match fee_rebates.and_then(|fee_rebates| fee_rebates.router_addr) {
+        Some(router_addr) if router_addr == env.contract.address => initiator,
+        _ => &env.contract.address,
+    };
+
+    let PaybackAmountResponse { payback_amount, .. } = deps.querier.query_wasm_smart(
+        vault,
+        &white_whale_std::vault_network::vault::QueryMsg::GetPaybackAmount {
+            amount,
+            initiator: Some(initiator.to_string()),
+        },
+    )?;
+
+    Ok(payback_amount)
+}
+
 pub fn complete_loan(
     deps: DepsMut,
     env: Env,
@@ -25,12 +56,8 @@ pub fn complete_loan(
     let messages: Vec<Vec<CosmosMsg>> = assets
         .into_iter()
         .map(|(vault, loaned_asset)| {
-            let payback_amount: PaybackAmountResponse = deps.querier.query_wasm_smart(
-                vault.clone(),
-                &white_whale_std::vault_network::vault::QueryMsg::GetPaybackAmount {
-                    amount: loaned_asset.amount,
-                },
-            )?;
+            let payback_amount =
+                query_payback_amount(deps.as_ref(), &env, &vault, loaned_asset.amount, &initiator)?;
 
             // calculate amount router has after performing flash-loan
             let final_amount = match &loaned_asset.info {
@@ -53,25 +80,22 @@ pub fn complete_loan(
                 }
             };
 
-            let profit_amount = final_amount
-                .checked_sub(payback_amount.payback_amount)
-                .map_err(|_| VaultRouterError::NegativeProfit {
+            let profit_amount = final_amount.checked_sub(payback_amount).map_err(|_| {
+                VaultRouterError::NegativeProfit {
                     input: loaned_asset.clone(),
                     output_amount: final_amount,
-                    required_amount: payback_amount.payback_amount,
-                })?;
+                    required_amount: payback_amount,
+                }
+            })?;
 
-            attributes.push(attr(
-                "payback_amount",
-                payback_amount.payback_amount.to_string(),
-            ));
+            attributes.push(attr("payback_amount", payback_amount.to_string()));
             attributes.push(attr("profit_amount", profit_amount.to_string()));
 
             let mut response_messages: Vec<CosmosMsg> = vec![];
             let payback_loan_msg: StdResult<CosmosMsg> = match loaned_asset.info.clone() {
                 AssetInfo::NativeToken { denom } => Ok(BankMsg::Send {
                     to_address: vault,
-                    amount: coins(payback_amount.payback_amount.u128(), denom),
+                    amount: coins(payback_amount.u128(), denom),
                 }
                 .into()),
                 AssetInfo::Token { contract_addr } => Ok(WasmMsg::Execute {
@@ -79,7 +103,7 @@ pub fn complete_loan(
                     funds: vec![],
                     msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
                         recipient: vault,
-                        amount: payback_amount.payback_amount,
+                        amount: payback_amount,
                     })?,
                 }
                 .into()),
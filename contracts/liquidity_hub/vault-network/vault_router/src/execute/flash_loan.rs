@@ -1,6 +1,10 @@
-use cosmwasm_std::{to_json_binary, CosmosMsg, DepsMut, MessageInfo, Response, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+};
 
 use white_whale_std::pool_network::asset::Asset;
+use white_whale_std::vault_network::vault::FlashLoanQuoteResponse;
+use white_whale_std::vault_network::vault_factory::VaultsResponse;
 use white_whale_std::vault_network::vault_router::ExecuteMsg;
 
 use crate::{
@@ -13,6 +17,7 @@ use crate::{
 /// vault.
 pub fn flash_loan(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     assets: Vec<Asset>,
     msgs: Vec<CosmosMsg>,
@@ -27,19 +32,15 @@ pub fn flash_loan(
     let vaults = assets
         .into_iter()
         .map(|asset| {
-            // query factory for address
-            let address: Option<String> = deps.querier.query_wasm_smart(
-                config.vault_factory.clone(),
-                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
-                    asset_info: asset.info.clone(),
-                },
+            // find the cheapest vault with enough liquidity for the loan
+            let address = select_vault(
+                deps.as_ref(),
+                &env,
+                &config.vault_factory,
+                &info.sender,
+                &asset,
             )?;
 
-            // return InvalidAsset if address doesn't exist
-            let address = address.ok_or(VaultRouterError::InvalidAsset {
-                asset: asset.clone(),
-            })?;
-
             Ok((address, asset))
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -55,13 +56,14 @@ pub fn flash_loan(
                     &white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                         amount: asset.amount,
                         msg: to_json_binary(&ExecuteMsg::NextLoan {
-                            initiator: info.sender,
+                            initiator: info.sender.clone(),
                             source_vault: vault.to_string(),
                             source_vault_asset_info: asset.info.clone(),
                             to_loan: next_vaults.to_vec(),
                             payload: msgs,
                             loaned_assets: vaults,
                         })?,
+                        initiator: Some(info.sender.to_string()),
                     },
                 )?,
                 funds: vec![],
@@ -75,22 +77,85 @@ pub fn flash_loan(
         .add_attributes(vec![("method", "flash_loan")]))
 }
 
+/// Selects the vault to flash-loan the given asset from. Out of all the vaults managing the asset,
+/// picks the one with the lowest payback amount for the initiator that has enough liquidity to
+/// serve the loan, counting the liquidity deployed in the vault's strategy. Vaults failing to quote
+/// the loan are skipped.
+fn select_vault(
+    deps: Deps,
+    env: &Env,
+    vault_factory: &Addr,
+    initiator: &Addr,
+    asset: &Asset,
+) -> StdResult<String> {
+    let VaultsResponse { vaults } = deps.querier.query_wasm_smart(
+        vault_factory,
+        &white_whale_std::vault_network::vault_factory::QueryMsg::AssetVaults {
+            asset_info: asset.info.clone(),
+        },
+    )?;
+
+    if vaults.is_empty() {
+        return Err(VaultRouterError::InvalidAsset {
+            asset: asset.clone(),
+        });
+    }
+
+    let mut selected_vault: Option<(String, Uint128)> = None;
+    for vault_info in vaults {
+        let quote: cosmwasm_std::StdResult<FlashLoanQuoteResponse> = deps.querier.query_wasm_smart(
+            vault_info.vault.clone(),
+            &white_whale_std::vault_network::vault::QueryMsg::FlashLoanQuote {
+                amount: asset.amount,
+                sender: env.contract.address.to_string(),
+                initiator: Some(initiator.to_string()),
+            },
+        );
+        // vaults failing to quote the loan, i.e. not migrated yet, are skipped
+        let Ok(FlashLoanQuoteResponse {
+            flash_loan_enabled,
+            available_liquidity,
+            payback_amount,
+        }) = quote
+        else {
+            continue;
+        };
+        if !flash_loan_enabled || available_liquidity < asset.amount {
+            continue;
+        }
+
+        match &selected_vault {
+            Some((_, selected_payback_amount)) if *selected_payback_amount <= payback_amount => {}
+            _ => selected_vault = Some((vault_info.vault, payback_amount)),
+        }
+    }
+
+    selected_vault
+        .map(|(vault, _)| vault)
+        .ok_or(VaultRouterError::InsufficientLiquidity {
+            asset: asset.clone(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
-        coins, from_json, to_json_binary, Attribute, BankMsg, CosmosMsg, Event, Response, Uint128,
-        WasmMsg,
+        coins, from_json, to_json_binary, Addr, Attribute, BankMsg, CosmosMsg, Decimal, Event,
+        Response, Uint128, WasmMsg,
     };
-    use cw_multi_test::Executor;
+    use cw_multi_test::{AppResponse, Executor};
 
+    use white_whale_std::fee::{Fee, VaultFee};
     use white_whale_std::pool_network::asset::{Asset, AssetInfo};
     use white_whale_std::vault_network::vault_router::ExecuteMsg;
 
     use crate::{
         err::VaultRouterError,
         tests::{
-            create_dummy_contract, mock_admin, mock_app_with_balance, mock_creator, mock_execute,
+            create_dummy_contract, get_fees, mock_admin, mock_app_with_balance, mock_creator,
+            mock_execute,
             mock_instantiate::{app_mock_instantiate, AppInstantiateResponse},
+            store_code::store_legacy_vault_code,
         },
     };
 
@@ -181,6 +246,234 @@ mod tests {
         assert_eq!(payload_event, expected_payload_event);
     }
 
+    #[test]
+    fn does_pick_cheapest_vault_with_enough_liquidity() {
+        let mut app = mock_app_with_balance(vec![(mock_admin(), coins(30_132, "uluna"))]);
+        let AppInstantiateResponse {
+            router_addr,
+            native_vault_addr,
+            factory_addr,
+            ..
+        } = app_mock_instantiate(&mut app);
+
+        // create a second, cheaper, uluna vault
+        app.execute_contract(
+            mock_admin(),
+            factory_addr.clone(),
+            &white_whale_std::vault_network::vault_factory::ExecuteMsg::CreateVault {
+                asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                fees: VaultFee {
+                    flash_loan_fee: Fee {
+                        share: Decimal::permille(1),
+                    },
+                    protocol_fee: Fee {
+                        share: Decimal::permille(1),
+                    },
+                    burn_fee: Fee {
+                        share: Decimal::zero(),
+                    },
+                },
+                token_factory_lp: false,
+                vault_identifier: Some("low_fee".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        let low_fee_vault_addr: Addr = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr,
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    vault_identifier: Some("low_fee".to_string()),
+                },
+            )
+            .unwrap();
+
+        // the cheap vault doesn't have enough liquidity for the loan yet
+        app.send_tokens(
+            mock_admin(),
+            low_fee_vault_addr.clone(),
+            &coins(500, "uluna"),
+        )
+        .unwrap();
+
+        let dummy_contract_addr = create_dummy_contract(&mut app);
+        app.send_tokens(
+            mock_admin(),
+            dummy_contract_addr.clone(),
+            &coins(132, "uluna"),
+        )
+        .unwrap();
+
+        let flash_loan_msg = ExecuteMsg::FlashLoan {
+            assets: vec![Asset {
+                amount: Uint128::new(1_000),
+                info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            }],
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: dummy_contract_addr.into_string(),
+                msg: to_json_binary(&crate::tests::ExecuteMsg::Send {
+                    to_address: router_addr.clone(),
+                    amount: coins(66, "uluna"),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+        };
+
+        let loaned_from = |res: &AppResponse, vault: &Addr| {
+            res.events.iter().any(|event| {
+                event.ty == "wasm"
+                    && event.attributes.contains(&Attribute {
+                        key: "_contract_addr".to_string(),
+                        value: vault.to_string(),
+                    })
+                    && event.attributes.contains(&Attribute {
+                        key: "method".to_string(),
+                        value: "flash_loan".to_string(),
+                    })
+            })
+        };
+
+        // should loan from the default vault, as the cheap one can't serve the loan
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                router_addr.clone(),
+                &flash_loan_msg,
+                &[],
+            )
+            .unwrap();
+        assert!(loaned_from(&res, &native_vault_addr));
+        assert!(!loaned_from(&res, &low_fee_vault_addr));
+
+        // add liquidity to the cheap vault, it should be picked now
+        app.send_tokens(
+            mock_admin(),
+            low_fee_vault_addr.clone(),
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        let res = app
+            .execute_contract(mock_creator().sender, router_addr, &flash_loan_msg, &[])
+            .unwrap();
+        assert!(loaned_from(&res, &low_fee_vault_addr));
+        assert!(!loaned_from(&res, &native_vault_addr));
+    }
+
+    #[test]
+    fn does_skip_vaults_failing_to_quote() {
+        let mut app = mock_app_with_balance(vec![(mock_admin(), coins(20_066, "uluna"))]);
+        let AppInstantiateResponse {
+            router_addr,
+            native_vault_addr,
+            factory_addr,
+            ..
+        } = app_mock_instantiate(&mut app);
+
+        // create a second uluna vault with enough liquidity, then migrate it to a code that
+        // can't quote loans
+        app.execute_contract(
+            mock_admin(),
+            factory_addr.clone(),
+            &white_whale_std::vault_network::vault_factory::ExecuteMsg::CreateVault {
+                asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+                fees: get_fees(),
+                token_factory_lp: false,
+                vault_identifier: Some("legacy".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+        let legacy_vault_addr: Addr = app
+            .wrap()
+            .query_wasm_smart(
+                factory_addr.clone(),
+                &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    vault_identifier: Some("legacy".to_string()),
+                },
+            )
+            .unwrap();
+        app.send_tokens(
+            mock_admin(),
+            legacy_vault_addr.clone(),
+            &coins(10_000, "uluna"),
+        )
+        .unwrap();
+
+        let legacy_vault_id = store_legacy_vault_code(&mut app);
+        app.execute_contract(
+            mock_admin(),
+            factory_addr,
+            &white_whale_std::vault_network::vault_factory::ExecuteMsg::MigrateVaults {
+                vault_addr: Some(legacy_vault_addr.to_string()),
+                vault_code_id: legacy_vault_id,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let dummy_contract_addr = create_dummy_contract(&mut app);
+        app.send_tokens(
+            mock_admin(),
+            dummy_contract_addr.clone(),
+            &coins(66, "uluna"),
+        )
+        .unwrap();
+
+        // the loan is served by the default vault
+        let res = app
+            .execute_contract(
+                mock_creator().sender,
+                router_addr.clone(),
+                &ExecuteMsg::FlashLoan {
+                    assets: vec![Asset {
+                        amount: Uint128::new(1_000),
+                        info: AssetInfo::NativeToken {
+                            denom: "uluna".to_string(),
+                        },
+                    }],
+                    msgs: vec![WasmMsg::Execute {
+                        contract_addr: dummy_contract_addr.into_string(),
+                        msg: to_json_binary(&crate::tests::ExecuteMsg::Send {
+                            to_address: router_addr,
+                            amount: coins(66, "uluna"),
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    }
+                    .into()],
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.events.iter().any(|event| {
+            event.ty == "wasm"
+                && event.attributes.contains(&Attribute {
+                    key: "_contract_addr".to_string(),
+                    value: native_vault_addr.to_string(),
+                })
+                && event.attributes.contains(&Attribute {
+                    key: "method".to_string(),
+                    value: "flash_loan".to_string(),
+                })
+        }));
+    }
+
     #[test]
     fn does_reject_invalid_asset() {
         let mut app = mock_app_with_balance(vec![(mock_admin(), coins(10_000, "uluna"))]);
@@ -215,6 +508,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn does_reject_loan_exceeding_liquidity() {
+        let mut app = mock_app_with_balance(vec![(mock_admin(), coins(10_000, "uluna"))]);
+        let AppInstantiateResponse { router_addr, .. } = app_mock_instantiate(&mut app);
+
+        let borrow_asset = Asset {
+            amount: Uint128::new(100_000),
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        };
+
+        let err: VaultRouterError = app
+            .execute_contract(
+                mock_creator().sender,
+                router_addr,
+                &ExecuteMsg::FlashLoan {
+                    assets: vec![borrow_asset.clone()],
+                    msgs: vec![],
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+
+        assert_eq!(
+            err,
+            VaultRouterError::InsufficientLiquidity {
+                asset: borrow_asset
+            }
+        );
+    }
+
     #[test]
     fn does_allow_empty_loan() {
         let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
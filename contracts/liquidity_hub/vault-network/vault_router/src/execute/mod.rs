@@ -1,4 +1,4 @@
-mod complete_loan;
+pub(crate) mod complete_loan;
 mod flash_loan;
 mod next_loan;
 mod update_config;
@@ -1,6 +1,7 @@
 use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, WasmMsg};
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 
+use white_whale_std::vault_network::vault_factory::VaultsResponse;
 use white_whale_std::vault_network::vault_router::ExecuteMsg;
 
 use crate::err::{StdResult, VaultRouterError};
@@ -21,20 +22,21 @@ pub fn next_loan(
     // check that the source vault is executing this message and it is a vault created by the WW vault factory
     let config = CONFIG.load(deps.storage)?;
 
-    let Some(queried_vault) = deps.querier.query_wasm_smart::<Option<String>>(
+    let VaultsResponse {
+        vaults: queried_vaults,
+    } = deps.querier.query_wasm_smart(
         config.vault_factory,
-        &white_whale_std::vault_network::vault_factory::QueryMsg::Vault {
+        &white_whale_std::vault_network::vault_factory::QueryMsg::AssetVaults {
             asset_info: source_vault_asset,
         },
-    )?
-    else {
-        return Err(VaultRouterError::Unauthorized {});
-    };
+    )?;
 
     let validated_source_vault = deps.api.addr_validate(&source_vault)?;
 
     if info.sender != validated_source_vault
-        || deps.api.addr_validate(&queried_vault)? != validated_source_vault
+        || !queried_vaults
+            .iter()
+            .any(|vault_info| vault_info.vault == validated_source_vault.as_str())
     {
         return Err(VaultRouterError::Unauthorized {});
     }
@@ -49,13 +51,14 @@ pub fn next_loan(
                     &white_whale_std::vault_network::vault::ExecuteMsg::FlashLoan {
                         amount: asset.amount,
                         msg: to_json_binary(&ExecuteMsg::NextLoan {
-                            initiator,
+                            initiator: initiator.clone(),
                             source_vault: vault.to_string(),
                             source_vault_asset_info: asset.info.clone(),
                             to_loan: loans.to_vec(),
                             payload,
                             loaned_assets,
                         })?,
+                        initiator: Some(initiator.to_string()),
                     },
                 )?,
             }
@@ -141,6 +144,7 @@ mod tests {
                     asset_info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
+                    vault_identifier: None,
                 },
             )
             .unwrap();
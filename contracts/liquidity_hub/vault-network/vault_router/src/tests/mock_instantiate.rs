@@ -93,6 +93,7 @@ pub fn app_mock_instantiate(app: &mut App) -> AppInstantiateResponse {
             },
             fees: get_fees(),
             token_factory_lp: false,
+            vault_identifier: None,
         },
         &[],
     )
@@ -106,6 +107,7 @@ pub fn app_mock_instantiate(app: &mut App) -> AppInstantiateResponse {
                 asset_info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
+                vault_identifier: None,
             },
         )
         .unwrap();
@@ -148,6 +150,7 @@ pub fn app_mock_instantiate(app: &mut App) -> AppInstantiateResponse {
             },
             fees: get_fees(),
             token_factory_lp: false,
+            vault_identifier: None,
         },
         &[],
     )
@@ -160,6 +163,7 @@ pub fn app_mock_instantiate(app: &mut App) -> AppInstantiateResponse {
                 asset_info: AssetInfo::Token {
                     contract_addr: token_addr.clone().into_string(),
                 },
+                vault_identifier: None,
             },
         )
         .unwrap();
@@ -1,3 +1,4 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
 use cw_multi_test::{App, ContractWrapper};
 
 use crate::contract::{execute, instantiate, migrate, query};
@@ -70,3 +71,31 @@ pub fn store_dummy_flash_loan_contract(app: &mut App) -> u64 {
 
     app.store_code(Box::new(contract))
 }
+
+/// Stores a stand-in for a vault that wasn't migrated yet, which answers no query.
+pub fn store_legacy_vault_code(app: &mut App) -> u64 {
+    fn instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        Err(StdError::generic_err("unsupported query"))
+    }
+
+    fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_migrate(migrate),
+    ))
+}
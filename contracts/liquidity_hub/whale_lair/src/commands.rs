@@ -1,37 +1,148 @@
 use cosmwasm_std::{
-    Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Order, Response, StdResult,
-    Timestamp, Uint128, Uint64,
+    from_json, to_json_binary, Addr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, Timestamp, Uint128, Uint64, WasmMsg,
 };
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use white_whale_std::pool_network::asset;
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::router::SwapOperation;
 
-use white_whale_std::whale_lair::Bond;
+use white_whale_std::whale_lair::{Bond, CallbackMsg, Cw20HookMsg, ExecuteMsg};
 
-use crate::helpers::validate_growth_rate;
+use crate::helpers::{get_total_weighted_amount, get_weighted_amount, validate_growth_rate};
 use crate::queries::MAX_PAGE_LIMIT;
-use crate::state::{update_global_weight, update_local_weight, BOND, CONFIG, GLOBAL, UNBOND};
+use crate::state::{
+    add_lock, get_lock_weight, get_vote_escrow_global_index, max_lock_duration_seconds,
+    remove_lock, save_checkpoint, update_global_weight, update_local_weight, BOND, CONFIG, GLOBAL,
+    LOCK, REWARD_RECIPIENT, UNBOND,
+};
 use crate::{helpers, ContractError};
 
-/// Bonds the provided asset.
+/// Bonds the provided native asset on behalf of the owner, or the sender if not set, locking it
+/// for the given duration in vote-escrow mode.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn bond(
-    mut deps: DepsMut,
+    deps: DepsMut,
     timestamp: Timestamp,
     info: MessageInfo,
     env: Env,
     asset: Asset,
+    lock_duration: Option<Uint64>,
+    owner: Option<String>,
 ) -> Result<Response, ContractError> {
-    // validate the denom sent is the whitelisted one for bonding
+    // cw20 tokens are bonded through the receive hook
     let denom = match asset.info.clone() {
         AssetInfo::NativeToken { denom } => denom,
         AssetInfo::Token { .. } => return Err(ContractError::InvalidBondingAsset {}),
     };
 
-    helpers::validate_funds(&deps, &info, &asset, denom.clone())?;
-    helpers::validate_claimed(&deps, &info)?;
+    helpers::validate_funds(&deps, &info, &asset, denom)?;
+    let owner =
+        helpers::get_bond_owner(&deps, &env, &info.sender, owner, &asset.info, lock_duration)?;
+
+    settle_and_bond(deps, timestamp, env, owner, asset, lock_duration)
+}
+
+/// Bonds the cw20 tokens sent to the contract with a [Cw20HookMsg::Bond].
+pub(crate) fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_json(&msg.msg)? {
+        Cw20HookMsg::Bond {
+            lock_duration,
+            owner,
+        } => {
+            // the sender of the message is the cw20 token contract
+            let asset = Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender.to_string(),
+                },
+                amount: msg.amount,
+            };
+
+            let config = CONFIG.load(deps.storage)?;
+            helpers::validate_bonding_asset(&config, &asset.info)?;
+            if asset.amount.is_zero() {
+                return Err(ContractError::AssetMismatch {});
+            }
+
+            let sender = deps.api.addr_validate(&msg.sender)?;
+            let owner =
+                helpers::get_bond_owner(&deps, &env, &sender, owner, &asset.info, lock_duration)?;
+            settle_and_bond(deps, env.block.time, env, owner, asset, lock_duration)
+        }
+        Cw20HookMsg::Redeem {} => {
+            let address = deps.api.addr_validate(&msg.sender)?;
+            redeem(deps, env, info.sender, address, msg.amount)
+        }
+    }
+}
+
+/// Bonds the provided asset, which was already received by the contract, on behalf of the address.
+/// Pending rewards on the fee distributor are claimed first if there are any.
+fn settle_and_bond(
+    deps: DepsMut,
+    timestamp: Timestamp,
+    env: Env,
+    address: Addr,
+    asset: Asset,
+    lock_duration: Option<Uint64>,
+) -> Result<Response, ContractError> {
     helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+    let lock_end = helpers::get_lock_end(&deps, timestamp, &address, &asset.info, lock_duration)?;
+
+    // settle the pending rewards before the bond changes, as they are based on it
+    if let Some(messages) = helpers::claim_before_callback(
+        &deps,
+        &env,
+        &address,
+        CallbackMsg::Bond {
+            address: address.clone(),
+            asset: asset.clone(),
+            lock_end,
+        },
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "bond".to_string()),
+                ("address", address.to_string()),
+                ("claim_rewards", "true".to_string()),
+            ]));
+    }
+
+    bond_asset(deps, timestamp, address, asset, lock_end)
+}
+
+/// Bonds the provided asset on behalf of the address. The asset must have been validated and
+/// received by the contract already. The lock end is only set in vote-escrow mode.
+fn bond_asset(
+    mut deps: DepsMut,
+    timestamp: Timestamp,
+    address: Addr,
+    asset: Asset,
+    lock_end: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    if let Some(lock_end) = lock_end {
+        lock_asset(&mut deps, timestamp, &address, &asset, lock_end)?;
+
+        return Ok(Response::default().add_attributes(vec![
+            ("action", "bond".to_string()),
+            ("address", address.to_string()),
+            ("asset", asset.to_string()),
+            ("lock_end", lock_end.to_string()),
+        ]));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let denom = asset.info.to_string();
+    let weighted_amount = get_weighted_amount(&config, &asset);
 
     let mut bond = BOND
-        .key((&info.sender, &denom))
+        .key((&address, &denom))
         .may_load(deps.storage)?
         .unwrap_or(Bond {
             asset: Asset {
@@ -44,34 +155,194 @@ pub(crate) fn bond(
     // update local values
     bond.asset.amount = bond.asset.amount.checked_add(asset.amount)?;
     // let new_bond_weight = get_weight(timestamp, bond.weight, asset.amount, config.growth_rate, bond.timestamp)?;
-    bond.weight = bond.weight.checked_add(asset.amount)?;
-    bond = update_local_weight(&mut deps, info.sender.clone(), timestamp, bond)?;
+    bond.weight = bond.weight.checked_add(weighted_amount)?;
+    bond = update_local_weight(&mut deps, address.clone(), timestamp, bond)?;
 
-    BOND.save(deps.storage, (&info.sender, &denom), &bond)?;
+    BOND.save(deps.storage, (&address, &denom), &bond)?;
 
     // update global values
     let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
     // global_index = update_global_weight(&mut deps, timestamp, global_index)?;
 
     // include time term in the weight
-    global_index.weight = global_index.weight.checked_add(asset.amount)?;
+    global_index.weight = global_index.weight.checked_add(weighted_amount)?;
     global_index.bonded_amount = global_index.bonded_amount.checked_add(asset.amount)?;
     global_index.bonded_assets =
         asset::aggregate_assets(global_index.bonded_assets, vec![asset.clone()])?;
+    global_index.weighted_amount = get_total_weighted_amount(&config, &global_index.bonded_assets)?;
     global_index = update_global_weight(&mut deps, timestamp, global_index)?;
 
     GLOBAL.save(deps.storage, &global_index)?;
+    save_checkpoint(deps.storage, &address, &denom, timestamp)?;
 
     Ok(Response::default().add_attributes(vec![
         ("action", "bond".to_string()),
-        ("address", info.sender.to_string()),
+        ("address", address.to_string()),
         ("asset", asset.to_string()),
     ]))
 }
 
+/// Adds the provided asset to the lock of the address, which ends at the given time, updating the
+/// vote-escrow totals. The asset amount can be zero when only the lock is extended.
+fn lock_asset(
+    deps: &mut DepsMut,
+    timestamp: Timestamp,
+    address: &Addr,
+    asset: &Asset,
+    lock_end: Timestamp,
+) -> Result<Bond, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let max_lock_duration = config
+        .max_lock_duration
+        .ok_or(ContractError::VoteEscrowDisabled {})?;
+    let denom = asset.info.to_string();
+
+    let mut bond = BOND
+        .key((address, &denom))
+        .may_load(deps.storage)?
+        .unwrap_or(Bond {
+            asset: Asset {
+                amount: Uint128::zero(),
+                ..asset.clone()
+            },
+            ..Bond::default()
+        });
+
+    let mut global_index = get_vote_escrow_global_index(
+        deps.storage,
+        timestamp,
+        GLOBAL.may_load(deps.storage)?.unwrap_or_default(),
+        max_lock_duration,
+    )?;
+    let mut vote_escrow = global_index.vote_escrow.unwrap_or_default();
+
+    // replace the current lock, if any, with the new one
+    if let Some(current_lock_end) = LOCK.may_load(deps.storage, (address, &denom))? {
+        remove_lock(
+            deps.storage,
+            timestamp,
+            &mut vote_escrow,
+            get_weighted_amount(&config, &bond.asset),
+            current_lock_end,
+        )?;
+    }
+
+    bond.asset.amount = bond.asset.amount.checked_add(asset.amount)?;
+    let weighted_amount = get_weighted_amount(&config, &bond.asset);
+    add_lock(
+        deps.storage,
+        timestamp,
+        &mut vote_escrow,
+        weighted_amount,
+        lock_end,
+    )?;
+
+    bond.weight = get_lock_weight(timestamp, weighted_amount, lock_end, max_lock_duration)?;
+    bond.timestamp = timestamp;
+
+    BOND.save(deps.storage, (address, &denom), &bond)?;
+    LOCK.save(deps.storage, (address, &denom), &lock_end)?;
+
+    global_index.weight =
+        vote_escrow.bias / Uint128::from(max_lock_duration_seconds(max_lock_duration)?);
+    global_index.vote_escrow = Some(vote_escrow);
+    if !asset.amount.is_zero() {
+        global_index.bonded_amount = global_index.bonded_amount.checked_add(asset.amount)?;
+        global_index.bonded_assets =
+            asset::aggregate_assets(global_index.bonded_assets, vec![asset.clone()])?;
+        global_index.weighted_amount =
+            get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+    }
+
+    GLOBAL.save(deps.storage, &global_index)?;
+    save_checkpoint(deps.storage, address, &denom, timestamp)?;
+
+    Ok(bond)
+}
+
+/// Extends the lock of the given denom of the sender, in vote-escrow mode.
+pub(crate) fn extend_lock(
+    deps: DepsMut,
+    timestamp: Timestamp,
+    info: MessageInfo,
+    env: Env,
+    denom: String,
+    lock_duration: Uint64,
+) -> Result<Response, ContractError> {
+    let max_lock_duration = CONFIG
+        .load(deps.storage)?
+        .max_lock_duration
+        .ok_or(ContractError::VoteEscrowDisabled {})?;
+    helpers::validate_lock_duration(lock_duration, max_lock_duration)?;
+
+    let current_lock_end = LOCK
+        .may_load(deps.storage, (&info.sender, &denom))?
+        .ok_or(ContractError::NothingToExtend {})?;
+    let lock_end = timestamp.plus_nanos(lock_duration.u64());
+    if current_lock_end > lock_end {
+        return Err(ContractError::LockCannotBeShortened {});
+    }
+
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    // settle the pending rewards before the lock changes, as they are based on it
+    if let Some(messages) = helpers::claim_before_callback(
+        &deps,
+        &env,
+        &info.sender,
+        CallbackMsg::ExtendLock {
+            address: info.sender.clone(),
+            denom: denom.clone(),
+            lock_end,
+        },
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "extend_lock".to_string()),
+                ("address", info.sender.to_string()),
+                ("claim_rewards", "true".to_string()),
+            ]));
+    }
+
+    extend_lock_asset(deps, timestamp, info.sender, denom, lock_end)
+}
+
+/// Extends the lock of the given denom of the address until the given time.
+fn extend_lock_asset(
+    mut deps: DepsMut,
+    timestamp: Timestamp,
+    address: Addr,
+    denom: String,
+    lock_end: Timestamp,
+) -> Result<Response, ContractError> {
+    let bond = BOND
+        .may_load(deps.storage, (&address, &denom))?
+        .ok_or(ContractError::NothingToExtend {})?;
+
+    let bond = lock_asset(
+        &mut deps,
+        timestamp,
+        &address,
+        &Asset {
+            info: bond.asset.info,
+            amount: Uint128::zero(),
+        },
+        lock_end,
+    )?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "extend_lock".to_string()),
+        ("address", address.to_string()),
+        ("denom", denom),
+        ("lock_end", lock_end.to_string()),
+        ("weight", bond.weight.to_string()),
+    ]))
+}
+
 /// Unbonds the provided amount of tokens
 pub(crate) fn unbond(
-    mut deps: DepsMut,
+    deps: DepsMut,
     timestamp: Timestamp,
     info: MessageInfo,
     env: Env,
@@ -81,63 +352,875 @@ pub(crate) fn unbond(
         return Err(ContractError::InvalidUnbondingAmount {});
     }
 
-    let denom = match asset.info.clone() {
-        AssetInfo::NativeToken { denom } => denom,
-        AssetInfo::Token { .. } => return Err(ContractError::InvalidBondingAsset {}),
-    };
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    // settle the pending rewards before the bond changes, as they are based on it
+    if let Some(messages) = helpers::claim_before_callback(
+        &deps,
+        &env,
+        &info.sender,
+        CallbackMsg::Unbond {
+            address: info.sender.clone(),
+            asset: asset.clone(),
+        },
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "unbond".to_string()),
+                ("address", info.sender.to_string()),
+                ("claim_rewards", "true".to_string()),
+            ]));
+    }
+
+    unbond_asset(deps, timestamp, info.sender.clone(), asset, &info.sender)
+}
+
+/// Unbonds the provided amount of tokens on behalf of the address. The unbonding is recorded for
+/// the unbonding address, which is the address itself unless liquid bonded tokens are redeemed.
+fn unbond_asset(
+    mut deps: DepsMut,
+    timestamp: Timestamp,
+    address: Addr,
+    asset: Asset,
+    unbonding_address: &Addr,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = asset.info.to_string();
+
+    match BOND.key((&address, &denom)).may_load(deps.storage)? {
+        Some(mut unbond) if unbond.asset.info == asset.info => {
+            // check if the address has enough bond
+            if unbond.asset.amount < asset.amount {
+                return Err(ContractError::InsufficientBond {});
+            }
+
+            let weight_slash = if config.max_lock_duration.is_some() {
+                // locked tokens can be unbonded once the lock expired, when they have no weight left
+                let lock_end = LOCK.load(deps.storage, (&address, &denom))?;
+                if lock_end.seconds() > timestamp.seconds() {
+                    return Err(ContractError::LockNotExpired {});
+                }
+                unbond.weight = Uint128::zero();
+                unbond.timestamp = timestamp;
+
+                Uint128::zero()
+            } else {
+                // update local values, decrease the bond
+                unbond =
+                    update_local_weight(&mut deps, address.clone(), timestamp, unbond.clone())?;
+                unbond.weight * Decimal::from_ratio(asset.amount, unbond.asset.amount)
+            };
+            unbond.weight = unbond.weight.checked_sub(weight_slash)?;
+            unbond.asset.amount = unbond.asset.amount.checked_sub(asset.amount)?;
+
+            if unbond.asset.amount.is_zero() {
+                BOND.remove(deps.storage, (&address, &denom));
+                LOCK.remove(deps.storage, (&address, &denom));
+            } else {
+                BOND.save(deps.storage, (&address, &denom), &unbond)?;
+            }
+
+            // record the unbonding, keeping the weight in case it is rebonded
+            UNBOND.save(
+                deps.storage,
+                (unbonding_address, &denom, timestamp.nanos()),
+                &Bond {
+                    asset: asset.clone(),
+                    weight: weight_slash,
+                    timestamp,
+                },
+            )?;
+
+            // update global values
+            let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
+            global_index = match config.max_lock_duration {
+                Some(max_lock_duration) => get_vote_escrow_global_index(
+                    deps.storage,
+                    timestamp,
+                    global_index,
+                    max_lock_duration,
+                )?,
+                None => update_global_weight(&mut deps, timestamp, global_index)?,
+            };
+            global_index.bonded_amount = global_index.bonded_amount.checked_sub(asset.amount)?;
+            global_index.bonded_assets =
+                asset::deduct_assets(global_index.bonded_assets, vec![asset.clone()])?;
+            global_index.weighted_amount =
+                get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+            global_index.weight = global_index.weight.checked_sub(weight_slash)?;
+
+            GLOBAL.save(deps.storage, &global_index)?;
+            save_checkpoint(deps.storage, &address, &denom, timestamp)?;
+
+            Ok(Response::default().add_attributes(vec![
+                ("action", "unbond".to_string()),
+                ("address", address.to_string()),
+                ("asset", asset.to_string()),
+            ]))
+        }
+        _ => Err(ContractError::NothingToUnbond {}),
+    }
+}
+
+/// Moves unbonding tokens back into the bond of the sender.
+pub(crate) fn rebond(
+    deps: DepsMut,
+    timestamp: Timestamp,
+    info: MessageInfo,
+    env: Env,
+    denom: String,
+    unbonding_timestamp: Option<u64>,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    if amount.is_some_and(|amount| amount.is_zero()) {
+        return Err(ContractError::InvalidRebondingAmount {});
+    }
+
+    // rebonded tokens would need a new lock, so they are bonded again instead
+    if CONFIG.load(deps.storage)?.max_lock_duration.is_some() {
+        return Err(ContractError::RebondUnavailable {});
+    }
 
-    helpers::validate_claimed(&deps, &info)?;
     helpers::validate_bonding_for_current_epoch(&deps, &env)?;
 
-    if let Some(mut unbond) = BOND.key((&info.sender, &denom)).may_load(deps.storage)? {
-        // check if the address has enough bond
-        if unbond.asset.amount < asset.amount {
-            return Err(ContractError::InsufficientBond {});
+    // settle the pending rewards before the bond changes, as they are based on it
+    if let Some(messages) = helpers::claim_before_callback(
+        &deps,
+        &env,
+        &info.sender,
+        CallbackMsg::Rebond {
+            address: info.sender.clone(),
+            denom: denom.clone(),
+            unbonding_timestamp,
+            amount,
+        },
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "rebond".to_string()),
+                ("address", info.sender.to_string()),
+                ("claim_rewards", "true".to_string()),
+            ]));
+    }
+
+    rebond_asset(
+        deps,
+        timestamp,
+        info.sender,
+        denom,
+        unbonding_timestamp,
+        amount,
+    )
+}
+
+/// Moves unbonding tokens back into the bond of the address, newest unbondings first. The weight
+/// recorded when the tokens were unbonded is restored pro rata, so the time spent unbonding doesn't
+/// add to it. Unbondings without a recorded weight restart from their weighted amount, as a new
+/// bond would.
+fn rebond_asset(
+    mut deps: DepsMut,
+    timestamp: Timestamp,
+    address: Addr,
+    denom: String,
+    unbonding_timestamp: Option<u64>,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let unbondings: Vec<(u64, Bond)> = match unbonding_timestamp {
+        Some(unbonding_timestamp) => UNBOND
+            .may_load(deps.storage, (&address, &denom, unbonding_timestamp))?
+            .map(|unbonding| vec![(unbonding_timestamp, unbonding)])
+            .unwrap_or_default(),
+        None => UNBOND
+            .prefix((&address, &denom))
+            .range(deps.storage, None, None, Order::Descending)
+            .take(MAX_PAGE_LIMIT as usize)
+            .collect::<StdResult<Vec<(u64, Bond)>>>()?,
+    };
+
+    if unbondings.is_empty() {
+        return Err(ContractError::NothingToRebond {});
+    }
+
+    let unbonding_amount = unbondings
+        .iter()
+        .try_fold(Uint128::zero(), |acc, (_, bond)| {
+            acc.checked_add(bond.asset.amount)
+        })?;
+    let mut remaining_amount = amount.unwrap_or(unbonding_amount);
+    if remaining_amount > unbonding_amount {
+        return Err(ContractError::InsufficientUnbonding {});
+    }
+
+    let mut asset = Asset {
+        info: unbondings[0].1.asset.info.clone(),
+        amount: Uint128::zero(),
+    };
+    let mut restored_weight = Uint128::zero();
+
+    for (ts, mut unbonding) in unbondings {
+        if remaining_amount.is_zero() {
+            break;
         }
-        // update local values, decrease the bond
-        unbond = update_local_weight(&mut deps, info.sender.clone(), timestamp, unbond.clone())?;
-        let weight_slash = unbond.weight * Decimal::from_ratio(asset.amount, unbond.asset.amount);
-        unbond.weight = unbond.weight.checked_sub(weight_slash)?;
-        unbond.asset.amount = unbond.asset.amount.checked_sub(asset.amount)?;
-
-        if unbond.asset.amount.is_zero() {
-            BOND.remove(deps.storage, (&info.sender, &denom));
+
+        let rebond_amount = remaining_amount.min(unbonding.asset.amount);
+        let weight = if unbonding.weight.is_zero() {
+            get_weighted_amount(
+                &config,
+                &Asset {
+                    info: unbonding.asset.info.clone(),
+                    amount: rebond_amount,
+                },
+            )
         } else {
-            BOND.save(deps.storage, (&info.sender, &denom), &unbond)?;
+            unbonding.weight * Decimal::from_ratio(rebond_amount, unbonding.asset.amount)
+        };
+
+        unbonding.asset.amount = unbonding.asset.amount.checked_sub(rebond_amount)?;
+        unbonding.weight = unbonding.weight.saturating_sub(weight);
+        if unbonding.asset.amount.is_zero() {
+            UNBOND.remove(deps.storage, (&address, &denom, ts));
+        } else {
+            UNBOND.save(deps.storage, (&address, &denom, ts), &unbonding)?;
         }
 
-        // record the unbonding
-        UNBOND.save(
-            deps.storage,
-            (&info.sender, &denom, timestamp.nanos()),
-            &Bond {
-                asset: asset.clone(),
-                weight: Uint128::zero(),
-                timestamp,
+        asset.amount = asset.amount.checked_add(rebond_amount)?;
+        restored_weight = restored_weight.checked_add(weight)?;
+        remaining_amount = remaining_amount.checked_sub(rebond_amount)?;
+    }
+
+    // bring the weights up to date before restoring the rebonded tokens
+    let mut bond = match BOND.key((&address, &denom)).may_load(deps.storage)? {
+        Some(bond) => update_local_weight(&mut deps, address.clone(), timestamp, bond)?,
+        None => Bond {
+            asset: Asset {
+                amount: Uint128::zero(),
+                ..asset.clone()
             },
+            timestamp,
+            ..Bond::default()
+        },
+    };
+    bond.asset.amount = bond.asset.amount.checked_add(asset.amount)?;
+    bond.weight = bond.weight.checked_add(restored_weight)?;
+
+    BOND.save(deps.storage, (&address, &denom), &bond)?;
+
+    let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
+    global_index = update_global_weight(&mut deps, timestamp, global_index)?;
+    global_index.weight = global_index.weight.checked_add(restored_weight)?;
+    global_index.bonded_amount = global_index.bonded_amount.checked_add(asset.amount)?;
+    global_index.bonded_assets =
+        asset::aggregate_assets(global_index.bonded_assets, vec![asset.clone()])?;
+    global_index.weighted_amount = get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+
+    GLOBAL.save(deps.storage, &global_index)?;
+    save_checkpoint(deps.storage, &address, &denom, timestamp)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "rebond".to_string()),
+        ("address", address.to_string()),
+        ("asset", asset.to_string()),
+        ("restored_weight", restored_weight.to_string()),
+    ]))
+}
+
+/// Bonds the provided native asset in the liquid bonding pool, minting receipt tokens for the
+/// sender.
+pub(crate) fn liquid_bond(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let liquid_bonding = helpers::get_liquid_bonding(&CONFIG.load(deps.storage)?)?;
+    if asset.info != liquid_bonding.asset {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    helpers::validate_funds(&deps, &info, &asset, asset.info.to_string())?;
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    // compound the pending rewards first, so they are not shared with the new receipt tokens
+    if let Some(messages) = helpers::compound_before_callback(
+        &deps,
+        &env,
+        &liquid_bonding,
+        Some(CallbackMsg::LiquidBond {
+            address: info.sender.clone(),
+            asset: asset.clone(),
+        }),
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "liquid_bond".to_string()),
+                ("address", info.sender.to_string()),
+                ("compound", "true".to_string()),
+            ]));
+    }
+
+    liquid_bond_asset(deps, env, info.sender, asset)
+}
+
+/// Bonds the provided asset in the liquid bonding pool, minting receipt tokens for the address at
+/// the current exchange rate. The asset must have been validated and received by the contract
+/// already.
+fn liquid_bond_asset(
+    mut deps: DepsMut,
+    env: Env,
+    address: Addr,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let liquid_bonding = helpers::get_liquid_bonding(&CONFIG.load(deps.storage)?)?;
+    let (total_shares, bonded_amount) =
+        helpers::get_liquid_bonding_pool(deps.as_ref(), &env, &liquid_bonding)?;
+
+    let shares = if total_shares.is_zero() {
+        asset.amount
+    } else {
+        asset
+            .amount
+            .checked_multiply_ratio(total_shares, bonded_amount)?
+    };
+    if shares.is_zero() {
+        return Err(ContractError::InvalidLiquidBondAmount {});
+    }
+
+    bond_asset(
+        deps.branch(),
+        env.block.time,
+        env.contract.address,
+        asset.clone(),
+        None,
+    )?;
+
+    Ok(Response::default()
+        .add_message(WasmMsg::Execute {
+            contract_addr: liquid_bonding.receipt_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: address.to_string(),
+                amount: shares,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "liquid_bond".to_string()),
+            ("address", address.to_string()),
+            ("asset", asset.to_string()),
+            ("shares", shares.to_string()),
+        ]))
+}
+
+/// Redeems the receipt tokens sent by the address, starting to unbond the tokens they are worth.
+fn redeem(
+    deps: DepsMut,
+    env: Env,
+    receipt_token: Addr,
+    address: Addr,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    let liquid_bonding = helpers::get_liquid_bonding(&CONFIG.load(deps.storage)?)?;
+    if receipt_token != liquid_bonding.receipt_token {
+        return Err(ContractError::AssetMismatch {});
+    }
+    if shares.is_zero() {
+        return Err(ContractError::InvalidRedeemAmount {});
+    }
+
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    // compound the pending rewards first, so the redeemed receipt tokens include their share
+    if let Some(messages) = helpers::compound_before_callback(
+        &deps,
+        &env,
+        &liquid_bonding,
+        Some(CallbackMsg::Redeem {
+            address: address.clone(),
+            shares,
+        }),
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "redeem".to_string()),
+                ("address", address.to_string()),
+                ("compound", "true".to_string()),
+            ]));
+    }
+
+    redeem_shares(deps, env, address, shares)
+}
+
+/// Burns the given amount of receipt tokens, which the contract holds already, and unbonds the
+/// tokens they are worth at the current exchange rate from the liquid bonding pool on behalf of
+/// the address.
+fn redeem_shares(
+    deps: DepsMut,
+    env: Env,
+    address: Addr,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    let liquid_bonding = helpers::get_liquid_bonding(&CONFIG.load(deps.storage)?)?;
+    let (total_shares, bonded_amount) =
+        helpers::get_liquid_bonding_pool(deps.as_ref(), &env, &liquid_bonding)?;
+
+    let asset = Asset {
+        info: liquid_bonding.asset,
+        amount: shares.checked_multiply_ratio(bonded_amount, total_shares)?,
+    };
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidRedeemAmount {});
+    }
+
+    unbond_asset(
+        deps,
+        env.block.time,
+        env.contract.address,
+        asset.clone(),
+        &address,
+    )?;
+
+    Ok(Response::default()
+        .add_message(WasmMsg::Execute {
+            contract_addr: liquid_bonding.receipt_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount: shares })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "redeem".to_string()),
+            ("address", address.to_string()),
+            ("asset", asset.to_string()),
+            ("shares", shares.to_string()),
+        ]))
+}
+
+/// Claims the pending rewards of the liquid bonding pool and bonds them back into it.
+pub(crate) fn compound(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let liquid_bonding = helpers::get_liquid_bonding(&CONFIG.load(deps.storage)?)?;
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    let messages = helpers::compound_before_callback(&deps, &env, &liquid_bonding, None)?
+        .ok_or(ContractError::NothingToCompound {})?;
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![("action", "compound")]))
+}
+
+/// Bonds the rewards claimed by the liquid bonding pool back into it, i.e. the balances the
+/// contract has on top of the balances it had before claiming. Rewards in other assets are swapped
+/// into the liquid bonding asset and compounded once received, or sent to the owner if the pool
+/// router has no route for them.
+fn compound_rewards(
+    deps: DepsMut,
+    env: Env,
+    balances_before: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let liquid_bonding = helpers::get_liquid_bonding(&config)?;
+
+    let mut rewards = Asset {
+        info: liquid_bonding.asset.clone(),
+        amount: Uint128::zero(),
+    };
+    let mut liquid_balance = Uint128::zero();
+    let mut swapped = false;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut attributes = vec![];
+
+    for balance_before in balances_before {
+        let balance = balance_before.info.query_pool(
+            &deps.querier,
+            deps.api,
+            env.contract.address.clone(),
         )?;
+        let reward = Asset {
+            info: balance_before.info,
+            amount: balance.saturating_sub(balance_before.amount),
+        };
 
-        // update global values
-        let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
-        global_index = update_global_weight(&mut deps, timestamp, global_index)?;
-        global_index.bonded_amount = global_index.bonded_amount.checked_sub(asset.amount)?;
-        global_index.bonded_assets =
-            asset::deduct_assets(global_index.bonded_assets, vec![asset.clone()])?;
-        global_index.weight = global_index.weight.checked_sub(weight_slash)?;
+        if reward.info == liquid_bonding.asset {
+            liquid_balance = balance;
+            rewards = reward;
+            continue;
+        }
+
+        if reward.amount.is_zero() {
+            continue;
+        }
+
+        let swap_route = config
+            .pool_router_addr
+            .as_ref()
+            .and_then(|pool_router_addr| {
+                helpers::query_swap_route(
+                    &deps,
+                    pool_router_addr,
+                    &reward.info,
+                    &liquid_bonding.asset,
+                )
+                .map(|route| (pool_router_addr, route))
+            });
 
-        GLOBAL.save(deps.storage, &global_index)?;
+        match swap_route {
+            Some((pool_router_addr, route)) => {
+                // no minimum is known for the rewards, the pools enforce their default max spread
+                messages.push(helpers::get_swap_msg(
+                    pool_router_addr,
+                    &reward,
+                    route,
+                    Uint128::zero(),
+                )?);
+                swapped = true;
+                attributes.push(("swapped_rewards", reward.to_string()));
+            }
+            None => {
+                messages.push(reward.clone().into_msg(config.owner.clone())?);
+                attributes.push(("unswapped_rewards", reward.to_string()));
+            }
+        }
+    }
+
+    // the tokens received from the swaps are compounded on top of the current balance
+    if swapped {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::Compound {
+                    balances_before: vec![Asset {
+                        info: liquid_bonding.asset,
+                        amount: liquid_balance,
+                    }],
+                }))?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
 
-        Ok(Response::default().add_attributes(vec![
-            ("action", "unbond".to_string()),
-            ("address", info.sender.to_string()),
+    if !rewards.amount.is_zero() {
+        bond_asset(
+            deps,
+            env.block.time,
+            env.contract.address,
+            rewards.clone(),
+            None,
+        )?;
+    }
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "compound".to_string()),
+            ("rewards", rewards.to_string()),
+        ])
+        .add_attributes(attributes))
+}
+
+/// Converts the bond of the given denom of the sender into a bond of another denom, swapping the
+/// bonded tokens through the pool router.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn convert_bond(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    from_denom: String,
+    to_denom: String,
+    route: Vec<SwapOperation>,
+    amount: Option<Uint128>,
+    minimum_receive: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.pool_router_addr.is_none() {
+        return Err(ContractError::BondConversionDisabled {});
+    }
+    // converted tokens would need a new lock, so they are unbonded and bonded again instead
+    if config.max_lock_duration.is_some() {
+        return Err(ContractError::ConvertBondUnavailable {});
+    }
+    if amount.is_some_and(|amount| amount.is_zero()) || minimum_receive.is_zero() {
+        return Err(ContractError::InvalidConversionAmount {});
+    }
+
+    helpers::validate_bond_conversion(&config, &from_denom, &to_denom, &route)?;
+    helpers::validate_bonding_for_current_epoch(&deps, &env)?;
+
+    // settle the pending rewards before the bond changes, as they are based on it
+    if let Some(messages) = helpers::claim_before_callback(
+        &deps,
+        &env,
+        &info.sender,
+        CallbackMsg::ConvertBond {
+            address: info.sender.clone(),
+            from_denom: from_denom.clone(),
+            to_denom: to_denom.clone(),
+            route: route.clone(),
+            amount,
+            minimum_receive,
+        },
+    )? {
+        return Ok(Response::default()
+            .add_messages(messages)
+            .add_attributes(vec![
+                ("action", "convert_bond".to_string()),
+                ("address", info.sender.to_string()),
+                ("claim_rewards", "true".to_string()),
+            ]));
+    }
+
+    convert_bond_asset(
+        deps,
+        env,
+        info.sender,
+        from_denom,
+        to_denom,
+        route,
+        amount,
+        minimum_receive,
+    )
+}
+
+/// Takes the tokens to convert out of the bond of the given denom of the address, along with their
+/// share of its weight, and swaps them through the pool router. The tokens received are bonded
+/// with that weight once the swap is done.
+#[allow(clippy::too_many_arguments)]
+fn convert_bond_asset(
+    mut deps: DepsMut,
+    env: Env,
+    address: Addr,
+    from_denom: String,
+    to_denom: String,
+    route: Vec<SwapOperation>,
+    amount: Option<Uint128>,
+    minimum_receive: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool_router_addr = config
+        .pool_router_addr
+        .clone()
+        .ok_or(ContractError::BondConversionDisabled {})?;
+    let (_, to_asset_info) =
+        helpers::validate_bond_conversion(&config, &from_denom, &to_denom, &route)?;
+    let timestamp = env.block.time;
+
+    let mut bond = BOND
+        .may_load(deps.storage, (&address, &from_denom))?
+        .ok_or(ContractError::NothingToConvert {})?;
+    let asset = Asset {
+        info: bond.asset.info.clone(),
+        amount: amount.unwrap_or(bond.asset.amount),
+    };
+    if bond.asset.amount < asset.amount {
+        return Err(ContractError::InsufficientBond {});
+    }
+
+    // update local values, taking the weight of the converted tokens out of the bond
+    bond = update_local_weight(&mut deps, address.clone(), timestamp, bond)?;
+    let weight = bond.weight * Decimal::from_ratio(asset.amount, bond.asset.amount);
+    bond.weight = bond.weight.checked_sub(weight)?;
+    bond.asset.amount = bond.asset.amount.checked_sub(asset.amount)?;
+
+    if bond.asset.amount.is_zero() {
+        BOND.remove(deps.storage, (&address, &from_denom));
+    } else {
+        BOND.save(deps.storage, (&address, &from_denom), &bond)?;
+    }
+
+    // update global values
+    let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
+    global_index = update_global_weight(&mut deps, timestamp, global_index)?;
+    global_index.bonded_amount = global_index.bonded_amount.checked_sub(asset.amount)?;
+    global_index.bonded_assets =
+        asset::deduct_assets(global_index.bonded_assets, vec![asset.clone()])?;
+    global_index.weighted_amount = get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+    global_index.weight = global_index.weight.checked_sub(weight)?;
+
+    GLOBAL.save(deps.storage, &global_index)?;
+    save_checkpoint(deps.storage, &address, &from_denom, timestamp)?;
+
+    // the tokens received are what the contract holds on top of its current balance after the swap
+    let balance_before =
+        to_asset_info.query_pool(&deps.querier, deps.api, env.contract.address.clone())?;
+
+    Ok(Response::default()
+        .add_message(helpers::get_swap_msg(
+            &pool_router_addr,
+            &asset,
+            route,
+            minimum_receive,
+        )?)
+        .add_message(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::CompleteBondConversion {
+                address: address.clone(),
+                converted: asset.clone(),
+                asset_info: to_asset_info,
+                balance_before,
+                weight,
+            }))?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "convert_bond".to_string()),
+            ("address", address.to_string()),
             ("asset", asset.to_string()),
+            ("to_denom", to_denom),
         ]))
+}
+
+/// Bonds the tokens received from converting a bond on behalf of the address, i.e. the balance of
+/// the given asset the contract has on top of the balance it had before the swap. The weight taken
+/// from the converted tokens is scaled by the ratio of the weighted amounts, so the converted bond
+/// keeps the time it accumulated.
+fn complete_bond_conversion(
+    mut deps: DepsMut,
+    env: Env,
+    address: Addr,
+    converted: Asset,
+    asset_info: AssetInfo,
+    balance_before: Uint128,
+    weight: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let timestamp = env.block.time;
+    let denom = asset_info.to_string();
+
+    let balance = asset_info.query_pool(&deps.querier, deps.api, env.contract.address)?;
+    let asset = Asset {
+        info: asset_info,
+        amount: balance.saturating_sub(balance_before),
+    };
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidConversionAmount {});
+    }
+
+    let weighted_amount = get_weighted_amount(&config, &asset);
+    let converted_weighted_amount = get_weighted_amount(&config, &converted);
+    let weight = if converted_weighted_amount.is_zero() {
+        weighted_amount
     } else {
-        Err(ContractError::NothingToUnbond {})
+        weight.checked_multiply_ratio(weighted_amount, converted_weighted_amount)?
+    };
+
+    // bring the weights up to date before adding the converted tokens
+    let mut bond = match BOND.key((&address, &denom)).may_load(deps.storage)? {
+        Some(bond) => update_local_weight(&mut deps, address.clone(), timestamp, bond)?,
+        None => Bond {
+            asset: Asset {
+                amount: Uint128::zero(),
+                ..asset.clone()
+            },
+            timestamp,
+            ..Bond::default()
+        },
+    };
+    bond.asset.amount = bond.asset.amount.checked_add(asset.amount)?;
+    bond.weight = bond.weight.checked_add(weight)?;
+
+    BOND.save(deps.storage, (&address, &denom), &bond)?;
+
+    let mut global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
+    global_index = update_global_weight(&mut deps, timestamp, global_index)?;
+    global_index.weight = global_index.weight.checked_add(weight)?;
+    global_index.bonded_amount = global_index.bonded_amount.checked_add(asset.amount)?;
+    global_index.bonded_assets =
+        asset::aggregate_assets(global_index.bonded_assets, vec![asset.clone()])?;
+    global_index.weighted_amount = get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+
+    GLOBAL.save(deps.storage, &global_index)?;
+    save_checkpoint(deps.storage, &address, &denom, timestamp)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "complete_bond_conversion".to_string()),
+        ("address", address.to_string()),
+        ("converted", converted.to_string()),
+        ("asset", asset.to_string()),
+        ("weight", weight.to_string()),
+    ]))
+}
+
+/// Handles the callback messages, which can only be invoked by the contract itself.
+pub(crate) fn callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CallbackMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::ExternalCallback {});
+    }
+
+    match msg {
+        CallbackMsg::Bond {
+            address,
+            asset,
+            lock_end,
+        } => bond_asset(deps, env.block.time, address, asset, lock_end),
+        CallbackMsg::Unbond { address, asset } => {
+            unbond_asset(deps, env.block.time, address.clone(), asset, &address)
+        }
+        CallbackMsg::Rebond {
+            address,
+            denom,
+            unbonding_timestamp,
+            amount,
+        } => rebond_asset(
+            deps,
+            env.block.time,
+            address,
+            denom,
+            unbonding_timestamp,
+            amount,
+        ),
+        CallbackMsg::ExtendLock {
+            address,
+            denom,
+            lock_end,
+        } => extend_lock_asset(deps, env.block.time, address, denom, lock_end),
+        CallbackMsg::Compound { balances_before } => compound_rewards(deps, env, balances_before),
+        CallbackMsg::LiquidBond { address, asset } => liquid_bond_asset(deps, env, address, asset),
+        CallbackMsg::Redeem { address, shares } => redeem_shares(deps, env, address, shares),
+        CallbackMsg::ConvertBond {
+            address,
+            from_denom,
+            to_denom,
+            route,
+            amount,
+            minimum_receive,
+        } => convert_bond_asset(
+            deps,
+            env,
+            address,
+            from_denom,
+            to_denom,
+            route,
+            amount,
+            minimum_receive,
+        ),
+        CallbackMsg::CompleteBondConversion {
+            address,
+            converted,
+            asset_info,
+            balance_before,
+            weight,
+        } => complete_bond_conversion(
+            deps,
+            env,
+            address,
+            converted,
+            asset_info,
+            balance_before,
+            weight,
+        ),
     }
 }
 
-/// Withdraws the rewards for the provided address
+/// Withdraws the rewards for the provided address. The denom is the contract address for cw20
+/// tokens.
 pub(crate) fn withdraw(
     deps: DepsMut,
     timestamp: Timestamp,
@@ -152,35 +1235,27 @@ pub(crate) fn withdraw(
         .take(MAX_PAGE_LIMIT as usize)
         .collect::<StdResult<Vec<(u64, Bond)>>>()?;
 
-    let mut refund_amount = Uint128::zero();
-
     if unbondings.is_empty() {
         return Err(ContractError::NothingToWithdraw {});
     }
 
+    let mut refund = Asset {
+        info: unbondings[0].1.asset.info.clone(),
+        amount: Uint128::zero(),
+    };
+
     for unbonding in unbondings {
         let (ts, bond) = unbonding;
         if timestamp.minus_nanos(config.unbonding_period.u64()) >= bond.timestamp {
-            let denom = match bond.asset.info {
-                AssetInfo::Token { .. } => return Err(ContractError::InvalidBondingAsset {}),
-                AssetInfo::NativeToken { denom } => denom,
-            };
-
-            refund_amount = refund_amount.checked_add(bond.asset.amount)?;
+            refund.amount = refund.amount.checked_add(bond.asset.amount)?;
             UNBOND.remove(deps.storage, (&address, &denom, ts));
         }
     }
 
-    let refund_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: address.to_string(),
-        amount: vec![Coin {
-            denom: denom.clone(),
-            amount: refund_amount,
-        }],
-    });
+    let refund_amount = refund.amount;
 
     Ok(Response::default()
-        .add_message(refund_msg)
+        .add_message(refund.into_msg(address.clone())?)
         .add_attributes(vec![
             ("action", "withdraw".to_string()),
             ("address", address.to_string()),
@@ -189,6 +1264,59 @@ pub(crate) fn withdraw(
         ]))
 }
 
+/// Sets the address the fee distributor sends the rewards of the sender to.
+pub(crate) fn set_reward_recipient(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    if recipient == info.sender {
+        REWARD_RECIPIENT.remove(deps.storage, &info.sender);
+    } else {
+        REWARD_RECIPIENT.save(deps.storage, &info.sender, &recipient)?;
+    }
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "set_reward_recipient".to_string()),
+        ("address", info.sender.to_string()),
+        ("recipient", recipient.to_string()),
+    ]))
+}
+
+/// Sets the pool router bonds are converted through, disabling the conversions if not set. Only
+/// the owner can set it.
+pub(crate) fn set_pool_router(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_router_addr: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.pool_router_addr = pool_router_addr
+        .map(|pool_router_addr| deps.api.addr_validate(&pool_router_addr))
+        .transpose()?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "set_pool_router".to_string()),
+        (
+            "pool_router_addr",
+            config
+                .pool_router_addr
+                .map_or("None".to_string(), |addr| addr.to_string()),
+        ),
+    ]))
+}
+
 /// Updates the configuration of the contract
 pub(crate) fn update_config(
     deps: DepsMut,
@@ -1,42 +1,60 @@
-use cosmwasm_std::{entry_point, Addr};
+use cosmwasm_std::{entry_point, Addr, Reply, ReplyOn, SubMsg, WasmMsg};
 use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 use cw2::{get_contract_version, set_contract_version};
+use cw20::MinterResponse;
+use cw_utils::parse_reply_instantiate_data;
 use semver::Version;
 use white_whale_std::pool_network::asset::AssetInfo;
+use white_whale_std::pool_network::token::InstantiateMsg as TokenInstantiateMsg;
 
-use white_whale_std::whale_lair::{Config, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use white_whale_std::whale_lair::{
+    Config, ExecuteMsg, InstantiateMsg, LiquidBonding, MigrateMsg, QueryMsg,
+};
 
 use crate::error::ContractError;
-use crate::helpers::validate_growth_rate;
-use crate::state::{BONDING_ASSETS_LIMIT, CONFIG};
+use crate::helpers::{
+    validate_growth_rate, validate_liquid_bonding, validate_max_lock_duration,
+    validate_weight_multipliers,
+};
+use crate::state::CONFIG;
 use crate::{commands, migrations, queries};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "white_whale-whale_lair";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const RECEIPT_TOKEN_NAME: &str = "Liquid bonded WHALE";
+const RECEIPT_TOKEN_SYMBOL: &str = "lbWHALE";
+pub(crate) const RECEIPT_TOKEN_REPLY_ID: u64 = 1;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    if msg.bonding_assets.len() > BONDING_ASSETS_LIMIT {
-        return Err(ContractError::InvalidBondingAssetsLimit(
-            BONDING_ASSETS_LIMIT,
-            msg.bonding_assets.len(),
-        ));
-    }
-
     validate_growth_rate(msg.growth_rate)?;
 
-    //todo since this should only accept native tokens, we could omit the asset type and pass the denom directly
     for asset in &msg.bonding_assets {
-        match asset {
-            AssetInfo::Token { .. } => return Err(ContractError::InvalidBondingAsset {}),
-            AssetInfo::NativeToken { .. } => {}
-        };
+        if let AssetInfo::Token { contract_addr } = asset {
+            deps.api.addr_validate(contract_addr)?;
+        }
+    }
+
+    let weight_multipliers = msg.weight_multipliers.unwrap_or_default();
+    validate_weight_multipliers(&msg.bonding_assets, &weight_multipliers)?;
+
+    if let Some(max_lock_duration) = msg.max_lock_duration {
+        validate_max_lock_duration(max_lock_duration)?;
+    }
+
+    if let Some(liquid_bonding) = &msg.liquid_bonding {
+        validate_liquid_bonding(
+            &msg.bonding_assets,
+            &liquid_bonding.asset,
+            msg.max_lock_duration,
+        )?;
     }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -47,6 +65,17 @@ pub fn instantiate(
         growth_rate: msg.growth_rate,
         bonding_assets: msg.bonding_assets,
         fee_distributor_addr: Addr::unchecked(""),
+        weight_multipliers,
+        max_lock_duration: msg.max_lock_duration,
+        // the receipt token address is set once it's instantiated
+        liquid_bonding: msg
+            .liquid_bonding
+            .as_ref()
+            .map(|liquid_bonding| LiquidBonding {
+                asset: liquid_bonding.asset.clone(),
+                receipt_token: Addr::unchecked(""),
+            }),
+        pool_router_addr: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -58,12 +87,66 @@ pub fn instantiate(
         .collect::<Vec<_>>()
         .join(", ");
 
-    Ok(Response::default().add_attributes(vec![
+    let mut response = Response::default().add_attributes(vec![
         ("action", "instantiate".to_string()),
         ("owner", config.owner.to_string()),
         ("unbonding_period", config.unbonding_period.to_string()),
         ("growth_rate", config.growth_rate.to_string()),
         ("bonding_assets", bonding_assets),
+    ]);
+
+    if let Some(liquid_bonding) = msg.liquid_bonding {
+        // create the receipt token, minted by this contract
+        response = response.add_submessage(SubMsg {
+            msg: WasmMsg::Instantiate {
+                admin: None,
+                code_id: liquid_bonding.token_code_id,
+                msg: to_json_binary(&TokenInstantiateMsg {
+                    name: RECEIPT_TOKEN_NAME.to_string(),
+                    symbol: RECEIPT_TOKEN_SYMBOL.to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: env.contract.address.to_string(),
+                        cap: None,
+                    }),
+                })?,
+                funds: vec![],
+                label: RECEIPT_TOKEN_NAME.to_string(),
+            }
+            .into(),
+            gas_limit: None,
+            id: RECEIPT_TOKEN_REPLY_ID,
+            reply_on: ReplyOn::Success,
+        });
+    }
+
+    Ok(response)
+}
+
+/// Stores the address of the liquid bonding receipt token once it's instantiated.
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != RECEIPT_TOKEN_REPLY_ID {
+        return Err(ContractError::UnknownReplyId(msg.id));
+    }
+
+    let receipt_token = deps
+        .api
+        .addr_validate(&parse_reply_instantiate_data(msg)?.contract_address)?;
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        let liquid_bonding = config
+            .liquid_bonding
+            .as_mut()
+            .ok_or(ContractError::LiquidBondingDisabled {})?;
+        liquid_bonding.receipt_token = receipt_token.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "reply".to_string()),
+        ("receipt_token", receipt_token.to_string()),
     ]))
 }
 
@@ -75,11 +158,57 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Bond { asset } => commands::bond(deps, env.block.time, info, env, asset),
+        ExecuteMsg::Bond {
+            asset,
+            lock_duration,
+            owner,
+        } => commands::bond(deps, env.block.time, info, env, asset, lock_duration, owner),
+        ExecuteMsg::Receive(msg) => commands::receive_cw20(deps, env, info, msg),
         ExecuteMsg::Unbond { asset } => commands::unbond(deps, env.block.time, info, env, asset),
+        ExecuteMsg::Rebond {
+            denom,
+            unbonding_timestamp,
+            amount,
+        } => commands::rebond(
+            deps,
+            env.block.time,
+            info,
+            env,
+            denom,
+            unbonding_timestamp,
+            amount,
+        ),
+        ExecuteMsg::ExtendLock {
+            denom,
+            lock_duration,
+        } => commands::extend_lock(deps, env.block.time, info, env, denom, lock_duration),
+        ExecuteMsg::LiquidBond { asset } => commands::liquid_bond(deps, info, env, asset),
+        ExecuteMsg::Compound {} => commands::compound(deps, env),
+        ExecuteMsg::ConvertBond {
+            from_denom,
+            to_denom,
+            route,
+            amount,
+            minimum_receive,
+        } => commands::convert_bond(
+            deps,
+            info,
+            env,
+            from_denom,
+            to_denom,
+            route,
+            amount,
+            minimum_receive,
+        ),
         ExecuteMsg::Withdraw { denom } => {
             commands::withdraw(deps, env.block.time, info.sender, denom)
         }
+        ExecuteMsg::SetRewardRecipient { recipient } => {
+            commands::set_reward_recipient(deps, info, recipient)
+        }
+        ExecuteMsg::SetPoolRouter { pool_router_addr } => {
+            commands::set_pool_router(deps, info, pool_router_addr)
+        }
         ExecuteMsg::UpdateConfig {
             owner,
             unbonding_period,
@@ -93,6 +222,7 @@ pub fn execute(
             growth_rate,
             fee_distributor_addr,
         ),
+        ExecuteMsg::Callback(msg) => commands::callback(deps, env, info, msg),
     }
 }
 
@@ -135,8 +265,37 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 global_index,
             )?)
         }
+        QueryMsg::WeightAt { address, timestamp } => {
+            to_json_binary(&queries::query_weight_at(deps, timestamp, address)?)
+        }
+        QueryMsg::WeightAtEpoch { address, epoch_id } => {
+            to_json_binary(&queries::query_weight_at_epoch(deps, address, epoch_id)?)
+        }
+        QueryMsg::GlobalIndexAt { timestamp } => {
+            to_json_binary(&queries::query_global_index_at(deps, timestamp)?)
+        }
+        QueryMsg::Lock { address, denom } => {
+            to_json_binary(&queries::query_lock(deps, env.block.time, address, denom)?)
+        }
+        QueryMsg::RewardRecipient { address } => {
+            to_json_binary(&queries::query_reward_recipient(deps, address)?)
+        }
         QueryMsg::TotalBonded {} => to_json_binary(&queries::query_total_bonded(deps)?),
         QueryMsg::GlobalIndex {} => to_json_binary(&queries::query_global_index(deps)?),
+        QueryMsg::Bonders { start_after, limit } => to_json_binary(&queries::query_bonders(
+            deps,
+            env.block.time,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::TopBonders { sort_by, limit } => to_json_binary(&queries::query_top_bonders(
+            deps,
+            env.block.time,
+            sort_by,
+            limit,
+        )?),
+        QueryMsg::Stats {} => to_json_binary(&queries::query_stats(deps)?),
+        QueryMsg::LiquidBonding {} => to_json_binary(&queries::query_liquid_bonding(deps, env)?),
     }
 }
 
@@ -159,6 +318,12 @@ pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Respons
 
     if storage_version < Version::parse("0.9.0")? {
         migrations::migrate_to_v090(deps.branch())?;
+    } else if storage_version < Version::parse("0.11.0")? {
+        migrations::migrate_to_v0110(deps.branch())?;
+    }
+
+    if storage_version < Version::parse("0.14.0")? {
+        migrations::migrate_to_v0140(deps.branch())?;
     }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -1,4 +1,5 @@
-use cosmwasm_std::{DivideByZeroError, OverflowError, StdError};
+use cosmwasm_std::{CheckedMultiplyRatioError, DivideByZeroError, OverflowError, StdError};
+use cw_utils::ParseReplyError;
 use semver::Version;
 use thiserror::Error;
 
@@ -28,20 +29,96 @@ pub enum ContractError {
     #[error("{0}")]
     OverflowError(#[from] OverflowError),
 
+    #[error("{0}")]
+    CheckedMultiplyRatioError(#[from] CheckedMultiplyRatioError),
+
     #[error("The growth rate must be between 0 and 1. i.e. 0.5 for 50%")]
     InvalidGrowthRate {},
 
+    #[error("Invalid weight multiplier for {0}. It must be positive and set for a bonding asset only once.")]
+    InvalidWeightMultiplier(String),
+
     #[error(
-        "The amount of bonding assets is greater than the limit allowed. Limit is {0}, sent {1}."
+        "Invalid bonding asset. Cw20 tokens can only be bonded by sending them to the contract."
     )]
-    InvalidBondingAssetsLimit(usize, usize),
-
-    #[error("Can only bond native assets.")]
     InvalidBondingAsset {},
 
     #[error("Nothing to unbond.")]
     NothingToUnbond {},
 
+    #[error("The amount of tokens to rebond must be greater than zero.")]
+    InvalidRebondingAmount {},
+
+    #[error("Nothing to rebond.")]
+    NothingToRebond {},
+
+    #[error("The amount of tokens to rebond is greater than the amount of tokens unbonding.")]
+    InsufficientUnbonding {},
+
+    #[error(
+        "Rebonding is not available in vote-escrow mode. Bond the tokens with a new lock instead."
+    )]
+    RebondUnavailable {},
+
+    #[error("Locks are only available in vote-escrow mode.")]
+    VoteEscrowDisabled {},
+
+    #[error("The maximum lock duration must be at least one second.")]
+    InvalidMaxLockDuration {},
+
+    #[error(
+        "The lock duration must be greater than zero and not exceed the maximum lock duration."
+    )]
+    InvalidLockDuration {},
+
+    #[error("A lock duration is required when there is no active lock for the asset.")]
+    MissingLockDuration {},
+
+    #[error("The lock can't end before the current lock of the asset.")]
+    LockCannotBeShortened {},
+
+    #[error("The tokens can't be unbonded until their lock expires.")]
+    LockNotExpired {},
+
+    #[error("Nothing to extend.")]
+    NothingToExtend {},
+
+    #[error("Tokens can't be bonded on behalf of the contract itself.")]
+    InvalidBondOwner {},
+
+    #[error("Tokens bonded on behalf of another address can't change the lock it already has.")]
+    CannotChangeOwnerLock {},
+
+    #[error("Liquid bonding is not enabled.")]
+    LiquidBondingDisabled {},
+
+    #[error("Invalid liquid bonding. The asset must be a native bonding asset, and liquid bonding is not available in vote-escrow mode.")]
+    InvalidLiquidBonding {},
+
+    #[error("The amount of tokens to liquid bond is too small to mint any receipt tokens.")]
+    InvalidLiquidBondAmount {},
+
+    #[error("The amount of receipt tokens to redeem is too small to unbond any tokens.")]
+    InvalidRedeemAmount {},
+
+    #[error("Nothing to compound.")]
+    NothingToCompound {},
+
+    #[error("Converting bonds is disabled, as no pool router is set.")]
+    BondConversionDisabled {},
+
+    #[error("Bonds can't be converted in vote-escrow mode.")]
+    ConvertBondUnavailable {},
+
+    #[error("Invalid bond conversion. The denoms must be different bonding assets, and the route must swap the first into the second.")]
+    InvalidBondConversion {},
+
+    #[error("The amount of tokens to convert and the minimum amount to receive must be greater than zero.")]
+    InvalidConversionAmount {},
+
+    #[error("Nothing to convert.")]
+    NothingToConvert {},
+
     #[error("Nothing to withdraw.")]
     NothingToWithdraw {},
 
@@ -51,8 +128,14 @@ pub enum ContractError {
         current_version: Version,
     },
 
-    #[error("There are unclaimed rewards available. Claim them before attempting to bond/unbond")]
-    UnclaimedRewards {},
+    #[error("{0}")]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error("Can't handle the given reply id: {0}")]
+    UnknownReplyId(u64),
+
+    #[error("Callbacks can only be invoked by the contract itself")]
+    ExternalCallback {},
 
     #[error("Trying to bond/unbond at a late time before the new/latest epoch has been created")]
     NewEpochNotCreatedYet {},
@@ -1,10 +1,17 @@
-use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, StdResult, Timestamp, Uint64};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, StdResult,
+    Timestamp, Uint128, Uint64, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, TokenInfoResponse};
 use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
 use white_whale_std::fee_distributor::{ClaimableEpochsResponse, EpochResponse};
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::router;
+use white_whale_std::pool_network::router::SwapOperation;
+use white_whale_std::whale_lair::{CallbackMsg, Config, ExecuteMsg, LiquidBonding};
 
 use crate::error::ContractError;
-use crate::state::CONFIG;
+use crate::state::{BOND, CONFIG, LOCK};
 
 /// Validates that the growth rate is between 0 and 1.
 pub fn validate_growth_rate(growth_rate: Decimal) -> Result<(), ContractError> {
@@ -14,6 +21,353 @@ pub fn validate_growth_rate(growth_rate: Decimal) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Validates that the maximum lock duration is at least a second, as lock weights are calculated
+/// in seconds.
+pub fn validate_max_lock_duration(max_lock_duration: Uint64) -> Result<(), ContractError> {
+    if max_lock_duration.u64() < 1_000_000_000 {
+        return Err(ContractError::InvalidMaxLockDuration {});
+    }
+    Ok(())
+}
+
+/// Validates that the lock duration is positive and doesn't exceed the maximum lock duration.
+pub fn validate_lock_duration(
+    lock_duration: Uint64,
+    max_lock_duration: Uint64,
+) -> Result<(), ContractError> {
+    if lock_duration.is_zero() || lock_duration > max_lock_duration {
+        return Err(ContractError::InvalidLockDuration {});
+    }
+    Ok(())
+}
+
+/// Gets the end of the lock for a bond of the given asset, which is only set in vote-escrow mode.
+/// With a lock duration, the lock ends once the duration has passed, which can't be before the
+/// current lock of the asset ends. Otherwise the bond is added to the current lock, which must not
+/// have expired.
+pub fn get_lock_end(
+    deps: &DepsMut,
+    timestamp: Timestamp,
+    address: &Addr,
+    asset_info: &AssetInfo,
+    lock_duration: Option<Uint64>,
+) -> Result<Option<Timestamp>, ContractError> {
+    let max_lock_duration = match CONFIG.load(deps.storage)?.max_lock_duration {
+        Some(max_lock_duration) => max_lock_duration,
+        None if lock_duration.is_some() => return Err(ContractError::VoteEscrowDisabled {}),
+        None => return Ok(None),
+    };
+
+    let current_lock_end = LOCK.may_load(deps.storage, (address, &asset_info.to_string()))?;
+
+    match lock_duration {
+        Some(lock_duration) => {
+            validate_lock_duration(lock_duration, max_lock_duration)?;
+            let lock_end = timestamp.plus_nanos(lock_duration.u64());
+            if current_lock_end.is_some_and(|current_lock_end| current_lock_end > lock_end) {
+                return Err(ContractError::LockCannotBeShortened {});
+            }
+
+            Ok(Some(lock_end))
+        }
+        None => match current_lock_end {
+            Some(lock_end) if lock_end.seconds() > timestamp.seconds() => Ok(Some(lock_end)),
+            _ => Err(ContractError::MissingLockDuration {}),
+        },
+    }
+}
+
+/// Validates that the liquid bonding asset is a native bonding asset. Liquid bonding is not
+/// available in vote-escrow mode, as the pool would need to be locked.
+pub fn validate_liquid_bonding(
+    bonding_assets: &[AssetInfo],
+    asset_info: &AssetInfo,
+    max_lock_duration: Option<Uint64>,
+) -> Result<(), ContractError> {
+    if max_lock_duration.is_some()
+        || !asset_info.is_native_token()
+        || !bonding_assets.contains(asset_info)
+    {
+        return Err(ContractError::InvalidLiquidBonding {});
+    }
+
+    Ok(())
+}
+
+/// Gets the liquid bonding mode of the contract, if enabled.
+pub fn get_liquid_bonding(config: &Config) -> Result<LiquidBonding, ContractError> {
+    config
+        .liquid_bonding
+        .clone()
+        .ok_or(ContractError::LiquidBondingDisabled {})
+}
+
+/// Gets the total supply of the receipt token and the amount of tokens bonded in the liquid
+/// bonding pool, which the contract bonds on its own behalf.
+pub fn get_liquid_bonding_pool(
+    deps: Deps,
+    env: &Env,
+    liquid_bonding: &LiquidBonding,
+) -> StdResult<(Uint128, Uint128)> {
+    let token_info: TokenInfoResponse = deps.querier.query_wasm_smart(
+        liquid_bonding.receipt_token.to_string(),
+        &Cw20QueryMsg::TokenInfo {},
+    )?;
+
+    let bonded_amount = BOND
+        .may_load(
+            deps.storage,
+            (&env.contract.address, &liquid_bonding.asset.to_string()),
+        )?
+        .map_or(Uint128::zero(), |bond| bond.asset.amount);
+
+    Ok((token_info.total_supply, bonded_amount))
+}
+
+/// If the liquid bonding pool has unclaimed rewards on the fee distributor, returns the messages
+/// to claim and bond them back into the pool, followed by the given callback if any, so the
+/// exchange rate is up to date before the callback uses it.
+pub fn compound_before_callback(
+    deps: &DepsMut,
+    env: &Env,
+    liquid_bonding: &LiquidBonding,
+    callback: Option<CallbackMsg>,
+) -> Result<Option<Vec<CosmosMsg>>, ContractError> {
+    let fee_distributor = CONFIG.load(deps.storage)?.fee_distributor_addr;
+    let claimable_rewards = query_claimable_rewards(deps, &fee_distributor, &env.contract.address)?;
+
+    if claimable_rewards.epochs.is_empty() {
+        return Ok(None);
+    }
+
+    // the rewards are what the contract holds on top of the balances it has before claiming, for
+    // each of the assets distributed in the claimable epochs
+    let mut balances_before = vec![];
+    let reward_asset_infos = claimable_rewards
+        .epochs
+        .iter()
+        .flat_map(|epoch| epoch.available.iter().map(|asset| asset.info.clone()));
+    for asset_info in std::iter::once(liquid_bonding.asset.clone()).chain(reward_asset_infos) {
+        if balances_before
+            .iter()
+            .any(|balance: &Asset| balance.info == asset_info)
+        {
+            continue;
+        }
+
+        balances_before.push(Asset {
+            amount: asset_info.query_pool(&deps.querier, deps.api, env.contract.address.clone())?,
+            info: asset_info,
+        });
+    }
+
+    let mut messages = claim_msgs(
+        &fee_distributor,
+        env,
+        &env.contract.address,
+        CallbackMsg::Compound { balances_before },
+    )?;
+
+    if let Some(callback) = callback {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Callback(callback))?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+
+    Ok(Some(messages))
+}
+
+/// Gets the address the tokens are bonded on behalf of, which is the owner if set or the sender
+/// otherwise. A lock duration can't be given on behalf of an owner that has a lock for the asset
+/// already, so others can't extend it.
+pub fn get_bond_owner(
+    deps: &DepsMut,
+    env: &Env,
+    sender: &Addr,
+    owner: Option<String>,
+    asset_info: &AssetInfo,
+    lock_duration: Option<Uint64>,
+) -> Result<Addr, ContractError> {
+    let Some(owner) = owner else {
+        return Ok(sender.clone());
+    };
+
+    let owner = deps.api.addr_validate(&owner)?;
+    // bonding on behalf of the liquid bonding pool would skew its exchange rate
+    if owner == env.contract.address {
+        return Err(ContractError::InvalidBondOwner {});
+    }
+
+    if owner != *sender
+        && lock_duration.is_some()
+        && LOCK.has(deps.storage, (&owner, &asset_info.to_string()))
+    {
+        return Err(ContractError::CannotChangeOwnerLock {});
+    }
+
+    Ok(owner)
+}
+
+/// Validates that a bond can be converted from one denom into another along the given route,
+/// returning the [AssetInfo] of both denoms. They must be different bonding assets, and the route
+/// must swap the first into the second.
+pub fn validate_bond_conversion(
+    config: &Config,
+    from_denom: &str,
+    to_denom: &str,
+    route: &[SwapOperation],
+) -> Result<(AssetInfo, AssetInfo), ContractError> {
+    let find_bonding_asset = |denom: &str| {
+        config
+            .bonding_assets
+            .iter()
+            .find(|asset_info| asset_info.to_string() == denom)
+            .cloned()
+            .ok_or(ContractError::InvalidBondConversion {})
+    };
+
+    let from_asset_info = find_bonding_asset(from_denom)?;
+    let to_asset_info = find_bonding_asset(to_denom)?;
+    if from_asset_info == to_asset_info {
+        return Err(ContractError::InvalidBondConversion {});
+    }
+
+    // each operation must offer the asset the previous one asks for
+    let mut offer_asset_info = from_asset_info.clone();
+    for operation in route {
+        match operation {
+            SwapOperation::TerraSwap {
+                offer_asset_info: operation_offer_asset_info,
+                ask_asset_info,
+            } => {
+                if operation_offer_asset_info != &offer_asset_info {
+                    return Err(ContractError::InvalidBondConversion {});
+                }
+                offer_asset_info = ask_asset_info.clone();
+            }
+        }
+    }
+
+    if route.is_empty() || offer_asset_info != to_asset_info {
+        return Err(ContractError::InvalidBondConversion {});
+    }
+
+    Ok((from_asset_info, to_asset_info))
+}
+
+/// Creates the message to swap the given asset through the pool router along the given route,
+/// sending the tokens received back to the contract.
+pub fn get_swap_msg(
+    pool_router_addr: &Addr,
+    asset: &Asset,
+    route: Vec<SwapOperation>,
+    minimum_receive: Uint128,
+) -> StdResult<CosmosMsg> {
+    let msg = match &asset.info {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: pool_router_addr.to_string(),
+            msg: to_json_binary(&router::ExecuteMsg::ExecuteSwapOperations {
+                operations: route,
+                minimum_receive: Some(minimum_receive),
+                to: None,
+                max_spread: None,
+            })?,
+            funds: coins(asset.amount.u128(), denom),
+        },
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: pool_router_addr.to_string(),
+                amount: asset.amount,
+                msg: to_json_binary(&router::Cw20HookMsg::ExecuteSwapOperations {
+                    operations: route,
+                    minimum_receive: Some(minimum_receive),
+                    to: None,
+                    max_spread: None,
+                })?,
+            })?,
+            funds: vec![],
+        },
+    };
+
+    Ok(msg.into())
+}
+
+/// Gets the route registered in the pool router to swap the given assets, if any.
+pub fn query_swap_route(
+    deps: &DepsMut,
+    pool_router_addr: &Addr,
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: &AssetInfo,
+) -> Option<Vec<SwapOperation>> {
+    deps.querier
+        .query_wasm_smart::<Vec<SwapOperation>>(
+            pool_router_addr.to_string(),
+            &router::QueryMsg::SwapRoute {
+                offer_asset_info: offer_asset_info.clone(),
+                ask_asset_info: ask_asset_info.clone(),
+            },
+        )
+        .ok()
+        .filter(|route| !route.is_empty())
+}
+
+/// Validates that the given asset is whitelisted for bonding.
+pub fn validate_bonding_asset(
+    config: &Config,
+    asset_info: &AssetInfo,
+) -> Result<(), ContractError> {
+    if !config.bonding_assets.contains(asset_info) {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    Ok(())
+}
+
+/// Validates the weight multipliers, which must be positive and set for bonding assets only.
+pub fn validate_weight_multipliers(
+    bonding_assets: &[AssetInfo],
+    weight_multipliers: &[(AssetInfo, Decimal)],
+) -> Result<(), ContractError> {
+    for (i, (asset_info, multiplier)) in weight_multipliers.iter().enumerate() {
+        if multiplier.is_zero()
+            || !bonding_assets.contains(asset_info)
+            || weight_multipliers[..i]
+                .iter()
+                .any(|(other_asset_info, _)| other_asset_info == asset_info)
+        {
+            return Err(ContractError::InvalidWeightMultiplier(
+                asset_info.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the amount of the given bonded asset scaled by its weight multiplier.
+pub fn get_weighted_amount(config: &Config, asset: &Asset) -> Uint128 {
+    let multiplier = config
+        .weight_multipliers
+        .iter()
+        .find(|(asset_info, _)| asset_info == &asset.info)
+        .map_or(Decimal::one(), |(_, multiplier)| *multiplier);
+
+    asset.amount * multiplier
+}
+
+/// Gets the total amount of the given bonded assets, scaled by the weight multiplier of each.
+pub fn get_total_weighted_amount(config: &Config, assets: &[Asset]) -> StdResult<Uint128> {
+    assets.iter().try_fold(Uint128::zero(), |total, asset| {
+        Ok(total.checked_add(get_weighted_amount(config, asset))?)
+    })
+}
+
 /// Validates that the asset sent on the message matches the asset provided and is whitelisted for bonding.
 pub fn validate_funds(
     deps: &DepsMut,
@@ -41,27 +395,63 @@ pub fn validate_funds(
     Ok(())
 }
 
-/// if user has unclaimed rewards, fail with an exception prompting them to claim
-pub fn validate_claimed(deps: &DepsMut, info: &MessageInfo) -> Result<(), ContractError> {
-    // Query fee distributor
-    // if user has unclaimed rewards, fail with an exception prompting them to claim
-    let config = CONFIG.load(deps.storage)?;
-    let fee_distributor = config.fee_distributor_addr;
+/// If the address has unclaimed rewards on the fee distributor, returns the messages to claim them
+/// on its behalf followed by the given callback, so the rewards are settled against the bonds
+/// the address had before the callback modifies them.
+pub fn claim_before_callback(
+    deps: &DepsMut,
+    env: &Env,
+    address: &Addr,
+    callback: CallbackMsg,
+) -> Result<Option<Vec<CosmosMsg>>, ContractError> {
+    let fee_distributor = CONFIG.load(deps.storage)?.fee_distributor_addr;
+    let claimable_rewards = query_claimable_rewards(deps, &fee_distributor, address)?;
 
-    // Do a smart query for Claimable
-    let claimable_rewards: ClaimableEpochsResponse = deps.querier.query_wasm_smart(
-        fee_distributor,
+    if claimable_rewards.epochs.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(claim_msgs(&fee_distributor, env, address, callback)?))
+}
+
+/// Queries the epochs the address has unclaimed rewards on in the fee distributor.
+fn query_claimable_rewards(
+    deps: &DepsMut,
+    fee_distributor: &Addr,
+    address: &Addr,
+) -> StdResult<ClaimableEpochsResponse> {
+    deps.querier.query_wasm_smart(
+        fee_distributor.to_string(),
         &white_whale_std::fee_distributor::QueryMsg::Claimable {
-            address: info.sender.to_string(),
+            address: address.to_string(),
         },
-    )?;
-
-    // If epochs is greater than none
-    if !claimable_rewards.epochs.is_empty() {
-        return Err(ContractError::UnclaimedRewards {});
-    }
+    )
+}
 
-    Ok(())
+/// Creates the messages to claim the rewards of the address on the fee distributor, followed by
+/// the given callback.
+fn claim_msgs(
+    fee_distributor: &Addr,
+    env: &Env,
+    address: &Addr,
+    callback: CallbackMsg,
+) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![
+        WasmMsg::Execute {
+            contract_addr: fee_distributor.to_string(),
+            msg: to_json_binary(&white_whale_std::fee_distributor::ExecuteMsg::ClaimFor {
+                address: address.to_string(),
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Callback(callback))?,
+            funds: vec![],
+        }
+        .into(),
+    ])
 }
 
 /// Validates that the current time is not more than a day after the epoch start time. Helps preventing
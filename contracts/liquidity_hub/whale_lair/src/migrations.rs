@@ -1,7 +1,8 @@
 #![cfg(not(tarpaulin_include))]
-use crate::state::CONFIG;
+use crate::helpers::get_total_weighted_amount;
+use crate::state::{CONFIG, GLOBAL, GLOBAL_CHECKPOINTS};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, DepsMut, StdError, Uint64};
+use cosmwasm_std::{Addr, Decimal, DepsMut, StdError, Uint128, Uint64};
 use cw_storage_plus::Item;
 use white_whale_std::pool_network::asset::AssetInfo;
 use white_whale_std::whale_lair::Config;
@@ -28,9 +29,71 @@ pub fn migrate_to_v090(deps: DepsMut) -> Result<(), StdError> {
         growth_rate: config_v080.growth_rate,
         bonding_assets: config_v080.bonding_assets,
         fee_distributor_addr: Addr::unchecked(""), // set it empty, then update with the new value
+        weight_multipliers: vec![],
+        max_lock_duration: None,
+        liquid_bonding: None,
+        pool_router_addr: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
 
     Ok(())
 }
+
+pub fn migrate_to_v0110(deps: DepsMut) -> Result<(), StdError> {
+    #[cw_serde]
+    pub struct ConfigV0100 {
+        /// Owner of the contract.
+        pub owner: Addr,
+        /// Unbonding period in nanoseconds.
+        pub unbonding_period: Uint64,
+        /// A fraction that controls the effect of time on the weight of a bond. If the growth rate is set
+        /// to zero, time will have no impact on the weight.
+        pub growth_rate: Decimal,
+        /// Denom of the asset to be bonded. Can't only be set at instantiation.
+        pub bonding_assets: Vec<AssetInfo>,
+        /// Address of the fee distributor contract.
+        pub fee_distributor_addr: Addr,
+    }
+    const CONFIGV0100: Item<ConfigV0100> = Item::new("config");
+    let config_v0100 = CONFIGV0100.load(deps.storage)?;
+
+    // the existing bonds keep a weight multiplier of one
+    let config = Config {
+        owner: config_v0100.owner,
+        unbonding_period: config_v0100.unbonding_period,
+        growth_rate: config_v0100.growth_rate,
+        bonding_assets: config_v0100.bonding_assets,
+        fee_distributor_addr: config_v0100.fee_distributor_addr,
+        weight_multipliers: vec![],
+        max_lock_duration: None,
+        liquid_bonding: None,
+        pool_router_addr: None,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(())
+}
+
+pub fn migrate_to_v0140(deps: DepsMut) -> Result<(), StdError> {
+    if let Some(mut global_index) = GLOBAL.may_load(deps.storage)? {
+        // the weighted amount the global weight grows with is kept apart from the bonded amount
+        let config = CONFIG.load(deps.storage)?;
+        global_index.bonded_amount = global_index
+            .bonded_assets
+            .iter()
+            .try_fold(Uint128::zero(), |total, asset| {
+                total.checked_add(asset.amount)
+            })?;
+        global_index.weighted_amount =
+            get_total_weighted_amount(&config, &global_index.bonded_assets)?;
+        GLOBAL.save(deps.storage, &global_index)?;
+
+        // record the current global index, so its history is known from the moment it was last
+        // updated. Bonds without checkpoints are read from the current state until they change.
+        GLOBAL_CHECKPOINTS.save(deps.storage, global_index.timestamp.nanos(), &global_index)?;
+    }
+
+    Ok(())
+}
@@ -1,22 +1,28 @@
+use std::cmp::Reverse;
 use std::collections::HashSet;
 
 use cosmwasm_std::{
-    to_json_binary, Decimal, Deps, Order, QueryRequest, StdError, StdResult, Timestamp, Uint128,
-    Uint64, WasmQuery,
+    to_json_binary, Addr, Decimal, Deps, Env, Order, QueryRequest, StdError, StdResult, Timestamp,
+    Uint128, Uint64, WasmQuery,
 };
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PrefixBound};
 
-use white_whale_std::fee_distributor::QueryMsg;
+use white_whale_std::fee_distributor::{EpochResponse, QueryMsg};
 use white_whale_std::{
-    pool_network::asset::AssetInfo,
+    pool_network::asset::{aggregate_assets, Asset, AssetInfo},
     whale_lair::{
-        Bond, BondedResponse, BondingWeightResponse, Config, GlobalIndex, UnbondingResponse,
-        WithdrawableResponse,
+        Bond, BondedResponse, BonderInfo, BonderSortBy, BondersResponse, BondingWeightResponse,
+        Config, GlobalIndex, LiquidBondingResponse, LockResponse, RewardRecipientResponse,
+        StatsResponse, UnbondingResponse, WithdrawableResponse,
     },
 };
 
 use crate::helpers;
-use crate::state::{get_weight, BOND, BONDING_ASSETS_LIMIT, CONFIG, GLOBAL, UNBOND};
+use crate::helpers::get_weighted_amount;
+use crate::state::{
+    get_bond_checkpoint, get_bond_weight, get_global_checkpoint, get_global_weight,
+    get_lock_weight, BOND, CONFIG, GLOBAL, LOCK, REWARD_RECIPIENT, UNBOND,
+};
 
 /// Queries the current configuration of the contract.
 pub(crate) fn query_config(deps: Deps) -> StdResult<Config> {
@@ -30,7 +36,6 @@ pub(crate) fn query_bonded(deps: Deps, address: String) -> StdResult<BondedRespo
     let bonds: Vec<Bond> = BOND
         .prefix(&address)
         .range(deps.storage, None, None, Order::Ascending)
-        .take(BONDING_ASSETS_LIMIT)
         .map(|item| {
             let (_, bond) = item?;
             Ok(bond)
@@ -97,7 +102,11 @@ pub(crate) fn query_unbonding(
         .take(limit)
         .map(|item| {
             let (_, bond) = item?;
-            Ok(bond)
+            // the weight kept in case the tokens are rebonded is internal to the contract
+            Ok(Bond {
+                weight: Uint128::zero(),
+                ..bond
+            })
         })
         .collect::<StdResult<Vec<Bond>>>()?;
 
@@ -169,13 +178,12 @@ pub(crate) fn query_weight(
     // Make an empty set of unique denoms
     let mut unique_denoms: HashSet<String> = HashSet::new();
 
-    for (_, mut bond) in bonds? {
-        bond.weight = get_weight(
+    for (denom, mut bond) in bonds? {
+        bond.weight = get_bond_weight(
+            &config,
             timestamp,
-            bond.weight,
-            bond.asset.amount,
-            config.growth_rate,
-            bond.timestamp,
+            &bond,
+            LOCK.may_load(deps.storage, (&address, &denom))?,
         )?;
 
         match bond.asset.info {
@@ -184,7 +192,7 @@ pub(crate) fn query_weight(
                 if !unique_denoms.contains(&denom) {
                     unique_denoms.insert(denom.clone());
                 }
-            } //todo the bonding assets can't be Tokens, so this could be removed
+            }
             AssetInfo::Token { contract_addr } => {
                 // If the contract_addr is not in the set of unique denoms, add it
                 if !unique_denoms.contains(&contract_addr) {
@@ -197,7 +205,7 @@ pub(crate) fn query_weight(
         total_bond_weight = total_bond_weight.checked_add(bond.weight)?;
     }
 
-    let mut global_index = if let Some(global_index) = global_index {
+    let global_index = if let Some(global_index) = global_index {
         global_index
     } else {
         GLOBAL
@@ -207,24 +215,118 @@ pub(crate) fn query_weight(
     };
 
     // If a global weight from an Epoch was passed, use that to get the weight, otherwise use the current global index weight
-    global_index.weight = get_weight(
+    let global_index = get_global_weight(deps.storage, &config, timestamp, global_index)?;
+
+    Ok(bonding_weight_response(
+        address,
+        timestamp,
+        total_bond_weight,
+        global_index.weight,
+    ))
+}
+
+/// Queries the weight of the given address at the provided timestamp from the recorded bond
+/// history, rather than extrapolating it from the current bonds.
+pub(crate) fn query_weight_at(
+    deps: Deps,
+    timestamp: Timestamp,
+    address: String,
+) -> StdResult<BondingWeightResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut total_bond_weight = Uint128::zero();
+    for asset_info in &config.bonding_assets {
+        if let Some(checkpoint) =
+            get_bond_checkpoint(deps.storage, &address, &asset_info.to_string(), timestamp)?
+        {
+            total_bond_weight = total_bond_weight.checked_add(get_bond_weight(
+                &config,
+                timestamp,
+                &checkpoint.bond,
+                checkpoint.lock_end,
+            )?)?;
+        }
+    }
+
+    let global_index = query_global_index_at(deps, timestamp)?;
+
+    Ok(bonding_weight_response(
+        address,
         timestamp,
+        total_bond_weight,
         global_index.weight,
-        global_index.bonded_amount,
-        config.growth_rate,
-        global_index.timestamp,
-    )?;
+    ))
+}
 
-    // Represents the share of the global weight that the address has
-    let share = Decimal::from_ratio(total_bond_weight, global_index.weight);
+/// Queries the weight of the given address at the start of the given fee distributor epoch.
+pub(crate) fn query_weight_at_epoch(
+    deps: Deps,
+    address: String,
+    epoch_id: Uint64,
+) -> StdResult<BondingWeightResponse> {
+    let fee_distributor_addr = CONFIG.load(deps.storage)?.fee_distributor_addr;
+    let epoch_response: EpochResponse = deps
+        .querier
+        .query_wasm_smart(fee_distributor_addr, &QueryMsg::Epoch { id: epoch_id })?;
 
-    Ok(BondingWeightResponse {
+    // the fee distributor returns a default epoch when it doesn't exist
+    if epoch_response.epoch.id != epoch_id {
+        return Err(StdError::generic_err(format!("Epoch {epoch_id} not found")));
+    }
+
+    query_weight_at(deps, epoch_response.epoch.start_time, address)
+}
+
+/// Builds the weight response for the given address, with its share of the global weight. All locks
+/// might have expired in vote-escrow mode, leaving no global weight at all.
+fn bonding_weight_response(
+    address: Addr,
+    timestamp: Timestamp,
+    weight: Uint128,
+    global_weight: Uint128,
+) -> BondingWeightResponse {
+    let share = if global_weight.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(weight, global_weight)
+    };
+
+    BondingWeightResponse {
         address: address.to_string(),
-        weight: total_bond_weight,
-        global_weight: global_index.weight,
+        weight,
+        global_weight,
         share,
         timestamp,
-    })
+    }
+}
+
+/// Queries the lock of the given denom for the specified address, in vote-escrow mode.
+pub(crate) fn query_lock(
+    deps: Deps,
+    timestamp: Timestamp,
+    address: String,
+    denom: String,
+) -> StdResult<LockResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let lock_end = LOCK.may_load(deps.storage, (&address, &denom))?;
+
+    let weight = match (
+        config.max_lock_duration,
+        lock_end,
+        BOND.may_load(deps.storage, (&address, &denom))?,
+    ) {
+        (Some(max_lock_duration), Some(lock_end), Some(bond)) => get_lock_weight(
+            timestamp,
+            get_weighted_amount(&config, &bond.asset),
+            lock_end,
+            max_lock_duration,
+        )?,
+        _ => Uint128::zero(),
+    };
+
+    Ok(LockResponse { lock_end, weight })
 }
 
 /// Queries the total amount of assets that have been bonded to the contract.
@@ -242,3 +344,195 @@ pub fn query_global_index(deps: Deps) -> StdResult<GlobalIndex> {
     let global_index = GLOBAL.may_load(deps.storage)?.unwrap_or_default();
     Ok(global_index)
 }
+
+/// Queries the global index at the provided timestamp from the recorded history, with its weight
+/// brought up to that time.
+pub fn query_global_index_at(deps: Deps, timestamp: Timestamp) -> StdResult<GlobalIndex> {
+    let config = CONFIG.load(deps.storage)?;
+    let global_index = get_global_checkpoint(deps.storage, timestamp)?;
+
+    get_global_weight(deps.storage, &config, timestamp, global_index)
+}
+
+/// Queries the bonders with their bonded assets and current weight, sorted by address.
+pub(crate) fn query_bonders(
+    deps: Deps,
+    timestamp: Timestamp,
+    start_after: Option<String>,
+    limit: Option<u8>,
+) -> StdResult<BondersResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+
+    let bonds = BOND.prefix_range(
+        deps.storage,
+        start_after.as_ref().map(PrefixBound::exclusive),
+        None,
+        Order::Ascending,
+    );
+
+    let bonders = group_bonds_by_bonder(bonds, Some(limit))?
+        .into_iter()
+        .map(|(address, bonds)| get_bonder_info(deps, &config, timestamp, address, bonds))
+        .collect::<StdResult<Vec<BonderInfo>>>()?;
+
+    Ok(BondersResponse { bonders })
+}
+
+/// Queries the bonders with the highest current weight or bonded amount. Bonders with the same
+/// value are sorted by address.
+pub(crate) fn query_top_bonders(
+    deps: Deps,
+    timestamp: Timestamp,
+    sort_by: BonderSortBy,
+    limit: Option<u8>,
+) -> StdResult<BondersResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+
+    let bonds = BOND.range(deps.storage, None, None, Order::Ascending);
+    let mut bonders = group_bonds_by_bonder(bonds, None)?
+        .into_iter()
+        .map(|(address, bonds)| get_bonder_info(deps, &config, timestamp, address, bonds))
+        .collect::<StdResult<Vec<BonderInfo>>>()?;
+
+    // the sort is stable, so ties keep the address order
+    match sort_by {
+        BonderSortBy::Weight => bonders.sort_by_key(|bonder| Reverse(bonder.weight)),
+        BonderSortBy::BondedAmount => bonders.sort_by_key(|bonder| Reverse(bonder.bonded_amount)),
+    }
+    bonders.truncate(limit);
+
+    Ok(BondersResponse { bonders })
+}
+
+/// Queries the number of bonders and the total amounts of bonded and unbonding assets.
+pub(crate) fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let mut bonder_count = 0u64;
+    let mut last_bonder: Option<Addr> = None;
+    for key in BOND.keys(deps.storage, None, None, Order::Ascending) {
+        let (address, _) = key?;
+        if last_bonder.as_ref() != Some(&address) {
+            bonder_count += 1;
+            last_bonder = Some(address);
+        }
+    }
+
+    let unbonding_assets = UNBOND
+        .range(deps.storage, None, None, Order::Ascending)
+        .try_fold(vec![], |unbonding_assets, item| -> StdResult<Vec<Asset>> {
+            let (_, unbonding) = item?;
+            aggregate_assets(unbonding_assets, vec![unbonding.asset])
+        })?;
+
+    Ok(StatsResponse {
+        bonder_count,
+        bonded_assets: GLOBAL
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .bonded_assets,
+        unbonding_assets,
+    })
+}
+
+/// Queries the address the rewards of the given address are sent to.
+pub(crate) fn query_reward_recipient(
+    deps: Deps,
+    address: String,
+) -> StdResult<RewardRecipientResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let recipient = REWARD_RECIPIENT
+        .may_load(deps.storage, &address)?
+        .unwrap_or(address);
+
+    Ok(RewardRecipientResponse {
+        recipient: recipient.to_string(),
+    })
+}
+
+/// Queries the state of the liquid bonding pool.
+pub(crate) fn query_liquid_bonding(deps: Deps, env: Env) -> StdResult<LiquidBondingResponse> {
+    let liquid_bonding = CONFIG
+        .load(deps.storage)?
+        .liquid_bonding
+        .ok_or_else(|| StdError::generic_err("Liquid bonding is not enabled"))?;
+
+    let (total_shares, bonded_amount) =
+        helpers::get_liquid_bonding_pool(deps, &env, &liquid_bonding)?;
+    let exchange_rate = if total_shares.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(bonded_amount, total_shares)
+    };
+
+    Ok(LiquidBondingResponse {
+        asset: liquid_bonding.asset,
+        receipt_token: liquid_bonding.receipt_token.to_string(),
+        total_shares,
+        bonded_amount,
+        exchange_rate,
+    })
+}
+
+/// The bonds of an address, along with their denoms.
+type BonderBonds = (Addr, Vec<(String, Bond)>);
+
+/// Groups the bonds, which are sorted by address, by bonder. Stops once the limit of bonders is
+/// reached, if any.
+fn group_bonds_by_bonder(
+    bonds: impl Iterator<Item = StdResult<((Addr, String), Bond)>>,
+    limit: Option<usize>,
+) -> StdResult<Vec<BonderBonds>> {
+    let mut bonders: Vec<BonderBonds> = vec![];
+
+    for item in bonds {
+        let ((address, denom), bond) = item?;
+        match bonders.last_mut() {
+            Some((last_address, last_bonds)) if *last_address == address => {
+                last_bonds.push((denom, bond));
+            }
+            _ => {
+                if limit.is_some_and(|limit| bonders.len() == limit) {
+                    break;
+                }
+                bonders.push((address, vec![(denom, bond)]));
+            }
+        }
+    }
+
+    Ok(bonders)
+}
+
+/// Gets the bonded assets and current weight of the given bonds of an address.
+fn get_bonder_info(
+    deps: Deps,
+    config: &Config,
+    timestamp: Timestamp,
+    address: Addr,
+    bonds: Vec<(String, Bond)>,
+) -> StdResult<BonderInfo> {
+    let mut bonded_amount = Uint128::zero();
+    let mut weight = Uint128::zero();
+    let mut bonded_assets = vec![];
+
+    for (denom, bond) in bonds {
+        bonded_amount = bonded_amount.checked_add(get_weighted_amount(config, &bond.asset))?;
+        weight = weight.checked_add(get_bond_weight(
+            config,
+            timestamp,
+            &bond,
+            LOCK.may_load(deps.storage, (&address, &denom))?,
+        )?)?;
+        bonded_assets.push(bond.asset);
+    }
+
+    Ok(BonderInfo {
+        address: address.to_string(),
+        bonded_assets,
+        bonded_amount,
+        weight,
+    })
+}
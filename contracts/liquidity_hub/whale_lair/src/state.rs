@@ -1,18 +1,46 @@
-use cosmwasm_std::{Addr, Decimal, DepsMut, StdError, StdResult, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Decimal, DepsMut, Order, StdError, StdResult, Storage, Timestamp, Uint128, Uint64,
+};
+use cw_storage_plus::{Bound, Item, Map};
 
-use white_whale_std::pool_network::asset::AssetInfo;
-use white_whale_std::whale_lair::{Bond, Config, GlobalIndex};
+use white_whale_std::whale_lair::{Bond, Config, GlobalIndex, VoteEscrowIndex};
 
+use crate::helpers::get_weighted_amount;
 use crate::ContractError;
 
+/// The denom of native tokens or the contract address of cw20 tokens.
 type Denom = str;
 
-pub const BONDING_ASSETS_LIMIT: usize = 2;
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const BOND: Map<(&Addr, &Denom), Bond> = Map::new("bond");
+/// The unbondings of an address. The weight of each unbonding is the weight the tokens had when
+/// unbonded, restored if they are rebonded.
 pub const UNBOND: Map<(&Addr, &Denom, u64), Bond> = Map::new("unbond");
 pub const GLOBAL: Item<GlobalIndex> = Item::new("global");
+/// The time at which the lock of a bond expires, in vote-escrow mode.
+pub const LOCK: Map<(&Addr, &Denom), Timestamp> = Map::new("lock");
+/// The weighted amounts of the locks expiring at a given time in seconds, by which the global
+/// slope decreases at that time. Entries are kept after expiring so the global weight can be
+/// calculated for past global indexes.
+pub const SLOPE_CHANGES: Map<u64, Uint128> = Map::new("slope_changes");
+/// The addresses the rewards of the bonders are sent to, for bonders that don't receive them
+/// themselves.
+pub const REWARD_RECIPIENT: Map<&Addr, Addr> = Map::new("reward_recipient");
+/// The bonds as they were after each change, keyed by the timestamp of the change in nanoseconds.
+pub const BOND_CHECKPOINTS: Map<(&Addr, &Denom, u64), BondCheckpoint> =
+    Map::new("bond_checkpoints");
+/// The global index as it was after each change, keyed by the timestamp of the change in
+/// nanoseconds.
+pub const GLOBAL_CHECKPOINTS: Map<u64, GlobalIndex> = Map::new("global_checkpoints");
+
+/// The state of a bond at a given time. Removed bonds are recorded with a zero amount.
+#[cw_serde]
+pub struct BondCheckpoint {
+    pub bond: Bond,
+    /// The end of the lock of the bond, in vote-escrow mode.
+    pub lock_end: Option<Timestamp>,
+}
 
 /// Updates the local weight of the given address.
 pub fn update_local_weight(
@@ -26,19 +54,18 @@ pub fn update_local_weight(
     bond.weight = get_weight(
         timestamp,
         bond.weight,
-        bond.asset.amount,
+        get_weighted_amount(&config, &bond.asset),
         config.growth_rate,
         bond.timestamp,
     )?;
 
     bond.timestamp = timestamp;
 
-    let denom: &String = match &bond.asset.info {
-        AssetInfo::Token { .. } => return Err(ContractError::AssetMismatch {}),
-        AssetInfo::NativeToken { denom } => denom,
-    };
-
-    BOND.save(deps.storage, (&address, denom), &bond)?;
+    BOND.save(
+        deps.storage,
+        (&address, &bond.asset.info.to_string()),
+        &bond,
+    )?;
 
     Ok(bond)
 }
@@ -54,7 +81,7 @@ pub fn update_global_weight(
     global_index.weight = get_weight(
         timestamp,
         global_index.weight,
-        global_index.bonded_amount,
+        global_index.weighted_amount,
         config.growth_rate,
         global_index.timestamp,
     )?;
@@ -87,3 +114,275 @@ pub fn get_weight(
 
     Ok(weight.checked_add(amount.checked_mul(time_factor)? * growth_rate)?)
 }
+
+/// Calculates the weight of a lock with the given weighted amount at the provided timestamp, which
+/// decays linearly to zero at the lock end.
+pub fn get_lock_weight(
+    current_timestamp: Timestamp,
+    weighted_amount: Uint128,
+    lock_end: Timestamp,
+    max_lock_duration: Uint64,
+) -> StdResult<Uint128> {
+    let remaining_time = lock_end
+        .seconds()
+        .saturating_sub(current_timestamp.seconds());
+
+    Ok(weighted_amount.multiply_ratio(
+        remaining_time,
+        max_lock_duration_seconds(max_lock_duration)?,
+    ))
+}
+
+/// Brings the vote-escrow totals of the global index up to the provided timestamp, removing the
+/// locks that expired in between.
+pub fn get_vote_escrow_global_index(
+    storage: &dyn Storage,
+    current_timestamp: Timestamp,
+    mut global_index: GlobalIndex,
+    max_lock_duration: Uint64,
+) -> StdResult<GlobalIndex> {
+    let mut vote_escrow = global_index.vote_escrow.unwrap_or_default();
+    let start = global_index.timestamp.seconds();
+    let end = current_timestamp.seconds();
+    if end < start {
+        return Err(StdError::generic_err("Error calculating time_factor"));
+    }
+
+    let slope_changes = SLOPE_CHANGES
+        .range(
+            storage,
+            Some(Bound::exclusive(start)),
+            Some(Bound::inclusive(end)),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<(u64, Uint128)>>>()?;
+
+    let mut last_checkpoint = start;
+    for (lock_end, expiring_amount) in slope_changes {
+        vote_escrow.bias = vote_escrow.bias.checked_sub(
+            vote_escrow
+                .slope
+                .checked_mul(Uint128::from(lock_end - last_checkpoint))?,
+        )?;
+        vote_escrow.slope = vote_escrow.slope.checked_sub(expiring_amount)?;
+        last_checkpoint = lock_end;
+    }
+    vote_escrow.bias = vote_escrow.bias.checked_sub(
+        vote_escrow
+            .slope
+            .checked_mul(Uint128::from(end - last_checkpoint))?,
+    )?;
+
+    global_index.weight =
+        vote_escrow.bias / Uint128::from(max_lock_duration_seconds(max_lock_duration)?);
+    global_index.timestamp = current_timestamp;
+    global_index.vote_escrow = Some(vote_escrow);
+
+    Ok(global_index)
+}
+
+/// Adds a lock to the vote-escrow totals, which must be up to date with the given timestamp.
+/// Expired locks don't contribute to the totals.
+pub fn add_lock(
+    storage: &mut dyn Storage,
+    timestamp: Timestamp,
+    vote_escrow: &mut VoteEscrowIndex,
+    weighted_amount: Uint128,
+    lock_end: Timestamp,
+) -> StdResult<()> {
+    if lock_end.seconds() <= timestamp.seconds() || weighted_amount.is_zero() {
+        return Ok(());
+    }
+
+    vote_escrow.slope = vote_escrow.slope.checked_add(weighted_amount)?;
+    vote_escrow.bias = vote_escrow.bias.checked_add(
+        weighted_amount.checked_mul(Uint128::from(lock_end.seconds() - timestamp.seconds()))?,
+    )?;
+    SLOPE_CHANGES.update(storage, lock_end.seconds(), |amount| -> StdResult<_> {
+        Ok(amount.unwrap_or_default().checked_add(weighted_amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Removes a lock from the vote-escrow totals, which must be up to date with the given timestamp.
+/// Expired locks were already removed from the totals when they expired.
+pub fn remove_lock(
+    storage: &mut dyn Storage,
+    timestamp: Timestamp,
+    vote_escrow: &mut VoteEscrowIndex,
+    weighted_amount: Uint128,
+    lock_end: Timestamp,
+) -> StdResult<()> {
+    if lock_end.seconds() <= timestamp.seconds() || weighted_amount.is_zero() {
+        return Ok(());
+    }
+
+    vote_escrow.slope = vote_escrow.slope.checked_sub(weighted_amount)?;
+    vote_escrow.bias = vote_escrow.bias.checked_sub(
+        weighted_amount.checked_mul(Uint128::from(lock_end.seconds() - timestamp.seconds()))?,
+    )?;
+    SLOPE_CHANGES.update(storage, lock_end.seconds(), |amount| -> StdResult<_> {
+        Ok(amount.unwrap_or_default().checked_sub(weighted_amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// Gets the maximum lock duration in seconds, as the lock weights are calculated in seconds.
+pub fn max_lock_duration_seconds(max_lock_duration: Uint64) -> StdResult<u64> {
+    match max_lock_duration.u64() / 1_000_000_000 {
+        0 => Err(StdError::generic_err("Invalid max lock duration")),
+        seconds => Ok(seconds),
+    }
+}
+
+/// Records the bond of the address for the given denom and the global index as they are after a
+/// change at the given timestamp. Their weights at any later time can be calculated from the
+/// latest checkpoint before it, as they only change linearly in between.
+pub fn save_checkpoint(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    denom: &Denom,
+    timestamp: Timestamp,
+) -> StdResult<()> {
+    let bond = BOND.may_load(storage, (address, denom))?.unwrap_or(Bond {
+        timestamp,
+        ..Bond::default()
+    });
+    let lock_end = LOCK.may_load(storage, (address, denom))?;
+
+    BOND_CHECKPOINTS.save(
+        storage,
+        (address, denom, timestamp.nanos()),
+        &BondCheckpoint { bond, lock_end },
+    )?;
+    let global_index = GLOBAL.load(storage)?;
+    GLOBAL_CHECKPOINTS.save(storage, timestamp.nanos(), &global_index)?;
+
+    Ok(())
+}
+
+/// Gets the bond of the address for the given denom as it was at the provided timestamp. Bonds
+/// that haven't changed since checkpoints were introduced are read from the current state.
+pub fn get_bond_checkpoint(
+    storage: &dyn Storage,
+    address: &Addr,
+    denom: &Denom,
+    timestamp: Timestamp,
+) -> StdResult<Option<BondCheckpoint>> {
+    let checkpoints = BOND_CHECKPOINTS.prefix((address, denom));
+
+    if let Some(checkpoint) = checkpoints
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(timestamp.nanos())),
+            Order::Descending,
+        )
+        .next()
+    {
+        return Ok(Some(checkpoint?.1));
+    }
+
+    // the first checkpoint is after the timestamp, so the bond didn't exist before
+    if checkpoints
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Ok(None);
+    }
+
+    BOND.may_load(storage, (address, denom))?
+        .filter(|bond| bond.timestamp <= timestamp)
+        .map(|bond| -> StdResult<BondCheckpoint> {
+            Ok(BondCheckpoint {
+                bond,
+                lock_end: LOCK.may_load(storage, (address, denom))?,
+            })
+        })
+        .transpose()
+}
+
+/// Gets the global index as it was at the provided timestamp, before bringing its weight up to
+/// that time. The global index is read from the current state if it hasn't changed since.
+pub fn get_global_checkpoint(
+    storage: &dyn Storage,
+    timestamp: Timestamp,
+) -> StdResult<GlobalIndex> {
+    if let Some(checkpoint) = GLOBAL_CHECKPOINTS
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(timestamp.nanos())),
+            Order::Descending,
+        )
+        .next()
+    {
+        return Ok(checkpoint?.1);
+    }
+
+    Ok(GLOBAL
+        .may_load(storage)?
+        .filter(|global_index| global_index.timestamp <= timestamp)
+        .unwrap_or_default())
+}
+
+/// Calculates the weight of the given bond at the provided timestamp, according to the mode the
+/// contract runs in.
+pub fn get_bond_weight(
+    config: &Config,
+    current_timestamp: Timestamp,
+    bond: &Bond,
+    lock_end: Option<Timestamp>,
+) -> StdResult<Uint128> {
+    let weighted_amount = get_weighted_amount(config, &bond.asset);
+
+    match (config.max_lock_duration, lock_end) {
+        // locks changed after the timestamp had no weight at that time
+        (Some(_), _) if bond.timestamp > current_timestamp => Ok(Uint128::zero()),
+        (Some(max_lock_duration), Some(lock_end)) => get_lock_weight(
+            current_timestamp,
+            weighted_amount,
+            lock_end,
+            max_lock_duration,
+        ),
+        (Some(_), None) => Ok(Uint128::zero()),
+        (None, _) => get_weight(
+            current_timestamp,
+            bond.weight,
+            weighted_amount,
+            config.growth_rate,
+            bond.timestamp,
+        ),
+    }
+}
+
+/// Brings the weight of the global index up to the provided timestamp, according to the mode the
+/// contract runs in.
+pub fn get_global_weight(
+    storage: &dyn Storage,
+    config: &Config,
+    current_timestamp: Timestamp,
+    global_index: GlobalIndex,
+) -> StdResult<GlobalIndex> {
+    match config.max_lock_duration {
+        Some(max_lock_duration) => get_vote_escrow_global_index(
+            storage,
+            current_timestamp,
+            global_index,
+            max_lock_duration,
+        ),
+        None => Ok(GlobalIndex {
+            weight: get_weight(
+                current_timestamp,
+                global_index.weight,
+                global_index.weighted_amount,
+                config.growth_rate,
+                global_index.timestamp,
+            )?,
+            ..global_index
+        }),
+    }
+}
@@ -4,6 +4,7 @@ use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 use white_whale_std::whale_lair::{BondedResponse, BondingWeightResponse};
 
 use crate::tests::robot::TestingRobot;
+use crate::ContractError;
 
 #[test]
 fn test_bond_successfully() {
@@ -245,3 +246,104 @@ fn test_bond_wrong_asset() {
             },
         );
 }
+
+#[test]
+fn test_bond_cw20_with_weight_multiplier() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot.instantiate_with_lp_token(Decimal::percent(200));
+    let lp_token = AssetInfo::Token {
+        contract_addr: robot.lp_token_addr.to_string(),
+    };
+
+    robot
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .bond_cw20(another_sender.clone(), Uint128::new(1_000u128), |res| {
+            res.unwrap();
+        })
+        .assert_bonded_response(
+            another_sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::new(1_000u128),
+                bonded_assets: vec![Asset {
+                    info: lp_token.clone(),
+                    amount: Uint128::new(1_000u128),
+                }],
+                first_bonded_epoch_id: Default::default(),
+            },
+        )
+        .query_lp_token_balance(another_sender.to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(999_999_000u128));
+        })
+        .fast_forward(10u64)
+        // the LP bond weighs twice as much as the ampWHALE one
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(11_000u128),
+                global_weight: Uint128::new(33_000u128),
+                share: Decimal::from_ratio(1u128, 3u128),
+                timestamp: Timestamp::from_nanos(1571797429879305533u64),
+            },
+        )
+        .assert_bonding_weight_response(
+            another_sender.to_string(),
+            BondingWeightResponse {
+                address: another_sender.to_string(),
+                weight: Uint128::new(22_000u128),
+                global_weight: Uint128::new(33_000u128),
+                share: Decimal::from_ratio(2u128, 3u128),
+                timestamp: Timestamp::from_nanos(1571797429879305533u64),
+            },
+        )
+        .query_total_bonded(|res| {
+            let bonded_response = res.unwrap().1;
+            // the total bonded amount is not scaled by the weight multipliers
+            assert_eq!(bonded_response.total_bonded, Uint128::new(2_000u128));
+            assert_eq!(
+                bonded_response.bonded_assets,
+                vec![
+                    Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "ampWHALE".to_string(),
+                        },
+                        amount: Uint128::new(1_000u128),
+                    },
+                    Asset {
+                        info: lp_token.clone(),
+                        amount: Uint128::new(1_000u128),
+                    },
+                ]
+            );
+        })
+        // cw20 tokens can't be bonded with the native bond message
+        .bond(
+            sender.clone(),
+            Asset {
+                info: lp_token.clone(),
+                amount: Uint128::new(1_000u128),
+            },
+            &[],
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidBondingAsset {}.to_string()
+                );
+            },
+        );
+}
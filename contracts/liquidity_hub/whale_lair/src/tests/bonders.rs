@@ -0,0 +1,133 @@
+use cosmwasm_std::{coins, Uint128};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{BonderInfo, BonderSortBy, StatsResponse};
+
+use crate::tests::robot::TestingRobot;
+
+fn asset(denom: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+/// Bonds from two addresses, the first one with the highest bonded amount and the second one with
+/// the highest weight.
+fn setup_bonders(robot: &mut TestingRobot) {
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond(
+            sender.clone(),
+            asset("ampWHALE", 1_000u128),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .bond(
+            another_sender,
+            asset("bWHALE", 3_000u128),
+            &coins(3_000u128, "bWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(sender.clone(), asset("ampWHALE", 200u128), |res| {
+            res.unwrap();
+        })
+        .bond(
+            sender,
+            asset("bWHALE", 5_000u128),
+            &coins(5_000u128, "bWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        );
+}
+
+fn sender_info(robot: &TestingRobot) -> BonderInfo {
+    BonderInfo {
+        address: robot.sender.to_string(),
+        bonded_assets: vec![asset("ampWHALE", 800u128), asset("bWHALE", 5_000u128)],
+        bonded_amount: Uint128::new(5_800u128),
+        weight: Uint128::new(21_800u128),
+    }
+}
+
+fn another_sender_info(robot: &TestingRobot) -> BonderInfo {
+    BonderInfo {
+        address: robot.another_sender.to_string(),
+        bonded_assets: vec![asset("bWHALE", 3_000u128)],
+        bonded_amount: Uint128::new(3_000u128),
+        weight: Uint128::new(33_000u128),
+    }
+}
+
+#[test]
+fn test_query_bonders_paginated() {
+    let mut robot = TestingRobot::default();
+    setup_bonders(&mut robot);
+    let sender_info = sender_info(&robot);
+    let another_sender_info = another_sender_info(&robot);
+
+    robot
+        .query_bonders(None, Some(1u8), |res| {
+            assert_eq!(res.unwrap().1.bonders, vec![sender_info.clone()]);
+        })
+        .query_bonders(Some(sender_info.address.clone()), None, |res| {
+            assert_eq!(res.unwrap().1.bonders, vec![another_sender_info.clone()]);
+        })
+        .query_bonders(Some(another_sender_info.address.clone()), None, |res| {
+            assert!(res.unwrap().1.bonders.is_empty());
+        });
+}
+
+#[test]
+fn test_query_top_bonders() {
+    let mut robot = TestingRobot::default();
+    setup_bonders(&mut robot);
+    let sender_info = sender_info(&robot);
+    let another_sender_info = another_sender_info(&robot);
+
+    robot
+        .query_top_bonders(BonderSortBy::Weight, None, |res| {
+            assert_eq!(
+                res.unwrap().1.bonders,
+                vec![another_sender_info.clone(), sender_info.clone()]
+            );
+        })
+        .query_top_bonders(BonderSortBy::BondedAmount, None, |res| {
+            assert_eq!(
+                res.unwrap().1.bonders,
+                vec![sender_info.clone(), another_sender_info.clone()]
+            );
+        })
+        .query_top_bonders(BonderSortBy::Weight, Some(1u8), |res| {
+            assert_eq!(res.unwrap().1.bonders, vec![another_sender_info.clone()]);
+        });
+}
+
+#[test]
+fn test_query_stats() {
+    let mut robot = TestingRobot::default();
+    setup_bonders(&mut robot);
+
+    robot.query_stats(|res| {
+        assert_eq!(
+            res.unwrap().1,
+            StatsResponse {
+                bonder_count: 2u64,
+                bonded_assets: vec![asset("ampWHALE", 800u128), asset("bWHALE", 8_000u128)],
+                unbonding_assets: vec![asset("ampWHALE", 200u128)],
+            }
+        );
+    });
+}
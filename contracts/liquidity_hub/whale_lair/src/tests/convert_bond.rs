@@ -0,0 +1,237 @@
+use cosmwasm_std::{coins, Addr, Uint128, Uint64};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::router::SwapOperation;
+
+use crate::tests::robot::TestingRobot;
+use crate::ContractError;
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    }
+}
+
+fn route(from_denom: &str, to_denom: &str) -> Vec<SwapOperation> {
+    vec![SwapOperation::TerraSwap {
+        offer_asset_info: native(from_denom),
+        ask_asset_info: native(to_denom),
+    }]
+}
+
+#[test]
+fn test_set_pool_router() {
+    let mut robot = TestingRobot::default();
+    let owner = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_default()
+        .set_pool_router(another_sender, Some("router".to_string()), |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::Unauthorized {}.to_string()
+            );
+        })
+        .set_pool_router(owner.clone(), Some("router".to_string()), |res| {
+            res.unwrap();
+        })
+        .query_config(|res| {
+            assert_eq!(
+                res.unwrap().1.pool_router_addr,
+                Some(Addr::unchecked("router"))
+            );
+        })
+        .set_pool_router(owner, None, |res| {
+            res.unwrap();
+        })
+        .query_config(|res| {
+            assert_eq!(res.unwrap().1.pool_router_addr, None);
+        });
+}
+
+#[test]
+fn test_convert_bond_disabled() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond(
+            sender.clone(),
+            Asset {
+                info: native("ampWHALE"),
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .convert_bond(
+            sender,
+            "ampWHALE".to_string(),
+            "bWHALE".to_string(),
+            route("ampWHALE", "bWHALE"),
+            None,
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::BondConversionDisabled {}.to_string()
+                );
+            },
+        );
+}
+
+#[test]
+fn test_convert_bond_unavailable_in_vote_escrow_mode() {
+    let mut robot = TestingRobot::default();
+    let owner = robot.sender.clone();
+
+    robot
+        .instantiate_vote_escrow(Uint64::new(1_000_000_000_000u64))
+        .set_pool_router(owner.clone(), Some("router".to_string()), |res| {
+            res.unwrap();
+        })
+        .convert_bond(
+            owner,
+            "ampWHALE".to_string(),
+            "bWHALE".to_string(),
+            route("ampWHALE", "bWHALE"),
+            None,
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::ConvertBondUnavailable {}.to_string()
+                );
+            },
+        );
+}
+
+#[test]
+fn test_invalid_bond_conversion() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    let invalid_conversions = vec![
+        // same denom
+        ("ampWHALE", "ampWHALE", route("ampWHALE", "ampWHALE")),
+        // not a bonding asset
+        ("ampWHALE", "uwhale", route("ampWHALE", "uwhale")),
+        // empty route
+        ("ampWHALE", "bWHALE", vec![]),
+        // the route doesn't start with the bonded denom
+        ("ampWHALE", "bWHALE", route("uwhale", "bWHALE")),
+        // the route doesn't end with the denom to convert to
+        ("ampWHALE", "bWHALE", route("ampWHALE", "uwhale")),
+        // the operations of the route are not chained
+        (
+            "ampWHALE",
+            "bWHALE",
+            vec![
+                SwapOperation::TerraSwap {
+                    offer_asset_info: native("ampWHALE"),
+                    ask_asset_info: native("uwhale"),
+                },
+                SwapOperation::TerraSwap {
+                    offer_asset_info: native("uusdc"),
+                    ask_asset_info: native("bWHALE"),
+                },
+            ],
+        ),
+    ];
+
+    robot
+        .instantiate_default()
+        .set_pool_router(sender.clone(), Some("router".to_string()), |res| {
+            res.unwrap();
+        })
+        .bond(
+            sender.clone(),
+            Asset {
+                info: native("ampWHALE"),
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        );
+
+    for (from_denom, to_denom, route) in invalid_conversions {
+        robot.convert_bond(
+            sender.clone(),
+            from_denom.to_string(),
+            to_denom.to_string(),
+            route,
+            None,
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidBondConversion {}.to_string()
+                );
+            },
+        );
+    }
+
+    robot
+        .convert_bond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            "bWHALE".to_string(),
+            route("ampWHALE", "bWHALE"),
+            None,
+            Uint128::zero(),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidConversionAmount {}.to_string()
+                );
+            },
+        )
+        .convert_bond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            "bWHALE".to_string(),
+            route("ampWHALE", "bWHALE"),
+            Some(Uint128::zero()),
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidConversionAmount {}.to_string()
+                );
+            },
+        )
+        .convert_bond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            "bWHALE".to_string(),
+            route("ampWHALE", "bWHALE"),
+            Some(Uint128::new(2_000u128)),
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InsufficientBond {}.to_string()
+                );
+            },
+        )
+        .convert_bond(
+            sender,
+            "bWHALE".to_string(),
+            "ampWHALE".to_string(),
+            route("bWHALE", "ampWHALE"),
+            None,
+            Uint128::new(900u128),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::NothingToConvert {}.to_string()
+                );
+            },
+        );
+}
@@ -0,0 +1,168 @@
+use cosmwasm_std::{coins, Uint128, Uint64};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{BondedResponse, RewardRecipientResponse};
+
+use crate::tests::robot::TestingRobot;
+use crate::ContractError;
+
+fn amp_whale(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: "ampWHALE".to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+#[test]
+fn test_bond_on_behalf_of_owner() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_default()
+        // the contract itself can't own bonds
+        .bond_on_behalf(
+            another_sender.clone(),
+            Some("contract1".to_string()),
+            amp_whale(1_000u128),
+            None,
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidBondOwner {}.to_string()
+                );
+            },
+        )
+        .bond_on_behalf(
+            another_sender.clone(),
+            Some(sender.to_string()),
+            amp_whale(1_000u128),
+            None,
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonded_response(
+            sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::new(1_000u128),
+                bonded_assets: vec![amp_whale(1_000u128)],
+                first_bonded_epoch_id: Default::default(),
+            },
+        )
+        .assert_bonded_response(
+            another_sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::zero(),
+                bonded_assets: vec![],
+                first_bonded_epoch_id: Default::default(),
+            },
+        )
+        // only the owner can unbond
+        .unbond(another_sender.clone(), amp_whale(1_000u128), |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::NothingToUnbond {}.to_string()
+            );
+        })
+        .unbond(sender.clone(), amp_whale(1_000u128), |res| {
+            res.unwrap();
+        });
+}
+
+#[test]
+fn test_bond_on_behalf_of_locked_owner() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_vote_escrow(Uint64::new(1_000_000_000_000u64))
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(Uint64::new(500_000_000_000u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        // others can't extend the lock of the owner
+        .bond_on_behalf(
+            another_sender.clone(),
+            Some(sender.to_string()),
+            amp_whale(1_000u128),
+            Some(Uint64::new(1_000_000_000_000u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::CannotChangeOwnerLock {}.to_string()
+                );
+            },
+        )
+        // but they can add to it
+        .bond_on_behalf(
+            another_sender.clone(),
+            Some(sender.to_string()),
+            amp_whale(1_000u128),
+            None,
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonded_response(
+            sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::new(2_000u128),
+                bonded_assets: vec![amp_whale(2_000u128)],
+                first_bonded_epoch_id: Default::default(),
+            },
+        );
+}
+
+#[test]
+fn test_set_reward_recipient() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_default()
+        .query_reward_recipient(sender.to_string(), |res| {
+            assert_eq!(
+                res.unwrap().1,
+                RewardRecipientResponse {
+                    recipient: sender.to_string(),
+                }
+            );
+        })
+        .set_reward_recipient(sender.clone(), Some(another_sender.to_string()), |res| {
+            res.unwrap();
+        })
+        .query_reward_recipient(sender.to_string(), |res| {
+            assert_eq!(
+                res.unwrap().1,
+                RewardRecipientResponse {
+                    recipient: another_sender.to_string(),
+                }
+            );
+        })
+        .set_reward_recipient(sender.clone(), None, |res| {
+            res.unwrap();
+        })
+        .query_reward_recipient(sender.to_string(), |res| {
+            assert_eq!(
+                res.unwrap().1,
+                RewardRecipientResponse {
+                    recipient: sender.to_string(),
+                }
+            );
+        });
+}
@@ -1,8 +1,9 @@
 use cosmwasm_std::{Addr, Decimal, Uint64};
 
 use crate::tests::robot::TestingRobot;
+use crate::ContractError;
 use white_whale_std::pool_network::asset::AssetInfo;
-use white_whale_std::whale_lair::Config;
+use white_whale_std::whale_lair::{Config, LiquidBondingInstantiateMsg};
 
 #[test]
 fn test_instantiate_successfully() {
@@ -20,6 +21,9 @@ fn test_instantiate_successfully() {
                     denom: "bWHALE".to_string(),
                 },
             ],
+            None,
+            None,
+            None,
             &vec![],
         )
         .assert_config(Config {
@@ -35,55 +39,220 @@ fn test_instantiate_successfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
+        });
+}
+
+#[test]
+fn test_instantiate_with_cw20_and_weight_multipliers_successfully() {
+    let mut robot = TestingRobot::default();
+
+    // more bonding assets than before, including a cw20 LP token with a boosted weight
+    robot
+        .instantiate(
+            Uint64::new(1_000u64),
+            Decimal::one(),
+            vec![
+                AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: "contract123".to_string(),
+                },
+            ],
+            Some(vec![(
+                AssetInfo::Token {
+                    contract_addr: "contract123".to_string(),
+                },
+                Decimal::percent(150),
+            )]),
+            None,
+            None,
+            &vec![],
+        )
+        .assert_config(Config {
+            owner: Addr::unchecked("owner"),
+            unbonding_period: Uint64::new(1_000u64),
+            growth_rate: Decimal::one(),
+            bonding_assets: vec![
+                AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "uwhale".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: "contract123".to_string(),
+                },
+            ],
+            fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![(
+                AssetInfo::Token {
+                    contract_addr: "contract123".to_string(),
+                },
+                Decimal::percent(150),
+            )],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         });
 }
 
 #[test]
 fn test_instantiate_unsuccessfully() {
     let mut robot = TestingRobot::default();
+    let bonding_assets = vec![
+        AssetInfo::NativeToken {
+            denom: "ampWHALE".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "bWHALE".to_string(),
+        },
+    ];
 
-    // over bonding assets limit
+    // weight multiplier for an asset that can't be bonded
     robot.instantiate_err(
         Uint64::new(1_000u64),
         Decimal::one(),
-        vec![
+        bonding_assets.clone(),
+        Some(vec![(
             AssetInfo::NativeToken {
-                denom: "ampWHALE".to_string(),
+                denom: "uwhale".to_string(),
             },
+            Decimal::one(),
+        )]),
+        None,
+        None,
+        &vec![],
+        |error| {
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidWeightMultiplier("uwhale".to_string()).to_string()
+            );
+        },
+    );
+
+    // zero weight multiplier
+    robot.instantiate_err(
+        Uint64::new(1_000u64),
+        Decimal::one(),
+        bonding_assets.clone(),
+        Some(vec![(
             AssetInfo::NativeToken {
-                denom: "bWHALE".to_string(),
+                denom: "ampWHALE".to_string(),
             },
-            AssetInfo::NativeToken {
+            Decimal::zero(),
+        )]),
+        None,
+        None,
+        &vec![],
+        |error| {
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidWeightMultiplier("ampWHALE".to_string()).to_string()
+            );
+        },
+    );
+
+    // duplicated weight multiplier
+    robot.instantiate_err(
+        Uint64::new(1_000u64),
+        Decimal::one(),
+        bonding_assets.clone(),
+        Some(vec![
+            (
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                Decimal::one(),
+            ),
+            (
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                Decimal::percent(200),
+            ),
+        ]),
+        None,
+        None,
+        &vec![],
+        |error| {
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidWeightMultiplier("bWHALE".to_string()).to_string()
+            );
+        },
+    );
+    // maximum lock duration shorter than a second
+    robot.instantiate_err(
+        Uint64::new(1_000u64),
+        Decimal::one(),
+        bonding_assets.clone(),
+        None,
+        Some(Uint64::new(999_999_999u64)),
+        None,
+        &vec![],
+        |error| {
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidMaxLockDuration {}.to_string()
+            );
+        },
+    );
+
+    // liquid bonding an asset that can't be bonded
+    robot.instantiate_err(
+        Uint64::new(1_000u64),
+        Decimal::one(),
+        bonding_assets.clone(),
+        None,
+        None,
+        Some(LiquidBondingInstantiateMsg {
+            asset: AssetInfo::NativeToken {
                 denom: "uwhale".to_string(),
             },
-        ],
+            token_code_id: 1u64,
+        }),
         &vec![],
         |error| {
-            println!("1 --{error:?}");
-            println!("2 --{:?}", error.root_cause());
-            //println!("3 --{:?}", error.root_cause().downcast_ref::<ContractError>());
-            // assert_eq!(
-            //     error.root_cause().downcast_ref::<ContractError>().unwrap(),
-            //     &ContractError::InvalidBondingAssetsLimit(BONDING_ASSETS_LIMIT, 3));
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidLiquidBonding {}.to_string()
+            );
         },
     );
 
-    // invalid tokens
+    // liquid bonding in vote-escrow mode
     robot.instantiate_err(
         Uint64::new(1_000u64),
         Decimal::one(),
-        vec![AssetInfo::Token {
-            contract_addr: "contract123".to_string(),
-        }],
+        bonding_assets,
+        None,
+        Some(Uint64::new(1_000_000_000u64)),
+        Some(LiquidBondingInstantiateMsg {
+            asset: AssetInfo::NativeToken {
+                denom: "ampWHALE".to_string(),
+            },
+            token_code_id: 1u64,
+        }),
         &vec![],
         |error| {
-            println!("1 --{error:?}");
-            println!("2 --{:?}", error.root_cause());
-            //println!("3 --{:?}", error.root_cause().downcast_ref::<ContractError>());
-
-            // assert_eq!(
-            //    error.root_cause().downcast_mut::<ContractError>().unwrap(),
-            //    ContractError::InvalidBondingAsset {});
+            assert_eq!(
+                error.root_cause().to_string(),
+                ContractError::InvalidLiquidBonding {}.to_string()
+            );
         },
     );
 }
@@ -0,0 +1,227 @@
+use cosmwasm_std::{coin, coins, Decimal, Timestamp, Uint128};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{Bond, LiquidBondingResponse, UnbondingResponse};
+
+use crate::tests::robot::TestingRobot;
+use crate::ContractError;
+
+fn uwhale(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn liquid_bonding_response(
+    robot: &TestingRobot,
+    total_shares: u128,
+    bonded_amount: u128,
+) -> LiquidBondingResponse {
+    LiquidBondingResponse {
+        asset: uwhale(0u128).info,
+        receipt_token: robot.receipt_token_addr.to_string(),
+        total_shares: Uint128::new(total_shares),
+        bonded_amount: Uint128::new(bonded_amount),
+        exchange_rate: Decimal::one(),
+    }
+}
+
+#[test]
+fn test_liquid_bond_and_redeem() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot.instantiate_liquid_bonding();
+    let initial_response = liquid_bonding_response(&robot, 0u128, 0u128);
+    let bonded_response = liquid_bonding_response(&robot, 1_500u128, 1_500u128);
+    let redeemed_response = liquid_bonding_response(&robot, 1_100u128, 1_100u128);
+
+    robot
+        .query_liquid_bonding(|res| {
+            assert_eq!(res.unwrap().1, initial_response);
+        })
+        .liquid_bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::AssetMismatch {}.to_string()
+                );
+            },
+        )
+        .liquid_bond(
+            sender.clone(),
+            uwhale(1_000u128),
+            &coins(1_000u128, "uwhale"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .liquid_bond(
+            another_sender.clone(),
+            uwhale(500u128),
+            &coins(500u128, "uwhale"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .query_receipt_token_balance(sender.to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(1_000u128));
+        })
+        .query_receipt_token_balance(another_sender.to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(500u128));
+        })
+        .query_liquid_bonding(|res| {
+            assert_eq!(res.unwrap().1, bonded_response);
+        })
+        .redeem(sender.clone(), Uint128::new(400u128), |res| {
+            res.unwrap();
+        })
+        .query_receipt_token_balance(sender.to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(600u128));
+        })
+        .query_liquid_bonding(|res| {
+            assert_eq!(res.unwrap().1, redeemed_response);
+        })
+        // the redeemed tokens are unbonding for the sender
+        .assert_unbonding_response(
+            sender.to_string(),
+            "uwhale".to_string(),
+            UnbondingResponse {
+                total_amount: Uint128::new(400u128),
+                unbonding_requests: vec![Bond {
+                    asset: uwhale(400u128),
+                    timestamp: Timestamp::from_nanos(1571797419879305533u64),
+                    weight: Uint128::zero(),
+                }],
+            },
+        )
+        .fast_forward(1_000u64)
+        .withdraw(sender.clone(), "uwhale".to_string(), |res| {
+            res.unwrap();
+        })
+        .assert_unbonding_response(
+            sender.to_string(),
+            "uwhale".to_string(),
+            UnbondingResponse {
+                total_amount: Uint128::zero(),
+                unbonding_requests: vec![],
+            },
+        );
+}
+
+#[test]
+fn test_compound_without_rewards() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_liquid_bonding()
+        .liquid_bond(
+            sender.clone(),
+            uwhale(1_000u128),
+            &coins(1_000u128, "uwhale"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .compound(sender.clone(), |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::NothingToCompound {}.to_string()
+            );
+        });
+}
+
+#[test]
+fn test_compound_rewards_in_other_assets() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+    let uusdc = |amount: u128| Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusdc".to_string(),
+        },
+        amount: Uint128::new(amount),
+    };
+
+    robot.instantiate_liquid_bonding();
+    let compounded_response = LiquidBondingResponse {
+        exchange_rate: Decimal::percent(110),
+        ..liquid_bonding_response(&robot, 1_000u128, 1_100u128)
+    };
+
+    robot
+        .liquid_bond(
+            another_sender.clone(),
+            uwhale(1_000u128),
+            &coins(1_000u128, "uwhale"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        // there's no route to swap the uusdc rewards through
+        .set_pool_router(sender.clone(), Some("router".to_string()), |res| {
+            res.unwrap();
+        })
+        .query_balance(sender.to_string(), "uusdc".to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(1_000_000_000u128));
+        })
+        .compound_rewards(
+            another_sender,
+            vec![coin(100u128, "uwhale"), coin(50u128, "uusdc")],
+            vec![uwhale(1_000u128), uusdc(0u128)],
+            |res| {
+                res.unwrap();
+            },
+        )
+        .query_liquid_bonding(|res| {
+            assert_eq!(res.unwrap().1, compounded_response);
+        })
+        // the uusdc rewards are sent to the owner rather than left in the contract
+        .query_balance(sender.to_string(), "uusdc".to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(1_000_000_050u128));
+        });
+}
+
+#[test]
+fn test_liquid_bonding_disabled() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .liquid_bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::LiquidBondingDisabled {}.to_string()
+                );
+            },
+        )
+        .compound(sender.clone(), |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::LiquidBondingDisabled {}.to_string()
+            );
+        });
+}
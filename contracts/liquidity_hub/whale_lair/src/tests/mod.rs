@@ -1,6 +1,13 @@
 mod bond;
+mod bonders;
+mod convert_bond;
+mod delegation;
 mod instantiate;
+mod liquid_bonding;
+mod rebond;
 mod robot;
 mod unbond;
 mod update_config;
+mod vote_escrow;
+mod weight_history;
 mod withdraw;
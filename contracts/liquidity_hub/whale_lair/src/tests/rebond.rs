@@ -0,0 +1,295 @@
+use cosmwasm_std::{coins, Decimal, Timestamp, Uint128};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{Bond, BondedResponse, BondingWeightResponse, UnbondingResponse};
+
+use crate::tests::robot::TestingRobot;
+use crate::ContractError;
+
+#[test]
+fn test_rebond_successfully() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(300u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(14_700u128),
+                global_weight: Uint128::new(14_700u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797439879305533u64),
+            },
+        )
+        .rebond(sender.clone(), "ampWHALE".to_string(), None, None, |res| {
+            res.unwrap();
+        })
+        // the weight the tokens had when unbonded is restored, without growing while unbonding
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(18_000u128),
+                global_weight: Uint128::new(18_000u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797439879305533u64),
+            },
+        )
+        .assert_bonded_response(
+            sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::new(1_000u128),
+                bonded_assets: vec![Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "ampWHALE".to_string(),
+                    },
+                    amount: Uint128::new(1_000u128),
+                }],
+                first_bonded_epoch_id: Default::default(),
+            },
+        )
+        .assert_unbonding_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            UnbondingResponse {
+                total_amount: Uint128::zero(),
+                unbonding_requests: vec![],
+            },
+        )
+        .fast_forward(10u64)
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(28_000u128),
+                global_weight: Uint128::new(28_000u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797449879305533u64),
+            },
+        );
+}
+
+#[test]
+fn test_rebond_partially() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(300u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(200u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        // the newest unbondings are rebonded first
+        .rebond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            None,
+            Some(Uint128::new(250u128)),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_unbonding_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            UnbondingResponse {
+                total_amount: Uint128::new(250u128),
+                unbonding_requests: vec![Bond {
+                    asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "ampWHALE".to_string(),
+                        },
+                        amount: Uint128::new(250u128),
+                    },
+                    timestamp: Timestamp::from_nanos(1571797429879305533u64),
+                    // the weight kept for rebonding isn't exposed by the query
+                    weight: Uint128::zero(),
+                }],
+            },
+        )
+        .rebond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            Some(1571797429879305533u64),
+            Some(Uint128::new(50u128)),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_unbonding_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            UnbondingResponse {
+                total_amount: Uint128::new(200u128),
+                unbonding_requests: vec![Bond {
+                    asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "ampWHALE".to_string(),
+                        },
+                        amount: Uint128::new(200u128),
+                    },
+                    timestamp: Timestamp::from_nanos(1571797429879305533u64),
+                    weight: Uint128::zero(),
+                }],
+            },
+        )
+        // together with the 2_201 weight left unbonding, it matches the weight of the original bond
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(15_799u128),
+                global_weight: Uint128::new(15_799u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797439879305533u64),
+            },
+        );
+}
+
+#[test]
+fn test_rebond_unsuccessfully() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .rebond(sender.clone(), "ampWHALE".to_string(), None, None, |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::NothingToRebond {}.to_string()
+            );
+        })
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(300u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        .rebond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            None,
+            Some(Uint128::zero()),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidRebondingAmount {}.to_string()
+                );
+            },
+        )
+        .rebond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            None,
+            Some(Uint128::new(301u128)),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InsufficientUnbonding {}.to_string()
+                );
+            },
+        )
+        .rebond(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            Some(1u64),
+            None,
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::NothingToRebond {}.to_string()
+                );
+            },
+        )
+        .rebond(sender, "bWHALE".to_string(), None, None, |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::NothingToRebond {}.to_string()
+            );
+        });
+}
@@ -1,10 +1,16 @@
-use cosmwasm_std::{coin, Addr, Coin, Decimal, StdResult, Uint64};
-use cw_multi_test::{App, AppResponse, Executor};
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Coin, Decimal, StdResult, Timestamp, Uint128, Uint64,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg};
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
 use white_whale_std::epoch_manager::epoch_manager::EpochConfig;
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::pool_network::router::SwapOperation;
 use white_whale_std::whale_lair::{
-    BondedResponse, BondingWeightResponse, Config, ExecuteMsg, InstantiateMsg, QueryMsg,
+    BondedResponse, BonderSortBy, BondersResponse, BondingWeightResponse, CallbackMsg, Config,
+    Cw20HookMsg, ExecuteMsg, GlobalIndex, InstantiateMsg, LiquidBondingInstantiateMsg,
+    LiquidBondingResponse, LockResponse, QueryMsg, RewardRecipientResponse, StatsResponse,
     UnbondingResponse, WithdrawableResponse,
 };
 use white_whale_testing::integration::contracts::{
@@ -16,6 +22,8 @@ pub struct TestingRobot {
     app: App,
     pub sender: Addr,
     pub another_sender: Addr,
+    pub lp_token_addr: Addr,
+    pub receipt_token_addr: Addr,
     whale_lair_addr: Addr,
 }
 
@@ -50,6 +58,8 @@ impl TestingRobot {
             ]),
             sender,
             another_sender,
+            lp_token_addr: Addr::unchecked(""),
+            receipt_token_addr: Addr::unchecked(""),
             whale_lair_addr: Addr::unchecked(""),
         }
     }
@@ -74,15 +84,147 @@ impl TestingRobot {
                     denom: "bWHALE".to_string(),
                 },
             ],
+            None,
+            None,
+            None,
+            &vec![],
+        )
+    }
+
+    /// Instantiates the contract in vote-escrow mode with ampWHALE and bWHALE as bonding assets and
+    /// no unbonding period, so unlocked tokens can be withdrawn right away.
+    pub(crate) fn instantiate_vote_escrow(&mut self, max_lock_duration: Uint64) -> &mut Self {
+        self.instantiate(
+            Uint64::zero(),
+            Decimal::one(),
+            vec![
+                AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+            ],
+            None,
+            Some(max_lock_duration),
+            None,
+            &vec![],
+        )
+    }
+
+    /// Instantiates the contract with ampWHALE, bWHALE and a cw20 LP token as bonding assets, the
+    /// latter with the given weight multiplier.
+    pub(crate) fn instantiate_with_lp_token(&mut self, weight_multiplier: Decimal) -> &mut Self {
+        self.create_lp_token();
+        let lp_token = AssetInfo::Token {
+            contract_addr: self.lp_token_addr.to_string(),
+        };
+
+        self.instantiate(
+            Uint64::new(1_000_000_000_000u64),
+            Decimal::one(),
+            vec![
+                AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                lp_token.clone(),
+            ],
+            Some(vec![(lp_token, weight_multiplier)]),
+            None,
+            None,
+            &vec![],
+        )
+    }
+
+    /// Instantiates the contract in liquid bonding mode with ampWHALE, bWHALE and uwhale as bonding
+    /// assets, uwhale being the one that can be liquid bonded.
+    pub(crate) fn instantiate_liquid_bonding(&mut self) -> &mut Self {
+        let token_code_id = self.store_cw20_code();
+        let uwhale = AssetInfo::NativeToken {
+            denom: "uwhale".to_string(),
+        };
+
+        self.instantiate(
+            Uint64::new(1_000_000_000_000u64),
+            Decimal::one(),
+            vec![
+                AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                uwhale.clone(),
+            ],
+            None,
+            None,
+            Some(LiquidBondingInstantiateMsg {
+                asset: uwhale,
+                token_code_id,
+            }),
             &vec![],
         )
+        .query_config(|res| {
+            let (robot, config) = res.unwrap();
+            robot.receipt_token_addr = config.liquid_bonding.unwrap().receipt_token;
+        })
+    }
+
+    fn store_cw20_code(&mut self) -> u64 {
+        self.app.store_code(Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        )))
+    }
+
+    /// Creates a cw20 LP token, funding both senders with it.
+    pub(crate) fn create_lp_token(&mut self) -> &mut Self {
+        let cw20_token_id = self.store_cw20_code();
+
+        self.lp_token_addr = self
+            .app
+            .instantiate_contract(
+                cw20_token_id,
+                self.sender.clone(),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "uWHALE-uusdc-LP".to_string(),
+                    symbol: "uLP".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: self.sender.to_string(),
+                            amount: Uint128::new(1_000_000_000u128),
+                        },
+                        Cw20Coin {
+                            address: self.another_sender.to_string(),
+                            amount: Uint128::new(1_000_000_000u128),
+                        },
+                    ],
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "lp_token",
+                None,
+            )
+            .unwrap();
+
+        self
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn instantiate(
         &mut self,
         unbonding_period: Uint64,
         growth_rate: Decimal,
         bonding_assets: Vec<AssetInfo>,
+        weight_multipliers: Option<Vec<(AssetInfo, Decimal)>>,
+        max_lock_duration: Option<Uint64>,
+        liquid_bonding: Option<LiquidBondingInstantiateMsg>,
         funds: &Vec<Coin>,
     ) -> &mut Self {
         let fee_collector_id = store_fee_collector_code(&mut self.app);
@@ -101,9 +243,17 @@ impl TestingRobot {
             .unwrap();
         println!("fee_collector_address: {}", fee_collector_address);
 
-        let whale_lair_addr =
-            instantiate_contract(self, unbonding_period, growth_rate, bonding_assets, funds)
-                .unwrap();
+        let whale_lair_addr = instantiate_contract(
+            self,
+            unbonding_period,
+            growth_rate,
+            bonding_assets,
+            weight_multipliers,
+            max_lock_duration,
+            liquid_bonding,
+            funds,
+        )
+        .unwrap();
 
         let fee_distributor_address = self
             .app
@@ -143,17 +293,30 @@ impl TestingRobot {
         self
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn instantiate_err(
         &mut self,
         unbonding_period: Uint64,
         growth_rate: Decimal,
         bonding_assets: Vec<AssetInfo>,
+        weight_multipliers: Option<Vec<(AssetInfo, Decimal)>>,
+        max_lock_duration: Option<Uint64>,
+        liquid_bonding: Option<LiquidBondingInstantiateMsg>,
         funds: &Vec<Coin>,
         error: impl Fn(anyhow::Error),
     ) -> &mut Self {
         error(
-            instantiate_contract(self, unbonding_period, growth_rate, bonding_assets, funds)
-                .unwrap_err(),
+            instantiate_contract(
+                self,
+                unbonding_period,
+                growth_rate,
+                bonding_assets,
+                weight_multipliers,
+                max_lock_duration,
+                liquid_bonding,
+                funds,
+            )
+            .unwrap_err(),
         );
 
         self
@@ -166,7 +329,34 @@ impl TestingRobot {
         funds: &[Coin],
         response: impl Fn(Result<AppResponse, anyhow::Error>),
     ) -> &mut Self {
-        let msg = ExecuteMsg::Bond { asset };
+        self.bond_with_lock(sender, asset, None, funds, response)
+    }
+
+    pub(crate) fn bond_with_lock(
+        &mut self,
+        sender: Addr,
+        asset: Asset,
+        lock_duration: Option<Uint64>,
+        funds: &[Coin],
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        self.bond_on_behalf(sender, None, asset, lock_duration, funds, response)
+    }
+
+    pub(crate) fn bond_on_behalf(
+        &mut self,
+        sender: Addr,
+        owner: Option<String>,
+        asset: Asset,
+        lock_duration: Option<Uint64>,
+        funds: &[Coin],
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::Bond {
+            asset,
+            lock_duration,
+            owner,
+        };
 
         response(
             self.app
@@ -176,6 +366,121 @@ impl TestingRobot {
         self
     }
 
+    pub(crate) fn bond_cw20(
+        &mut self,
+        sender: Addr,
+        amount: Uint128,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = Cw20ExecuteMsg::Send {
+            contract: self.whale_lair_addr.to_string(),
+            amount,
+            msg: to_json_binary(&Cw20HookMsg::Bond {
+                lock_duration: None,
+                owner: None,
+            })
+            .unwrap(),
+        };
+
+        response(
+            self.app
+                .execute_contract(sender, self.lp_token_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn set_reward_recipient(
+        &mut self,
+        sender: Addr,
+        recipient: Option<String>,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::SetRewardRecipient { recipient };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn liquid_bond(
+        &mut self,
+        sender: Addr,
+        asset: Asset,
+        funds: &[Coin],
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::LiquidBond { asset };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, funds),
+        );
+
+        self
+    }
+
+    pub(crate) fn redeem(
+        &mut self,
+        sender: Addr,
+        amount: Uint128,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = Cw20ExecuteMsg::Send {
+            contract: self.whale_lair_addr.to_string(),
+            amount,
+            msg: to_json_binary(&Cw20HookMsg::Redeem {}).unwrap(),
+        };
+
+        response(
+            self.app
+                .execute_contract(sender, self.receipt_token_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn compound(
+        &mut self,
+        sender: Addr,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        response(self.app.execute_contract(
+            sender,
+            self.whale_lair_addr.clone(),
+            &ExecuteMsg::Compound {},
+            &[],
+        ));
+
+        self
+    }
+
+    /// Sends the given funds to the contract, as if they were claimed from the fee distributor, and
+    /// compounds them on top of the given balances.
+    pub(crate) fn compound_rewards(
+        &mut self,
+        sender: Addr,
+        funds: Vec<Coin>,
+        balances_before: Vec<Asset>,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        self.app
+            .send_tokens(sender, self.whale_lair_addr.clone(), &funds)
+            .unwrap();
+
+        response(self.app.execute_contract(
+            self.whale_lair_addr.clone(),
+            self.whale_lair_addr.clone(),
+            &ExecuteMsg::Callback(CallbackMsg::Compound { balances_before }),
+            &[],
+        ));
+
+        self
+    }
+
     pub(crate) fn unbond(
         &mut self,
         sender: Addr,
@@ -192,6 +497,91 @@ impl TestingRobot {
         self
     }
 
+    pub(crate) fn rebond(
+        &mut self,
+        sender: Addr,
+        denom: String,
+        unbonding_timestamp: Option<u64>,
+        amount: Option<Uint128>,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::Rebond {
+            denom,
+            unbonding_timestamp,
+            amount,
+        };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn extend_lock(
+        &mut self,
+        sender: Addr,
+        denom: String,
+        lock_duration: Uint64,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::ExtendLock {
+            denom,
+            lock_duration,
+        };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn convert_bond(
+        &mut self,
+        sender: Addr,
+        from_denom: String,
+        to_denom: String,
+        route: Vec<SwapOperation>,
+        amount: Option<Uint128>,
+        minimum_receive: Uint128,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::ConvertBond {
+            from_denom,
+            to_denom,
+            route,
+            amount,
+            minimum_receive,
+        };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
+    pub(crate) fn set_pool_router(
+        &mut self,
+        sender: Addr,
+        pool_router_addr: Option<String>,
+        response: impl Fn(Result<AppResponse, anyhow::Error>),
+    ) -> &mut Self {
+        let msg = ExecuteMsg::SetPoolRouter { pool_router_addr };
+
+        response(
+            self.app
+                .execute_contract(sender, self.whale_lair_addr.clone(), &msg, &[]),
+        );
+
+        self
+    }
+
     pub(crate) fn withdraw(
         &mut self,
         sender: Addr,
@@ -232,17 +622,24 @@ impl TestingRobot {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn instantiate_contract(
     robot: &mut TestingRobot,
     unbonding_period: Uint64,
     growth_rate: Decimal,
     bonding_assets: Vec<AssetInfo>,
+    weight_multipliers: Option<Vec<(AssetInfo, Decimal)>>,
+    max_lock_duration: Option<Uint64>,
+    liquid_bonding: Option<LiquidBondingInstantiateMsg>,
     funds: &Vec<Coin>,
 ) -> anyhow::Result<Addr> {
     let msg = InstantiateMsg {
         unbonding_period,
         growth_rate,
         bonding_assets,
+        weight_multipliers,
+        max_lock_duration,
+        liquid_bonding,
     };
 
     let whale_lair_id = robot.app.store_code(whale_lair_contract());
@@ -297,6 +694,63 @@ impl TestingRobot {
         self
     }
 
+    pub(crate) fn query_weight_at(
+        &mut self,
+        address: String,
+        timestamp: Timestamp,
+        response: impl Fn(StdResult<(&mut Self, BondingWeightResponse)>),
+    ) -> &mut Self {
+        let bonding_weight_response = self.app.wrap().query_wasm_smart(
+            &self.whale_lair_addr,
+            &QueryMsg::WeightAt { address, timestamp },
+        );
+
+        match bonding_weight_response {
+            Ok(bonding_weight_response) => response(Ok((self, bonding_weight_response))),
+            Err(err) => response(Err(err)),
+        }
+
+        self
+    }
+
+    pub(crate) fn query_weight_at_epoch(
+        &mut self,
+        address: String,
+        epoch_id: Uint64,
+        response: impl Fn(StdResult<(&mut Self, BondingWeightResponse)>),
+    ) -> &mut Self {
+        let bonding_weight_response = self.app.wrap().query_wasm_smart(
+            &self.whale_lair_addr,
+            &QueryMsg::WeightAtEpoch { address, epoch_id },
+        );
+
+        match bonding_weight_response {
+            Ok(bonding_weight_response) => response(Ok((self, bonding_weight_response))),
+            Err(err) => response(Err(err)),
+        }
+
+        self
+    }
+
+    pub(crate) fn query_global_index_at(
+        &mut self,
+        timestamp: Timestamp,
+        response: impl Fn(StdResult<(&mut Self, GlobalIndex)>),
+    ) -> &mut Self {
+        let global_index: GlobalIndex = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.whale_lair_addr,
+                &QueryMsg::GlobalIndexAt { timestamp },
+            )
+            .unwrap();
+
+        response(Ok((self, global_index)));
+
+        self
+    }
+
     pub(crate) fn query_bonded(
         &mut self,
         address: String,
@@ -360,6 +814,178 @@ impl TestingRobot {
         self
     }
 
+    pub(crate) fn query_lp_token_balance(
+        &mut self,
+        address: String,
+        response: impl Fn(StdResult<(&mut Self, Uint128)>),
+    ) -> &mut Self {
+        let balance_response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.lp_token_addr,
+                &cw20::Cw20QueryMsg::Balance { address },
+            )
+            .unwrap();
+
+        response(Ok((self, balance_response.balance)));
+
+        self
+    }
+
+    pub(crate) fn query_balance(
+        &mut self,
+        address: String,
+        denom: String,
+        response: impl Fn(StdResult<(&mut Self, Uint128)>),
+    ) -> &mut Self {
+        let balance = self.app.wrap().query_balance(address, denom).unwrap();
+
+        response(Ok((self, balance.amount)));
+
+        self
+    }
+
+    pub(crate) fn query_receipt_token_balance(
+        &mut self,
+        address: String,
+        response: impl Fn(StdResult<(&mut Self, Uint128)>),
+    ) -> &mut Self {
+        let balance_response: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.receipt_token_addr,
+                &cw20::Cw20QueryMsg::Balance { address },
+            )
+            .unwrap();
+
+        response(Ok((self, balance_response.balance)));
+
+        self
+    }
+
+    pub(crate) fn query_reward_recipient(
+        &mut self,
+        address: String,
+        response: impl Fn(StdResult<(&mut Self, RewardRecipientResponse)>),
+    ) -> &mut Self {
+        let reward_recipient_response: RewardRecipientResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.whale_lair_addr,
+                &QueryMsg::RewardRecipient { address },
+            )
+            .unwrap();
+
+        response(Ok((self, reward_recipient_response)));
+
+        self
+    }
+
+    pub(crate) fn query_liquid_bonding(
+        &mut self,
+        response: impl Fn(StdResult<(&mut Self, LiquidBondingResponse)>),
+    ) -> &mut Self {
+        let liquid_bonding_response: LiquidBondingResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.whale_lair_addr, &QueryMsg::LiquidBonding {})
+            .unwrap();
+
+        response(Ok((self, liquid_bonding_response)));
+
+        self
+    }
+
+    pub(crate) fn query_lock(
+        &mut self,
+        address: String,
+        denom: String,
+        response: impl Fn(StdResult<(&mut Self, LockResponse)>),
+    ) -> &mut Self {
+        let lock_response: LockResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.whale_lair_addr, &QueryMsg::Lock { address, denom })
+            .unwrap();
+
+        response(Ok((self, lock_response)));
+
+        self
+    }
+
+    pub(crate) fn query_global_index(
+        &mut self,
+        response: impl Fn(StdResult<(&mut Self, GlobalIndex)>),
+    ) -> &mut Self {
+        let global_index: GlobalIndex = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.whale_lair_addr, &QueryMsg::GlobalIndex {})
+            .unwrap();
+
+        response(Ok((self, global_index)));
+
+        self
+    }
+
+    pub(crate) fn query_bonders(
+        &mut self,
+        start_after: Option<String>,
+        limit: Option<u8>,
+        response: impl Fn(StdResult<(&mut Self, BondersResponse)>),
+    ) -> &mut Self {
+        let bonders_response: BondersResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.whale_lair_addr,
+                &QueryMsg::Bonders { start_after, limit },
+            )
+            .unwrap();
+
+        response(Ok((self, bonders_response)));
+
+        self
+    }
+
+    pub(crate) fn query_top_bonders(
+        &mut self,
+        sort_by: BonderSortBy,
+        limit: Option<u8>,
+        response: impl Fn(StdResult<(&mut Self, BondersResponse)>),
+    ) -> &mut Self {
+        let bonders_response: BondersResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.whale_lair_addr,
+                &QueryMsg::TopBonders { sort_by, limit },
+            )
+            .unwrap();
+
+        response(Ok((self, bonders_response)));
+
+        self
+    }
+
+    pub(crate) fn query_stats(
+        &mut self,
+        response: impl Fn(StdResult<(&mut Self, StatsResponse)>),
+    ) -> &mut Self {
+        let stats_response: StatsResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.whale_lair_addr, &QueryMsg::Stats {})
+            .unwrap();
+
+        response(Ok((self, stats_response)));
+
+        self
+    }
+
     pub(crate) fn query_total_bonded(
         &mut self,
         response: impl Fn(StdResult<(&mut Self, BondedResponse)>),
@@ -409,6 +1035,18 @@ impl TestingRobot {
         })
     }
 
+    pub(crate) fn assert_lock_response(
+        &mut self,
+        address: String,
+        denom: String,
+        expected: LockResponse,
+    ) -> &mut Self {
+        self.query_lock(address, denom, |res| {
+            let lock_response = res.unwrap().1;
+            assert_eq!(lock_response, expected);
+        })
+    }
+
     pub(crate) fn assert_unbonding_response(
         &mut self,
         address: String,
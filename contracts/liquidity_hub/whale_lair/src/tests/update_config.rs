@@ -25,6 +25,10 @@ fn test_update_config_successfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         })
         .update_config(
             owner.clone(),
@@ -49,6 +53,10 @@ fn test_update_config_successfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         })
         .update_config(
             owner,
@@ -70,6 +78,10 @@ fn test_update_config_successfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         });
 }
 
@@ -92,6 +104,10 @@ fn test_update_config_unsuccessfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         })
         .update_config(
             Addr::unchecked("unauthorized"),
@@ -122,6 +138,10 @@ fn test_update_config_unsuccessfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         })
         .update_config(
             Addr::unchecked("owner"),
@@ -152,5 +172,9 @@ fn test_update_config_unsuccessfully() {
                 },
             ],
             fee_distributor_addr: Addr::unchecked("contract2"),
+            weight_multipliers: vec![],
+            max_lock_duration: None,
+            liquid_bonding: None,
+            pool_router_addr: None,
         });
 }
@@ -0,0 +1,363 @@
+use cosmwasm_std::{coins, Decimal, Timestamp, Uint128, Uint64};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::{
+    BondedResponse, BondingWeightResponse, LockResponse, VoteEscrowIndex,
+};
+
+use crate::tests::robot::TestingRobot;
+use crate::ContractError;
+
+/// 1000 seconds, in nanoseconds.
+const MAX_LOCK_DURATION: Uint64 = Uint64::new(1_000_000_000_000u64);
+
+fn seconds(seconds: u64) -> Uint64 {
+    Uint64::new(seconds * 1_000_000_000u64)
+}
+
+fn amp_whale(amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: "ampWHALE".to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+#[test]
+fn test_lock_weight_decays_until_expiry() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_vote_escrow(MAX_LOCK_DURATION)
+        .bond(
+            sender.clone(),
+            amp_whale(1_000u128),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::MissingLockDuration {}.to_string()
+                );
+            },
+        )
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(MAX_LOCK_DURATION.checked_add(Uint64::one()).unwrap()),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::InvalidLockDuration {}.to_string()
+                );
+            },
+        )
+        // locked for half the maximum duration, so it starts with half the weight
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(seconds(500u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(500u128),
+                global_weight: Uint128::new(500u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797419879305533u64),
+            },
+        )
+        .fast_forward(100u64)
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(400u128),
+                global_weight: Uint128::new(400u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797519879305533u64),
+            },
+        )
+        .bond_with_lock(
+            another_sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                amount: Uint128::new(2_000u128),
+            },
+            Some(MAX_LOCK_DURATION),
+            &coins(2_000u128, "bWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(400u128),
+                global_weight: Uint128::new(2_400u128),
+                share: Decimal::from_ratio(400u128, 2_400u128),
+                timestamp: Timestamp::from_nanos(1571797519879305533u64),
+            },
+        )
+        // the first lock expired, so it no longer counts for the global weight either
+        .fast_forward(400u64)
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::zero(),
+                global_weight: Uint128::new(1_200u128),
+                share: Decimal::zero(),
+                timestamp: Timestamp::from_nanos(1571797919879305533u64),
+            },
+        )
+        .assert_bonding_weight_response(
+            another_sender.to_string(),
+            BondingWeightResponse {
+                address: another_sender.to_string(),
+                weight: Uint128::new(1_200u128),
+                global_weight: Uint128::new(1_200u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797919879305533u64),
+            },
+        )
+        .assert_lock_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            LockResponse {
+                lock_end: Some(Timestamp::from_nanos(1571797919879305533u64)),
+                weight: Uint128::zero(),
+            },
+        )
+        .fast_forward(600u64)
+        .assert_bonding_weight_response(
+            another_sender.to_string(),
+            BondingWeightResponse {
+                address: another_sender.to_string(),
+                weight: Uint128::zero(),
+                global_weight: Uint128::zero(),
+                share: Decimal::zero(),
+                timestamp: Timestamp::from_nanos(1571798519879305533u64),
+            },
+        );
+}
+
+#[test]
+fn test_increase_and_extend_lock() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_vote_escrow(MAX_LOCK_DURATION)
+        .extend_lock(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            seconds(500u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::NothingToExtend {}.to_string()
+                );
+            },
+        )
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(seconds(500u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(100u64)
+        // the tokens are added to the current lock, which ends in 400 seconds
+        .bond(
+            sender.clone(),
+            amp_whale(1_000u128),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(800u128),
+                global_weight: Uint128::new(800u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797519879305533u64),
+            },
+        )
+        .extend_lock(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            seconds(300u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::LockCannotBeShortened {}.to_string()
+                );
+            },
+        )
+        .extend_lock(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            MAX_LOCK_DURATION,
+            |res| {
+                res.unwrap();
+            },
+        )
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(2_000u128),
+                global_weight: Uint128::new(2_000u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571797519879305533u64),
+            },
+        )
+        .query_global_index(|res| {
+            let global_index = res.unwrap().1;
+            assert_eq!(
+                global_index.vote_escrow,
+                Some(VoteEscrowIndex {
+                    slope: Uint128::new(2_000u128),
+                    bias: Uint128::new(2_000_000u128),
+                })
+            );
+        })
+        // past the original lock end, the extended lock keeps counting for the global weight
+        .fast_forward(500u64)
+        .assert_bonding_weight_response(
+            sender.to_string(),
+            BondingWeightResponse {
+                address: sender.to_string(),
+                weight: Uint128::new(1_000u128),
+                global_weight: Uint128::new(1_000u128),
+                share: Decimal::one(),
+                timestamp: Timestamp::from_nanos(1571798019879305533u64),
+            },
+        )
+        .assert_lock_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            LockResponse {
+                lock_end: Some(Timestamp::from_nanos(1571798519879305533u64)),
+                weight: Uint128::new(1_000u128),
+            },
+        );
+}
+
+#[test]
+fn test_unbond_expired_lock() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_vote_escrow(MAX_LOCK_DURATION)
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(seconds(100u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .unbond(sender.clone(), amp_whale(1_000u128), |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::LockNotExpired {}.to_string()
+            );
+        })
+        .fast_forward(100u64)
+        // an expired lock must be locked again before adding to it
+        .bond(
+            sender.clone(),
+            amp_whale(1_000u128),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::MissingLockDuration {}.to_string()
+                );
+            },
+        )
+        .unbond(sender.clone(), amp_whale(1_000u128), |res| {
+            res.unwrap();
+        })
+        .rebond(sender.clone(), "ampWHALE".to_string(), None, None, |res| {
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::RebondUnavailable {}.to_string()
+            );
+        })
+        .withdraw(sender.clone(), "ampWHALE".to_string(), |res| {
+            res.unwrap();
+        })
+        .assert_lock_response(
+            sender.to_string(),
+            "ampWHALE".to_string(),
+            LockResponse {
+                lock_end: None,
+                weight: Uint128::zero(),
+            },
+        )
+        .assert_bonded_response(
+            sender.to_string(),
+            BondedResponse {
+                total_bonded: Uint128::zero(),
+                bonded_assets: vec![],
+                first_bonded_epoch_id: Default::default(),
+            },
+        )
+        .query_total_bonded(|res| {
+            let bonded_response = res.unwrap().1;
+            assert_eq!(bonded_response.total_bonded, Uint128::zero());
+        });
+}
+
+#[test]
+fn test_lock_unavailable_without_vote_escrow() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond_with_lock(
+            sender.clone(),
+            amp_whale(1_000u128),
+            Some(seconds(100u64)),
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::VoteEscrowDisabled {}.to_string()
+                );
+            },
+        )
+        .extend_lock(
+            sender.clone(),
+            "ampWHALE".to_string(),
+            seconds(100u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap_err().root_cause().to_string(),
+                    ContractError::VoteEscrowDisabled {}.to_string()
+                );
+            },
+        );
+}
@@ -0,0 +1,153 @@
+use cosmwasm_std::{coins, Decimal, Timestamp, Uint128, Uint64};
+
+use white_whale_std::pool_network::asset::{Asset, AssetInfo};
+use white_whale_std::whale_lair::BondingWeightResponse;
+
+use crate::tests::robot::TestingRobot;
+
+#[test]
+fn test_weight_at_past_timestamps() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+    let another_sender = robot.another_sender.clone();
+
+    robot
+        .instantiate_default()
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .bond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "ampWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .bond(
+            another_sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "bWHALE".to_string(),
+                },
+                amount: Uint128::new(1_000u128),
+            },
+            &coins(1_000u128, "bWHALE"),
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        .unbond(
+            sender.clone(),
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "ampWHALE".to_string(),
+                },
+                amount: Uint128::new(500u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        .fast_forward(10u64)
+        // before bonding there was no weight at all
+        .query_weight_at(
+            sender.to_string(),
+            Timestamp::from_nanos(1571797418879305533u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap().1,
+                    BondingWeightResponse {
+                        address: sender.to_string(),
+                        weight: Uint128::zero(),
+                        global_weight: Uint128::zero(),
+                        share: Decimal::zero(),
+                        timestamp: Timestamp::from_nanos(1571797418879305533u64),
+                    }
+                );
+            },
+        )
+        // halfway through the first bond, before the other bonds were done
+        .query_weight_at(
+            sender.to_string(),
+            Timestamp::from_nanos(1571797424879305533u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap().1,
+                    BondingWeightResponse {
+                        address: sender.to_string(),
+                        weight: Uint128::new(6_000u128),
+                        global_weight: Uint128::new(6_000u128),
+                        share: Decimal::one(),
+                        timestamp: Timestamp::from_nanos(1571797424879305533u64),
+                    }
+                );
+            },
+        )
+        // halfway between the second bond and the unbond
+        .query_weight_at(
+            sender.to_string(),
+            Timestamp::from_nanos(1571797434879305533u64),
+            |res| {
+                assert_eq!(
+                    res.unwrap().1,
+                    BondingWeightResponse {
+                        address: sender.to_string(),
+                        weight: Uint128::new(32_000u128),
+                        global_weight: Uint128::new(38_000u128),
+                        share: Decimal::from_ratio(32_000u128, 38_000u128),
+                        timestamp: Timestamp::from_nanos(1571797434879305533u64),
+                    }
+                );
+            },
+        )
+        .query_global_index_at(Timestamp::from_nanos(1571797434879305533u64), |res| {
+            let global_index = res.unwrap().1;
+            assert_eq!(global_index.weight, Uint128::new(38_000u128));
+            assert_eq!(global_index.bonded_amount, Uint128::new(3_000u128));
+        })
+        // after the last change, the history matches the current weight
+        .query_weight(sender.to_string(), |res| {
+            let (robot, weight) = res.unwrap();
+            robot.query_weight_at(sender.to_string(), weight.timestamp, |res| {
+                assert_eq!(res.unwrap().1, weight);
+            });
+        })
+        .query_weight(another_sender.to_string(), |res| {
+            let (robot, weight) = res.unwrap();
+            robot.query_weight_at(another_sender.to_string(), weight.timestamp, |res| {
+                assert_eq!(res.unwrap().1, weight);
+            });
+        });
+}
+
+#[test]
+fn test_weight_at_unknown_epoch() {
+    let mut robot = TestingRobot::default();
+    let sender = robot.sender.clone();
+
+    robot.instantiate_default().query_weight_at_epoch(
+        sender.to_string(),
+        Uint64::new(1u64),
+        |res| {
+            assert!(res.err().unwrap().to_string().contains("Epoch 1 not found"));
+        },
+    );
+}
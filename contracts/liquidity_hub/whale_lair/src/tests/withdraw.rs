@@ -1,4 +1,4 @@
-use cosmwasm_std::{coins, Event, Uint128};
+use cosmwasm_std::{coins, Decimal, Event, Uint128};
 
 use white_whale_std::pool_network::asset::{Asset, AssetInfo};
 use white_whale_std::whale_lair::WithdrawableResponse;
@@ -117,3 +117,47 @@ fn test_withdraw_unsuccessfully() {
             //assert error is NothingToWithdraw
         });
 }
+
+#[test]
+fn test_withdraw_cw20_successfully() {
+    let mut robot = TestingRobot::default();
+    let another_sender = robot.another_sender.clone();
+
+    robot.instantiate_with_lp_token(Decimal::percent(200));
+    let lp_token = AssetInfo::Token {
+        contract_addr: robot.lp_token_addr.to_string(),
+    };
+
+    robot
+        .bond_cw20(another_sender.clone(), Uint128::new(1_000u128), |res| {
+            res.unwrap();
+        })
+        .fast_forward(10u64)
+        .unbond(
+            another_sender.clone(),
+            Asset {
+                info: lp_token.clone(),
+                amount: Uint128::new(400u128),
+            },
+            |res| {
+                res.unwrap();
+            },
+        )
+        .query_total_bonded(|res| {
+            assert_eq!(res.unwrap().1.total_bonded, Uint128::new(600u128));
+        })
+        .fast_forward(1000u64)
+        .assert_withdrawable_response(
+            another_sender.to_string(),
+            lp_token.to_string(),
+            WithdrawableResponse {
+                withdrawable_amount: Uint128::new(400u128),
+            },
+        )
+        .withdraw(another_sender.clone(), lp_token.to_string(), |res| {
+            res.unwrap();
+        })
+        .query_lp_token_balance(another_sender.to_string(), |res| {
+            assert_eq!(res.unwrap().1, Uint128::new(999_999_400u128));
+        });
+}
@@ -64,9 +64,20 @@ pub enum ExecuteMsg {
     /// Can only be executed by the fee collector.
     NewEpoch {},
 
-    /// Claims tokens from the current epoch and all epochs that are in the grace period.
-    /// Sends all tokens to the sender.
-    Claim {},
+    /// Claims tokens from the current epoch and all epochs that are in the grace period, oldest
+    /// first, up to the given maximum number of epochs and up to the epoch with id [until_epoch]
+    /// included if set. The remaining epochs can be claimed later on. Epochs whose rewards exceed
+    /// their available fees are skipped. Sends all tokens to the reward recipient of the sender,
+    /// returning a [ClaimResponse] as data.
+    Claim {
+        max_epochs: Option<u64>,
+        until_epoch: Option<Uint64>,
+    },
+
+    /// Claims tokens from the current epoch and all epochs that are in the grace period on behalf
+    /// of the given address, sending them to its reward recipient. Can only be executed by the
+    /// bonding contract.
+    ClaimFor { address: String },
 
     /// Updates the [Config] of the contract.
     UpdateConfig {
@@ -113,5 +124,28 @@ pub struct ClaimableEpochsResponse {
     pub epochs: Vec<Epoch>,
 }
 
+/// Data returned by the Claim and ClaimFor messages.
+#[cw_serde]
+pub struct ClaimResponse {
+    /// The address the rewards were sent to.
+    pub recipient: String,
+    /// The total rewards claimed.
+    pub rewards: Vec<Asset>,
+    /// The rewards claimed from each epoch, oldest first.
+    pub epochs: Vec<EpochClaim>,
+}
+
+/// The rewards claimed from an epoch.
+#[cw_serde]
+pub struct EpochClaim {
+    pub epoch_id: Uint64,
+    /// The rewards claimed from the epoch, empty if it was skipped.
+    pub rewards: Vec<Asset>,
+    /// Whether the epoch was skipped because the rewards computed for it exceed its available
+    /// fees, which can happen once the weights changed after it was created. Skipped epochs are
+    /// not claimed again, their fees are forwarded to a new epoch once they expire.
+    pub skipped: bool,
+}
+
 #[cw_serde]
 pub struct MigrateMsg {}
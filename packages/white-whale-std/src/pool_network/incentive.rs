@@ -2,8 +2,12 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal256, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Decimal256, StdResult, Uint128, WasmMsg,
+};
+use cw20::Expiration;
 
+use crate::epoch_manager::hooks::EpochChangedHookMsg;
 use crate::pool_network::asset::{Asset, AssetInfo};
 
 #[cw_serde]
@@ -16,8 +20,16 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Makes a snapshot of the current global weight, at the current epoch.
+    /// Makes a snapshot of the current global weight, at the current epoch. The snapshots of the
+    /// epochs missed since the last one are taken as well.
     TakeGlobalWeightSnapshot {},
+    /// Takes the global weight snapshots when a new epoch is created. Does nothing unless called
+    /// by the epoch manager set with [ExecuteMsg::UpdateEpochManager], so it never makes the
+    /// creation of the epoch fail.
+    EpochChangedHook(EpochChangedHookMsg),
+    /// Sets the epoch manager allowed to execute the [ExecuteMsg::EpochChangedHook], or removes it
+    /// if left empty. Can only be called by the incentive factory.
+    UpdateEpochManager { epoch_manager_addr: Option<String> },
     /// Opens a new liquidity flow
     OpenFlow {
         /// The epoch at which the flow will start. If unspecified, the flow will start at the
@@ -71,10 +83,133 @@ pub enum ExecuteMsg {
         /// The unbonding duration of the position to close.
         unbonding_duration: u64,
     },
+    /// Transfers an open position to another address, together with its weight. The pending
+    /// rewards of the sender are claimed before the position is transferred.
+    ///
+    /// If the recipient already has a position with the same unbonding duration, the transferred
+    /// position is merged into it.
+    TransferPosition {
+        /// The unbonding duration of the position to transfer.
+        unbonding_duration: u64,
+        /// The address to transfer the position to.
+        recipient: String,
+    },
     /// Withdraws the LP tokens from a closed position once the unbonding duration has passed.
     Withdraw {},
+    /// Unlocks a position before its unbonding period is over, returning the LP tokens right away
+    /// minus a penalty which is sent to the fee collector. The penalty grows with the weight
+    /// multiplier of the position and the time left to unbond.
+    ///
+    /// If `unbonding_duration` is specified, the open position with that duration is unlocked.
+    /// Otherwise, all the closed positions that are still unbonding are unlocked.
+    EmergencyUnlock {
+        /// The unbonding duration of the open position to unlock.
+        unbonding_duration: Option<u64>,
+    },
+    /// Recomputes the boost applied to the weight of the given address, based on its current
+    /// bonding share in the whale lair. Can be executed by anyone, so boosts that are no longer
+    /// backed by bonded WHALE can be brought down.
+    ///
+    /// The boost scales down with the weight of the positions of the address, but only grows back
+    /// when poked again.
+    PokeBoost {
+        /// The address to recompute the boost for.
+        address: String,
+    },
+    /// Mints a cw721 NFT representing the open position with the given unbonding duration to the
+    /// sender. While tokenized, the position and its reward rights belong to the owner of the NFT.
+    TokenizePosition {
+        /// The unbonding duration of the position to tokenize.
+        unbonding_duration: u64,
+    },
+    /// Transfers a position NFT, together with the position and its weight, to the recipient. The
+    /// pending rewards of the current owner are claimed before the transfer.
+    ///
+    /// The recipient can't have a position with the same unbonding duration.
+    TransferNft {
+        /// The address to transfer the NFT to.
+        recipient: String,
+        /// The id of the NFT to transfer.
+        token_id: String,
+    },
+    /// Sends a position NFT to a contract, triggering a [Cw721ReceiveMsg] on the receiving
+    /// contract. Works like [ExecuteMsg::TransferNft] otherwise.
+    SendNft {
+        /// The contract to send the NFT to.
+        contract: String,
+        /// The id of the NFT to send.
+        token_id: String,
+        /// The message to pass to the receiving contract.
+        msg: Binary,
+    },
+    /// Allows the spender to transfer, send or burn the position NFT.
+    Approve {
+        /// The address to approve.
+        spender: String,
+        /// The id of the NFT to approve the spender for.
+        token_id: String,
+        /// When the approval expires. If unspecified, it never expires.
+        expires: Option<Expiration>,
+    },
+    /// Removes a previously granted approval on a position NFT.
+    Revoke {
+        /// The address to remove the approval for.
+        spender: String,
+        /// The id of the NFT to revoke the approval on.
+        token_id: String,
+    },
+    /// Allows the operator to transfer, send, burn or approve any of the position NFTs of the
+    /// sender.
+    ApproveAll {
+        /// The address to approve.
+        operator: String,
+        /// When the approval expires. If unspecified, it never expires.
+        expires: Option<Expiration>,
+    },
+    /// Removes a previously granted operator.
+    RevokeAll {
+        /// The operator to remove.
+        operator: String,
+    },
+    /// Burns a position NFT. The position is kept by the owner as a regular position.
+    Burn {
+        /// The id of the NFT to burn.
+        token_id: String,
+    },
     /// Claims the flow rewards.
-    Claim {},
+    ///
+    /// The claim can be split across multiple transactions when there are many epochs to claim,
+    /// resuming where the previous one left off.
+    Claim {
+        /// The last epoch to claim the rewards for. If unspecified, claims up to the current epoch.
+        until_epoch: Option<u64>,
+        /// The maximum amount of epochs to claim for each flow. If unspecified, defaults to 100.
+        max_epochs: Option<u64>,
+        /// The address to claim the rewards for. The rewards are always sent to the owner.
+        ///
+        /// If set to an address other than the sender, the sender must be the claim operator of
+        /// the owner. If left empty, defaults to the message sender.
+        owner: Option<String>,
+        /// If set, the rewards in any of the pair's assets are provided as liquidity to the pair
+        /// and the resulting LP tokens are added to the owner's position.
+        ///
+        /// Can only be set by the owner. The claims done by the claim operator use the
+        /// auto-compound preference set by the owner with [ExecuteMsg::UpdateClaimOperator].
+        auto_compound: Option<AutoCompound>,
+    },
+    /// Sets the address allowed to claim the rewards on behalf of the sender. If left empty, the
+    /// current claim operator is removed.
+    ///
+    /// The operator picks when to claim, so it can time the claim to sandwich the swap that
+    /// compounds the rewards. Only the max spread of `auto_compound` protects the swap, which is
+    /// why it defaults to a tight 0.5% when not set. Only trust operators with that.
+    UpdateClaimOperator {
+        /// The new claim operator.
+        operator: Option<String>,
+        /// If set, the rewards claimed by the operator are compounded into the sender's position
+        /// with these parameters. Otherwise they are sent to the sender.
+        auto_compound: Option<AutoCompound>,
+    },
     /// Expands an existing flow.
     ExpandFlow {
         /// The identifier of the flow to expand, whether an id or a label.
@@ -84,6 +219,47 @@ pub enum ExecuteMsg {
         /// The asset to expand this flow with.
         flow_asset: Asset,
     },
+    /// Callback messages, only callable by the contract itself.
+    Callback(CallbackMsg),
+}
+
+/// The callback messages available. Only callable by the incentive contract itself.
+#[cw_serde]
+pub enum CallbackMsg {
+    /// Adds the LP tokens received by the contract since `lp_balance_before` was recorded to the
+    /// owner's position.
+    CompoundRewards {
+        /// The owner of the position.
+        owner: Addr,
+        /// The unbonding duration of the position to add the LP tokens to.
+        unbonding_duration: u64,
+        /// The LP balance of the contract before the rewards were provided as liquidity.
+        lp_balance_before: Uint128,
+    },
+    /// Provides the pair's assets received by the contract since `balances_before` was recorded
+    /// as liquidity, at the ratio of the pair's reserves after the rewards were swapped. The
+    /// amounts that don't fit the ratio are sent to the owner.
+    ProvideCompoundedRewards {
+        /// The owner of the position.
+        owner: Addr,
+        /// The unbonding duration of the position to add the LP tokens to.
+        unbonding_duration: u64,
+        /// The maximum spread allowed when providing the rewards as liquidity.
+        max_spread: Option<Decimal>,
+        /// The balances of the pair's assets held by the contract before the rewards were
+        /// swapped, not counting the rewards to compound.
+        balances_before: Vec<Asset>,
+    },
+}
+
+/// The parameters to auto-compound the claimed rewards with.
+#[cw_serde]
+pub struct AutoCompound {
+    /// The unbonding duration of the position to add the LP tokens to.
+    pub unbonding_duration: u64,
+    /// The maximum spread allowed when swapping and providing the rewards as liquidity. Defaults
+    /// to 0.5%.
+    pub max_spread: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -104,7 +280,6 @@ pub struct Flow {
     pub claimed_amount: Uint128,
     /// The type of curve the flow has.
     pub curve: Curve,
-    //todo not doing anything for now
     /// The epoch at which the flow starts.
     pub start_epoch: u64,
     /// The epoch at which the flow ends.
@@ -200,6 +375,105 @@ pub enum QueryMsg {
         /// The address to query the rewards share for.
         address: String,
     },
+    /// Retrieves the claim operator of an address.
+    #[returns(ClaimOperatorResponse)]
+    ClaimOperator {
+        /// The address to get the claim operator for.
+        address: String,
+    },
+    /// Retrieves the owner of a position NFT, together with the approvals on it.
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        /// The id of the NFT.
+        token_id: String,
+        /// Whether to include the expired approvals. Defaults to false.
+        include_expired: Option<bool>,
+    },
+    /// Retrieves the metadata of a position NFT, i.e. the position it represents.
+    #[returns(NftInfoResponse)]
+    NftInfo {
+        /// The id of the NFT.
+        token_id: String,
+    },
+    /// Retrieves the ids of the position NFTs owned by an address.
+    #[returns(TokensResponse)]
+    Tokens {
+        /// The owner of the NFTs.
+        owner: String,
+        /// The id of the NFT to start after, for pagination.
+        start_after: Option<String>,
+        /// The amount of ids to retrieve.
+        limit: Option<u32>,
+    },
+    /// Retrieves the approval of the spender on a position NFT. Fails if there's none.
+    #[returns(ApprovalResponse)]
+    Approval {
+        /// The id of the NFT.
+        token_id: String,
+        /// The address to get the approval of.
+        spender: String,
+        /// Whether to include the approval if expired. Defaults to false.
+        include_expired: Option<bool>,
+    },
+    /// Retrieves the approvals on a position NFT.
+    #[returns(ApprovalsResponse)]
+    Approvals {
+        /// The id of the NFT.
+        token_id: String,
+        /// Whether to include the expired approvals. Defaults to false.
+        include_expired: Option<bool>,
+    },
+    /// Retrieves the operators allowed to handle all the position NFTs of an owner.
+    #[returns(OperatorsResponse)]
+    AllOperators {
+        /// The owner of the NFTs.
+        owner: String,
+        /// Whether to include the expired operators. Defaults to false.
+        include_expired: Option<bool>,
+        /// The operator to start after, for pagination.
+        start_after: Option<String>,
+        /// The amount of operators to retrieve.
+        limit: Option<u32>,
+    },
+    /// Retrieves the amount of position NFTs in existence.
+    #[returns(NumTokensResponse)]
+    NumTokens {},
+    /// Retrieves the name and symbol of the position NFTs.
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    /// Retrieves the owner and approvals of a position NFT, together with its metadata.
+    #[returns(AllNftInfoResponse)]
+    AllNftInfo {
+        /// The id of the NFT.
+        token_id: String,
+        /// Whether to include the expired approvals. Defaults to false.
+        include_expired: Option<bool>,
+    },
+    /// Retrieves the ids of all the position NFTs.
+    #[returns(TokensResponse)]
+    AllTokens {
+        /// The id of the NFT to start after, for pagination.
+        start_after: Option<String>,
+        /// The amount of ids to retrieve.
+        limit: Option<u32>,
+    },
+    /// Retrieves the flows that haven't finished emitting yet, along with their remaining
+    /// emissions, sorted by flow id.
+    #[returns(ActiveFlowsResponse)]
+    ActiveFlows {
+        /// If set, only retrieves the flows distributing the given asset.
+        flow_asset: Option<AssetInfo>,
+        /// If set, only retrieves the flows opened by the given address.
+        creator: Option<String>,
+        /// If set, only retrieves the flows emitting at or after the given epoch.
+        start_epoch: Option<u64>,
+        /// If set, only retrieves the flows emitting at or before the given epoch.
+        end_epoch: Option<u64>,
+        /// The flow id to start after, for pagination.
+        start_after: Option<u64>,
+        /// The amount of flows to retrieve.
+        limit: Option<u32>,
+    },
 }
 
 /// Stores the reply data set in the response when instantiating an incentive contract.
@@ -227,12 +501,31 @@ pub struct Config {
 pub enum Curve {
     /// A linear curve that releases assets as we approach the end of the flow period.
     Linear,
+    /// A front-loaded curve where the emissions halve every `half_life_epochs` epochs.
+    ExponentialDecay {
+        /// The amount of epochs it takes for the emissions to halve.
+        half_life_epochs: u64,
+    },
+    /// A curve where the emissions change in steps over the flow period.
+    Step {
+        /// A list of (epoch offset from the start of the flow, emission weight). Each step sets the
+        /// relative weight of the emissions from its epoch until the next step. Epochs before the
+        /// first step have no emissions.
+        schedule: Vec<(u64, Decimal256)>,
+    },
+    /// A curve that releases all the assets at once in the last epoch of the flow period.
+    Cliff,
 }
 
 impl std::fmt::Display for Curve {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Curve::Linear => write!(f, "Linear"),
+            Curve::ExponentialDecay { half_life_epochs } => {
+                write!(f, "ExponentialDecay(half_life_epochs: {half_life_epochs})")
+            }
+            Curve::Step { schedule } => write!(f, "Step(steps: {})", schedule.len()),
+            Curve::Cliff => write!(f, "Cliff"),
         }
     }
 }
@@ -252,6 +545,42 @@ pub struct FlowsResponse {
     pub flows: Vec<Flow>,
 }
 
+/// A summary of a flow that hasn't finished emitting yet.
+#[cw_serde]
+pub struct ActiveFlow {
+    /// A unique identifier of the flow.
+    pub flow_id: u64,
+    /// An alternative flow label.
+    pub flow_label: Option<String>,
+    /// The account which opened the flow.
+    pub flow_creator: Addr,
+    /// The asset the flow distributes, including expansions.
+    pub flow_asset: Asset,
+    /// The type of curve the flow has.
+    pub curve: Curve,
+    /// The epoch at which the flow starts.
+    pub start_epoch: u64,
+    /// The epoch at which the flow ends, including expansions.
+    pub end_epoch: u64,
+    /// The amount of the `flow_asset` that is yet to be emitted, including the current epoch.
+    pub remaining_emissions: Uint128,
+    /// The amount of the `flow_asset` the flow emits in the current epoch.
+    pub current_epoch_emissions: Uint128,
+    /// The amount of the `flow_asset` emitted in the current epoch per unit of weight. Meant as
+    /// a hint for estimating the APR of the flow.
+    pub reward_per_weight: Decimal256,
+}
+
+#[cw_serde]
+pub struct ActiveFlowsResponse {
+    /// The LP token asset tied to the incentive contract.
+    pub lp_asset: AssetInfo,
+    /// The epoch the emissions were computed for.
+    pub epoch_id: u64,
+    /// The active flows.
+    pub flows: Vec<ActiveFlow>,
+}
+
 #[cw_serde]
 pub enum QueryPosition {
     /// Represents a position that a user has deposited, but not yet begun to unbond.
@@ -280,12 +609,27 @@ pub struct PositionsResponse {
     pub timestamp: u64,
     /// All the positions a user has.
     pub positions: Vec<QueryPosition>,
+    /// The boost multiplier applied to the weight of the user.
+    pub boost: Decimal,
 }
 
 #[cw_serde]
 pub struct RewardsResponse {
     /// The rewards that is available to a user if they executed the `claim` function at this point.
     pub rewards: Vec<Asset>,
+    /// The epochs that are still pending to be claimed on each flow.
+    pub pending_epochs: Vec<PendingEpochs>,
+}
+
+/// The range of epochs a user has yet to claim on a flow.
+#[cw_serde]
+pub struct PendingEpochs {
+    /// The id of the flow.
+    pub flow_id: u64,
+    /// The first epoch pending to be claimed.
+    pub start_epoch: u64,
+    /// The last epoch that can be claimed at this point, inclusive.
+    pub end_epoch: u64,
 }
 
 #[cw_serde]
@@ -303,6 +647,8 @@ pub struct RewardsShareResponse {
     pub address_weight: Uint128,
     pub share: Decimal256,
     pub epoch_id: u64,
+    /// The boost multiplier currently applied to the weight of the address.
+    pub boost: Decimal,
 }
 
 #[cw_serde]
@@ -319,3 +665,123 @@ impl fmt::Display for FlowIdentifier {
         }
     }
 }
+
+#[cw_serde]
+pub struct ClaimOperatorResponse {
+    /// The address allowed to claim the rewards on behalf of the queried address, if any.
+    pub operator: Option<Addr>,
+    /// The parameters the rewards claimed by the operator are compounded with, if any.
+    pub auto_compound: Option<AutoCompound>,
+}
+
+/// An approval to transfer a position NFT on behalf of its owner.
+#[cw_serde]
+pub struct Approval {
+    /// The address allowed to transfer the NFT.
+    pub spender: String,
+    /// When the approval expires.
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    /// The owner of the NFT.
+    pub owner: String,
+    /// The approvals on the NFT.
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalResponse {
+    /// The approval of the spender.
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    /// The approvals on the NFT.
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    /// The operators of the owner, as approvals on all of its NFTs.
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    /// The name of the NFTs.
+    pub name: String,
+    /// The symbol of the NFTs.
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse {
+    /// The owner of the NFT and the approvals on it.
+    pub access: OwnerOfResponse,
+    /// The metadata of the NFT.
+    pub info: NftInfoResponse,
+}
+
+/// The metadata of a position NFT.
+#[cw_serde]
+pub struct PositionMetadata {
+    /// The amount of LP tokens in the position.
+    pub amount: Uint128,
+    /// The unbonding duration of the position.
+    pub unbonding_duration: u64,
+    /// The weight of the position.
+    pub weight: Uint128,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    /// Position NFTs have no token uri, the metadata is on chain.
+    pub token_uri: Option<String>,
+    /// The position the NFT represents.
+    pub extension: PositionMetadata,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    /// The ids of the NFTs.
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    /// The amount of NFTs.
+    pub count: u64,
+}
+
+/// The message sent to a contract receiving a position NFT with [ExecuteMsg::SendNft], following
+/// the cw721 receiver interface.
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    /// The address that sent the NFT.
+    pub sender: String,
+    /// The id of the NFT.
+    pub token_id: String,
+    /// The message passed by the sender.
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    /// Creates the message to send this receive message to the given contract.
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_json_binary(&ReceiverExecuteMsg::ReceiveNft(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// The message the receiving contract of a position NFT has to handle.
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
@@ -1,7 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 
 use crate::pool_network::asset::{Asset, AssetInfo};
+use crate::pool_network::incentive::ActiveFlow;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -23,9 +24,18 @@ pub struct InstantiateMsg {
     pub min_unbonding_duration: u64,
     /// The maximum amount of seconds that a user must bond their tokens for.
     pub max_unbonding_duration: u64,
+    /// The penalty applied when unlocking a position early, for a position with a weight
+    /// multiplier of one and `max_unbonding_duration` left to unbond.
+    pub emergency_unlock_penalty: Decimal,
+    /// The maximum penalty applied when unlocking a position early.
+    pub max_emergency_unlock_penalty: Decimal,
+    /// The maximum multiplier applied to the weight of a position, based on the bonding share of
+    /// its owner in the whale lair. A value of one disables the boost.
+    pub max_boost: Decimal,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Creates a new incentive contract tied to the `lp_asset` specified.
     CreateIncentive { lp_asset: AssetInfo },
@@ -70,6 +80,38 @@ pub enum ExecuteMsg {
         ///
         /// If unspecified, the `max_unbonding_duration` will not change.
         max_unbonding_duration: Option<u64>,
+        /// The penalty applied when unlocking a position early.
+        ///
+        /// If unspecified, the `emergency_unlock_penalty` will not change.
+        emergency_unlock_penalty: Option<Decimal>,
+        /// The maximum penalty applied when unlocking a position early.
+        ///
+        /// If unspecified, the `max_emergency_unlock_penalty` will not change.
+        max_emergency_unlock_penalty: Option<Decimal>,
+        /// The maximum multiplier applied to the weight of a position.
+        ///
+        /// If unspecified, the `max_boost` will not change.
+        max_boost: Option<Decimal>,
+        /// The assets to add to the whitelist of assets flows can be opened with. While the
+        /// whitelist is empty, flows can be opened with any asset.
+        flow_assets_to_add: Option<Vec<AssetInfo>>,
+        /// The assets to remove from the whitelist of assets flows can be opened with.
+        flow_assets_to_remove: Option<Vec<AssetInfo>>,
+        /// Alternative fees to create a flow, which can be paid instead of the `create_flow_fee`,
+        /// e.g. a discounted fee when paid in WHALE. Replaces the existing override for the same
+        /// asset, if any.
+        flow_fee_overrides_to_set: Option<Vec<Asset>>,
+        /// The assets to remove the flow fee overrides for.
+        flow_fee_overrides_to_remove: Option<Vec<AssetInfo>>,
+        /// Fees to create a flow charged to specific creators instead of any other fee, e.g. a zero
+        /// fee for the protocol treasury. Replaces the existing fee for the same creator, if any.
+        creator_flow_fees_to_set: Option<Vec<CreatorFlowFee>>,
+        /// The creators to remove the flow fees for.
+        creator_flow_fees_to_remove: Option<Vec<String>>,
+        /// The new epoch manager to register the incentive contracts with as hooks.
+        ///
+        /// If unspecified, the epoch manager will not change.
+        epoch_manager_addr: Option<String>,
     },
     MigrateIncentives {
         /// The address of the incentive contract. If unspecified, will migrate all incentive contracts.
@@ -77,6 +119,16 @@ pub enum ExecuteMsg {
         /// The new code ID to migrate the incentive contract to.
         code_id: u64,
     },
+    /// Sets the epoch manager on incentive contracts and registers them as hooks on it, so they
+    /// take a global weight snapshot on every new epoch. The incentive contracts created while an
+    /// epoch manager is set go through the same on creation.
+    ///
+    /// Only the admin of the epoch manager can add hooks. If that's not the factory, the hooks are
+    /// left for the admin to add.
+    AddEpochHooks {
+        /// The address of the incentive contract. If unspecified, will register all incentive contracts.
+        incentive_address: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -101,6 +153,61 @@ pub enum QueryMsg {
         /// If unspecified, will default to a value specified by the contract.
         limit: Option<u32>,
     },
+    /// Retrieves the whitelist of assets flows can be opened with. If empty, flows can be opened
+    /// with any asset.
+    #[returns(FlowAssetsResponse)]
+    FlowAssets {},
+    /// Retrieves whether flows can be opened with the given asset, i.e. whether it's in the
+    /// whitelist or the whitelist is empty.
+    #[returns(FlowAssetAllowedResponse)]
+    FlowAssetAllowed {
+        /// The asset to open the flow with.
+        flow_asset: AssetInfo,
+    },
+    /// Retrieves the alternative fees to create a flow.
+    #[returns(FlowFeeOverridesResponse)]
+    FlowFeeOverrides {},
+    /// Retrieves the fees charged to specific flow creators.
+    #[returns(CreatorFlowFeesResponse)]
+    CreatorFlowFees {
+        /// The creator to start searching after.
+        start_after: Option<String>,
+        /// The amount of creator fees to return.
+        ///
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
+    /// Retrieves the fees the given creator can choose from to create a flow.
+    #[returns(FlowFeeResponse)]
+    FlowFee {
+        /// The address of the flow creator.
+        creator: String,
+    },
+    /// Retrieves the flows that haven't finished emitting yet across all the incentive contracts,
+    /// sorted by LP asset and flow id.
+    ///
+    /// A page scans a limited amount of incentive contracts, so it can hold fewer flows than the
+    /// limit, or none, while there are more to retrieve. Keep paginating with the returned
+    /// `next_start_after` until it's empty.
+    #[returns(ActiveFlowsResponse)]
+    ActiveFlows {
+        /// If set, only retrieves the flows of the incentive contract of the given LP asset.
+        lp_asset: Option<AssetInfo>,
+        /// If set, only retrieves the flows distributing the given asset.
+        flow_asset: Option<AssetInfo>,
+        /// If set, only retrieves the flows opened by the given address.
+        creator: Option<String>,
+        /// If set, only retrieves the flows emitting at or after the given epoch.
+        start_epoch: Option<u64>,
+        /// If set, only retrieves the flows emitting at or before the given epoch.
+        end_epoch: Option<u64>,
+        /// The LP asset and flow id to start after, for pagination.
+        start_after: Option<(AssetInfo, u64)>,
+        /// The amount of flows to retrieve. Also caps the amount of incentive contracts scanned.
+        ///
+        /// If unspecified, will default to a value specified by the contract.
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -126,6 +233,17 @@ pub struct Config {
     pub min_unbonding_duration: u64,
     /// The maximum amount of seconds that a user must bond their tokens for.
     pub max_unbonding_duration: u64,
+    /// The penalty applied when unlocking a position early, for a position with a weight
+    /// multiplier of one and `max_unbonding_duration` left to unbond. The penalty scales linearly
+    /// with the weight multiplier and the unbonding time left.
+    pub emergency_unlock_penalty: Decimal,
+    /// The maximum penalty applied when unlocking a position early.
+    pub max_emergency_unlock_penalty: Decimal,
+    /// The maximum multiplier applied to the weight of a position, based on the bonding share of
+    /// its owner in the whale lair. A value of one disables the boost.
+    pub max_boost: Decimal,
+    /// The epoch manager the incentive contracts are registered with as hooks, if any.
+    pub epoch_manager_addr: Option<Addr>,
 }
 
 pub type ConfigResponse = Config;
@@ -139,3 +257,44 @@ pub struct IncentivesContract {
     pub lp_reference: Vec<u8>,
 }
 pub type IncentivesResponse = Vec<IncentivesContract>;
+
+/// The fee to create a flow charged to a specific creator.
+#[cw_serde]
+pub struct CreatorFlowFee {
+    /// The address of the flow creator.
+    pub creator: String,
+    /// The fee charged to the creator.
+    pub fee: Asset,
+}
+
+pub type FlowAssetsResponse = Vec<AssetInfo>;
+pub type FlowAssetAllowedResponse = bool;
+pub type FlowFeeOverridesResponse = Vec<Asset>;
+pub type CreatorFlowFeesResponse = Vec<CreatorFlowFee>;
+
+/// An active flow of one of the incentive contracts.
+#[cw_serde]
+pub struct IncentiveFlow {
+    /// The address of the incentive contract.
+    pub incentive_address: Addr,
+    /// The LP token asset tied to the incentive contract.
+    pub lp_asset: AssetInfo,
+    /// The epoch the emissions were computed for.
+    pub epoch_id: u64,
+    /// The flow.
+    pub flow: ActiveFlow,
+}
+#[cw_serde]
+pub struct ActiveFlowsResponse {
+    /// The active flows.
+    pub flows: Vec<IncentiveFlow>,
+    /// The cursor to pass as `start_after` to retrieve the next page, if the scan of the incentive
+    /// contracts isn't over.
+    pub next_start_after: Option<(AssetInfo, u64)>,
+}
+
+#[cw_serde]
+pub struct FlowFeeResponse {
+    /// The fees the creator can choose from to create a flow. Paying any one of them is enough.
+    pub fees: Vec<Asset>,
+}
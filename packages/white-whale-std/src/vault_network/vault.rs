@@ -1,7 +1,7 @@
 use crate::fee::VaultFee;
 use crate::pool_network::asset::{Asset, AssetInfo};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Timestamp, Uint128};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -26,7 +26,35 @@ pub enum CallbackMsg {
     AfterTrade {
         old_balance: Uint128,
         loan_amount: Uint128,
+        /// The discount on the flash-loan fee granted to the initiator of the loan.
+        fee_discount: Option<Decimal>,
     },
+    /// Provides the strategy assets held by the vault as liquidity to the strategy pool, together
+    /// with up to `max_amount` of the vault asset. `swapped_amount` is the amount of vault asset
+    /// swapped into the other strategy assets, which is added to the cost basis of the position.
+    ProvideStrategyLiquidity {
+        max_amount: Uint128,
+        swapped_amount: Uint128,
+    },
+    /// Swaps the strategy assets held by the vault, other than the vault asset, back into the
+    /// vault asset. `liquid_balance` is the balance of the vault asset before the position was
+    /// withdrawn, and `unwound_cost` the cost basis of the withdrawn share of the position, which
+    /// the unwind is expected to return within the max spread of the strategy.
+    SwapStrategyAssets {
+        liquid_balance: Uint128,
+        unwound_cost: Uint128,
+    },
+    /// Performs a flash-loan after the strategy position has been unwound.
+    ContinueFlashLoan {
+        sender: String,
+        amount: Uint128,
+        msg: Binary,
+        fee_discount: Option<Decimal>,
+    },
+    /// Performs a withdrawal after the strategy position has been unwound.
+    ContinueWithdraw { sender: String, amount: Uint128 },
+    /// Processes the withdrawal queue after the strategy position has been unwound.
+    ContinueWithdrawalQueue { limit: Option<u32> },
 }
 
 #[cw_serde]
@@ -56,9 +84,70 @@ pub struct UpdateConfigParams {
     pub new_vault_fees: Option<VaultFee>,
     /// The new address of the fee collector
     pub new_fee_collector_addr: Option<String>,
+    /// The new strategy for the idle liquidity of the vault. Setting a `deploy_share` of zero
+    /// unwinds the position on the next [ExecuteMsg::Rebalance].
+    pub new_strategy: Option<StrategyParams>,
+    /// The new cap on the total assets of the vault, beyond which deposits are rejected. A cap
+    /// of zero removes it.
+    pub new_deposit_cap: Option<Uint128>,
+    /// If withdrawals exceeding the liquid balance of the vault should be queued rather than
+    /// failing.
+    pub withdrawal_queue_enabled: Option<bool>,
+    /// The new flash-loan fee rebates for WHALE bonders. Passing no tiers removes the rebates.
+    pub new_fee_rebates: Option<FeeRebateParams>,
+    /// The new address of the epoch manager, the share price snapshots are taken per epoch.
+    pub new_epoch_manager_addr: Option<String>,
+}
+
+/// The pool the vault provides its idle liquidity to.
+#[cw_serde]
+pub enum StrategyPool {
+    /// A pair on the pool network. Half of the deployed amount is swapped into the other asset
+    /// of the pair before providing liquidity.
+    Pair { contract_addr: String },
+    /// A stableswap trio on the pool network. The deployed amount is split evenly across the
+    /// assets of the trio before providing liquidity.
+    Trio { contract_addr: String },
+}
+
+impl StrategyPool {
+    pub fn contract_addr(&self) -> &str {
+        match self {
+            StrategyPool::Pair { contract_addr } | StrategyPool::Trio { contract_addr } => {
+                contract_addr
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct StrategyParams {
+    /// The pool to provide the idle liquidity to.
+    pub pool: StrategyPool,
+    /// The share of the vault's assets to keep deployed in the pool.
+    pub deploy_share: Decimal,
+    /// The max spread allowed when swapping in and out of the pool. When unwinding the position,
+    /// the spread is measured against the cost basis of the unwound share.
+    pub max_spread: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct Strategy {
+    /// The pool the idle liquidity is provided to.
+    pub pool: StrategyPool,
+    /// The share of the vault's assets to keep deployed in the pool.
+    pub deploy_share: Decimal,
+    /// The max spread allowed when swapping in and out of the pool. When unwinding the position,
+    /// the spread is measured against the cost basis of the unwound share.
+    pub max_spread: Option<Decimal>,
+    /// The assets of the pool.
+    pub pool_assets: Vec<AssetInfo>,
+    /// The LP asset of the pool.
+    pub lp_asset: AssetInfo,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Deposits a given amount into the vault.
     Deposit {
@@ -70,9 +159,27 @@ pub enum ExecuteMsg {
     FlashLoan {
         amount: Uint128,
         msg: Binary,
+        /// The account the loan is taken on behalf of, whose bonded WHALE determines the fee
+        /// rebate. Only honored when the sender is the router set in the fee rebates, otherwise
+        /// the sender is the initiator.
+        initiator: Option<String>,
     },
     /// Collects the Protocol fees
     CollectProtocolFees {},
+    /// Deploys or unwinds the idle liquidity of the vault so the strategy position matches the
+    /// strategy's `deploy_share`. Only callable by the owner.
+    Rebalance {},
+    /// Pays out the queued withdrawals in FIFO order, for as long as the liquid balance of the
+    /// vault covers them. Callable by anyone. If the owner calls it and the liquid balance can't
+    /// cover the withdrawal at the front of the queue, the shortfall is unwound from the strategy
+    /// position and the queue processed again.
+    ProcessWithdrawalQueue {
+        limit: Option<u32>,
+    },
+    /// Cancels a queued withdrawal of the sender, returning the LP tokens held for it.
+    CancelQueuedWithdrawal {
+        id: u64,
+    },
     /// Updates the configuration of the contract.
     /// If a field is not specified, it will not be modified.
     UpdateConfig(UpdateConfigParams),
@@ -97,7 +204,53 @@ pub enum QueryMsg {
     BurnedFees {},
     /// Retrieves the [`Uint128`] amount that must be sent back to the contract to pay off a loan taken out.
     #[returns(PaybackAmountResponse)]
-    GetPaybackAmount { amount: Uint128 },
+    GetPaybackAmount {
+        amount: Uint128,
+        /// The initiator of the loan, to apply its fee rebate to the payback amount.
+        initiator: Option<String>,
+    },
+    /// Quotes a flash-loan of `amount` requested by `sender` on behalf of `initiator`, i.e. whether
+    /// the vault can serve it and the amount that must be paid back for it.
+    #[returns(FlashLoanQuoteResponse)]
+    FlashLoanQuote {
+        amount: Uint128,
+        sender: String,
+        initiator: Option<String>,
+    },
+    /// Retrieves the strategy of the vault and the current position in the strategy pool.
+    #[returns(StrategyResponse)]
+    Strategy {},
+    /// Retrieves the share price snapshots of the vault, taken once per epoch and keyed by epoch
+    /// id, together with the current share price and the trailing APR.
+    #[returns(SharePriceHistoryResponse)]
+    SharePriceHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Retrieves the deposit cap of the vault, if any.
+    #[returns(DepositCapResponse)]
+    DepositCap {},
+    /// Retrieves the queued withdrawals, in FIFO order.
+    #[returns(WithdrawalQueueResponse)]
+    WithdrawalQueue {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Retrieves the queued withdrawals of the given address and their position in the queue.
+    ///
+    /// A page scans up to `limit` withdrawals of the queue after `start_after`, so it can hold
+    /// none of the address while there are more to retrieve. Keep paginating with the returned
+    /// `next_start_after` until it's empty. Positions are counted from `start_after`, so add the
+    /// `limit` of every previous page to get the position in the whole queue.
+    #[returns(WithdrawalQueuePositionResponse)]
+    WithdrawalQueuePosition {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Retrieves the flash-loan fee rebates for WHALE bonders, if any.
+    #[returns(FeeRebatesResponse)]
+    FeeRebates {},
 }
 
 #[cw_serde]
@@ -142,3 +295,126 @@ pub struct PaybackAmountResponse {
     /// The amount of fee to be burned
     pub burn_fee: Uint128,
 }
+
+#[cw_serde]
+pub struct FlashLoanQuoteResponse {
+    /// If flash-loans are enabled in the vault.
+    pub flash_loan_enabled: bool,
+    /// The liquidity available for flash-loans, including the strategy position which is unwound
+    /// when the liquid balance can't cover a loan.
+    pub available_liquidity: Uint128,
+    /// The amount that must be paid back for the loan, with the fee rebate of the initiator.
+    pub payback_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct StrategyResponse {
+    /// The strategy of the vault, if any.
+    pub strategy: Option<Strategy>,
+    /// The amount of LP tokens of the strategy pool held by the vault.
+    pub lp_amount: Uint128,
+    /// The value of the strategy position, denominated in the vault asset. The position is valued
+    /// at the current reserves of the pool, as if it was withdrawn and swapped into the vault asset.
+    pub value: Uint128,
+}
+
+/// A discount on the flash-loan fee for initiators holding at least `min_share` of the global
+/// bonding weight in the whale lair.
+#[cw_serde]
+pub struct FeeRebateTier {
+    pub min_share: Decimal,
+    /// The share of the flash-loan fee that is waived.
+    pub discount: Decimal,
+}
+
+#[cw_serde]
+pub struct FeeRebateParams {
+    /// The whale lair contract the bonded amounts are queried from.
+    pub whale_lair_addr: String,
+    /// The vault router, which is trusted to pass the initiator of the loans it performs.
+    pub router_addr: Option<String>,
+    pub tiers: Vec<FeeRebateTier>,
+}
+
+#[cw_serde]
+pub struct FeeRebates {
+    /// The whale lair contract the bonded amounts are queried from.
+    pub whale_lair_addr: Addr,
+    /// The vault router, which is trusted to pass the initiator of the loans it performs.
+    pub router_addr: Option<Addr>,
+    /// The tiers, sorted by `min_share`.
+    pub tiers: Vec<FeeRebateTier>,
+}
+
+#[cw_serde]
+pub struct FeeRebatesResponse {
+    pub fee_rebates: Option<FeeRebates>,
+}
+
+#[cw_serde]
+pub struct SharePriceSnapshot {
+    /// The id of the epoch the snapshot was taken in.
+    pub epoch_id: u64,
+    /// The time the snapshot was taken.
+    pub timestamp: Timestamp,
+    /// The total supply of the LP asset.
+    pub lp_supply: Uint128,
+    /// The total amount of assets managed by the vault.
+    pub total_assets: Uint128,
+    /// The amount of assets a single LP token is entitled to.
+    pub share_price: Decimal,
+}
+
+#[cw_serde]
+pub struct SharePriceHistoryResponse {
+    /// The share price snapshots, in ascending order.
+    pub snapshots: Vec<SharePriceSnapshot>,
+    /// The current share price.
+    pub current_share_price: Decimal,
+    /// The annualized return over the trailing window of epochs, from the oldest snapshot within
+    /// it up to now. It doesn't depend on the pagination of the snapshots. Zero if the share price
+    /// didn't grow, or there are no snapshots in the window.
+    pub apr: Decimal,
+}
+
+#[cw_serde]
+pub struct DepositCapResponse {
+    /// The cap on the total assets of the vault, if any.
+    pub deposit_cap: Option<Uint128>,
+    /// The total assets of the vault.
+    pub total_assets: Uint128,
+}
+
+#[cw_serde]
+pub struct QueuedWithdrawal {
+    /// The id of the withdrawal, increasing in the order withdrawals are queued.
+    pub id: u64,
+    /// The address the withdrawn assets are sent to.
+    pub sender: Addr,
+    /// The amount of LP tokens being withdrawn, held by the vault until the withdrawal is paid.
+    pub lp_amount: Uint128,
+    /// The time the withdrawal was queued.
+    pub queued_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct WithdrawalQueueResponse {
+    /// If withdrawals exceeding the liquid balance are queued.
+    pub enabled: bool,
+    pub withdrawals: Vec<QueuedWithdrawal>,
+}
+
+#[cw_serde]
+pub struct QueuedWithdrawalPosition {
+    pub withdrawal: QueuedWithdrawal,
+    /// The number of withdrawals ahead in the queue, since the `start_after` of the query.
+    pub position: u64,
+}
+
+#[cw_serde]
+pub struct WithdrawalQueuePositionResponse {
+    pub withdrawals: Vec<QueuedWithdrawalPosition>,
+    /// The cursor to pass as `start_after` to retrieve the next page, if the scan of the queue
+    /// isn't over.
+    pub next_start_after: Option<u64>,
+}
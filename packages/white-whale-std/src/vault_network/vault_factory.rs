@@ -19,6 +19,7 @@ pub struct InstantiateMsg {
 
 /// The execution message
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Creates a new vault given the asset info the vault should manage deposits and withdrawals
     /// for and the fees
@@ -28,6 +29,9 @@ pub enum ExecuteMsg {
         /// If true, the vault will use the token factory to create the LP token. If false, it will
         /// use a cw20 token instead.
         token_factory_lp: bool,
+        /// The identifier of the vault, used to tell apart multiple vaults managing the same asset,
+        /// i.e. a low-fee vault and a premium one. Defaults to [DEFAULT_VAULT_IDENTIFIER].
+        vault_identifier: Option<String>,
     },
     /// Migrates vaults to the given code_id. If a [vault_addr] is provided, then migrates only that
     /// vault.
@@ -35,8 +39,12 @@ pub enum ExecuteMsg {
         vault_addr: Option<String>,
         vault_code_id: u64,
     },
-    /// Removes a vault given its [AssetInfo]
-    RemoveVault { asset_info: AssetInfo },
+    /// Removes a vault given its [AssetInfo] and identifier. If no [vault_identifier] is provided,
+    /// the [DEFAULT_VAULT_IDENTIFIER] is used.
+    RemoveVault {
+        asset_info: AssetInfo,
+        vault_identifier: Option<String>,
+    },
     /// Updates a vault config
     UpdateVaultConfig {
         vault_addr: String,
@@ -59,13 +67,24 @@ pub enum QueryMsg {
     /// Retrieves the configuration of the vault.
     #[returns(Config)]
     Config {},
-    /// Retrieves the address of a given vault.
+    /// Retrieves the address of a given vault. If no [vault_identifier] is provided, the
+    /// [DEFAULT_VAULT_IDENTIFIER] is used.
     #[returns(Option<String>)]
-    Vault { asset_info: AssetInfo },
+    Vault {
+        asset_info: AssetInfo,
+        vault_identifier: Option<String>,
+    },
+    /// Retrieves all the vaults managing the given asset.
+    #[returns(VaultsResponse)]
+    AssetVaults { asset_info: AssetInfo },
     /// Retrieves the addresses for all the vaults.
+    ///
+    /// The pagination starts after the vault with the given [start_after] asset info reference
+    /// and [start_after_identifier] vault identifier, which must be provided together.
     #[returns(VaultsResponse)]
     Vaults {
         start_after: Option<Vec<u8>>,
+        start_after_identifier: Option<String>,
         limit: Option<u32>,
     },
 }
@@ -77,6 +96,9 @@ pub struct MigrateMsg {}
 /// The `reply` code ID for the submessage after instantiating the vault.
 pub const INSTANTIATE_VAULT_REPLY_ID: u64 = 1;
 
+/// The identifier given to a vault when none is specified on creation.
+pub const DEFAULT_VAULT_IDENTIFIER: &str = "default";
+
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
@@ -97,4 +119,5 @@ pub struct VaultInfo {
     pub vault: String,
     pub asset_info: AssetInfo,
     pub asset_info_reference: Vec<u8>,
+    pub vault_identifier: String,
 }
@@ -1,6 +1,8 @@
 use crate::pool_network::asset::{Asset, AssetInfo};
+use crate::pool_network::router::SwapOperation;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128, Uint64};
+use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
 pub struct Config {
@@ -15,6 +17,32 @@ pub struct Config {
     pub bonding_assets: Vec<AssetInfo>,
     /// Address of the fee distributor contract.
     pub fee_distributor_addr: Addr,
+    /// Multipliers applied to the weight of the bonds of the given assets, i.e. LP tokens can be
+    /// weighted differently than WHALE. Bonding assets not listed here have a multiplier of one.
+    /// Can only be set at instantiation.
+    pub weight_multipliers: Vec<(AssetInfo, Decimal)>,
+    /// Maximum lock duration in nanoseconds. If set, the contract runs in vote-escrow mode: bonds
+    /// are locked for a duration up to this maximum, and their weight is proportional to the
+    /// remaining lock time instead of growing with the growth rate. Can only be set at
+    /// instantiation.
+    pub max_lock_duration: Option<Uint64>,
+    /// The liquid bonding mode, if enabled. Can only be set at instantiation.
+    pub liquid_bonding: Option<LiquidBonding>,
+    /// Address of the pool router bonds are converted through. Converting bonds between bonding
+    /// assets is disabled if not set.
+    pub pool_router_addr: Option<Addr>,
+}
+
+/// Liquid bonding mode, where the tokens of the given asset are bonded in a pool held by the
+/// contract in exchange for receipt tokens. The rewards of the pool are bonded back into it, so
+/// the receipt tokens accrue them through their exchange rate.
+#[cw_serde]
+pub struct LiquidBonding {
+    /// The asset that can be liquid bonded. Should be the distribution asset of the fee
+    /// distributor, so the rewards can be bonded back into the pool.
+    pub asset: AssetInfo,
+    /// The cw20 receipt token, minted by the contract.
+    pub receipt_token: Addr,
 }
 
 #[cw_serde]
@@ -47,12 +75,31 @@ impl Default for Bond {
 pub struct GlobalIndex {
     /// The total amount of tokens bonded in the contract.
     pub bonded_amount: Uint128,
+    /// The total amount of tokens bonded in the contract, scaled by the weight multiplier of each
+    /// bonding asset. The global weight grows with it.
+    #[serde(default)]
+    pub weighted_amount: Uint128,
     /// Assets that are bonded in the contract.
     pub bonded_assets: Vec<Asset>,
     /// The timestamp at which the total bond was registered.
     pub timestamp: Timestamp,
     /// The total weight of the bond at the given block height.
     pub weight: Uint128,
+    /// The totals of the unexpired locks, in vote-escrow mode.
+    pub vote_escrow: Option<VoteEscrowIndex>,
+}
+
+/// Tracks the decaying global weight of the locks in vote-escrow mode.
+#[cw_serde]
+#[derive(Default)]
+pub struct VoteEscrowIndex {
+    /// The sum of the weighted amounts of the unexpired locks. The bias decreases by this amount
+    /// every second, until the locks expire.
+    pub slope: Uint128,
+    /// The sum of the weighted amounts of the unexpired locks times their remaining lock time in
+    /// seconds, at the timestamp of the global index. The global weight is the bias divided by the
+    /// maximum lock duration in seconds.
+    pub bias: Uint128,
 }
 
 #[cw_serde]
@@ -63,16 +110,82 @@ pub struct InstantiateMsg {
     pub growth_rate: Decimal,
     /// [AssetInfo] of the assets that can be bonded.
     pub bonding_assets: Vec<AssetInfo>,
+    /// Multipliers applied to the weight of the bonds of the given bonding assets. Defaults to one
+    /// for the bonding assets not listed.
+    pub weight_multipliers: Option<Vec<(AssetInfo, Decimal)>>,
+    /// Maximum lock duration in nanoseconds, which enables the vote-escrow mode if set.
+    pub max_lock_duration: Option<Uint64>,
+    /// Enables the liquid bonding mode if set. Not available in vote-escrow mode.
+    pub liquid_bonding: Option<LiquidBondingInstantiateMsg>,
+}
+
+#[cw_serde]
+pub struct LiquidBondingInstantiateMsg {
+    /// The native bonding asset that can be liquid bonded.
+    pub asset: AssetInfo,
+    /// The code id of the cw20 token to instantiate as the receipt token.
+    pub token_code_id: u64,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Bonds the specified [Asset].
-    Bond { asset: Asset },
+    /// Bonds the specified native [Asset] on behalf of the owner if set, or the sender otherwise.
+    /// In vote-escrow mode, the tokens are locked until the given lock duration in nanoseconds has
+    /// passed, which can't end before the current lock of the asset. Without a lock duration, the
+    /// tokens are added to the current lock of the asset.
+    Bond {
+        asset: Asset,
+        lock_duration: Option<Uint64>,
+        owner: Option<String>,
+    },
+    /// Bonds the cw20 tokens sent with a [Cw20HookMsg::Bond].
+    Receive(Cw20ReceiveMsg),
     /// Unbonds the specified [Asset].
     Unbond { asset: Asset },
-    /// Sends withdrawable unbonded tokens to the user.
+    /// Moves unbonding tokens of the given denom back into the user's bond, newest unbondings
+    /// first. Rebonds the unbonding with the given timestamp only if set, and everything if no
+    /// amount is given. The weight the tokens had when unbonded is restored, without growth for the
+    /// time spent unbonding. The denom is the contract address for cw20 tokens.
+    Rebond {
+        denom: String,
+        unbonding_timestamp: Option<u64>,
+        amount: Option<Uint128>,
+    },
+    /// Extends the lock of the given denom to end once the given duration in nanoseconds has
+    /// passed, relocking it if it already expired. Only available in vote-escrow mode. The denom is
+    /// the contract address for cw20 tokens.
+    ExtendLock {
+        denom: String,
+        lock_duration: Uint64,
+    },
+    /// Bonds the specified native [Asset] in the liquid bonding pool, minting receipt tokens for it
+    /// at the current exchange rate. The pending rewards of the pool are compounded first.
+    LiquidBond { asset: Asset },
+    /// Claims the pending rewards of the liquid bonding pool and bonds them back into it. Rewards
+    /// in other assets are swapped into the liquid bonding asset along the route registered in the
+    /// pool router, or sent to the owner if there's none.
+    Compound {},
+    /// Converts the bond of the given denom into a bond of another denom, swapping the bonded
+    /// tokens through the pool router along the given route. Converts the whole bond if no amount
+    /// is given. The weight of the converted tokens is kept, scaled by the weight multipliers of
+    /// the denoms, and the conversion fails if less than the minimum is received. Not available in
+    /// vote-escrow mode. The denoms are the contract address for cw20 tokens.
+    ConvertBond {
+        from_denom: String,
+        to_denom: String,
+        route: Vec<SwapOperation>,
+        amount: Option<Uint128>,
+        minimum_receive: Uint128,
+    },
+    /// Sends withdrawable unbonded tokens to the user. The denom is the contract address for cw20
+    /// tokens.
     Withdraw { denom: String },
+    /// Sets the address the fee distributor sends the rewards of the sender to. Resets it to the
+    /// sender if no recipient is given.
+    SetRewardRecipient { recipient: Option<String> },
+    /// Enables converting bonds through the given pool router, or disables it if not set. Only
+    /// callable by the owner.
+    SetPoolRouter { pool_router_addr: Option<String> },
     /// Updates the [Config] of the contract.
     UpdateConfig {
         owner: Option<String>,
@@ -80,6 +193,82 @@ pub enum ExecuteMsg {
         growth_rate: Option<Decimal>,
         fee_distributor_addr: Option<String>,
     },
+    /// Callback messages, only callable by the contract itself.
+    Callback(CallbackMsg),
+}
+
+/// The messages that can be sent along with cw20 tokens to the whale lair contract.
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Bonds the cw20 tokens sent on behalf of the owner if set, or the sender otherwise, locking
+    /// them for the given duration in vote-escrow mode.
+    Bond {
+        lock_duration: Option<Uint64>,
+        owner: Option<String>,
+    },
+    /// Burns the receipt tokens sent, which must be the liquid bonding receipt token, and starts
+    /// unbonding the tokens they are worth at the current exchange rate.
+    Redeem {},
+}
+
+/// The callback messages available. Only callable by the whale lair contract itself.
+#[cw_serde]
+pub enum CallbackMsg {
+    /// Bonds the specified [Asset] on behalf of the address, once its pending rewards on the fee
+    /// distributor have been claimed. The lock end is only set in vote-escrow mode.
+    Bond {
+        address: Addr,
+        asset: Asset,
+        lock_end: Option<Timestamp>,
+    },
+    /// Unbonds the specified [Asset] on behalf of the address, once its pending rewards on the fee
+    /// distributor have been claimed.
+    Unbond { address: Addr, asset: Asset },
+    /// Rebonds the unbonding tokens of the address, once its pending rewards on the fee distributor
+    /// have been claimed.
+    Rebond {
+        address: Addr,
+        denom: String,
+        unbonding_timestamp: Option<u64>,
+        amount: Option<Uint128>,
+    },
+    /// Extends the lock of the given denom of the address, once its pending rewards on the fee
+    /// distributor have been claimed.
+    ExtendLock {
+        address: Addr,
+        denom: String,
+        lock_end: Timestamp,
+    },
+    /// Bonds the rewards claimed by the liquid bonding pool back into it, i.e. the balances the
+    /// contract has on top of the given ones. Rewards in other assets are swapped into the liquid
+    /// bonding asset through the pool router first.
+    Compound { balances_before: Vec<Asset> },
+    /// Bonds the specified [Asset] in the liquid bonding pool on behalf of the address, once the
+    /// pending rewards of the pool have been compounded.
+    LiquidBond { address: Addr, asset: Asset },
+    /// Redeems the given amount of receipt tokens on behalf of the address, once the pending
+    /// rewards of the liquid bonding pool have been compounded.
+    Redeem { address: Addr, shares: Uint128 },
+    /// Converts the bond of the given denom of the address, once its pending rewards on the fee
+    /// distributor have been claimed.
+    ConvertBond {
+        address: Addr,
+        from_denom: String,
+        to_denom: String,
+        route: Vec<SwapOperation>,
+        amount: Option<Uint128>,
+        minimum_receive: Uint128,
+    },
+    /// Bonds the tokens received from swapping the converted [Asset] on behalf of the address, i.e.
+    /// the balance of the given asset the contract has on top of the given balance, along with the
+    /// weight taken from the converted tokens.
+    CompleteBondConversion {
+        address: Addr,
+        converted: Asset,
+        asset_info: AssetInfo,
+        balance_before: Uint128,
+        weight: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -116,6 +305,31 @@ pub enum QueryMsg {
         global_index: Option<GlobalIndex>,
     },
 
+    /// Returns the weight of the address at the given timestamp, from the recorded history of its
+    /// bonds rather than extrapolated from the current ones.
+    #[returns(BondingWeightResponse)]
+    WeightAt {
+        address: String,
+        timestamp: Timestamp,
+    },
+
+    /// Returns the weight of the address at the start of the given fee distributor epoch, from the
+    /// recorded history of its bonds.
+    #[returns(BondingWeightResponse)]
+    WeightAtEpoch { address: String, epoch_id: Uint64 },
+
+    /// Returns the global index at the given timestamp, from its recorded history.
+    #[returns(GlobalIndex)]
+    GlobalIndexAt { timestamp: Timestamp },
+
+    /// Returns the lock of the given denom for the specified address, in vote-escrow mode.
+    #[returns(LockResponse)]
+    Lock { address: String, denom: String },
+
+    /// Returns the address the rewards of the specified address are sent to.
+    #[returns(RewardRecipientResponse)]
+    RewardRecipient { address: String },
+
     /// Returns the total amount of assets that have been bonded to the contract.
     #[returns(BondedResponse)]
     TotalBonded {},
@@ -123,6 +337,31 @@ pub enum QueryMsg {
     /// Returns the global index of the contract.
     #[returns(GlobalIndex)]
     GlobalIndex {},
+
+    /// Returns the bonders with their bonded assets and current weight, sorted by address.
+    /// Allows pagination with start_after and limit.
+    #[returns(BondersResponse)]
+    Bonders {
+        start_after: Option<String>,
+        limit: Option<u8>,
+    },
+
+    /// Returns the bonders with the highest current weight or bonded amount. Goes through all the
+    /// bonders, so it's meant to be queried off-chain.
+    #[returns(BondersResponse)]
+    TopBonders {
+        sort_by: BonderSortBy,
+        limit: Option<u8>,
+    },
+
+    /// Returns aggregate stats of the bonders. Goes through all the bonds and unbondings, so it's
+    /// meant to be queried off-chain.
+    #[returns(StatsResponse)]
+    Stats {},
+
+    /// Returns the state of the liquid bonding pool, in liquid bonding mode.
+    #[returns(LiquidBondingResponse)]
+    LiquidBonding {},
 }
 
 #[cw_serde]
@@ -136,6 +375,56 @@ pub struct BondedResponse {
     pub first_bonded_epoch_id: Uint64,
 }
 
+/// The criteria to sort the bonders by in the TopBonders query.
+#[cw_serde]
+pub enum BonderSortBy {
+    /// The current weight of the bonder.
+    Weight,
+    /// The amount bonded by the bonder, scaled by the weight multiplier of each bonding asset.
+    BondedAmount,
+}
+
+/// The bonds of an address.
+#[cw_serde]
+pub struct BonderInfo {
+    pub address: String,
+    pub bonded_assets: Vec<Asset>,
+    /// The bonded amount, scaled by the weight multiplier of each bonding asset.
+    pub bonded_amount: Uint128,
+    /// The current weight of the bonds.
+    pub weight: Uint128,
+}
+
+/// Response for the Bonders and TopBonders queries
+#[cw_serde]
+pub struct BondersResponse {
+    pub bonders: Vec<BonderInfo>,
+}
+
+/// Response for the Stats query
+#[cw_serde]
+pub struct StatsResponse {
+    /// The number of addresses with bonded assets.
+    pub bonder_count: u64,
+    /// The assets that are bonded in the contract.
+    pub bonded_assets: Vec<Asset>,
+    /// The assets that are being unbonded or can be withdrawn.
+    pub unbonding_assets: Vec<Asset>,
+}
+
+/// Response for the LiquidBonding query
+#[cw_serde]
+pub struct LiquidBondingResponse {
+    pub asset: AssetInfo,
+    pub receipt_token: String,
+    /// The total supply of the receipt token.
+    pub total_shares: Uint128,
+    /// The amount of tokens bonded in the liquid bonding pool.
+    pub bonded_amount: Uint128,
+    /// The amount of bonded tokens each receipt token is worth.
+    pub exchange_rate: Decimal,
+}
+
 /// Response for the Unbonding query
 #[cw_serde]
 pub struct UnbondingResponse {
@@ -149,6 +438,22 @@ pub struct WithdrawableResponse {
     pub withdrawable_amount: Uint128,
 }
 
+/// Response for the RewardRecipient query
+#[cw_serde]
+pub struct RewardRecipientResponse {
+    /// The address the rewards are sent to, which is the bonder itself unless set otherwise.
+    pub recipient: String,
+}
+
+/// Response for the Lock query
+#[cw_serde]
+pub struct LockResponse {
+    /// The time at which the lock expires, if there is any.
+    pub lock_end: Option<Timestamp>,
+    /// The current weight of the lock, which decays to zero at the lock end.
+    pub weight: Uint128,
+}
+
 /// Response for the Weight query.
 #[cw_serde]
 pub struct BondingWeightResponse {
@@ -7,7 +7,8 @@ pub fn whale_lair_contract() -> Box<dyn Contract<Empty>> {
         whale_lair::contract::instantiate,
         whale_lair::contract::query,
     )
-    .with_migrate(whale_lair::contract::migrate);
+    .with_migrate(whale_lair::contract::migrate)
+    .with_reply(whale_lair::contract::reply);
 
     Box::new(contract)
 }